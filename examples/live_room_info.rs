@@ -5,16 +5,15 @@
 //! cargo run --example live_room_info --features live
 //! ```
 
-use bpi_rs::{BpiClient, BpiError, BpiResult};
+use bpi_rs::ids::RoomId;
+use bpi_rs::{BpiClient, BpiResult};
 
-const DEFAULT_ROOM_ID: i64 = 3818081;
+const DEFAULT_ROOM_ID: u64 = 3818081;
 
-fn room_id() -> BpiResult<i64> {
+fn room_id() -> BpiResult<RoomId> {
     match std::env::var("BILI_ROOM_ID") {
-        Ok(value) => value.parse().map_err(|_| {
-            BpiError::invalid_parameter("room_id", "room id must be a signed integer")
-        }),
-        Err(_) => Ok(DEFAULT_ROOM_ID),
+        Ok(value) => value.parse(),
+        Err(_) => RoomId::new(DEFAULT_ROOM_ID),
     }
 }
 
@@ -22,9 +21,17 @@ fn room_id() -> BpiResult<i64> {
 async fn main() -> BpiResult<()> {
     let room_id = room_id()?;
     let client = BpiClient::new()?;
-    let info = client.live().room_info(room_id).await?;
+    let live = client.live();
+    let room = live.resolve_room(room_id).await?;
+    let info = live.room_info(room.room_id).await?;
 
-    println!("直播间: {} ({})", info.title, info.room_id);
+    match room.short_id {
+        Some(short_id) => println!(
+            "直播间: {} ({}，短号 {})",
+            info.title, room.room_id, short_id
+        ),
+        None => println!("直播间: {} ({})", info.title, room.room_id),
+    }
     println!("主播 mid: {}", info.uid);
     println!(
         "状态: live_status={} 分区={}·{}",
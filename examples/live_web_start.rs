@@ -16,8 +16,9 @@
 mod account;
 
 use bpi_rs::BpiResult;
+use bpi_rs::ids::RoomId;
 
-const DEFAULT_ROOM_ID: i64 = 4354019;
+const DEFAULT_ROOM_ID: u64 = 4354019;
 const DEFAULT_AREA_ID: u64 = 309;
 const MUTATING_ENV: &str = "BPI_MUTATING_TEST";
 
@@ -36,10 +37,11 @@ fn mutating_enabled() -> bool {
 async fn main() -> BpiResult<()> {
     let stop_if_live = std::env::args().any(|a| a == "--stop-if-live");
     let stop_only = std::env::args().any(|a| a == "--stop-only");
-    let room_id: i64 = std::env::var("BILI_ROOM_ID")
+    let room_id: u64 = std::env::var("BILI_ROOM_ID")
         .ok()
         .and_then(|s| s.parse().ok())
         .unwrap_or(DEFAULT_ROOM_ID);
+    let room_id = RoomId::new(room_id)?;
     let area_id: u64 = std::env::var("BILI_AREA_ID")
         .ok()
         .and_then(|s| s.parse().ok())
@@ -65,7 +67,7 @@ async fn main() -> BpiResult<()> {
     if stop_only {
         if info.live_status == 1 {
             println!("执行关播...");
-            let stop = live.live_stop(room_id as u64, "pc").await?;
+            let stop = live.live_stop(room_id, "pc").await?;
             println!("关播结果: status={}", stop.status);
             tokio::time::sleep(std::time::Duration::from_secs(3)).await;
         } else {
@@ -88,7 +90,7 @@ async fn main() -> BpiResult<()> {
     if info.live_status == 1 {
         if stop_if_live {
             println!("已在直播，--stop-if-live：先关播...");
-            let stop = live.live_stop(room_id as u64, "pc").await?;
+            let stop = live.live_stop(room_id, "pc").await?;
             println!("关播结果: status={}", stop.status);
             tokio::time::sleep(std::time::Duration::from_secs(2)).await;
         } else {
@@ -105,7 +107,7 @@ async fn main() -> BpiResult<()> {
     println!("  rtmp: {}{}", stream.addr.addr, mask(&stream.addr.code));
 
     println!("\n[2/2] WebLiveCenterStartLive (WBI)...");
-    let start = live.live_web_center_start(room_id as u64, area_id).await?;
+    let start = live.live_web_center_start(room_id, area_id).await?;
     println!("  status={} live_key={}", start.status, start.live_key);
     if let Some(rtmp) = &start.rtmp {
         println!("  rtmp(响应): {}{}", rtmp.addr, mask(&rtmp.code));
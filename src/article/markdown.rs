@@ -0,0 +1,1519 @@
+//! 专栏正文转换
+//!
+//! 把 HTML 正文（`type=0`）、opus JSON 正文（`type=3`）和动态 opus 段落统一解析为
+//! [`RichDocument`]，再输出 Markdown 或纯文本。正文中引用的视频、专栏和直播卡片可以通过
+//! [`ArticleClient::cards`] 解析出标题。
+
+use crate::article::card::{CardData, CardItem};
+use crate::article::view::{
+    ArticleOpus, ArticleViewData, OpusAttribute, OpusInsert, OpusRichInsert,
+};
+use crate::article::{ArticleCardsParams, ArticleClient, ArticleViewParams};
+use crate::{BpiError, BpiResult};
+
+/// 富文本文档，由若干块级元素组成。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RichDocument {
+    pub blocks: Vec<RichBlock>,
+}
+
+/// 块级元素。
+#[derive(Debug, Clone, PartialEq)]
+pub enum RichBlock {
+    /// 标题，`level` 取值 1-6
+    Heading { level: u8, inlines: Vec<RichInline> },
+    /// 段落
+    Paragraph(Vec<RichInline>),
+    /// 图片
+    Image(RichImage),
+    /// 代码块
+    Code { lang: Option<String>, code: String },
+    /// 引用
+    Quote(Vec<RichBlock>),
+    /// 列表
+    List {
+        ordered: bool,
+        items: Vec<RichListItem>,
+    },
+    /// 独立成行的公式，LaTeX 格式
+    Formula(String),
+    /// 视频、专栏、直播等卡片
+    Card(RichCard),
+    /// 分割线
+    Rule,
+}
+
+/// 行内元素。
+#[derive(Debug, Clone, PartialEq)]
+pub enum RichInline {
+    /// 文本
+    Text { text: String, style: RichStyle },
+    /// 链接
+    Link { text: String, url: String },
+    /// 行内公式，LaTeX 格式
+    Formula(String),
+    /// 换行
+    LineBreak,
+}
+
+/// 文本样式。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RichStyle {
+    pub bold: bool,
+    pub italic: bool,
+    pub strike: bool,
+}
+
+/// 图片。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RichImage {
+    /// 图片地址，协议相对地址会补全为 https
+    pub url: String,
+    /// 备用文本
+    pub alt: String,
+    /// 图注
+    pub caption: String,
+}
+
+/// 列表项。
+#[derive(Debug, Clone, PartialEq)]
+pub struct RichListItem {
+    /// 缩进级别，从 1 开始
+    pub level: u8,
+    pub inlines: Vec<RichInline>,
+}
+
+/// 卡片类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RichCardKind {
+    Video,
+    Article,
+    Live,
+    Other,
+}
+
+/// 卡片。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RichCard {
+    pub kind: RichCardKind,
+    /// 卡片 ID，视频为 `av`/`BV` 号，专栏为 `cv` 号，直播为 `lv` 加房间号
+    pub id: String,
+    /// 已知的标题
+    pub title: Option<String>,
+    /// 已知的跳转地址
+    pub url: Option<String>,
+}
+
+impl RichCard {
+    /// 按前缀识别卡片类型，纯数字 ID 按 `kind` 补全前缀。
+    pub fn new(kind: RichCardKind, id: impl Into<String>) -> Self {
+        let id = id.into().trim().to_string();
+        let id = if id.bytes().all(|b| b.is_ascii_digit()) {
+            match kind {
+                RichCardKind::Video => format!("av{id}"),
+                RichCardKind::Article => format!("cv{id}"),
+                RichCardKind::Live => format!("lv{id}"),
+                RichCardKind::Other => id,
+            }
+        } else {
+            id
+        };
+
+        Self {
+            kind,
+            id,
+            title: None,
+            url: None,
+        }
+    }
+
+    /// 从 `av170001`、`BV1xx`、`cv1`、`lv1` 形式的 ID 创建卡片。
+    pub fn from_id(id: impl Into<String>) -> Self {
+        let id = id.into();
+        let lower = id.to_ascii_lowercase();
+        let kind = if lower.starts_with("av") || lower.starts_with("bv") {
+            RichCardKind::Video
+        } else if lower.starts_with("cv") {
+            RichCardKind::Article
+        } else if lower.starts_with("lv") {
+            RichCardKind::Live
+        } else {
+            RichCardKind::Other
+        };
+
+        Self::new(kind, id)
+    }
+
+    /// 可以交给 [`ArticleClient::cards`] 解析的卡片。
+    fn is_resolvable(&self) -> bool {
+        self.kind != RichCardKind::Other && self.url.is_none()
+    }
+
+    fn label(&self) -> &'static str {
+        match self.kind {
+            RichCardKind::Video => "视频",
+            RichCardKind::Article => "专栏",
+            RichCardKind::Live => "直播",
+            RichCardKind::Other => "卡片",
+        }
+    }
+
+    /// 返回卡片的展示标题和跳转地址，优先使用 `cards` 中的解析结果。
+    fn resolve(&self, cards: Option<&CardData>) -> (String, Option<String>) {
+        let resolved = cards
+            .and_then(|cards| cards.get(&self.id))
+            .and_then(|item| match item {
+                CardItem::Video(card) => Some((
+                    card.title.clone(),
+                    format!("https://www.bilibili.com/video/{}", card.bvid),
+                )),
+                CardItem::Article(card) => Some((
+                    card.title.clone(),
+                    format!("https://www.bilibili.com/read/cv{}", card.id),
+                )),
+                CardItem::Live(card) => Some((
+                    card.title.clone(),
+                    format!("https://live.bilibili.com/{}", card.room_id),
+                )),
+                CardItem::Unknown(_) => None,
+            });
+        if let Some((title, url)) = resolved {
+            return (format!("{}：{}", self.label(), title.trim()), Some(url));
+        }
+
+        let title = match &self.title {
+            Some(title) => format!("{}：{}", self.label(), title.trim()),
+            None => format!("{} {}", self.label(), self.id),
+        };
+        let url = self.url.clone().or_else(|| match self.kind {
+            RichCardKind::Video => Some(format!("https://www.bilibili.com/video/{}", self.id)),
+            RichCardKind::Article => Some(format!("https://www.bilibili.com/read/{}", self.id)),
+            RichCardKind::Live => Some(format!(
+                "https://live.bilibili.com/{}",
+                self.id.trim_start_matches("lv")
+            )),
+            RichCardKind::Other => None,
+        });
+
+        (title, url)
+    }
+}
+
+impl RichDocument {
+    /// 解析专栏 HTML 正文。
+    pub fn from_html(html: &str) -> Self {
+        let mut builder = HtmlBuilder::default();
+        for token in tokenize_html(html) {
+            builder.feed(token);
+        }
+
+        builder.finish()
+    }
+
+    /// 解析专栏 opus JSON 正文（Quill delta 格式）。
+    pub fn from_opus(opus: &ArticleOpus) -> Self {
+        let mut builder = OpsBuilder::default();
+        for op in &opus.ops {
+            let attribute = op.attribute.as_ref();
+            match &op.insert {
+                OpusInsert::Text(text) => {
+                    let mut lines = text.split('\n');
+                    if let Some(first) = lines.next() {
+                        builder.push_text(first, attribute);
+                    }
+                    for line in lines {
+                        builder.end_line(attribute);
+                        builder.push_text(line, attribute);
+                    }
+                }
+                OpusInsert::Rich(rich) => builder.push_rich(rich),
+            }
+        }
+
+        builder.finish()
+    }
+
+    /// 正文中需要通过 [`ArticleClient::cards`] 解析的卡片 ID，已去重。
+    pub fn card_ids(&self) -> Vec<String> {
+        let mut ids = Vec::new();
+        collect_card_ids(&self.blocks, &mut ids);
+        ids
+    }
+
+    /// 输出 Markdown，卡片只显示 ID。
+    pub fn to_markdown(&self) -> String {
+        render_markdown(&self.blocks, None)
+    }
+
+    /// 输出 Markdown，卡片标题取自 [`ArticleClient::cards`] 的结果。
+    pub fn to_markdown_with_cards(&self, cards: &CardData) -> String {
+        render_markdown(&self.blocks, Some(cards))
+    }
+
+    /// 输出去除格式的纯文本。
+    pub fn to_plain_text(&self) -> String {
+        render_plain(&self.blocks, None)
+    }
+}
+
+impl ArticleViewData {
+    /// 按 `type` 解析正文：0 为 HTML，3 为 opus JSON。
+    pub fn document(&self) -> BpiResult<RichDocument> {
+        match self.r#type {
+            3 => {
+                let opus = match serde_json::from_str::<ArticleOpus>(&self.content) {
+                    Ok(opus) => opus,
+                    Err(err) => self
+                        .opus
+                        .clone()
+                        .filter(|opus| !opus.ops.is_empty())
+                        .ok_or_else(|| BpiError::parse(format!("解析专栏正文失败: {}", err)))?,
+                };
+                Ok(RichDocument::from_opus(&opus))
+            }
+            _ => Ok(RichDocument::from_html(&self.content)),
+        }
+    }
+
+    /// 输出带标题的 Markdown，卡片只显示 ID。
+    pub fn to_markdown(&self) -> BpiResult<String> {
+        self.render_markdown(None)
+    }
+
+    /// 输出带标题的 Markdown，卡片标题取自 [`ArticleClient::cards`] 的结果。
+    pub fn to_markdown_with_cards(&self, cards: &CardData) -> BpiResult<String> {
+        self.render_markdown(Some(cards))
+    }
+
+    /// 输出带标题的纯文本。
+    pub fn to_plain_text(&self) -> BpiResult<String> {
+        let body = self.document()?.to_plain_text();
+        Ok(format!("{}\n\n{}", self.title.trim(), body))
+    }
+
+    /// 点评类专栏关联的番剧或影视条目。
+    fn media_reference(&self) -> Option<String> {
+        (self.media.media_id != 0).then(|| {
+            format!(
+                "> 点评：[{}](https://www.bilibili.com/bangumi/media/md{})",
+                escape_markdown(&self.media.title),
+                self.media.media_id
+            )
+        })
+    }
+
+    fn render_markdown(&self, cards: Option<&CardData>) -> BpiResult<String> {
+        let document = self.document()?;
+        let mut parts = vec![format!("# {}", escape_markdown(self.title.trim()))];
+        parts.extend(self.media_reference());
+        parts.push(render_markdown(&document.blocks, cards));
+
+        Ok(parts.join("\n\n"))
+    }
+}
+
+impl<'a> ArticleClient<'a> {
+    /// 获取专栏内容，解析其中引用的卡片后转换为 Markdown。
+    pub async fn markdown(&self, params: ArticleViewParams) -> BpiResult<String> {
+        let view = self.view(params).await?;
+        let ids = view.document()?.card_ids();
+        if ids.is_empty() {
+            return view.to_markdown();
+        }
+
+        let cards = self.cards(ArticleCardsParams::new(ids.join(","))?).await?;
+        view.to_markdown_with_cards(&cards)
+    }
+}
+
+#[cfg(feature = "dynamic")]
+mod paragraphs {
+    use super::*;
+    use crate::dynamic::{Card, Paragraph, TextNode};
+
+    impl RichDocument {
+        /// 转换动态和图文的 opus 段落。
+        pub fn from_paragraphs(paragraphs: &[Paragraph]) -> Self {
+            let mut blocks = Vec::new();
+            for paragraph in paragraphs {
+                for block in paragraph_blocks(paragraph) {
+                    push_merged(&mut blocks, block);
+                }
+            }
+
+            Self { blocks }
+        }
+    }
+
+    fn paragraph_blocks(paragraph: &Paragraph) -> Vec<RichBlock> {
+        match paragraph.para_type {
+            1 => paragraph
+                .text
+                .as_ref()
+                .and_then(|text| non_empty_inlines(text_nodes_inlines(&text.nodes)))
+                .map(RichBlock::Paragraph)
+                .into_iter()
+                .collect(),
+            2 => paragraph
+                .pics
+                .iter()
+                .flat_map(|pics| &pics.pics)
+                .map(|pic| {
+                    RichBlock::Image(RichImage {
+                        url: normalize_url(&pic.url),
+                        ..RichImage::default()
+                    })
+                })
+                .collect(),
+            3 => vec![RichBlock::Rule],
+            4 => paragraph
+                .text
+                .as_ref()
+                .and_then(|text| non_empty_inlines(text_nodes_inlines(&text.nodes)))
+                .map(|inlines| RichBlock::Quote(vec![RichBlock::Paragraph(inlines)]))
+                .into_iter()
+                .collect(),
+            5 => paragraph
+                .list
+                .as_ref()
+                .map(|list| RichBlock::List {
+                    ordered: list.style == 1,
+                    items: list
+                        .items
+                        .iter()
+                        .map(|item| RichListItem {
+                            level: u8::try_from(item.level.max(1)).unwrap_or(u8::MAX),
+                            inlines: trim_inlines(text_nodes_inlines(&item.nodes)),
+                        })
+                        .collect(),
+                })
+                .into_iter()
+                .collect(),
+            6 => paragraph
+                .link_card
+                .as_ref()
+                .and_then(|link| link_card_block(&link.card))
+                .into_iter()
+                .collect(),
+            7 => paragraph
+                .code
+                .as_ref()
+                .map(|code| RichBlock::Code {
+                    lang: code_lang(&code.lang)
+                        .or_else(|| Some(code.lang.clone()).filter(|lang| !lang.is_empty())),
+                    code: code.content.clone(),
+                })
+                .into_iter()
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn text_nodes_inlines(nodes: &[TextNode]) -> Vec<RichInline> {
+        let mut inlines = Vec::new();
+        for node in nodes {
+            if let Some(formula) = &node.formula {
+                inlines.push(RichInline::Formula(formula.latex_content.clone()));
+            } else if let Some(word) = &node.word {
+                let style = RichStyle {
+                    bold: style_flag(&word.style, "bold"),
+                    italic: style_flag(&word.style, "italic"),
+                    strike: style_flag(&word.style, "strikethrough"),
+                };
+                push_text_lines(&mut inlines, &word.words, style);
+            } else if let Some(rich) = &node.rich {
+                let text = rich
+                    .get("text")
+                    .or_else(|| rich.get("orig_text"))
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_default();
+                match rich.get("jump_url").and_then(serde_json::Value::as_str) {
+                    Some(url) if !url.is_empty() => inlines.push(RichInline::Link {
+                        text: text.to_string(),
+                        url: normalize_url(url),
+                    }),
+                    _ => push_text_lines(&mut inlines, text, RichStyle::default()),
+                }
+            }
+        }
+
+        inlines
+    }
+
+    fn link_card_block(card: &Card) -> Option<RichBlock> {
+        let oid = Some(card.oid.as_str()).filter(|oid| !oid.is_empty() && *oid != "undefined");
+        match card.type_field.as_str() {
+            "LINK_CARD_TYPE_UGC" => oid.map(|oid| RichCard::new(RichCardKind::Video, oid)),
+            "LINK_CARD_TYPE_LIVE" => oid.map(|oid| RichCard::new(RichCardKind::Live, oid)),
+            "LINK_CARD_TYPE_OPUS" => card.opus.as_ref().map(|opus| RichCard {
+                title: Some(opus.title.clone()),
+                url: Some(normalize_url(&opus.jump_url)),
+                ..RichCard::new(RichCardKind::Other, oid.unwrap_or_default())
+            }),
+            "LINK_CARD_TYPE_ITEM_NULL" => {
+                return card
+                    .item_null
+                    .as_ref()
+                    .filter(|item| !item.text.is_empty())
+                    .map(|item| RichBlock::Paragraph(vec![plain_text(&item.text)]));
+            }
+            _ => oid.map(|oid| {
+                let common = card.common.as_ref();
+                let field = |name: &str| {
+                    common
+                        .and_then(|common| common.get(name))
+                        .and_then(serde_json::Value::as_str)
+                        .filter(|value| !value.is_empty())
+                };
+                RichCard {
+                    title: field("title").map(str::to_string),
+                    url: field("jump_url").map(normalize_url),
+                    ..RichCard::new(RichCardKind::Other, oid)
+                }
+            }),
+        }
+        .map(RichBlock::Card)
+    }
+
+    fn style_flag(style: &serde_json::Value, name: &str) -> bool {
+        style
+            .get(name)
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+    }
+}
+
+/// HTML 标记。
+#[derive(Debug, PartialEq)]
+enum HtmlToken {
+    Open {
+        name: String,
+        attrs: Vec<(String, String)>,
+    },
+    Close(String),
+    Text(String),
+}
+
+fn tokenize_html(html: &str) -> Vec<HtmlToken> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+
+        let is_tag = rest.starts_with('<')
+            && rest[1..]
+                .chars()
+                .next()
+                .is_some_and(|c| c == '/' || c.is_ascii_alphabetic());
+        if !is_tag {
+            let first = rest.chars().next().map_or(1, char::len_utf8);
+            let end = rest[first..]
+                .find('<')
+                .map_or(rest.len(), |end| end + first);
+            tokens.push(HtmlToken::Text(decode_entities(&rest[..end])));
+            rest = &rest[end..];
+            continue;
+        }
+
+        let end = tag_end(rest);
+        let tag = &rest[1..end];
+        rest = rest.get(end + 1..).unwrap_or("");
+
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(HtmlToken::Close(name.trim().to_ascii_lowercase()));
+            continue;
+        }
+
+        let tag = tag.trim_end_matches('/');
+        let name_end = tag.find(|c: char| c.is_whitespace()).unwrap_or(tag.len());
+        tokens.push(HtmlToken::Open {
+            name: tag[..name_end].to_ascii_lowercase(),
+            attrs: parse_attrs(&tag[name_end..]),
+        });
+    }
+
+    tokens
+}
+
+/// 找到标签结尾的 `>`，忽略属性值中的 `>`。
+fn tag_end(tag: &str) -> usize {
+    let mut quote = None;
+    for (index, c) in tag.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (None, '>') => return index,
+            _ => {}
+        }
+    }
+
+    tag.len()
+}
+
+fn parse_attrs(mut rest: &str) -> Vec<(String, String)> {
+    let mut attrs = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+
+        let name_end = rest
+            .find(|c: char| c.is_whitespace() || c == '=')
+            .unwrap_or(rest.len());
+        let name = rest[..name_end].to_ascii_lowercase();
+        rest = rest[name_end..].trim_start();
+
+        let value = if let Some(value) = rest.strip_prefix('=') {
+            let value = value.trim_start();
+            match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let inner = &value[1..];
+                    let end = inner.find(quote).unwrap_or(inner.len());
+                    rest = inner.get(end + 1..).unwrap_or("");
+                    &inner[..end]
+                }
+                _ => {
+                    let end = value.find(char::is_whitespace).unwrap_or(value.len());
+                    rest = &value[end..];
+                    &value[..end]
+                }
+            }
+        } else {
+            ""
+        };
+
+        if !name.is_empty() {
+            attrs.push((name, decode_entities(value)));
+        }
+    }
+
+    attrs
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest
+            .find(';')
+            .filter(|end| *end <= 10)
+            .map(|end| (&rest[1..end], end));
+        let replacement = entity.and_then(|(name, _)| match name {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => {
+                let code = match name.strip_prefix('#') {
+                    Some(hex) if hex.starts_with(['x', 'X']) => {
+                        u32::from_str_radix(&hex[1..], 16).ok()
+                    }
+                    Some(dec) => dec.parse().ok(),
+                    None => None,
+                };
+                code.and_then(char::from_u32)
+            }
+        });
+
+        match (replacement, entity) {
+            (Some(c), Some((_, end))) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+
+    decoded
+}
+
+fn attr<'t>(attrs: &'t [(String, String)], name: &str) -> Option<&'t str> {
+    attrs
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+        .filter(|value| !value.is_empty())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InlineTarget {
+    Paragraph,
+    Heading(u8),
+    ListItem(u8),
+}
+
+/// 按 HTML 标记构建文档。
+#[derive(Default)]
+struct HtmlBuilder {
+    /// 根容器和嵌套的引用块
+    containers: Vec<Vec<RichBlock>>,
+    inline: Option<(InlineTarget, Vec<RichInline>)>,
+    lists: Vec<(bool, Vec<RichListItem>)>,
+    bold: usize,
+    italic: usize,
+    strike: usize,
+    link: Option<(String, String)>,
+    /// 代码块的语言、内容以及内容是否来自 `codecontent` 属性
+    pre: Option<(Option<String>, String, bool)>,
+    caption: Option<String>,
+}
+
+impl HtmlBuilder {
+    fn feed(&mut self, token: HtmlToken) {
+        if let Some((_, code, from_attr)) = &mut self.pre {
+            match token {
+                HtmlToken::Close(name) if name == "pre" => {
+                    if let Some((lang, code, _)) = self.pre.take() {
+                        self.push_block(RichBlock::Code { lang, code });
+                    }
+                }
+                HtmlToken::Text(text) if !*from_attr => code.push_str(&text),
+                _ => {}
+            }
+            return;
+        }
+
+        match token {
+            HtmlToken::Open { name, attrs } => self.open(&name, &attrs),
+            HtmlToken::Close(name) => self.close(&name),
+            HtmlToken::Text(text) => self.text(&text),
+        }
+    }
+
+    fn open(&mut self, name: &str, attrs: &[(String, String)]) {
+        match name {
+            "p" | "div" => self.start_inline(InlineTarget::Paragraph),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.start_inline(InlineTarget::Heading(name.as_bytes()[1] - b'0'));
+            }
+            "blockquote" => {
+                self.flush_inline();
+                // 先建立根容器，文章以引用开头时引用块才不会成为根容器
+                self.container();
+                self.containers.push(Vec::new());
+            }
+            "ul" | "ol" => {
+                self.flush_inline();
+                self.lists.push((name == "ol", Vec::new()));
+            }
+            "li" => {
+                let level = u8::try_from(self.lists.len().max(1)).unwrap_or(u8::MAX);
+                self.start_inline(InlineTarget::ListItem(level));
+            }
+            "strong" | "b" => self.bold += 1,
+            "em" | "i" => self.italic += 1,
+            "del" | "s" | "strike" => self.strike += 1,
+            "a" => {
+                let href = attr(attrs, "href").map(normalize_url).unwrap_or_default();
+                self.link = Some((href, String::new()));
+            }
+            "br" => match &mut self.link {
+                Some((_, text)) => text.push(' '),
+                None => self.push_inline(RichInline::LineBreak),
+            },
+            "hr" => self.push_block(RichBlock::Rule),
+            "img" => self.image(attrs),
+            "pre" => {
+                self.flush_inline();
+                let lang = attr(attrs, "class")
+                    .and_then(|class| class.split_whitespace().find_map(code_lang));
+                let code = attr(attrs, "codecontent").map(str::to_string);
+                let from_attr = code.is_some();
+                self.pre = Some((lang, code.unwrap_or_default(), from_attr));
+            }
+            "figcaption" => self.caption = Some(String::new()),
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        match name {
+            "p" | "div" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "li" => self.flush_inline(),
+            "blockquote" => {
+                self.flush_inline();
+                if self.containers.len() > 1
+                    && let Some(blocks) = self.containers.pop()
+                    && !blocks.is_empty()
+                {
+                    self.push_block(RichBlock::Quote(blocks));
+                }
+            }
+            "ul" | "ol" => {
+                self.flush_inline();
+                if let Some((ordered, items)) = self.lists.pop()
+                    && !items.is_empty()
+                {
+                    self.push_block(RichBlock::List { ordered, items });
+                }
+            }
+            "strong" | "b" => self.bold = self.bold.saturating_sub(1),
+            "em" | "i" => self.italic = self.italic.saturating_sub(1),
+            "del" | "s" | "strike" => self.strike = self.strike.saturating_sub(1),
+            "a" => {
+                if let Some((url, text)) = self.link.take() {
+                    let text = collapse_whitespace(&text);
+                    if url.is_empty() {
+                        self.text(&text);
+                    } else if !text.is_empty() {
+                        self.push_inline(RichInline::Link { text, url });
+                    }
+                }
+            }
+            "figcaption" => {
+                let caption = self.caption.take().unwrap_or_default();
+                let caption = collapse_whitespace(&caption);
+                if let Some(RichBlock::Image(image)) = self.container().last_mut()
+                    && !caption.is_empty()
+                {
+                    image.caption = caption;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if let Some(caption) = &mut self.caption {
+            caption.push_str(text);
+            return;
+        }
+        if let Some((_, link_text)) = &mut self.link {
+            link_text.push_str(text);
+            return;
+        }
+
+        let text = text.replace(['\n', '\r'], " ");
+        if self.inline.is_none() && text.trim().is_empty() {
+            return;
+        }
+        let style = RichStyle {
+            bold: self.bold > 0,
+            italic: self.italic > 0,
+            strike: self.strike > 0,
+        };
+        self.push_inline(RichInline::Text { text, style });
+    }
+
+    fn image(&mut self, attrs: &[(String, String)]) {
+        let class = attr(attrs, "class").unwrap_or_default();
+        if class.contains("latex") {
+            let latex = attr(attrs, "alt").unwrap_or_default().to_string();
+            if self.inline.is_some() {
+                self.push_inline(RichInline::Formula(latex));
+            } else {
+                self.push_block(RichBlock::Formula(latex));
+            }
+        } else if class.contains("cut-off") {
+            self.push_block(RichBlock::Rule);
+        } else if let Some(aid) = attr(attrs, "aid") {
+            self.push_block(RichBlock::Card(RichCard::from_id(aid)));
+        } else if let Some(url) = attr(attrs, "data-src").or_else(|| attr(attrs, "src")) {
+            self.push_block(RichBlock::Image(RichImage {
+                url: normalize_url(url),
+                alt: attr(attrs, "alt").unwrap_or_default().to_string(),
+                caption: String::new(),
+            }));
+        }
+    }
+
+    fn container(&mut self) -> &mut Vec<RichBlock> {
+        if self.containers.is_empty() {
+            self.containers.push(Vec::new());
+        }
+        let last = self.containers.len() - 1;
+        &mut self.containers[last]
+    }
+
+    fn start_inline(&mut self, target: InlineTarget) {
+        self.flush_inline();
+        self.inline = Some((target, Vec::new()));
+    }
+
+    fn push_inline(&mut self, inline: RichInline) {
+        let (_, inlines) = self
+            .inline
+            .get_or_insert_with(|| (InlineTarget::Paragraph, Vec::new()));
+        push_merged_inline(inlines, inline);
+    }
+
+    fn push_block(&mut self, block: RichBlock) {
+        self.flush_inline();
+        self.container().push(block);
+    }
+
+    /// 结束当前段落、标题或列表项，丢弃只有空白和换行的内容。
+    fn flush_inline(&mut self) {
+        let Some((target, inlines)) = self.inline.take() else {
+            return;
+        };
+        let Some(inlines) = non_empty_inlines(inlines) else {
+            return;
+        };
+
+        match target {
+            InlineTarget::Paragraph => self.container().push(RichBlock::Paragraph(inlines)),
+            InlineTarget::Heading(level) => {
+                self.container().push(RichBlock::Heading { level, inlines })
+            }
+            InlineTarget::ListItem(level) => match self.lists.last_mut() {
+                Some((_, items)) => items.push(RichListItem { level, inlines }),
+                None => self.container().push(RichBlock::Paragraph(inlines)),
+            },
+        }
+    }
+
+    fn finish(mut self) -> RichDocument {
+        self.flush_inline();
+        while let Some((ordered, items)) = self.lists.pop() {
+            if !items.is_empty() {
+                self.container().push(RichBlock::List { ordered, items });
+            }
+        }
+        while self.containers.len() > 1 {
+            if let Some(blocks) = self.containers.pop()
+                && !blocks.is_empty()
+            {
+                self.container().push(RichBlock::Quote(blocks));
+            }
+        }
+
+        RichDocument {
+            blocks: self.containers.pop().unwrap_or_default(),
+        }
+    }
+}
+
+/// 按 Quill delta 操作构建文档，行属性挂在结束该行的换行符上。
+#[derive(Default)]
+struct OpsBuilder {
+    blocks: Vec<RichBlock>,
+    line: Vec<RichInline>,
+}
+
+impl OpsBuilder {
+    fn push_text(&mut self, text: &str, attribute: Option<&OpusAttribute>) {
+        if text.is_empty() {
+            return;
+        }
+
+        let flag = |value: Option<bool>| value.unwrap_or(false);
+        let inline = match attribute.and_then(|attribute| attribute.link.as_deref()) {
+            Some(url) if !url.is_empty() => RichInline::Link {
+                text: text.to_string(),
+                url: normalize_url(url),
+            },
+            _ => RichInline::Text {
+                text: text.to_string(),
+                style: attribute
+                    .map(|attribute| RichStyle {
+                        bold: flag(attribute.bold),
+                        italic: flag(attribute.italic),
+                        strike: flag(attribute.strike),
+                    })
+                    .unwrap_or_default(),
+            },
+        };
+        push_merged_inline(&mut self.line, inline);
+    }
+
+    fn end_line(&mut self, attribute: Option<&OpusAttribute>) {
+        let line = std::mem::take(&mut self.line);
+        let Some(inlines) = non_empty_inlines(line) else {
+            return;
+        };
+
+        let block = match attribute {
+            Some(OpusAttribute {
+                header: Some(level),
+                ..
+            }) => RichBlock::Heading {
+                level: u8::try_from((*level).clamp(1, 6)).unwrap_or(1),
+                inlines,
+            },
+            Some(OpusAttribute {
+                blockquote: Some(true),
+                ..
+            }) => RichBlock::Quote(vec![RichBlock::Paragraph(inlines)]),
+            Some(OpusAttribute {
+                list: Some(list), ..
+            }) => RichBlock::List {
+                ordered: list == "ordered",
+                items: vec![RichListItem { level: 1, inlines }],
+            },
+            _ => RichBlock::Paragraph(inlines),
+        };
+        push_merged(&mut self.blocks, block);
+    }
+
+    fn push_rich(&mut self, rich: &OpusRichInsert) {
+        self.end_line(None);
+
+        let block = if let Some(image) = &rich.native_image {
+            RichBlock::Image(RichImage {
+                url: normalize_url(&image.url),
+                alt: image.alt.clone(),
+                caption: String::new(),
+            })
+        } else if rich.cut_off.is_some() {
+            RichBlock::Rule
+        } else if let Some(card) = &rich.video_card {
+            RichBlock::Card(RichCard::new(RichCardKind::Video, &card.id))
+        } else if let Some(card) = &rich.article_card {
+            RichBlock::Card(RichCard::new(RichCardKind::Article, &card.id))
+        } else if let Some(card) = &rich.live_card {
+            RichBlock::Card(RichCard::new(RichCardKind::Live, &card.id))
+        } else if let Some(card) = &rich.vote_card {
+            RichBlock::Card(RichCard {
+                title: Some(card.alt.clone()).filter(|alt| !alt.is_empty()),
+                ..RichCard::new(RichCardKind::Other, &card.id)
+            })
+        } else {
+            return;
+        };
+        self.blocks.push(block);
+    }
+
+    fn finish(mut self) -> RichDocument {
+        self.end_line(None);
+        RichDocument {
+            blocks: self.blocks,
+        }
+    }
+}
+
+/// 追加块，相邻的同类列表和引用合并为一个。
+fn push_merged(blocks: &mut Vec<RichBlock>, block: RichBlock) {
+    match (blocks.last_mut(), block) {
+        (
+            Some(RichBlock::List { ordered, items }),
+            RichBlock::List {
+                ordered: next_ordered,
+                items: next_items,
+            },
+        ) if *ordered == next_ordered => items.extend(next_items),
+        (Some(RichBlock::Quote(blocks)), RichBlock::Quote(next)) => blocks.extend(next),
+        (_, block) => blocks.push(block),
+    }
+}
+
+/// 追加行内元素，相邻且样式相同的文本合并为一个。
+fn push_merged_inline(inlines: &mut Vec<RichInline>, inline: RichInline) {
+    if let (
+        Some(RichInline::Text { text, style }),
+        RichInline::Text {
+            text: next,
+            style: next_style,
+        },
+    ) = (inlines.last_mut(), &inline)
+        && style == next_style
+    {
+        text.push_str(next);
+        return;
+    }
+
+    inlines.push(inline);
+}
+
+#[cfg(feature = "dynamic")]
+fn push_text_lines(inlines: &mut Vec<RichInline>, text: &str, style: RichStyle) {
+    for (index, line) in text.split('\n').enumerate() {
+        if index > 0 {
+            inlines.push(RichInline::LineBreak);
+        }
+        if !line.is_empty() {
+            push_merged_inline(
+                inlines,
+                RichInline::Text {
+                    text: line.to_string(),
+                    style,
+                },
+            );
+        }
+    }
+}
+
+#[cfg(feature = "dynamic")]
+fn plain_text(text: &str) -> RichInline {
+    RichInline::Text {
+        text: text.to_string(),
+        style: RichStyle::default(),
+    }
+}
+
+/// 去掉首尾的换行和空白，内容为空时返回 `None`。
+fn non_empty_inlines(inlines: Vec<RichInline>) -> Option<Vec<RichInline>> {
+    let inlines = trim_inlines(inlines);
+    let has_content = inlines.iter().any(|inline| match inline {
+        RichInline::Text { text, .. } => !text.trim().is_empty(),
+        RichInline::LineBreak => false,
+        _ => true,
+    });
+
+    has_content.then_some(inlines)
+}
+
+fn trim_inlines(mut inlines: Vec<RichInline>) -> Vec<RichInline> {
+    let is_blank = |inline: &RichInline| match inline {
+        RichInline::LineBreak => true,
+        RichInline::Text { text, .. } => text.trim().is_empty(),
+        _ => false,
+    };
+    while inlines.last().is_some_and(is_blank) {
+        inlines.pop();
+    }
+    let leading = inlines.iter().take_while(|inline| is_blank(inline)).count();
+    inlines.drain(..leading);
+
+    if let Some(RichInline::Text { text, .. }) = inlines.first_mut() {
+        *text = text.trim_start().to_string();
+    }
+    if let Some(RichInline::Text { text, .. }) = inlines.last_mut() {
+        *text = text.trim_end().to_string();
+    }
+
+    inlines
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 从 `language-rust` 形式的类名中取出语言。
+fn code_lang(class: &str) -> Option<String> {
+    class
+        .strip_prefix("language-")
+        .filter(|lang| !lang.is_empty())
+        .map(str::to_string)
+}
+
+fn normalize_url(url: &str) -> String {
+    let url = url.trim();
+    if url.starts_with("//") {
+        format!("https:{url}")
+    } else {
+        url.to_string()
+    }
+}
+
+fn collect_card_ids(blocks: &[RichBlock], ids: &mut Vec<String>) {
+    for block in blocks {
+        match block {
+            RichBlock::Card(card) if card.is_resolvable() && !ids.contains(&card.id) => {
+                ids.push(card.id.clone());
+            }
+            RichBlock::Quote(blocks) => collect_card_ids(blocks, ids),
+            _ => {}
+        }
+    }
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '~') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+fn markdown_url(url: &str) -> String {
+    url.replace(' ', "%20")
+        .replace('(', "%28")
+        .replace(')', "%29")
+}
+
+fn render_markdown(blocks: &[RichBlock], cards: Option<&CardData>) -> String {
+    blocks
+        .iter()
+        .map(|block| markdown_block(block, cards))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn markdown_block(block: &RichBlock, cards: Option<&CardData>) -> String {
+    match block {
+        RichBlock::Heading { level, inlines } => format!(
+            "{} {}",
+            "#".repeat(usize::from((*level).clamp(1, 6))),
+            markdown_inlines(inlines, true)
+        ),
+        RichBlock::Paragraph(inlines) => markdown_inlines(inlines, false),
+        RichBlock::Image(image) => {
+            let alt = if image.alt.is_empty() {
+                &image.caption
+            } else {
+                &image.alt
+            };
+            let mut markdown = format!("![{}]({})", escape_markdown(alt), markdown_url(&image.url));
+            if !image.caption.is_empty() {
+                markdown.push_str(&format!("\n\n*{}*", escape_markdown(&image.caption)));
+            }
+            markdown
+        }
+        RichBlock::Code { lang, code } => {
+            let longest = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+            let fence = "`".repeat(longest.max(2) + 1);
+            format!(
+                "{fence}{}\n{}\n{fence}",
+                lang.as_deref().unwrap_or_default(),
+                code.trim_end_matches('\n')
+            )
+        }
+        RichBlock::Quote(blocks) => render_markdown(blocks, cards)
+            .lines()
+            .map(|line| {
+                if line.is_empty() {
+                    ">".to_string()
+                } else {
+                    format!("> {line}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        RichBlock::List { ordered, items } => {
+            list_lines(*ordered, items, |inlines| markdown_inlines(inlines, false))
+        }
+        RichBlock::Formula(latex) => format!("$$\n{}\n$$", latex.trim()),
+        RichBlock::Card(card) => {
+            let (title, url) = card.resolve(cards);
+            match url {
+                Some(url) => format!("[{}]({})", escape_markdown(&title), markdown_url(&url)),
+                None => escape_markdown(&title),
+            }
+        }
+        RichBlock::Rule => "---".to_string(),
+    }
+}
+
+/// 渲染行内元素；标题本身已经醒目，忽略其中的加粗。
+fn markdown_inlines(inlines: &[RichInline], in_heading: bool) -> String {
+    let mut markdown = String::new();
+    for inline in inlines {
+        match inline {
+            RichInline::Text { text, style } => {
+                let core = text.trim();
+                if core.is_empty() {
+                    markdown.push_str(text);
+                    continue;
+                }
+
+                let mut marker = String::new();
+                if style.bold && !in_heading {
+                    marker.push_str("**");
+                }
+                if style.italic {
+                    marker.push('*');
+                }
+                if style.strike {
+                    marker.push_str("~~");
+                }
+                let closing = marker.chars().rev().collect::<String>();
+                let leading = &text[..text.len() - text.trim_start().len()];
+                let trailing = &text[text.trim_end().len()..];
+                markdown.push_str(&format!(
+                    "{leading}{marker}{}{closing}{trailing}",
+                    escape_markdown(core)
+                ));
+            }
+            RichInline::Link { text, url } => {
+                markdown.push_str(&format!(
+                    "[{}]({})",
+                    escape_markdown(text),
+                    markdown_url(url)
+                ));
+            }
+            RichInline::Formula(latex) => markdown.push_str(&format!("${}$", latex.trim())),
+            RichInline::LineBreak => markdown.push_str("\\\n"),
+        }
+    }
+
+    markdown
+}
+
+fn list_lines(
+    ordered: bool,
+    items: &[RichListItem],
+    render: impl Fn(&[RichInline]) -> String,
+) -> String {
+    let mut counters: Vec<usize> = Vec::new();
+    items
+        .iter()
+        .map(|item| {
+            let depth = usize::from(item.level.max(1));
+            counters.resize(depth, 0);
+            counters[depth - 1] += 1;
+            let marker = if ordered {
+                format!("{}.", counters[depth - 1])
+            } else {
+                "-".to_string()
+            };
+            let indent = "    ".repeat(depth - 1);
+            let text = render(&item.inlines).replace('\n', &format!("\n{indent}  "));
+            format!("{indent}{marker} {text}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_plain(blocks: &[RichBlock], cards: Option<&CardData>) -> String {
+    blocks
+        .iter()
+        .filter_map(|block| plain_block(block, cards))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn plain_block(block: &RichBlock, cards: Option<&CardData>) -> Option<String> {
+    let text = match block {
+        RichBlock::Heading { inlines, .. } | RichBlock::Paragraph(inlines) => {
+            plain_inlines(inlines)
+        }
+        RichBlock::Image(image) => {
+            if image.caption.is_empty() {
+                image.alt.clone()
+            } else {
+                image.caption.clone()
+            }
+        }
+        RichBlock::Code { code, .. } => code.trim_end_matches('\n').to_string(),
+        RichBlock::Quote(blocks) => render_plain(blocks, cards),
+        RichBlock::List { ordered, items } => list_lines(*ordered, items, plain_inlines),
+        RichBlock::Formula(latex) => latex.trim().to_string(),
+        RichBlock::Card(card) => card.resolve(cards).0,
+        RichBlock::Rule => String::new(),
+    };
+
+    (!text.is_empty()).then_some(text)
+}
+
+fn plain_inlines(inlines: &[RichInline]) -> String {
+    inlines
+        .iter()
+        .map(|inline| match inline {
+            RichInline::Text { text, .. } | RichInline::Link { text, .. } => text.as_str(),
+            RichInline::Formula(latex) => latex.as_str(),
+            RichInline::LineBreak => "\n",
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ApiEnvelope;
+
+    #[test]
+    fn html_converts_common_blocks_to_markdown() {
+        let html = concat!(
+            r#"<h1><strong>标题</strong><br/></h1>"#,
+            r#"<p>普通<strong>加粗</strong>和<a href="//www.bilibili.com/read/cv1">链接</a>&amp;符号</p>"#,
+            r#"<p><br/></p>"#,
+            r#"<figure class="img-box"><img data-src="//i0.hdslb.com/bfs/article/a.png" alt=""/>"#,
+            r#"<figcaption>图注</figcaption></figure>"#,
+            r#"<figure class="img-box"><img src="//i0.hdslb.com/bfs/article/line.png" class="cut-off-5"/></figure>"#,
+            r#"<figure class="code-box"><pre class="language-rust" codecontent="fn main() {&#10;}"><code>ignored</code></pre></figure>"#,
+            r#"<blockquote><p>引用一</p><p>引用二</p></blockquote>"#,
+            r#"<ol><li>第一</li><li>第二</li></ol>"#,
+            r#"<figure class="img-box"><img aid="av170001" class="video-card nomal" type="normal"/></figure>"#,
+            r#"<p>公式<img class="latex" alt="E=mc^2" src="//example.invalid/tex.png"/>结束</p>"#,
+        );
+
+        let markdown = RichDocument::from_html(html).to_markdown();
+
+        assert_eq!(
+            markdown,
+            concat!(
+                "# 标题\n\n",
+                "普通**加粗**和[链接](https://www.bilibili.com/read/cv1)&符号\n\n",
+                "![图注](https://i0.hdslb.com/bfs/article/a.png)\n\n*图注*\n\n",
+                "---\n\n",
+                "```rust\nfn main() {\n}\n```\n\n",
+                "> 引用一\n>\n> 引用二\n\n",
+                "1. 第一\n2. 第二\n\n",
+                "[视频 av170001](https://www.bilibili.com/video/av170001)\n\n",
+                "公式$E=mc^2$结束"
+            )
+        );
+    }
+
+    #[test]
+    fn html_leading_blockquote_stays_a_quote() {
+        let document =
+            RichDocument::from_html("<blockquote><p>开头引用</p></blockquote><p>正文</p>");
+
+        assert!(matches!(
+            document.blocks.as_slice(),
+            [RichBlock::Quote(quote), RichBlock::Paragraph(_)]
+                if matches!(quote.as_slice(), [RichBlock::Paragraph(_)])
+        ));
+        assert_eq!(document.to_markdown(), "> 开头引用\n\n正文");
+    }
+
+    #[test]
+    fn html_plain_text_drops_markup() {
+        let document = RichDocument::from_html(
+            "<p>第一行<br/>第二行</p><ul><li><em>条目</em></li></ul><pre>let x = 1;</pre>",
+        );
+
+        assert_eq!(
+            document.to_plain_text(),
+            "第一行\n第二行\n- 条目\nlet x = 1;"
+        );
+        assert_eq!(
+            document.blocks[2],
+            RichBlock::Code {
+                lang: None,
+                code: "let x = 1;".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn cards_resolve_titles_from_cards_response() -> BpiResult<()> {
+        let document = RichDocument::from_html(
+            r#"<figure><img aid="cv1" class="article-card"/></figure><figure><img aid="cv1" class="article-card"/></figure>"#,
+        );
+        let cards = ApiEnvelope::<CardData>::from_slice(include_bytes!(
+            "../../tests/contracts/article/cards/responses/normal.success.json"
+        ))?
+        .into_payload()?;
+        let Some(CardItem::Article(card)) = cards.get("cv1") else {
+            return Err(BpiError::missing_data());
+        };
+
+        assert_eq!(document.card_ids(), vec!["cv1".to_string()]);
+        assert!(
+            document
+                .to_markdown_with_cards(&cards)
+                .starts_with(&format!("[专栏：{}]", escape_markdown(card.title.trim())))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn opus_ops_apply_line_attributes() -> BpiResult<()> {
+        let opus: ArticleOpus = serde_json::from_value(serde_json::json!({
+            "ops": [
+                { "insert": "小标题" },
+                { "attribute": { "header": 2 }, "insert": "\n" },
+                { "attribute": { "bold": true }, "insert": "重点" },
+                { "insert": "说明\n条目一" },
+                { "attribute": { "list": "bullet" }, "insert": "\n" },
+                { "insert": "条目二" },
+                { "attribute": { "list": "bullet" }, "insert": "\n" },
+                { "insert": { "native_image": {
+                    "alt": "图", "url": "//i0.hdslb.com/bfs/article/b.png",
+                    "width": 1, "height": 1, "size": 1, "status": "loaded"
+                } } },
+                { "insert": { "video_card": {
+                    "alt": "", "height": 0, "id": "170001", "size": null,
+                    "status": "", "tid": 0, "url": "", "width": 0
+                } } }
+            ]
+        }))?;
+
+        let markdown = RichDocument::from_opus(&opus).to_markdown();
+
+        assert_eq!(
+            markdown,
+            concat!(
+                "## 小标题\n\n",
+                "**重点**说明\n\n",
+                "- 条目一\n- 条目二\n\n",
+                "![图](https://i0.hdslb.com/bfs/article/b.png)\n\n",
+                "[视频 av170001](https://www.bilibili.com/video/av170001)"
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn article_view_fixture_converts_to_markdown() -> BpiResult<()> {
+        let view = ApiEnvelope::<ArticleViewData>::from_slice(include_bytes!(
+            "../../tests/contracts/article/view/responses/normal.success.json"
+        ))?
+        .into_payload()?;
+
+        let markdown = view.to_markdown()?;
+        let plain = view.to_plain_text()?;
+
+        assert!(markdown.starts_with(&format!("# {}\n\n", escape_markdown(view.title.trim()))));
+        assert!(markdown.contains("\n# 严令禁止条例\n"));
+        assert!(!markdown.contains('<'));
+        assert!(plain.contains("严令禁止条例"));
+        assert!(!plain.contains("**"));
+        Ok(())
+    }
+
+    #[test]
+    fn markdown_escapes_special_characters() {
+        let document = RichDocument::from_html("<p>a*b_c [d]</p>");
+
+        assert_eq!(document.to_markdown(), r"a\*b\_c \[d\]");
+    }
+
+    #[cfg(feature = "dynamic")]
+    #[test]
+    fn opus_paragraphs_share_the_same_ast() -> BpiResult<()> {
+        use crate::dynamic::Paragraph;
+
+        let paragraphs: Vec<Paragraph> = serde_json::from_value(serde_json::json!([
+            { "align": 0, "para_type": 1, "text": { "nodes": [
+                { "type": "TEXT_NODE_TYPE_WORD", "word": {
+                    "font_size": 17, "style": { "bold": true }, "words": "加粗"
+                } },
+                { "type": "TEXT_NODE_TYPE_FORMULA", "formula": { "latex_content": "x^2" } }
+            ] } },
+            { "align": 0, "para_type": 5, "list": { "style": 1, "items": [
+                { "level": 1, "order": 1, "nodes": [
+                    { "type": "TEXT_NODE_TYPE_WORD", "word": {
+                        "font_size": 17, "style": {}, "words": "一"
+                    } }
+                ] },
+                { "level": 2, "order": 1, "nodes": [
+                    { "type": "TEXT_NODE_TYPE_WORD", "word": {
+                        "font_size": 17, "style": {}, "words": "一点一"
+                    } }
+                ] }
+            ] } },
+            { "align": 0, "para_type": 7, "code": {
+                "content": "print(1)", "lang": "language-python"
+            } },
+            { "align": 0, "para_type": 6, "link_card": { "card": {
+                "oid": "2", "type": "LINK_CARD_TYPE_UGC"
+            } } }
+        ]))?;
+
+        let document = RichDocument::from_paragraphs(&paragraphs);
+
+        assert_eq!(
+            document.to_markdown(),
+            concat!(
+                "**加粗**$x^2$\n\n",
+                "1. 一\n    1. 一点一\n\n",
+                "```python\nprint(1)\n```\n\n",
+                "[视频 av2](https://www.bilibili.com/video/av2)"
+            )
+        );
+        assert_eq!(document.card_ids(), vec!["av2".to_string()]);
+        Ok(())
+    }
+}
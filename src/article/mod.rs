@@ -10,11 +10,15 @@ pub mod card;
 pub mod category;
 mod client;
 pub mod info;
+pub mod markdown;
 mod models;
 pub mod params;
 pub mod view;
 
 pub use client::ArticleClient;
+pub use markdown::{
+    RichBlock, RichCard, RichCardKind, RichDocument, RichImage, RichInline, RichListItem, RichStyle,
+};
 pub use params::{
     ArticleArticlesInfoParams, ArticleCardsParams, ArticleCoinParams, ArticleFavoriteParams,
     ArticleInfoParams, ArticleLikeParams, ArticleViewParams,
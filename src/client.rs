@@ -34,6 +34,8 @@ use crate::fav::FavClient;
 use crate::historytoview::HistoryToViewClient;
 #[cfg(feature = "live")]
 use crate::live::LiveClient;
+#[cfg(feature = "live")]
+use crate::live::room_init::LiveRoomCache;
 #[cfg(feature = "login")]
 use crate::login::LoginClient;
 #[cfg(feature = "manga")]
@@ -229,6 +231,8 @@ impl BpiClientBuilder {
             origin: validate_header("origin", &self.origin)?,
             cookie_header: Mutex::new(cookie_header),
             wbi_key_cache: WbiKeyCache::default(),
+            #[cfg(feature = "live")]
+            live_room_cache: LiveRoomCache::default(),
         })
     }
 }
@@ -243,6 +247,8 @@ pub struct BpiClient {
     origin: HeaderValue,
     cookie_header: Mutex<Option<String>>,
     wbi_key_cache: WbiKeyCache,
+    #[cfg(feature = "live")]
+    live_room_cache: LiveRoomCache,
 }
 
 impl BpiClient {
@@ -357,6 +363,12 @@ impl BpiClient {
         self.apply_default_headers(url, self.client.post(url))
     }
 
+    /// 使用此客户端默认的 Bilibili 请求头创建 PUT 请求。
+    #[cfg(feature = "creativecenter")]
+    pub(crate) fn put(&self, url: &str) -> RequestBuilder {
+        self.apply_default_headers(url, self.client.put(url))
+    }
+
     fn apply_default_headers(&self, url: &str, builder: RequestBuilder) -> RequestBuilder {
         let builder = builder
             .header(USER_AGENT, self.user_agent.clone())
@@ -382,6 +394,16 @@ impl BpiClient {
         &self.wbi_key_cache
     }
 
+    #[cfg(feature = "live")]
+    pub(crate) fn user_agent(&self) -> &str {
+        self.user_agent.to_str().unwrap_or_default()
+    }
+
+    #[cfg(feature = "live")]
+    pub(crate) fn live_room_cache(&self) -> &LiveRoomCache {
+        &self.live_room_cache
+    }
+
     #[cfg(test)]
     fn cookie_header_for_test(&self) -> Option<String> {
         self.cookie_header
@@ -0,0 +1,731 @@
+//! 创作中心数据分析
+//!
+//! 把 `up_stat`、`article_stat`、`video_trend`、`article_trend`、`archive_compare`、
+//! `play_source` 和 `viewer_data` 的响应统一转换为长表格式的
+//! `(metric, dimension, timestamp, value)` 数据点，便于落盘和计算两次快照之间的变化。
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write as _;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::creativecenter::statistics_data::{
+    ArchiveCompareData, ArticleTrendItem, PlaySourceData, UpArticleStatData, UpStatData,
+    VideoTrendItem, ViewerBaseDetail, ViewerData,
+};
+use crate::creativecenter::{
+    CreativeCenterClient, UpArchiveCompareParams, UpArticleTrendMetric, UpArticleTrendParams,
+    UpVideoTrendMetric, UpVideoTrendParams,
+};
+use crate::utils::csv;
+use crate::{BpiError, BpiResult};
+
+const VIDEO_TREND_METRICS: [UpVideoTrendMetric; 8] = [
+    UpVideoTrendMetric::Play,
+    UpVideoTrendMetric::Danmaku,
+    UpVideoTrendMetric::Reply,
+    UpVideoTrendMetric::Share,
+    UpVideoTrendMetric::Coin,
+    UpVideoTrendMetric::Favorite,
+    UpVideoTrendMetric::Charge,
+    UpVideoTrendMetric::Like,
+];
+
+const ARTICLE_TREND_METRICS: [UpArticleTrendMetric; 6] = [
+    UpArticleTrendMetric::Read,
+    UpArticleTrendMetric::Reply,
+    UpArticleTrendMetric::Share,
+    UpArticleTrendMetric::Coin,
+    UpArticleTrendMetric::Favorite,
+    UpArticleTrendMetric::Like,
+];
+
+/// 长表格式的一个数据点。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalyticsPoint {
+    /// 指标名，形如 `video.play`
+    pub metric: String,
+    /// 维度，如 `total`、`incr`、稿件 bvid 或 `fan:male`
+    pub dimension: String,
+    /// 数据对应的秒级时间戳
+    pub timestamp: i64,
+    /// 数值
+    pub value: i64,
+}
+
+impl AnalyticsPoint {
+    pub fn new(
+        metric: impl Into<String>,
+        dimension: impl Into<String>,
+        timestamp: i64,
+        value: i64,
+    ) -> Self {
+        Self {
+            metric: metric.into(),
+            dimension: dimension.into(),
+            timestamp,
+            value,
+        }
+    }
+}
+
+/// 视频总览数据，`timestamp` 为采集时间。
+pub fn up_stat_points(data: &UpStatData, timestamp: i64) -> Vec<AnalyticsPoint> {
+    let pairs = [
+        ("video.click", data.total_click, data.incr_click),
+        ("video.coin", data.total_coin, data.inc_coin),
+        ("video.danmaku", data.total_dm, data.incr_dm),
+        ("video.elec", data.total_elec, data.inc_elec),
+        ("video.fans", data.total_fans, data.incr_fans),
+        ("video.fav", data.total_fav, data.inc_fav),
+        ("video.like", data.total_like, data.inc_like),
+        ("video.reply", data.total_reply, data.incr_reply),
+        ("video.share", data.total_share, data.inc_share),
+    ];
+    total_incr_points(&pairs, timestamp)
+}
+
+/// 专栏总览数据，`timestamp` 为采集时间。
+pub fn article_stat_points(data: &UpArticleStatData, timestamp: i64) -> Vec<AnalyticsPoint> {
+    let pairs = [
+        ("article.view", data.view, data.incr_view),
+        ("article.reply", data.reply, data.incr_reply),
+        ("article.like", data.like, data.incr_like),
+        ("article.coin", data.coin, data.incr_coin),
+        ("article.fav", data.fav, data.incr_fav),
+        ("article.share", data.share, data.incr_share),
+    ];
+    total_incr_points(&pairs, timestamp)
+}
+
+/// 视频每日增量趋势，时间戳取自 `date_key`。
+pub fn video_trend_points(
+    metric: UpVideoTrendMetric,
+    items: &[VideoTrendItem],
+) -> Vec<AnalyticsPoint> {
+    let metric = video_trend_metric_name(metric);
+    items
+        .iter()
+        .map(|item| AnalyticsPoint::new(metric, "daily_inc", item.date_key, item.total_inc))
+        .collect()
+}
+
+/// 专栏每日增量趋势，时间戳取自 `date_key`。
+pub fn article_trend_points(
+    metric: UpArticleTrendMetric,
+    items: &[ArticleTrendItem],
+) -> Vec<AnalyticsPoint> {
+    let metric = article_trend_metric_name(metric);
+    items
+        .iter()
+        .map(|item| AnalyticsPoint::new(metric, "daily_inc", item.date_key, item.total_inc))
+        .collect()
+}
+
+/// 稿件对比数据，维度为稿件 bvid，`timestamp` 为采集时间。
+pub fn archive_compare_points(data: &ArchiveCompareData, timestamp: i64) -> Vec<AnalyticsPoint> {
+    let mut points = Vec::new();
+    for item in &data.list {
+        let stat = &item.stat;
+        let values = [
+            ("archive.play", stat.play),
+            ("archive.like", stat.like),
+            ("archive.comment", stat.comment),
+            ("archive.danmaku", stat.dm),
+            ("archive.fav", stat.fav),
+            ("archive.coin", stat.coin),
+            ("archive.share", stat.share),
+            ("archive.new_attention", stat.total_new_attention_cnt),
+            ("archive.unfollow", stat.unfollow),
+            ("archive.full_play_ratio", stat.full_play_ratio),
+            ("archive.avg_play_time", stat.avg_play_time),
+        ];
+        points.extend(
+            values
+                .into_iter()
+                .map(|(metric, value)| AnalyticsPoint::new(metric, &item.bvid, timestamp, value)),
+        );
+    }
+
+    points
+}
+
+/// 播放来源和播放平台占比，`timestamp` 为采集时间。
+pub fn play_source_points(data: &PlaySourceData, timestamp: i64) -> Vec<AnalyticsPoint> {
+    let page = &data.page_source;
+    let platform = &data.play_proportion;
+    let values = [
+        ("play_source.page", "dynamic", page.dynamic),
+        ("play_source.page", "other", page.other),
+        ("play_source.page", "related_video", page.related_video),
+        ("play_source.page", "search", page.search),
+        ("play_source.page", "space", page.space),
+        ("play_source.page", "tenma", page.tenma),
+        ("play_source.platform", "android", platform.android),
+        ("play_source.platform", "h5", platform.h5),
+        ("play_source.platform", "ios", platform.ios),
+        ("play_source.platform", "out", platform.out),
+        ("play_source.platform", "pc", platform.pc),
+    ];
+
+    values
+        .into_iter()
+        .map(|(metric, dimension, value)| AnalyticsPoint::new(metric, dimension, timestamp, value))
+        .collect()
+}
+
+/// 观众画像，维度形如 `fan:male`、`not_fan:广东`，`timestamp` 为采集时间。
+pub fn viewer_data_points(data: &ViewerData, timestamp: i64) -> Vec<AnalyticsPoint> {
+    let mut points = Vec::new();
+    for (audience, detail) in [
+        ("fan", &data.viewer_base.fan),
+        ("not_fan", &data.viewer_base.not_fan),
+    ] {
+        points.extend(viewer_base_points(audience, detail, timestamp));
+    }
+
+    for (audience, areas) in [
+        ("fan", &data.viewer_area.fan),
+        ("not_fan", &data.viewer_area.not_fan),
+    ] {
+        let areas = areas.iter().collect::<BTreeMap<_, _>>();
+        points.extend(areas.into_iter().map(|(area, value)| {
+            AnalyticsPoint::new(
+                "viewer.area",
+                format!("{audience}:{area}"),
+                timestamp,
+                *value,
+            )
+        }));
+    }
+
+    points
+}
+
+/// 一次采集得到的全部数据点。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalyticsSnapshot {
+    /// 采集时间，秒级时间戳
+    pub taken_at: i64,
+    /// 数据点
+    pub points: Vec<AnalyticsPoint>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AnalyticsRow {
+    taken_at: i64,
+    #[serde(flatten)]
+    point: AnalyticsPoint,
+}
+
+impl AnalyticsSnapshot {
+    pub fn new(taken_at: i64, points: Vec<AnalyticsPoint>) -> Self {
+        Self { taken_at, points }
+    }
+
+    /// 序列化为 JSONL，每行一个数据点并带上 `taken_at`。
+    pub fn to_jsonl(&self) -> BpiResult<String> {
+        let mut jsonl = String::new();
+        for point in &self.points {
+            let row = AnalyticsRow {
+                taken_at: self.taken_at,
+                point: point.clone(),
+            };
+            jsonl.push_str(&serde_json::to_string(&row)?);
+            jsonl.push('\n');
+        }
+
+        Ok(jsonl)
+    }
+
+    /// 解析 [`Self::to_jsonl`] 写出的内容，按 `taken_at` 分组还原为快照。
+    pub fn parse_jsonl(jsonl: &str) -> BpiResult<Vec<Self>> {
+        let mut snapshots: BTreeMap<i64, Vec<AnalyticsPoint>> = BTreeMap::new();
+        for line in jsonl.lines().filter(|line| !line.trim().is_empty()) {
+            let row: AnalyticsRow = serde_json::from_str(line)?;
+            snapshots.entry(row.taken_at).or_default().push(row.point);
+        }
+
+        Ok(snapshots
+            .into_iter()
+            .map(|(taken_at, points)| Self { taken_at, points })
+            .collect())
+    }
+
+    /// 序列化为 CSV 数据行，不含表头。
+    pub fn to_csv_rows(&self) -> String {
+        let mut csv = String::new();
+        for point in &self.points {
+            csv::push_row(
+                &mut csv,
+                &[
+                    self.taken_at.to_string(),
+                    point.metric.clone(),
+                    point.dimension.clone(),
+                    point.timestamp.to_string(),
+                    point.value.to_string(),
+                ],
+            );
+        }
+
+        csv
+    }
+
+    /// 序列化为带表头的 CSV。
+    pub fn to_csv(&self) -> String {
+        format!("{}\n{}", CSV_HEADER, self.to_csv_rows())
+    }
+
+    /// 追加写入 JSONL 文件，文件不存在时创建。
+    pub fn append_jsonl(&self, path: impl AsRef<Path>) -> BpiResult<()> {
+        append_file(path.as_ref(), None, &self.to_jsonl()?)
+    }
+
+    /// 追加写入 CSV 文件，文件不存在或为空时先写表头。
+    pub fn append_csv(&self, path: impl AsRef<Path>) -> BpiResult<()> {
+        append_file(path.as_ref(), Some(CSV_HEADER), &self.to_csv_rows())
+    }
+
+    /// 与上一次快照对比每个 `(metric, dimension)` 的最新值。
+    ///
+    /// 同一指标和维度有多个数据点时（如每日趋势），取时间戳最新的一个比较。
+    /// 只在一侧出现的指标也会列出，缺失一侧的值为 `None`。
+    pub fn delta(&self, previous: &AnalyticsSnapshot) -> Vec<AnalyticsDelta> {
+        let before = latest_points(&previous.points);
+        let after = latest_points(&self.points);
+        let keys = before.keys().chain(after.keys()).collect::<BTreeSet<_>>();
+
+        keys.into_iter()
+            .map(|key| {
+                let previous = before.get(key);
+                let current = after.get(key);
+                AnalyticsDelta {
+                    metric: key.0.to_string(),
+                    dimension: key.1.to_string(),
+                    previous: previous.map(|point| point.value),
+                    current: current.map(|point| point.value),
+                    change: previous
+                        .zip(current)
+                        .map(|(previous, current)| current.value - previous.value),
+                }
+            })
+            .collect()
+    }
+}
+
+const CSV_HEADER: &str = "taken_at,metric,dimension,timestamp,value";
+
+/// 两次快照之间某个指标的变化。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalyticsDelta {
+    pub metric: String,
+    pub dimension: String,
+    /// 上一次快照中的值
+    pub previous: Option<i64>,
+    /// 本次快照中的值
+    pub current: Option<i64>,
+    /// `current - previous`，任一侧缺失时为 `None`
+    pub change: Option<i64>,
+}
+
+/// 快照采集的数据源。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AnalyticsSource {
+    /// 视频总览
+    UpStat,
+    /// 专栏总览
+    ArticleStat,
+    /// 视频每日增量，每个指标一次请求
+    VideoTrend,
+    /// 专栏每日增量，每个指标一次请求
+    ArticleTrend,
+    /// 稿件对比
+    ArchiveCompare,
+    /// 播放来源
+    PlaySource,
+    /// 观众画像
+    ViewerData,
+}
+
+/// [`CreativeCenterClient::analytics_snapshot`] 的参数。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalyticsSnapshotParams {
+    sources: BTreeSet<AnalyticsSource>,
+}
+
+impl AnalyticsSnapshotParams {
+    /// 采集全部数据源。
+    pub fn all() -> Self {
+        Self {
+            sources: BTreeSet::from([
+                AnalyticsSource::UpStat,
+                AnalyticsSource::ArticleStat,
+                AnalyticsSource::VideoTrend,
+                AnalyticsSource::ArticleTrend,
+                AnalyticsSource::ArchiveCompare,
+                AnalyticsSource::PlaySource,
+                AnalyticsSource::ViewerData,
+            ]),
+        }
+    }
+
+    /// 只采集指定的数据源。
+    pub fn new(sources: impl IntoIterator<Item = AnalyticsSource>) -> BpiResult<Self> {
+        let sources = sources.into_iter().collect::<BTreeSet<_>>();
+        if sources.is_empty() {
+            return Err(BpiError::invalid_parameter(
+                "sources",
+                "at least one source is required",
+            ));
+        }
+
+        Ok(Self { sources })
+    }
+}
+
+impl<'a> CreativeCenterClient<'a> {
+    /// 按参数采集一次数据快照。
+    pub async fn analytics_snapshot(
+        &self,
+        params: &AnalyticsSnapshotParams,
+    ) -> BpiResult<AnalyticsSnapshot> {
+        let taken_at = Utc::now().timestamp();
+        let mut points = Vec::new();
+
+        for source in &params.sources {
+            match source {
+                AnalyticsSource::UpStat => {
+                    points.extend(up_stat_points(&self.up_stat().await?, taken_at));
+                }
+                AnalyticsSource::ArticleStat => {
+                    points.extend(article_stat_points(&self.article_stat().await?, taken_at));
+                }
+                AnalyticsSource::VideoTrend => {
+                    for metric in VIDEO_TREND_METRICS {
+                        let items = self.video_trend(UpVideoTrendParams::new(metric)).await?;
+                        points.extend(video_trend_points(metric, &items));
+                    }
+                }
+                AnalyticsSource::ArticleTrend => {
+                    for metric in ARTICLE_TREND_METRICS {
+                        let items = self
+                            .article_trend(UpArticleTrendParams::new(metric))
+                            .await?;
+                        points.extend(article_trend_points(metric, &items));
+                    }
+                }
+                AnalyticsSource::ArchiveCompare => {
+                    let data = self.archive_compare(UpArchiveCompareParams::new()).await?;
+                    points.extend(archive_compare_points(&data, taken_at));
+                }
+                AnalyticsSource::PlaySource => {
+                    points.extend(play_source_points(&self.play_source().await?, taken_at));
+                }
+                AnalyticsSource::ViewerData => {
+                    points.extend(viewer_data_points(&self.viewer_data().await?, taken_at));
+                }
+            }
+        }
+
+        Ok(AnalyticsSnapshot::new(taken_at, points))
+    }
+
+    /// 创建按固定间隔采集快照的采集器。
+    pub fn analytics_snapshotter(
+        &self,
+        params: AnalyticsSnapshotParams,
+        interval: Duration,
+    ) -> AnalyticsSnapshotter<'a> {
+        AnalyticsSnapshotter {
+            client: *self,
+            params,
+            interval,
+            previous: None,
+        }
+    }
+}
+
+/// 定期采集数据快照，并给出与上一次快照的差异。
+pub struct AnalyticsSnapshotter<'a> {
+    client: CreativeCenterClient<'a>,
+    params: AnalyticsSnapshotParams,
+    interval: Duration,
+    previous: Option<AnalyticsSnapshot>,
+}
+
+impl<'a> AnalyticsSnapshotter<'a> {
+    /// 设置上一次快照，通常来自 [`AnalyticsSnapshot::parse_jsonl`]，用于跨进程计算差异。
+    pub fn with_previous(mut self, previous: AnalyticsSnapshot) -> Self {
+        self.previous = Some(previous);
+        self
+    }
+
+    /// 立即采集一次快照，返回快照和与上一次快照的差异；首次采集没有差异。
+    pub async fn poll(&mut self) -> BpiResult<(AnalyticsSnapshot, Vec<AnalyticsDelta>)> {
+        let snapshot = self.client.analytics_snapshot(&self.params).await?;
+        let deltas = self
+            .previous
+            .as_ref()
+            .map(|previous| snapshot.delta(previous))
+            .unwrap_or_default();
+        self.previous = Some(snapshot.clone());
+
+        Ok((snapshot, deltas))
+    }
+
+    /// 等待一个采集间隔后采集快照。
+    pub async fn next(&mut self) -> BpiResult<(AnalyticsSnapshot, Vec<AnalyticsDelta>)> {
+        tokio::time::sleep(self.interval).await;
+        self.poll().await
+    }
+}
+
+fn total_incr_points(pairs: &[(&str, i64, i64)], timestamp: i64) -> Vec<AnalyticsPoint> {
+    pairs
+        .iter()
+        .flat_map(|(metric, total, incr)| {
+            [
+                AnalyticsPoint::new(*metric, "total", timestamp, *total),
+                AnalyticsPoint::new(*metric, "incr", timestamp, *incr),
+            ]
+        })
+        .collect()
+}
+
+fn viewer_base_points(
+    audience: &str,
+    detail: &ViewerBaseDetail,
+    timestamp: i64,
+) -> Vec<AnalyticsPoint> {
+    let values = [
+        ("viewer.gender", "male", detail.male),
+        ("viewer.gender", "female", detail.female),
+        ("viewer.age", "0-16", detail.age_one),
+        ("viewer.age", "16-25", detail.age_two),
+        ("viewer.age", "25-40", detail.age_three),
+        ("viewer.age", "40+", detail.age_four),
+        ("viewer.platform", "pc", detail.plat_pc),
+        ("viewer.platform", "h5", detail.plat_h5),
+        ("viewer.platform", "out", detail.plat_out),
+        ("viewer.platform", "ios", detail.plat_ios),
+        ("viewer.platform", "android", detail.plat_android),
+        ("viewer.platform", "other_app", detail.plat_other_app),
+    ];
+
+    values
+        .into_iter()
+        .map(|(metric, dimension, value)| {
+            AnalyticsPoint::new(metric, format!("{audience}:{dimension}"), timestamp, value)
+        })
+        .collect()
+}
+
+fn video_trend_metric_name(metric: UpVideoTrendMetric) -> &'static str {
+    match metric {
+        UpVideoTrendMetric::Play => "video.play",
+        UpVideoTrendMetric::Danmaku => "video.danmaku",
+        UpVideoTrendMetric::Reply => "video.reply",
+        UpVideoTrendMetric::Share => "video.share",
+        UpVideoTrendMetric::Coin => "video.coin",
+        UpVideoTrendMetric::Favorite => "video.fav",
+        UpVideoTrendMetric::Charge => "video.elec",
+        UpVideoTrendMetric::Like => "video.like",
+    }
+}
+
+fn article_trend_metric_name(metric: UpArticleTrendMetric) -> &'static str {
+    match metric {
+        UpArticleTrendMetric::Read => "article.view",
+        UpArticleTrendMetric::Reply => "article.reply",
+        UpArticleTrendMetric::Share => "article.share",
+        UpArticleTrendMetric::Coin => "article.coin",
+        UpArticleTrendMetric::Favorite => "article.fav",
+        UpArticleTrendMetric::Like => "article.like",
+    }
+}
+
+fn latest_points(points: &[AnalyticsPoint]) -> BTreeMap<(&str, &str), &AnalyticsPoint> {
+    let mut latest: BTreeMap<(&str, &str), &AnalyticsPoint> = BTreeMap::new();
+    for point in points {
+        let key = (point.metric.as_str(), point.dimension.as_str());
+        match latest.get(&key) {
+            Some(existing) if existing.timestamp >= point.timestamp => {}
+            _ => {
+                latest.insert(key, point);
+            }
+        }
+    }
+
+    latest
+}
+
+fn append_file(path: &Path, header: Option<&str>, content: &str) -> BpiResult<()> {
+    let needs_header = header.is_some()
+        && std::fs::metadata(path)
+            .map(|metadata| metadata.len() == 0)
+            .unwrap_or(true);
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| BpiError::parse(format!("打开快照文件失败: {}", e)))?;
+
+    let mut buffer = String::new();
+    if let Some(header) = header.filter(|_| needs_header) {
+        buffer.push_str(header);
+        buffer.push('\n');
+    }
+    buffer.push_str(content);
+    file.write_all(buffer.as_bytes())
+        .map_err(|e| BpiError::parse(format!("写入快照文件失败: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ApiEnvelope;
+
+    fn play_source() -> BpiResult<PlaySourceData> {
+        let bytes = br#"{
+            "code": 0,
+            "message": "0",
+            "data": {
+                "page_source": {
+                    "dynamic": 10, "other": 1, "related_video": 20,
+                    "search": 5, "space": 3, "tenma": 60
+                },
+                "play_proportion": { "android": 50, "h5": 5, "ios": 20, "out": 1, "pc": 24 }
+            }
+        }"#;
+
+        ApiEnvelope::<PlaySourceData>::from_slice(bytes)?.into_payload()
+    }
+
+    #[test]
+    fn play_source_points_use_page_and_platform_dimensions() -> BpiResult<()> {
+        let points = play_source_points(&play_source()?, 100);
+
+        assert_eq!(points.len(), 11);
+        assert_eq!(
+            points[0],
+            AnalyticsPoint::new("play_source.page", "dynamic", 100, 10)
+        );
+        assert_eq!(
+            points[10],
+            AnalyticsPoint::new("play_source.platform", "pc", 100, 24)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn trend_points_use_date_key_as_timestamp() {
+        let items = vec![
+            VideoTrendItem {
+                date_key: 1_700_000_000,
+                total_inc: 3,
+            },
+            VideoTrendItem {
+                date_key: 1_700_086_400,
+                total_inc: 5,
+            },
+        ];
+
+        let points = video_trend_points(UpVideoTrendMetric::Play, &items);
+
+        assert_eq!(
+            points,
+            vec![
+                AnalyticsPoint::new("video.play", "daily_inc", 1_700_000_000, 3),
+                AnalyticsPoint::new("video.play", "daily_inc", 1_700_086_400, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn snapshot_delta_compares_latest_values() {
+        let previous = AnalyticsSnapshot::new(
+            100,
+            vec![
+                AnalyticsPoint::new("video.click", "total", 100, 10),
+                AnalyticsPoint::new("video.play", "daily_inc", 1, 3),
+                AnalyticsPoint::new("video.like", "total", 100, 4),
+            ],
+        );
+        let current = AnalyticsSnapshot::new(
+            200,
+            vec![
+                AnalyticsPoint::new("video.click", "total", 200, 15),
+                AnalyticsPoint::new("video.play", "daily_inc", 1, 3),
+                AnalyticsPoint::new("video.play", "daily_inc", 2, 8),
+            ],
+        );
+
+        let deltas = current.delta(&previous);
+
+        assert_eq!(deltas.len(), 3);
+        assert_eq!(deltas[0].metric, "video.click");
+        assert_eq!(deltas[0].change, Some(5));
+        assert_eq!(deltas[1].metric, "video.like");
+        assert_eq!(deltas[1].current, None);
+        assert_eq!(deltas[2].metric, "video.play");
+        assert_eq!(deltas[2].change, Some(5));
+    }
+
+    #[test]
+    fn snapshot_jsonl_round_trips() -> BpiResult<()> {
+        let first = AnalyticsSnapshot::new(
+            100,
+            vec![AnalyticsPoint::new("video.click", "total", 100, 10)],
+        );
+        let second = AnalyticsSnapshot::new(
+            200,
+            vec![AnalyticsPoint::new("viewer.area", "fan:广东", 200, 7)],
+        );
+        let jsonl = format!("{}{}", first.to_jsonl()?, second.to_jsonl()?);
+
+        assert_eq!(AnalyticsSnapshot::parse_jsonl(&jsonl)?, vec![first, second]);
+        Ok(())
+    }
+
+    #[test]
+    fn snapshot_csv_escapes_dimensions() {
+        let snapshot = AnalyticsSnapshot::new(
+            100,
+            vec![AnalyticsPoint::new("viewer.area", "fan:a,b", 100, 7)],
+        );
+
+        assert_eq!(
+            snapshot.to_csv(),
+            "taken_at,metric,dimension,timestamp,value\n100,viewer.area,\"fan:a,b\",100,7\n"
+        );
+    }
+
+    #[test]
+    fn snapshot_appends_csv_header_once() -> BpiResult<()> {
+        let path =
+            std::env::temp_dir().join(format!("bpi-rs-analytics-{}.csv", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let snapshot = AnalyticsSnapshot::new(
+            100,
+            vec![AnalyticsPoint::new("video.click", "total", 100, 10)],
+        );
+
+        snapshot.append_csv(&path)?;
+        snapshot.append_csv(&path)?;
+        let content = std::fs::read_to_string(&path).map_err(|e| BpiError::parse(e.to_string()))?;
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(content.matches("taken_at").count(), 1);
+        assert_eq!(content.lines().count(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn snapshot_params_reject_empty_sources() {
+        assert!(AnalyticsSnapshotParams::new([]).is_err());
+        assert!(AnalyticsSnapshotParams::new([AnalyticsSource::UpStat]).is_ok());
+    }
+}
@@ -0,0 +1,390 @@
+//! 创作中心稿件投稿
+//!
+//! [`ArchiveSubmission`] 描述一次投稿，分P来自 [`CreativeCenterClient::upload_video`]，
+//! 封面来自 [`CreativeCenterClient::upload_cover`]。
+
+use chrono::{Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::creativecenter::CreativeCenterClient;
+use crate::creativecenter::upload::UploadCoverData;
+use crate::creativecenter::upos::UploadedVideo;
+use crate::video::{Zone, ZoneVersion};
+use crate::{BilibiliRequest, BpiError, BpiResult};
+
+const ADD_ARCHIVE_ENDPOINT: &str = "https://member.bilibili.com/x/vu/web/add/v3";
+
+const MAX_TITLE_CHARS: usize = 80;
+const MAX_DESC_CHARS: usize = 2000;
+const MAX_TAGS: usize = 12;
+const MAX_TAG_CHARS: usize = 20;
+/// 定时发布时间必须在 2 小时之后、15 天之内。
+const MIN_DTIME_DELAY_HOURS: i64 = 2;
+const MAX_DTIME_DELAY_DAYS: i64 = 15;
+
+/// 稿件类型。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArchiveCopyright {
+    /// 自制，禁止转载。
+    Original,
+    /// 转载，需要注明来源。
+    Reprint { source: String },
+}
+
+/// 投稿中的一个分P。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivePart {
+    /// 上传后得到的文件名
+    pub filename: String,
+    /// 分P标题
+    pub title: String,
+    /// 分P简介
+    #[serde(default)]
+    pub desc: String,
+    /// 分P的 `cid`
+    pub cid: u64,
+}
+
+impl ArchivePart {
+    /// 设置分P标题。
+    pub fn with_title(mut self, title: impl Into<String>) -> BpiResult<Self> {
+        self.title = normalize_non_blank("videos.title", title.into())?;
+        Ok(self)
+    }
+}
+
+impl From<UploadedVideo> for ArchivePart {
+    fn from(video: UploadedVideo) -> Self {
+        Self {
+            filename: video.filename,
+            title: video.title,
+            desc: String::new(),
+            cid: video.cid,
+        }
+    }
+}
+
+/// 视频投稿内容。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveSubmission {
+    title: String,
+    tid: u32,
+    tags: Vec<String>,
+    desc: String,
+    cover: Option<String>,
+    videos: Vec<ArchivePart>,
+    copyright: ArchiveCopyright,
+    dtime: Option<i64>,
+    dynamic: String,
+}
+
+impl ArchiveSubmission {
+    /// 创建投稿，分区必须是 v2 分区，可传入 [`Zone`] 或 [`VideoPartitionV2`](crate::video::video_zone_v2::VideoPartitionV2)。
+    pub fn new(title: impl Into<String>, zone: impl Into<Zone>) -> BpiResult<Self> {
+        Ok(Self {
+            title: validate_title(title.into())?,
+            tid: zone.into().expect_version(ZoneVersion::V2)?,
+            tags: Vec::new(),
+            desc: String::new(),
+            cover: None,
+            videos: Vec::new(),
+            copyright: ArchiveCopyright::Original,
+            dtime: None,
+            dynamic: String::new(),
+        })
+    }
+
+    /// 设置标签，最多 12 个，每个不超过 20 字且不能包含逗号。
+    pub fn with_tags<I, S>(mut self, tags: I) -> BpiResult<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags = normalize_tags(tags)?;
+        Ok(self)
+    }
+
+    /// 设置简介，不超过 2000 字。
+    pub fn with_description(mut self, desc: impl Into<String>) -> BpiResult<Self> {
+        self.desc = validate_description(desc.into())?;
+        Ok(self)
+    }
+
+    /// 设置封面，使用 [`CreativeCenterClient::upload_cover`] 的返回值。
+    pub fn with_cover(mut self, cover: UploadCoverData) -> BpiResult<Self> {
+        self.cover = Some(normalize_non_blank("cover", cover.url)?);
+        Ok(self)
+    }
+
+    /// 追加一个分P。
+    pub fn with_part(mut self, part: impl Into<ArchivePart>) -> Self {
+        self.videos.push(part.into());
+        self
+    }
+
+    /// 设置稿件类型。转载稿件的来源不能为空。
+    pub fn with_copyright(mut self, copyright: ArchiveCopyright) -> BpiResult<Self> {
+        if let ArchiveCopyright::Reprint { source } = &copyright
+            && source.trim().is_empty()
+        {
+            return Err(BpiError::invalid_parameter(
+                "source",
+                "reprint source cannot be blank",
+            ));
+        }
+
+        self.copyright = copyright;
+        Ok(self)
+    }
+
+    /// 设置定时发布时间，秒级时间戳，必须在 2 小时之后、15 天之内。
+    pub fn with_dtime(mut self, dtime: i64) -> BpiResult<Self> {
+        let now = Utc::now();
+        let earliest = (now + ChronoDuration::hours(MIN_DTIME_DELAY_HOURS)).timestamp();
+        let latest = (now + ChronoDuration::days(MAX_DTIME_DELAY_DAYS)).timestamp();
+        if dtime < earliest || dtime > latest {
+            return Err(BpiError::invalid_parameter(
+                "dtime",
+                "scheduled time must be between 2 hours and 15 days from now",
+            ));
+        }
+
+        self.dtime = Some(dtime);
+        Ok(self)
+    }
+
+    /// 设置投稿时同步发布的动态文字。
+    pub fn with_dynamic(mut self, dynamic: impl Into<String>) -> Self {
+        self.dynamic = dynamic.into();
+        self
+    }
+
+    /// 检查投稿必填项。
+    pub fn validate(&self) -> BpiResult<()> {
+        if self.videos.is_empty() {
+            return Err(BpiError::invalid_parameter(
+                "videos",
+                "at least one video part is required",
+            ));
+        }
+        if self.tags.is_empty() {
+            return Err(BpiError::invalid_parameter(
+                "tag",
+                "between 1 and 12 tags are required",
+            ));
+        }
+        if self.cover.is_none() {
+            return Err(BpiError::invalid_parameter("cover", "cover is required"));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn to_body(&self) -> serde_json::Value {
+        let (copyright, source, no_reprint) = match &self.copyright {
+            ArchiveCopyright::Original => (1, String::new(), 1),
+            ArchiveCopyright::Reprint { source } => (2, source.trim().to_string(), 0),
+        };
+
+        let mut body = serde_json::json!({
+            "copyright": copyright,
+            "source": source,
+            "no_reprint": no_reprint,
+            "tid": self.tid,
+            "cover": self.cover.clone().unwrap_or_default(),
+            "title": self.title,
+            "desc": self.desc,
+            "dynamic": self.dynamic,
+            "tag": self.tags.join(","),
+            "videos": self.videos,
+        });
+        if let Some(dtime) = self.dtime {
+            body["dtime"] = dtime.into();
+        }
+
+        body
+    }
+}
+
+/// 投稿成功后的稿件 ID。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveSubmitData {
+    /// 稿件 avid
+    pub aid: u64,
+    /// 稿件 bvid
+    pub bvid: String,
+}
+
+impl<'a> CreativeCenterClient<'a> {
+    /// 提交视频投稿。
+    ///
+    /// 提交前会检查分P、标签和封面是否齐全。
+    pub async fn submit_archive(
+        &self,
+        submission: &ArchiveSubmission,
+    ) -> BpiResult<ArchiveSubmitData> {
+        submission.validate()?;
+        let csrf = self.client.csrf()?;
+
+        self.client
+            .post(ADD_ARCHIVE_ENDPOINT)
+            .query(&[("csrf", csrf)])
+            .json(&submission.to_body())
+            .send_bpi_payload("creativecenter.archive.add")
+            .await
+    }
+}
+
+pub(super) fn normalize_tags<I, S>(tags: I) -> BpiResult<Vec<String>>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let mut normalized = Vec::new();
+    for tag in tags {
+        let tag = normalize_non_blank("tag", tag.into())?;
+        if tag.contains([',', '，']) || tag.chars().count() > MAX_TAG_CHARS {
+            return Err(BpiError::invalid_parameter(
+                "tag",
+                "tag must be at most 20 characters without commas",
+            ));
+        }
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    if normalized.is_empty() || normalized.len() > MAX_TAGS {
+        return Err(BpiError::invalid_parameter(
+            "tag",
+            "between 1 and 12 tags are required",
+        ));
+    }
+
+    Ok(normalized)
+}
+
+pub(super) fn validate_description(desc: String) -> BpiResult<String> {
+    if desc.chars().count() > MAX_DESC_CHARS {
+        return Err(BpiError::invalid_parameter(
+            "desc",
+            "description must be at most 2000 characters",
+        ));
+    }
+
+    Ok(desc)
+}
+
+pub(super) fn validate_title(title: String) -> BpiResult<String> {
+    let title = normalize_non_blank("title", title)?;
+    if title.chars().count() > MAX_TITLE_CHARS {
+        return Err(BpiError::invalid_parameter(
+            "title",
+            "title must be at most 80 characters",
+        ));
+    }
+
+    Ok(title)
+}
+
+pub(super) fn normalize_non_blank(field: &'static str, value: String) -> BpiResult<String> {
+    let value = value.trim().to_string();
+    if value.is_empty() {
+        return Err(BpiError::invalid_parameter(field, "value cannot be blank"));
+    }
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::video::video_zone_v2::{Douga, VideoPartitionV2};
+
+    fn uploaded(cid: u64) -> UploadedVideo {
+        UploadedVideo {
+            filename: format!("n230101{cid}"),
+            cid,
+            title: format!("P{cid}"),
+        }
+    }
+
+    fn submission() -> BpiResult<ArchiveSubmission> {
+        ArchiveSubmission::new("测试投稿", VideoPartitionV2::Douga(Douga::FanAnime))?
+            .with_tags(["动画", "测试", "动画"])?
+            .with_cover(UploadCoverData {
+                url: "https://i0.hdslb.com/bfs/archive/cover.jpg".to_string(),
+            })?
+            .with_part(uploaded(1))
+            .with_part(ArchivePart::from(uploaded(2)).with_title("第二P")?)
+            .with_description("简介")
+    }
+
+    #[test]
+    fn submission_serializes_add_v3_body() -> BpiResult<()> {
+        let body = submission()?.to_body();
+
+        assert_eq!(body["copyright"], 1);
+        assert_eq!(body["no_reprint"], 1);
+        assert_eq!(body["tid"], VideoPartitionV2::Douga(Douga::FanAnime).tid());
+        assert_eq!(body["tag"], "动画,测试");
+        assert_eq!(body["videos"][0]["filename"], "n2301011");
+        assert_eq!(body["videos"][1]["title"], "第二P");
+        assert_eq!(body["videos"][1]["cid"], 2);
+        assert!(body.get("dtime").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn submission_serializes_reprint_and_schedule() -> BpiResult<()> {
+        let dtime = (Utc::now() + ChronoDuration::days(1)).timestamp();
+        let body = submission()?
+            .with_copyright(ArchiveCopyright::Reprint {
+                source: " https://example.com/video ".to_string(),
+            })?
+            .with_dtime(dtime)?
+            .to_body();
+
+        assert_eq!(body["copyright"], 2);
+        assert_eq!(body["source"], "https://example.com/video");
+        assert_eq!(body["no_reprint"], 0);
+        assert_eq!(body["dtime"], dtime);
+        Ok(())
+    }
+
+    #[test]
+    fn submission_rejects_invalid_fields() -> BpiResult<()> {
+        let partition = VideoPartitionV2::Douga(Douga::Douga);
+
+        assert!(ArchiveSubmission::new(" ", partition).is_err());
+        assert!(ArchiveSubmission::new("a".repeat(81), partition).is_err());
+        assert!(ArchiveSubmission::new("标题", Zone::v1(21)?).is_err());
+        assert!(ArchiveSubmission::new("标题", Zone::lookup("fan_anime")?).is_ok());
+        assert!(submission()?.with_tags(["a,b"]).is_err());
+        assert!(submission()?.with_tags(Vec::<String>::new()).is_err());
+        assert!(
+            submission()?
+                .with_copyright(ArchiveCopyright::Reprint {
+                    source: " ".to_string()
+                })
+                .is_err()
+        );
+        assert!(submission()?.with_dtime(Utc::now().timestamp()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn submission_validate_requires_parts_tags_and_cover() -> BpiResult<()> {
+        let partition = VideoPartitionV2::Douga(Douga::Douga);
+        let empty = ArchiveSubmission::new("标题", partition)?;
+
+        assert!(matches!(
+            empty.validate(),
+            Err(BpiError::InvalidParameter {
+                field: "videos",
+                ..
+            })
+        ));
+        assert!(submission()?.validate().is_ok());
+        Ok(())
+    }
+}
@@ -0,0 +1,566 @@
+//! 创作中心稿件编辑
+//!
+//! [`CreativeCenterClient::prepare_archive_edit`] 读取稿件当前内容并应用 [`ArchivePatch`]，
+//! 得到可以预览差异的 [`ArchiveEditDraft`]；[`CreativeCenterClient::submit_archive_edit`]
+//! 提交草稿。
+
+use std::fmt::Write as _;
+
+use serde::{Deserialize, Serialize};
+
+use crate::creativecenter::CreativeCenterClient;
+use crate::creativecenter::archive::{
+    ArchivePart, ArchiveSubmitData, normalize_non_blank, normalize_tags, validate_description,
+    validate_title,
+};
+use crate::creativecenter::upload::UploadCoverData;
+use crate::ids::Aid;
+use crate::{BilibiliRequest, BpiError, BpiResult};
+
+const ARCHIVE_VIEW_ENDPOINT: &str = "https://member.bilibili.com/x/vupre/web/archive/view";
+const ARCHIVE_EDIT_ENDPOINT: &str = "https://member.bilibili.com/x/vu/web/edit";
+
+/// 稿件编辑页的稿件信息。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveEditInfo {
+    /// av号
+    pub aid: u64,
+    /// bvid
+    pub bvid: String,
+    /// 标题
+    pub title: String,
+    /// 封面 URL
+    pub cover: String,
+    /// 分区 ID
+    pub tid: u32,
+    /// 标签，逗号分隔
+    #[serde(default)]
+    pub tag: String,
+    /// 简介
+    #[serde(default)]
+    pub desc: String,
+    /// 1: 自制, 2: 转载
+    pub copyright: u8,
+    /// 转载来源
+    #[serde(default)]
+    pub source: String,
+    /// 投稿动态文字
+    #[serde(default)]
+    pub dynamic: String,
+    /// 定时发布时间，未定时为 0
+    #[serde(default)]
+    pub dtime: i64,
+    /// 是否禁止转载
+    #[serde(default)]
+    pub no_reprint: u8,
+    /// 是否仅自己可见
+    #[serde(default)]
+    pub is_only_self: u8,
+}
+
+/// 稿件编辑页的分P。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveEditVideo {
+    /// 上传时的文件名
+    pub filename: String,
+    /// 分P cid
+    pub cid: u64,
+    /// 分P标题
+    pub title: String,
+    /// 分P简介
+    #[serde(default)]
+    pub desc: String,
+    /// 视频时长（秒）
+    #[serde(default)]
+    pub duration: i64,
+}
+
+impl From<ArchivePart> for ArchiveEditVideo {
+    fn from(part: ArchivePart) -> Self {
+        Self {
+            filename: part.filename,
+            cid: part.cid,
+            title: part.title,
+            desc: part.desc,
+            duration: 0,
+        }
+    }
+}
+
+/// 稿件编辑页数据。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveEditView {
+    /// 稿件信息
+    pub archive: ArchiveEditInfo,
+    /// 分P列表
+    #[serde(default)]
+    pub videos: Vec<ArchiveEditVideo>,
+}
+
+/// 稿件可见范围。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveVisibility {
+    /// 公开
+    Public,
+    /// 仅自己可见
+    Private,
+}
+
+impl ArchiveVisibility {
+    fn is_only_self(self) -> u8 {
+        match self {
+            Self::Public => 0,
+            Self::Private => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PartEdit {
+    Add(ArchivePart),
+    Remove(u64),
+    Reorder(Vec<u64>),
+}
+
+/// 稿件修改内容，未设置的字段保持原样。分P操作按添加顺序依次应用。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchivePatch {
+    title: Option<String>,
+    desc: Option<String>,
+    tags: Option<Vec<String>>,
+    cover: Option<String>,
+    visibility: Option<ArchiveVisibility>,
+    parts: Vec<PartEdit>,
+}
+
+impl ArchivePatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> BpiResult<Self> {
+        self.title = Some(validate_title(title.into())?);
+        Ok(self)
+    }
+
+    pub fn with_description(mut self, desc: impl Into<String>) -> BpiResult<Self> {
+        self.desc = Some(validate_description(desc.into())?);
+        Ok(self)
+    }
+
+    pub fn with_tags<I, S>(mut self, tags: I) -> BpiResult<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags = Some(normalize_tags(tags)?);
+        Ok(self)
+    }
+
+    /// 设置封面，使用 [`CreativeCenterClient::upload_cover`] 的返回值。
+    pub fn with_cover(mut self, cover: UploadCoverData) -> BpiResult<Self> {
+        self.cover = Some(normalize_non_blank("cover", cover.url)?);
+        Ok(self)
+    }
+
+    pub fn with_visibility(mut self, visibility: ArchiveVisibility) -> Self {
+        self.visibility = Some(visibility);
+        self
+    }
+
+    /// 在末尾追加分P，分P来自 [`CreativeCenterClient::upload_video`]。
+    pub fn add_part(mut self, part: impl Into<ArchivePart>) -> Self {
+        self.parts.push(PartEdit::Add(part.into()));
+        self
+    }
+
+    /// 删除指定 cid 的分P。
+    pub fn remove_part(mut self, cid: u64) -> Self {
+        self.parts.push(PartEdit::Remove(cid));
+        self
+    }
+
+    /// 按 cid 重新排列分P，必须包含当前全部分P。
+    pub fn reorder_parts(mut self, cids: impl IntoIterator<Item = u64>) -> Self {
+        self.parts
+            .push(PartEdit::Reorder(cids.into_iter().collect()));
+        self
+    }
+
+    /// 把修改应用到稿件当前内容上。
+    pub fn apply(&self, view: &ArchiveEditView) -> BpiResult<ArchiveEditDraft> {
+        let mut edited = view.clone();
+        if let Some(title) = &self.title {
+            edited.archive.title = title.clone();
+        }
+        if let Some(desc) = &self.desc {
+            edited.archive.desc = desc.clone();
+        }
+        if let Some(tags) = &self.tags {
+            edited.archive.tag = tags.join(",");
+        }
+        if let Some(cover) = &self.cover {
+            edited.archive.cover = cover.clone();
+        }
+        if let Some(visibility) = self.visibility {
+            edited.archive.is_only_self = visibility.is_only_self();
+        }
+
+        for edit in &self.parts {
+            apply_part_edit(&mut edited.videos, edit)?;
+        }
+        if edited.videos.is_empty() {
+            return Err(BpiError::invalid_parameter(
+                "videos",
+                "at least one video part is required",
+            ));
+        }
+
+        Ok(ArchiveEditDraft {
+            original: view.clone(),
+            edited,
+        })
+    }
+}
+
+/// 应用修改后待提交的稿件。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEditDraft {
+    /// 稿件当前内容
+    pub original: ArchiveEditView,
+    /// 修改后的内容
+    pub edited: ArchiveEditView,
+}
+
+impl ArchiveEditDraft {
+    /// 是否有任何修改。
+    pub fn has_changes(&self) -> bool {
+        self.original != self.edited
+    }
+
+    /// 返回修改前后的差异，每项一行。
+    pub fn preview(&self) -> String {
+        let before = &self.original.archive;
+        let after = &self.edited.archive;
+        let mut preview = String::new();
+
+        push_change(&mut preview, "title", &before.title, &after.title);
+        push_change(&mut preview, "desc", &before.desc, &after.desc);
+        push_change(&mut preview, "tag", &before.tag, &after.tag);
+        push_change(&mut preview, "cover", &before.cover, &after.cover);
+        if before.is_only_self != after.is_only_self {
+            let _ = writeln!(
+                preview,
+                "visibility: {} -> {}",
+                visibility_label(before.is_only_self),
+                visibility_label(after.is_only_self)
+            );
+        }
+
+        let before_cids = cids(&self.original.videos);
+        let after_cids = cids(&self.edited.videos);
+        for video in &self.edited.videos {
+            if !before_cids.contains(&video.cid) {
+                let _ = writeln!(preview, "+ part {} {:?}", video.cid, video.title);
+            }
+        }
+        for video in &self.original.videos {
+            if !after_cids.contains(&video.cid) {
+                let _ = writeln!(preview, "- part {} {:?}", video.cid, video.title);
+            }
+        }
+        let kept_before = before_cids
+            .iter()
+            .filter(|cid| after_cids.contains(cid))
+            .collect::<Vec<_>>();
+        let kept_after = after_cids
+            .iter()
+            .filter(|cid| before_cids.contains(cid))
+            .collect::<Vec<_>>();
+        if kept_before != kept_after {
+            let order = after_cids
+                .iter()
+                .map(u64::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+            let _ = writeln!(preview, "~ parts order: {order}");
+        }
+
+        preview.trim_end().to_string()
+    }
+
+    pub(crate) fn to_body(&self) -> serde_json::Value {
+        let archive = &self.edited.archive;
+        let videos = self
+            .edited
+            .videos
+            .iter()
+            .map(|video| {
+                serde_json::json!({
+                    "filename": video.filename,
+                    "title": video.title,
+                    "desc": video.desc,
+                    "cid": video.cid,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut body = serde_json::json!({
+            "aid": archive.aid,
+            "copyright": archive.copyright,
+            "source": archive.source,
+            "no_reprint": archive.no_reprint,
+            "tid": archive.tid,
+            "cover": archive.cover,
+            "title": archive.title,
+            "desc": archive.desc,
+            "dynamic": archive.dynamic,
+            "tag": archive.tag,
+            "is_only_self": archive.is_only_self,
+            "videos": videos,
+        });
+        if archive.dtime > 0 {
+            body["dtime"] = archive.dtime.into();
+        }
+
+        body
+    }
+}
+
+impl<'a> CreativeCenterClient<'a> {
+    /// 获取稿件编辑页数据。
+    pub async fn archive_edit_view(&self, aid: Aid) -> BpiResult<ArchiveEditView> {
+        self.client
+            .get(ARCHIVE_VIEW_ENDPOINT)
+            .query(&[("aid", aid.to_string())])
+            .send_bpi_payload("creativecenter.archive.view")
+            .await
+    }
+
+    /// 读取稿件当前内容并应用修改，返回可预览的草稿，不会提交。
+    pub async fn prepare_archive_edit(
+        &self,
+        aid: Aid,
+        patch: &ArchivePatch,
+    ) -> BpiResult<ArchiveEditDraft> {
+        let view = self.archive_edit_view(aid).await?;
+        patch.apply(&view)
+    }
+
+    /// 提交稿件编辑草稿。
+    pub async fn submit_archive_edit(
+        &self,
+        draft: &ArchiveEditDraft,
+    ) -> BpiResult<ArchiveSubmitData> {
+        let csrf = self.client.csrf()?;
+
+        self.client
+            .post(ARCHIVE_EDIT_ENDPOINT)
+            .query(&[("csrf", csrf)])
+            .json(&draft.to_body())
+            .send_bpi_payload("creativecenter.archive.edit")
+            .await
+    }
+
+    /// 读取稿件、应用修改并提交。
+    ///
+    /// 提交前会把差异预览写入日志；需要人工确认时请先调用 [`Self::prepare_archive_edit`]，
+    /// 确认后再调用 [`Self::submit_archive_edit`]。没有任何修改时直接返回错误，不会提交。
+    pub async fn edit_archive(
+        &self,
+        aid: Aid,
+        patch: ArchivePatch,
+    ) -> BpiResult<ArchiveSubmitData> {
+        let draft = self.prepare_archive_edit(aid, &patch).await?;
+        if !draft.has_changes() {
+            return Err(BpiError::invalid_parameter("patch", "patch has no changes"));
+        }
+
+        tracing::info!(aid = aid.get(), preview = %draft.preview(), "提交稿件编辑");
+        self.submit_archive_edit(&draft).await
+    }
+}
+
+fn apply_part_edit(videos: &mut Vec<ArchiveEditVideo>, edit: &PartEdit) -> BpiResult<()> {
+    match edit {
+        PartEdit::Add(part) => {
+            if videos.iter().any(|video| video.cid == part.cid) {
+                return Err(BpiError::invalid_parameter(
+                    "videos",
+                    "part with the same cid already exists",
+                ));
+            }
+            videos.push(part.clone().into());
+        }
+        PartEdit::Remove(cid) => {
+            let index = videos
+                .iter()
+                .position(|video| video.cid == *cid)
+                .ok_or_else(|| BpiError::invalid_parameter("videos", "part cid not found"))?;
+            videos.remove(index);
+        }
+        PartEdit::Reorder(order) => {
+            let mut expected = cids(videos);
+            let mut requested = order.clone();
+            expected.sort_unstable();
+            requested.sort_unstable();
+            if expected != requested {
+                return Err(BpiError::invalid_parameter(
+                    "videos",
+                    "reorder must list every current part exactly once",
+                ));
+            }
+
+            let mut reordered = Vec::with_capacity(videos.len());
+            for cid in order {
+                if let Some(index) = videos.iter().position(|video| video.cid == *cid) {
+                    reordered.push(videos.remove(index));
+                }
+            }
+            *videos = reordered;
+        }
+    }
+
+    Ok(())
+}
+
+fn cids(videos: &[ArchiveEditVideo]) -> Vec<u64> {
+    videos.iter().map(|video| video.cid).collect()
+}
+
+fn push_change(preview: &mut String, field: &str, before: &str, after: &str) {
+    if before != after {
+        let _ = writeln!(preview, "{field}: {before:?} -> {after:?}");
+    }
+}
+
+fn visibility_label(is_only_self: u8) -> &'static str {
+    if is_only_self == 1 {
+        "private"
+    } else {
+        "public"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ApiEnvelope;
+
+    fn view() -> BpiResult<ArchiveEditView> {
+        let bytes = br#"{
+            "code": 0,
+            "message": "0",
+            "data": {
+                "archive": {
+                    "aid": 113602455409683,
+                    "bvid": "BV1xx411c7mD",
+                    "title": "old title",
+                    "cover": "https://i0.hdslb.com/bfs/archive/old.jpg",
+                    "tid": 27,
+                    "tag": "a,b",
+                    "desc": "old desc",
+                    "copyright": 1,
+                    "no_reprint": 1,
+                    "is_only_self": 0
+                },
+                "videos": [
+                    { "filename": "n1", "cid": 1, "title": "P1", "duration": 10 },
+                    { "filename": "n2", "cid": 2, "title": "P2", "duration": 20 }
+                ]
+            }
+        }"#;
+
+        ApiEnvelope::<ArchiveEditView>::from_slice(bytes)?.into_payload()
+    }
+
+    fn part(cid: u64) -> ArchivePart {
+        ArchivePart {
+            filename: format!("n{cid}"),
+            title: format!("P{cid}"),
+            desc: String::new(),
+            cid,
+        }
+    }
+
+    #[test]
+    fn patch_applies_fields_and_part_edits() -> BpiResult<()> {
+        let patch = ArchivePatch::new()
+            .with_title("new title")?
+            .with_tags(["c"])?
+            .with_visibility(ArchiveVisibility::Private)
+            .add_part(part(3))
+            .remove_part(1)
+            .reorder_parts([3, 2]);
+
+        let draft = patch.apply(&view()?)?;
+
+        assert_eq!(draft.edited.archive.title, "new title");
+        assert_eq!(draft.edited.archive.tag, "c");
+        assert_eq!(draft.edited.archive.desc, "old desc");
+        assert_eq!(draft.edited.archive.is_only_self, 1);
+        assert_eq!(cids(&draft.edited.videos), vec![3, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn patch_rejects_invalid_part_edits() -> BpiResult<()> {
+        let view = view()?;
+
+        assert!(ArchivePatch::new().remove_part(9).apply(&view).is_err());
+        assert!(ArchivePatch::new().add_part(part(1)).apply(&view).is_err());
+        assert!(ArchivePatch::new().reorder_parts([2]).apply(&view).is_err());
+        assert!(
+            ArchivePatch::new()
+                .remove_part(1)
+                .remove_part(2)
+                .apply(&view)
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn draft_preview_lists_changes() -> BpiResult<()> {
+        let draft = ArchivePatch::new()
+            .with_title("new title")?
+            .with_visibility(ArchiveVisibility::Private)
+            .add_part(part(3))
+            .remove_part(1)
+            .apply(&view()?)?;
+
+        assert_eq!(
+            draft.preview(),
+            "title: \"old title\" -> \"new title\"\n\
+             visibility: public -> private\n\
+             + part 3 \"P3\"\n\
+             - part 1 \"P1\""
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn draft_preview_reports_reorder_only() -> BpiResult<()> {
+        let draft = ArchivePatch::new().reorder_parts([2, 1]).apply(&view()?)?;
+
+        assert!(draft.has_changes());
+        assert_eq!(draft.preview(), "~ parts order: 2,1");
+        assert!(!ArchivePatch::new().apply(&view()?)?.has_changes());
+        Ok(())
+    }
+
+    #[test]
+    fn draft_serializes_edit_body() -> BpiResult<()> {
+        let body = ArchivePatch::new()
+            .with_description("new desc")?
+            .apply(&view()?)?
+            .to_body();
+
+        assert_eq!(body["aid"], 113602455409683_u64);
+        assert_eq!(body["tid"], 27);
+        assert_eq!(body["desc"], "new desc");
+        assert_eq!(body["videos"][1]["cid"], 2);
+        assert!(body.get("dtime").is_none());
+        Ok(())
+    }
+}
@@ -1,5 +1,8 @@
 //! 用户中心
 
+pub mod analytics;
+pub mod archive;
+pub mod archive_edit;
 pub mod client;
 pub mod opus;
 pub mod params;
@@ -7,10 +10,24 @@ pub mod railgun;
 pub mod season;
 pub mod statistics_data;
 pub mod upload;
+pub mod upos;
 pub mod videos;
 
+pub use analytics::{
+    AnalyticsDelta, AnalyticsPoint, AnalyticsSnapshot, AnalyticsSnapshotParams,
+    AnalyticsSnapshotter, AnalyticsSource,
+};
+pub use archive::{ArchiveCopyright, ArchivePart, ArchiveSubmission, ArchiveSubmitData};
+pub use archive_edit::{
+    ArchiveEditDraft, ArchiveEditInfo, ArchiveEditVideo, ArchiveEditView, ArchivePatch,
+    ArchiveVisibility,
+};
 pub use client::CreativeCenterClient;
 pub use params::{
     UpArchiveCompareParams, UpArchiveVideosParams, UpArchivesListParams, UpArticleTrendMetric,
     UpArticleTrendParams, UpVideoTrendMetric, UpVideoTrendParams,
 };
+pub use upos::{
+    PreuploadData, PreuploadParams, UPOS_MAX_CONCURRENCY, UploadedVideo, UposUploadState,
+    VideoUploadParams,
+};
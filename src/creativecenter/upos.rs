@@ -0,0 +1,692 @@
+//! 创作中心视频上传（UPOS）
+//!
+//! 上传流程：`preupload` 获取上传节点和鉴权，初始化分片上传，按分片并发 `PUT` 文件内容，
+//! 最后提交分片列表完成上传。上传进度会写入断点文件，中断后可以用同一个断点文件续传。
+
+use std::collections::BTreeSet;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use futures_util::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+use crate::creativecenter::CreativeCenterClient;
+use crate::{BilibiliRequest, BpiError, BpiResult};
+
+const PREUPLOAD_ENDPOINT: &str = "https://member.bilibili.com/preupload";
+const DEFAULT_PROFILE: &str = "ugcfr/pc3";
+const DEFAULT_UPCDN: &str = "bda2";
+const DEFAULT_CHUNK_RETRY: u32 = 3;
+const DEFAULT_CHUNK_RETRY_DELAY: Duration = Duration::from_secs(2);
+/// 单个文件最多同时上传的分片数。
+pub const UPOS_MAX_CONCURRENCY: usize = 8;
+
+/// `preupload` 的参数。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreuploadParams {
+    name: String,
+    size: u64,
+    profile: String,
+    upcdn: String,
+}
+
+impl PreuploadParams {
+    pub fn new(name: impl Into<String>, size: u64) -> BpiResult<Self> {
+        let name = name.into().trim().to_string();
+        if name.is_empty() {
+            return Err(BpiError::invalid_parameter("name", "value cannot be blank"));
+        }
+        if size == 0 {
+            return Err(BpiError::invalid_parameter(
+                "size",
+                "value must be non-zero",
+            ));
+        }
+
+        Ok(Self {
+            name,
+            size,
+            profile: DEFAULT_PROFILE.to_string(),
+            upcdn: DEFAULT_UPCDN.to_string(),
+        })
+    }
+
+    /// 设置上传线路，如 `bda2`、`qn`、`ws`。
+    pub fn with_upcdn(mut self, upcdn: impl Into<String>) -> BpiResult<Self> {
+        let upcdn = upcdn.into().trim().to_string();
+        if upcdn.is_empty() {
+            return Err(BpiError::invalid_parameter(
+                "upcdn",
+                "value cannot be blank",
+            ));
+        }
+
+        self.upcdn = upcdn;
+        Ok(self)
+    }
+
+    pub(crate) fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("name", self.name.clone()),
+            ("size", self.size.to_string()),
+            ("r", "upos".to_string()),
+            ("profile", self.profile.clone()),
+            ("ssl", "0".to_string()),
+            ("version", "2.14.0".to_string()),
+            ("build", "2140000".to_string()),
+            ("upcdn", self.upcdn.clone()),
+            ("probe_version", "20221109".to_string()),
+        ]
+    }
+}
+
+/// `preupload` 返回的上传节点信息。该接口不使用标准响应包装。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreuploadData {
+    /// 成功时为 1
+    #[serde(rename = "OK")]
+    pub ok: i32,
+    /// 上传鉴权，放在 `X-Upos-Auth` 请求头中
+    pub auth: String,
+    /// 上传完成后作为分P的 `cid`
+    pub biz_id: u64,
+    /// 分片大小，单位字节
+    pub chunk_size: u64,
+    /// 上传节点，形如 `//upos-cs-upcdnbda2.bilivideo.com`
+    pub endpoint: String,
+    /// 备选上传节点
+    #[serde(default)]
+    pub endpoints: Vec<String>,
+    /// 建议并发数
+    #[serde(default)]
+    pub threads: u32,
+    /// 单个请求超时，单位秒
+    #[serde(default)]
+    pub timeout: u64,
+    /// 文件在 UPOS 中的位置，形如 `upos://ugcfr/n230101abc.mp4`
+    pub upos_uri: String,
+}
+
+/// 初始化分片上传的响应。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UposInitData {
+    #[serde(rename = "OK")]
+    ok: i32,
+    upload_id: String,
+}
+
+/// 完成分片上传的响应。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UposCompleteData {
+    #[serde(rename = "OK")]
+    ok: i32,
+}
+
+/// 视频上传断点，序列化为 JSON 保存在磁盘上。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UposUploadState {
+    /// 本地文件名
+    pub file_name: String,
+    /// 文件大小，单位字节
+    pub file_size: u64,
+    /// 文件修改时间，Unix 时间戳（毫秒）；与断点记录不一致时重新上传
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_modified: Option<u64>,
+    /// 分片大小，单位字节
+    pub chunk_size: u64,
+    /// 上传节点
+    pub endpoint: String,
+    /// 上传鉴权
+    pub auth: String,
+    /// 文件在 UPOS 中的位置
+    pub upos_uri: String,
+    /// 上传完成后作为分P的 `cid`
+    pub biz_id: u64,
+    /// 单个请求超时，单位秒
+    pub timeout: u64,
+    /// 分片上传 ID
+    pub upload_id: String,
+    /// 已完成的分片序号，从 0 开始
+    pub completed_chunks: BTreeSet<u64>,
+}
+
+impl UposUploadState {
+    /// 从断点文件读取上传状态。
+    pub fn load(path: impl AsRef<Path>) -> BpiResult<Self> {
+        let bytes = std::fs::read(path.as_ref())
+            .map_err(|e| BpiError::parse(format!("读取上传断点失败: {}", e)))?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// 把上传状态写入断点文件。
+    pub fn save(&self, path: impl AsRef<Path>) -> BpiResult<()> {
+        let bytes = serde_json::to_vec_pretty(self)?;
+        std::fs::write(path.as_ref(), bytes)
+            .map_err(|e| BpiError::parse(format!("写入上传断点失败: {}", e)))
+    }
+
+    /// 在异步上传过程中读取断点文件，文件不存在或无法解析时返回 `None`。
+    async fn load_async(path: &Path) -> Option<Self> {
+        let bytes = tokio::fs::read(path).await.ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// 在异步上传过程中写入断点文件，不阻塞运行时。
+    async fn save_async(&self, path: &Path) -> BpiResult<()> {
+        let bytes = serde_json::to_vec_pretty(self)?;
+        tokio::fs::write(path, bytes)
+            .await
+            .map_err(|e| BpiError::parse(format!("写入上传断点失败: {}", e)))
+    }
+
+    /// 分片总数。
+    pub fn chunk_count(&self) -> u64 {
+        self.file_size.div_ceil(self.chunk_size)
+    }
+
+    /// 尚未上传的分片序号。
+    pub fn pending_chunks(&self) -> Vec<u64> {
+        (0..self.chunk_count())
+            .filter(|chunk| !self.completed_chunks.contains(chunk))
+            .collect()
+    }
+
+    /// 是否全部分片都已上传。
+    pub fn is_complete(&self) -> bool {
+        self.completed_chunks.len() as u64 == self.chunk_count()
+    }
+
+    /// 上传地址，由上传节点和 `upos_uri` 拼接而成。
+    pub fn upload_url(&self) -> String {
+        let endpoint = self.endpoint.trim_start_matches("https:");
+        format!(
+            "https:{}/{}",
+            endpoint,
+            self.upos_uri.trim_start_matches("upos://")
+        )
+    }
+
+    /// 投稿时使用的文件名，即 `upos_uri` 去掉路径和扩展名。
+    pub fn remote_filename(&self) -> String {
+        let name = self.upos_uri.rsplit('/').next().unwrap_or(&self.upos_uri);
+        name.split_once('.')
+            .map_or(name, |(stem, _)| stem)
+            .to_string()
+    }
+
+    fn chunk_range(&self, chunk: u64) -> (u64, u64) {
+        let start = chunk * self.chunk_size;
+        let end = (start + self.chunk_size).min(self.file_size);
+        (start, end)
+    }
+
+    fn matches_file(&self, file_name: &str, file_size: u64, file_modified: Option<u64>) -> bool {
+        self.file_name == file_name
+            && self.file_size == file_size
+            && self.file_modified == file_modified
+    }
+}
+
+/// [`CreativeCenterClient::upload_video`] 的参数。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VideoUploadParams {
+    path: PathBuf,
+    upcdn: String,
+    concurrency: Option<usize>,
+    chunk_retry: u32,
+    chunk_retry_delay: Duration,
+    resume_file: Option<PathBuf>,
+}
+
+impl VideoUploadParams {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            upcdn: DEFAULT_UPCDN.to_string(),
+            concurrency: None,
+            chunk_retry: DEFAULT_CHUNK_RETRY,
+            chunk_retry_delay: DEFAULT_CHUNK_RETRY_DELAY,
+            resume_file: None,
+        }
+    }
+
+    /// 设置上传线路。
+    pub fn with_upcdn(mut self, upcdn: impl Into<String>) -> BpiResult<Self> {
+        let upcdn = upcdn.into().trim().to_string();
+        if upcdn.is_empty() {
+            return Err(BpiError::invalid_parameter(
+                "upcdn",
+                "value cannot be blank",
+            ));
+        }
+
+        self.upcdn = upcdn;
+        Ok(self)
+    }
+
+    /// 设置并发分片数，默认使用 `preupload` 建议的并发数。
+    pub fn with_concurrency(mut self, concurrency: usize) -> BpiResult<Self> {
+        if concurrency == 0 || concurrency > UPOS_MAX_CONCURRENCY {
+            return Err(BpiError::invalid_parameter(
+                "concurrency",
+                "value must be between 1 and 8",
+            ));
+        }
+
+        self.concurrency = Some(concurrency);
+        Ok(self)
+    }
+
+    /// 设置单个分片失败后的重试次数和重试间隔。
+    pub fn with_chunk_retry(mut self, retry: u32, delay: Duration) -> Self {
+        self.chunk_retry = retry;
+        self.chunk_retry_delay = delay;
+        self
+    }
+
+    /// 设置断点文件。文件存在且与待上传文件匹配时从断点续传，上传成功后删除。
+    pub fn with_resume_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.resume_file = Some(path.into());
+        self
+    }
+
+    fn file_name(&self) -> BpiResult<String> {
+        self.path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(str::to_string)
+            .ok_or_else(|| BpiError::invalid_parameter("path", "path must name a file"))
+    }
+}
+
+/// 上传完成的视频文件，用于投稿时的分P。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadedVideo {
+    /// 投稿时使用的文件名
+    pub filename: String,
+    /// 分P的 `cid`
+    pub cid: u64,
+    /// 本地文件名去掉扩展名，默认作为分P标题
+    pub title: String,
+}
+
+impl<'a> CreativeCenterClient<'a> {
+    /// 获取视频上传节点和鉴权。
+    pub async fn preupload(&self, params: PreuploadParams) -> BpiResult<PreuploadData> {
+        let bytes = self
+            .client
+            .get(PREUPLOAD_ENDPOINT)
+            .query(&params.query_pairs())
+            .send_request("creativecenter.upload.preupload")
+            .await?;
+        let data: PreuploadData = serde_json::from_slice(&bytes)?;
+        if data.ok != 1 {
+            return Err(BpiError::unsupported_response(format!(
+                "preupload 返回 OK={}",
+                data.ok
+            )));
+        }
+
+        Ok(data)
+    }
+
+    /// 分片上传本地视频文件。
+    ///
+    /// 分片按 `preupload` 返回的大小切分并发上传，单个分片失败会按
+    /// [`VideoUploadParams::with_chunk_retry`] 重试。设置断点文件后，每完成一个分片都会更新断点。
+    pub async fn upload_video(&self, params: VideoUploadParams) -> BpiResult<UploadedVideo> {
+        let file_name = params.file_name()?;
+        let metadata = tokio::fs::metadata(&params.path)
+            .await
+            .map_err(|e| BpiError::parse(format!("读取文件失败: {}", e)))?;
+        let file_size = metadata.len();
+        let file_modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .and_then(|elapsed| u64::try_from(elapsed.as_millis()).ok());
+
+        let resumed = match &params.resume_file {
+            Some(path) => UposUploadState::load_async(path).await,
+            None => None,
+        }
+        .filter(|state| state.matches_file(&file_name, file_size, file_modified));
+        let (mut state, threads) = match resumed {
+            Some(state) => (state, None),
+            None => {
+                let preupload = self
+                    .preupload(
+                        PreuploadParams::new(&file_name, file_size)?
+                            .with_upcdn(params.upcdn.clone())?,
+                    )
+                    .await?;
+                let threads = usize::try_from(preupload.threads).ok();
+                (
+                    self.init_upload(&file_name, file_size, file_modified, preupload)
+                        .await?,
+                    threads,
+                )
+            }
+        };
+        if let Some(path) = &params.resume_file {
+            state.save_async(path).await?;
+        }
+
+        let concurrency = params
+            .concurrency
+            .or(threads)
+            .unwrap_or(3)
+            .clamp(1, UPOS_MAX_CONCURRENCY);
+        let state_ref = &state;
+        let params_ref = &params;
+        let mut uploads = stream::iter(state.pending_chunks())
+            .map(|chunk| async move {
+                self.upload_chunk_with_retry(state_ref, params_ref, chunk)
+                    .await
+                    .map(|()| chunk)
+            })
+            .buffer_unordered(concurrency);
+
+        // 上传中的分片借用了 `state`，断点写入单独的副本
+        let mut progress = state.clone();
+        let mut failure = None;
+        while let Some(result) = uploads.next().await {
+            match result {
+                Ok(chunk) => {
+                    progress.completed_chunks.insert(chunk);
+                    if let Some(path) = &params.resume_file
+                        && let Err(err) = progress.save_async(path).await
+                    {
+                        failure = Some(err);
+                        break;
+                    }
+                }
+                Err(err) => {
+                    failure = Some(err);
+                    break;
+                }
+            }
+        }
+        drop(uploads);
+
+        state.completed_chunks = progress.completed_chunks;
+        if let Some(err) = failure {
+            return Err(err);
+        }
+
+        self.complete_upload(&state).await?;
+        if let Some(path) = &params.resume_file {
+            let _ = tokio::fs::remove_file(path).await;
+        }
+
+        Ok(UploadedVideo {
+            filename: state.remote_filename(),
+            cid: state.biz_id,
+            title: Path::new(&file_name)
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or(&file_name)
+                .to_string(),
+        })
+    }
+
+    async fn init_upload(
+        &self,
+        file_name: &str,
+        file_size: u64,
+        file_modified: Option<u64>,
+        preupload: PreuploadData,
+    ) -> BpiResult<UposUploadState> {
+        if preupload.chunk_size == 0 {
+            return Err(BpiError::unsupported_response(
+                "preupload 返回的分片大小为 0",
+            ));
+        }
+
+        let mut state = UposUploadState {
+            file_name: file_name.to_string(),
+            file_size,
+            file_modified,
+            chunk_size: preupload.chunk_size,
+            endpoint: preupload.endpoint,
+            auth: preupload.auth,
+            upos_uri: preupload.upos_uri,
+            biz_id: preupload.biz_id,
+            timeout: preupload.timeout,
+            upload_id: String::new(),
+            completed_chunks: BTreeSet::new(),
+        };
+
+        let bytes = self
+            .client
+            .post(&state.upload_url())
+            .header("X-Upos-Auth", &state.auth)
+            .query(&[("uploads", ""), ("output", "json")])
+            .send_request("creativecenter.upload.init")
+            .await?;
+        let data: UposInitData = serde_json::from_slice(&bytes)?;
+        if data.ok != 1 {
+            return Err(BpiError::unsupported_response(format!(
+                "初始化分片上传返回 OK={}",
+                data.ok
+            )));
+        }
+
+        state.upload_id = data.upload_id;
+        Ok(state)
+    }
+
+    async fn upload_chunk_with_retry(
+        &self,
+        state: &UposUploadState,
+        params: &VideoUploadParams,
+        chunk: u64,
+    ) -> BpiResult<()> {
+        let mut attempt = 0;
+        loop {
+            match self.upload_chunk(state, &params.path, chunk).await {
+                Ok(()) => return Ok(()),
+                Err(err) if attempt < params.chunk_retry => {
+                    attempt += 1;
+                    tracing::warn!(chunk, attempt, error = %err, "视频分片上传失败，准备重试");
+                    tokio::time::sleep(params.chunk_retry_delay * attempt).await;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    async fn upload_chunk(
+        &self,
+        state: &UposUploadState,
+        path: &Path,
+        chunk: u64,
+    ) -> BpiResult<()> {
+        let (start, end) = state.chunk_range(chunk);
+        let mut buffer = vec![0; (end - start) as usize];
+        let mut file = tokio::fs::File::open(path)
+            .await
+            .map_err(|e| BpiError::parse(format!("读取文件失败: {}", e)))?;
+        file.seek(SeekFrom::Start(start))
+            .await
+            .map_err(|e| BpiError::parse(format!("读取文件失败: {}", e)))?;
+        file.read_exact(&mut buffer)
+            .await
+            .map_err(|e| BpiError::parse(format!("读取文件失败: {}", e)))?;
+
+        let mut request = self
+            .client
+            .put(&state.upload_url())
+            .header("X-Upos-Auth", &state.auth)
+            .query(&chunk_query_pairs(state, chunk))
+            .body(buffer);
+        if state.timeout > 0 {
+            request = request.timeout(Duration::from_secs(state.timeout));
+        }
+        request.send_request("creativecenter.upload.chunk").await?;
+
+        Ok(())
+    }
+
+    async fn complete_upload(&self, state: &UposUploadState) -> BpiResult<()> {
+        let parts = (1..=state.chunk_count())
+            .map(|part| serde_json::json!({ "partNumber": part, "eTag": "etag" }))
+            .collect::<Vec<_>>();
+        let bytes = self
+            .client
+            .post(&state.upload_url())
+            .header("X-Upos-Auth", &state.auth)
+            .query(&[
+                ("output", "json".to_string()),
+                ("name", state.file_name.clone()),
+                ("profile", DEFAULT_PROFILE.to_string()),
+                ("uploadId", state.upload_id.clone()),
+                ("biz_id", state.biz_id.to_string()),
+            ])
+            .json(&serde_json::json!({ "parts": parts }))
+            .send_request("creativecenter.upload.complete")
+            .await?;
+        let data: UposCompleteData = serde_json::from_slice(&bytes)?;
+        if data.ok != 1 {
+            return Err(BpiError::unsupported_response(format!(
+                "完成分片上传返回 OK={}",
+                data.ok
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+fn chunk_query_pairs(state: &UposUploadState, chunk: u64) -> Vec<(&'static str, String)> {
+    let (start, end) = state.chunk_range(chunk);
+    vec![
+        ("partNumber", (chunk + 1).to_string()),
+        ("uploadId", state.upload_id.clone()),
+        ("chunk", chunk.to_string()),
+        ("chunks", state.chunk_count().to_string()),
+        ("size", (end - start).to_string()),
+        ("start", start.to_string()),
+        ("end", end.to_string()),
+        ("total", state.file_size.to_string()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> UposUploadState {
+        UposUploadState {
+            file_name: "demo.mp4".to_string(),
+            file_size: 25,
+            file_modified: Some(1_700_000_000_000),
+            chunk_size: 10,
+            endpoint: "//upos-cs-upcdnbda2.bilivideo.com".to_string(),
+            auth: "auth".to_string(),
+            upos_uri: "upos://ugcfr/n230101abc.mp4".to_string(),
+            biz_id: 123,
+            timeout: 600,
+            upload_id: "upload".to_string(),
+            completed_chunks: BTreeSet::from([1]),
+        }
+    }
+
+    #[test]
+    fn preupload_params_serialize_query() -> BpiResult<()> {
+        let params = PreuploadParams::new("demo.mp4", 25)?.with_upcdn("qn")?;
+        let pairs = params.query_pairs();
+
+        assert!(pairs.contains(&("name", "demo.mp4".to_string())));
+        assert!(pairs.contains(&("size", "25".to_string())));
+        assert!(pairs.contains(&("upcdn", "qn".to_string())));
+        assert!(PreuploadParams::new(" ", 25).is_err());
+        assert!(PreuploadParams::new("demo.mp4", 0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn preupload_data_parses_unwrapped_response() -> BpiResult<()> {
+        let data: PreuploadData = serde_json::from_str(
+            r#"{
+                "OK": 1,
+                "auth": "ak=1&cdn=%2F%2Fupos-cs-upcdnbda2.bilivideo.com",
+                "biz_id": 123,
+                "chunk_size": 10485760,
+                "endpoint": "//upos-cs-upcdnbda2.bilivideo.com",
+                "endpoints": ["//upos-cs-upcdnbda2.bilivideo.com"],
+                "threads": 3,
+                "timeout": 600,
+                "upos_uri": "upos://ugcfr/n230101abc.mp4"
+            }"#,
+        )?;
+
+        assert_eq!(data.ok, 1);
+        assert_eq!(data.chunk_size, 10_485_760);
+        assert_eq!(data.threads, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn upload_state_plans_chunks_and_urls() {
+        let state = state();
+
+        assert_eq!(state.chunk_count(), 3);
+        assert_eq!(state.pending_chunks(), vec![0, 2]);
+        assert!(!state.is_complete());
+        assert_eq!(state.chunk_range(2), (20, 25));
+        assert_eq!(
+            state.upload_url(),
+            "https://upos-cs-upcdnbda2.bilivideo.com/ugcfr/n230101abc.mp4"
+        );
+        assert_eq!(state.remote_filename(), "n230101abc");
+    }
+
+    #[test]
+    fn chunk_query_uses_one_based_part_number() {
+        let pairs = chunk_query_pairs(&state(), 2);
+
+        assert_eq!(
+            pairs,
+            vec![
+                ("partNumber", "3".to_string()),
+                ("uploadId", "upload".to_string()),
+                ("chunk", "2".to_string()),
+                ("chunks", "3".to_string()),
+                ("size", "5".to_string()),
+                ("start", "20".to_string()),
+                ("end", "25".to_string()),
+                ("total", "25".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn upload_state_round_trips_through_resume_file() -> BpiResult<()> {
+        let path =
+            std::env::temp_dir().join(format!("bpi-rs-upos-state-{}.json", std::process::id()));
+        let state = state();
+
+        state.save(&path)?;
+        let loaded = UposUploadState::load(&path)?;
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(loaded, state);
+        assert!(loaded.matches_file("demo.mp4", 25, Some(1_700_000_000_000)));
+        assert!(!loaded.matches_file("demo.mp4", 26, Some(1_700_000_000_000)));
+        assert!(!loaded.matches_file("demo.mp4", 25, Some(1_700_000_000_001)));
+        assert!(!loaded.matches_file("demo.mp4", 25, None));
+        Ok(())
+    }
+
+    #[test]
+    fn video_upload_params_validate_concurrency() {
+        let params = VideoUploadParams::new("demo.mp4");
+
+        assert!(params.clone().with_concurrency(0).is_err());
+        assert!(params.clone().with_concurrency(9).is_err());
+        assert!(params.with_concurrency(4).is_ok());
+    }
+}
@@ -7,10 +7,14 @@ use crate::dynamic::detail::{
 };
 use crate::dynamic::get_dynamic_detail::RecentUpData;
 use crate::dynamic::nav::DynamicNavData;
+use crate::dynamic::space::DynamicSpaceData;
+use crate::dynamic::topic::{TopicCardsData, TopicDetailsData, TopicSearchData};
 use crate::dynamic::{
     DynamicAllParams, DynamicCheckNewParams, DynamicDetailParams, DynamicForwardItemParams,
     DynamicForwardsParams, DynamicLiveUsersParams, DynamicLotteryNoticeParams,
-    DynamicNavFeedParams, DynamicPicsParams, DynamicReactionsParams, DynamicUpUsersParams,
+    DynamicNavFeedParams, DynamicPicsParams, DynamicReactionsParams, DynamicSpaceParams,
+    DynamicTopicCardsParams, DynamicTopicDetailsParams, DynamicTopicSearchParams,
+    DynamicUpUsersParams,
 };
 use crate::{BilibiliRequest, BpiClient, BpiResult};
 
@@ -33,6 +37,10 @@ const LIVE_USERS_ENDPOINT: &str =
 const UP_USERS_ENDPOINT: &str =
     "https://api.vc.bilibili.com/dynamic_svr/v1/dynamic_svr/w_dyn_uplist";
 const RECENT_UP_ENDPOINT: &str = "https://api.bilibili.com/x/polymer/web-dynamic/v1/portal";
+const SPACE_ENDPOINT: &str = "https://api.bilibili.com/x/polymer/web-dynamic/v1/feed/space";
+const TOPIC_DETAILS_ENDPOINT: &str = "https://app.bilibili.com/x/topic/web/details/top";
+const TOPIC_CARDS_ENDPOINT: &str = "https://app.bilibili.com/x/topic/web/details/cards";
+const TOPIC_SEARCH_ENDPOINT: &str = "https://app.bilibili.com/x/topic/pub/search";
 
 /// 动态 API 客户端。
 #[derive(Clone, Copy)]
@@ -240,6 +248,50 @@ impl<'a> DynamicClient<'a> {
             .send_bpi_payload("dynamic.recent_up")
             .await
     }
+
+    /// 获取用户空间动态，翻页时传入上一页返回的 `offset`。
+    pub async fn space(&self, params: DynamicSpaceParams) -> BpiResult<DynamicSpaceData> {
+        let signed_params = self.client.sign_wbi_params(params.query_pairs()).await?;
+
+        self.client
+            .get(SPACE_ENDPOINT)
+            .query(&signed_params)
+            .send_bpi_payload("dynamic.feed_space")
+            .await
+    }
+
+    /// 获取话题详情。
+    pub async fn topic_details(
+        &self,
+        params: DynamicTopicDetailsParams,
+    ) -> BpiResult<TopicDetailsData> {
+        self.client
+            .get(TOPIC_DETAILS_ENDPOINT)
+            .query(&params.query_pairs())
+            .send_bpi_payload("dynamic.topic_details")
+            .await
+    }
+
+    /// 获取话题下的动态，翻页时传入上一页返回的 `offset`。
+    pub async fn topic_cards(&self, params: DynamicTopicCardsParams) -> BpiResult<TopicCardsData> {
+        self.client
+            .get(TOPIC_CARDS_ENDPOINT)
+            .query(&params.query_pairs())
+            .send_bpi_payload("dynamic.topic_cards")
+            .await
+    }
+
+    /// 按关键词搜索话题。
+    pub async fn topic_search(
+        &self,
+        params: DynamicTopicSearchParams,
+    ) -> BpiResult<TopicSearchData> {
+        self.client
+            .get(TOPIC_SEARCH_ENDPOINT)
+            .query(&params.query_pairs())
+            .send_bpi_payload("dynamic.topic_search")
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -255,12 +307,16 @@ mod tests {
     };
     use crate::dynamic::get_dynamic_detail::RecentUpData;
     use crate::dynamic::nav::DynamicNavData;
+    use crate::dynamic::space::DynamicSpaceData;
+    use crate::dynamic::topic::{TopicCardsData, TopicDetailsData, TopicSearchData};
     use crate::dynamic::{
         DynamicAllParams, DynamicCheckNewParams, DynamicDetailParams, DynamicForwardItemParams,
         DynamicForwardsParams, DynamicLiveUsersParams, DynamicLotteryNoticeParams,
-        DynamicNavFeedParams, DynamicPicsParams, DynamicReactionsParams, DynamicUpUsersParams,
+        DynamicNavFeedParams, DynamicPicsParams, DynamicReactionsParams, DynamicSpaceParams,
+        DynamicTopicCardsParams, DynamicTopicDetailsParams, DynamicTopicSearchParams,
+        DynamicUpUsersParams,
     };
-    use crate::ids::DynamicId;
+    use crate::ids::{DynamicId, Mid};
     use crate::probe::contract::HttpMethod;
     use crate::probe::endpoint_contract::EndpointContract;
     use crate::{BpiClient, BpiError, BpiResult};
@@ -347,6 +403,30 @@ mod tests {
     {
     }
 
+    fn assert_space_future<F>(_future: F)
+    where
+        F: Future<Output = BpiResult<DynamicSpaceData>>,
+    {
+    }
+
+    fn assert_topic_details_future<F>(_future: F)
+    where
+        F: Future<Output = BpiResult<TopicDetailsData>>,
+    {
+    }
+
+    fn assert_topic_cards_future<F>(_future: F)
+    where
+        F: Future<Output = BpiResult<TopicCardsData>>,
+    {
+    }
+
+    fn assert_topic_search_future<F>(_future: F)
+    where
+        F: Future<Output = BpiResult<TopicSearchData>>,
+    {
+    }
+
     fn contract(path: &str) -> BpiResult<EndpointContract> {
         let bytes = match path {
             "feed/all" => {
@@ -427,6 +507,10 @@ mod tests {
         assert_live_users_future(dynamic.live_users(DynamicLiveUsersParams::new().with_size(1)?));
         assert_up_users_future(dynamic.up_users(DynamicUpUsersParams::new()));
         assert_recent_up_future(dynamic.recent_up());
+        assert_space_future(dynamic.space(DynamicSpaceParams::new(Mid::new(2)?)));
+        assert_topic_details_future(dynamic.topic_details(DynamicTopicDetailsParams::new(1)?));
+        assert_topic_cards_future(dynamic.topic_cards(DynamicTopicCardsParams::new(1)?));
+        assert_topic_search_future(dynamic.topic_search(DynamicTopicSearchParams::new("keyword")?));
         Ok(())
     }
 
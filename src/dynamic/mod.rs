@@ -14,6 +14,7 @@ pub mod params;
 pub mod publish;
 pub mod space;
 pub mod topic;
+pub mod watcher;
 
 mod module;
 mod serde_utils;
@@ -24,6 +25,9 @@ pub use module::*;
 pub use params::{
     DynamicAllParams, DynamicCheckNewParams, DynamicDetailParams, DynamicForwardItemParams,
     DynamicForwardsParams, DynamicLiveUsersParams, DynamicLotteryNoticeParams,
-    DynamicNavFeedParams, DynamicPicsParams, DynamicReactionsParams, DynamicUpUsersParams,
+    DynamicNavFeedParams, DynamicPicsParams, DynamicReactionsParams, DynamicSpaceParams,
+    DynamicTopicCardsParams, DynamicTopicDetailsParams, DynamicTopicSearchParams, DynamicTopicSort,
+    DynamicUpUsersParams,
 };
 pub use publish::{DynamicComplexCreateParams, DynamicTextCreateParams, DynamicUploadPicParams};
+pub use watcher::{DynamicFeed, DynamicFeedCursor, DynamicWatchCursor, DynamicWatcher};
@@ -4,6 +4,10 @@ use crate::{BpiError, BpiResult};
 const DEFAULT_ALL_FEATURES: &str = "itemOpusStyle,listOnlyfans,opusBigCover,onlyfansVote,decorationCard,onlyfansAssetsV2,forwardListHidden,ugcDelete";
 const DEFAULT_ALL_WEB_LOCATION: &str = "333.1365";
 const DEFAULT_DETAIL_FEATURES: &str = "htmlNewStyle,itemOpusStyle,decorationCard";
+const DEFAULT_SPACE_WEB_LOCATION: &str = "333.1387";
+const DEFAULT_TIMEZONE_OFFSET: &str = "-480";
+const DEFAULT_TOPIC_SOURCE: &str = "Web";
+const DEFAULT_TOPIC_PAGE_SIZE: u32 = 20;
 
 /// `/x/polymer/web-dynamic/v1/feed/all` 的参数。
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -80,6 +84,44 @@ impl DynamicAllParams {
     }
 }
 
+/// `/x/polymer/web-dynamic/v1/feed/space` 的参数。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamicSpaceParams {
+    host_mid: Mid,
+    offset: Option<String>,
+    features: String,
+}
+
+impl DynamicSpaceParams {
+    pub fn new(host_mid: Mid) -> Self {
+        Self {
+            host_mid,
+            offset: None,
+            features: DEFAULT_ALL_FEATURES.to_string(),
+        }
+    }
+
+    pub fn with_offset(mut self, offset: impl Into<String>) -> BpiResult<Self> {
+        self.offset = Some(normalize_non_blank("offset", offset.into())?);
+        Ok(self)
+    }
+
+    pub fn with_features(mut self, features: impl Into<String>) -> BpiResult<Self> {
+        self.features = normalize_non_blank("features", features.into())?;
+        Ok(self)
+    }
+
+    pub fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("host_mid", self.host_mid.to_string()),
+            ("offset", self.offset.clone().unwrap_or_default()),
+            ("timezone_offset", DEFAULT_TIMEZONE_OFFSET.to_string()),
+            ("features", self.features.clone()),
+            ("web_location", DEFAULT_SPACE_WEB_LOCATION.to_string()),
+        ]
+    }
+}
+
 /// `/x/polymer/web-dynamic/v1/feed/all/update` 的参数。
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DynamicCheckNewParams {
@@ -315,6 +357,160 @@ impl DynamicForwardItemParams {
     }
 }
 
+/// 话题下动态的排序方式。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DynamicTopicSort {
+    /// 默认排序。
+    #[default]
+    Default,
+    /// 推荐排序。
+    Recommend,
+    /// 按热度排序。
+    Hot,
+    /// 按发布时间排序。
+    Latest,
+}
+
+impl DynamicTopicSort {
+    fn as_query_value(self) -> &'static str {
+        match self {
+            Self::Default => "0",
+            Self::Recommend => "1",
+            Self::Hot => "2",
+            Self::Latest => "3",
+        }
+    }
+}
+
+/// `/x/topic/web/details/top` 的参数。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DynamicTopicDetailsParams {
+    topic_id: u64,
+}
+
+impl DynamicTopicDetailsParams {
+    pub fn new(topic_id: u64) -> BpiResult<Self> {
+        Ok(Self {
+            topic_id: validate_topic_id(topic_id)?,
+        })
+    }
+
+    pub fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("topic_id", self.topic_id.to_string()),
+            ("source", DEFAULT_TOPIC_SOURCE.to_string()),
+        ]
+    }
+}
+
+/// `/x/topic/web/details/cards` 的参数。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamicTopicCardsParams {
+    topic_id: u64,
+    sort_by: DynamicTopicSort,
+    offset: Option<String>,
+    page_size: u32,
+}
+
+impl DynamicTopicCardsParams {
+    pub fn new(topic_id: u64) -> BpiResult<Self> {
+        Ok(Self {
+            topic_id: validate_topic_id(topic_id)?,
+            sort_by: DynamicTopicSort::Default,
+            offset: None,
+            page_size: DEFAULT_TOPIC_PAGE_SIZE,
+        })
+    }
+
+    pub fn with_sort_by(mut self, sort_by: DynamicTopicSort) -> Self {
+        self.sort_by = sort_by;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: impl Into<String>) -> BpiResult<Self> {
+        self.offset = Some(normalize_non_blank("offset", offset.into())?);
+        Ok(self)
+    }
+
+    pub fn with_page_size(mut self, page_size: u32) -> BpiResult<Self> {
+        self.page_size = validate_page_size(page_size)?;
+        Ok(self)
+    }
+
+    pub fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![
+            ("topic_id", self.topic_id.to_string()),
+            ("sort_by", self.sort_by.as_query_value().to_string()),
+            ("page_size", self.page_size.to_string()),
+            ("source", DEFAULT_TOPIC_SOURCE.to_string()),
+        ];
+        if let Some(offset) = &self.offset {
+            query.push(("offset", offset.clone()));
+        }
+
+        query
+    }
+}
+
+/// `/x/topic/pub/search` 的参数。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamicTopicSearchParams {
+    keywords: String,
+    offset: u32,
+    page_size: u32,
+}
+
+impl DynamicTopicSearchParams {
+    pub fn new(keywords: impl Into<String>) -> BpiResult<Self> {
+        Ok(Self {
+            keywords: normalize_non_blank("keywords", keywords.into())?,
+            offset: 0,
+            page_size: DEFAULT_TOPIC_PAGE_SIZE,
+        })
+    }
+
+    pub fn with_offset(mut self, offset: u32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_page_size(mut self, page_size: u32) -> BpiResult<Self> {
+        self.page_size = validate_page_size(page_size)?;
+        Ok(self)
+    }
+
+    pub fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("keywords", self.keywords.clone()),
+            ("content", String::new()),
+            ("offset", self.offset.to_string()),
+            ("page_size", self.page_size.to_string()),
+        ]
+    }
+}
+
+fn validate_topic_id(topic_id: u64) -> BpiResult<u64> {
+    if topic_id == 0 {
+        return Err(BpiError::invalid_parameter(
+            "topic_id",
+            "id must be non-zero",
+        ));
+    }
+
+    Ok(topic_id)
+}
+
+fn validate_page_size(page_size: u32) -> BpiResult<u32> {
+    if page_size == 0 {
+        return Err(BpiError::invalid_parameter(
+            "page_size",
+            "value must be non-zero",
+        ));
+    }
+
+    Ok(page_size)
+}
+
 fn dynamic_offset_query(id: &DynamicId, offset: Option<&str>) -> Vec<(&'static str, String)> {
     let mut query = vec![("id", id.to_string())];
     if let Some(offset) = offset {
@@ -403,6 +599,100 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn space_params_serializes_defaults_with_empty_offset() -> BpiResult<()> {
+        let params = DynamicSpaceParams::new(Mid::new(12345)?);
+
+        assert_eq!(
+            params.query_pairs(),
+            vec![
+                ("host_mid", "12345".to_string()),
+                ("offset", String::new()),
+                ("timezone_offset", "-480".to_string()),
+                (
+                    "features",
+                    "itemOpusStyle,listOnlyfans,opusBigCover,onlyfansVote,decorationCard,onlyfansAssetsV2,forwardListHidden,ugcDelete".to_string(),
+                ),
+                ("web_location", "333.1387".to_string()),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn space_params_serializes_offset() -> BpiResult<()> {
+        let params = DynamicSpaceParams::new(Mid::new(12345)?).with_offset("offset-token")?;
+
+        assert!(
+            params
+                .query_pairs()
+                .contains(&("offset", "offset-token".to_string()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn topic_details_params_rejects_zero_topic_id() {
+        let err = DynamicTopicDetailsParams::new(0).unwrap_err();
+
+        assert!(matches!(
+            err,
+            BpiError::InvalidParameter {
+                field: "topic_id",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn topic_cards_params_serializes_sort_and_cursor() -> BpiResult<()> {
+        let params = DynamicTopicCardsParams::new(1001)?
+            .with_sort_by(DynamicTopicSort::Latest)
+            .with_page_size(10)?
+            .with_offset("offset-token")?;
+
+        assert_eq!(
+            params.query_pairs(),
+            vec![
+                ("topic_id", "1001".to_string()),
+                ("sort_by", "3".to_string()),
+                ("page_size", "10".to_string()),
+                ("source", "Web".to_string()),
+                ("offset", "offset-token".to_string()),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn topic_search_params_serializes_keywords_and_page() -> BpiResult<()> {
+        let params = DynamicTopicSearchParams::new(" keyword ")?.with_offset(20);
+
+        assert_eq!(
+            params.query_pairs(),
+            vec![
+                ("keywords", "keyword".to_string()),
+                ("content", String::new()),
+                ("offset", "20".to_string()),
+                ("page_size", "20".to_string()),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn topic_search_params_rejects_blank_keywords() {
+        let err = DynamicTopicSearchParams::new("  ").unwrap_err();
+
+        assert!(matches!(
+            err,
+            BpiError::InvalidParameter {
+                field: "keywords",
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn check_new_params_serializes_required_baseline() -> BpiResult<()> {
         let params = DynamicCheckNewParams::new("baseline-token")?;
@@ -1 +1,61 @@
-// https://github.com/Yuelioi/bilibili-API-collect/tree/cfc5fddcc8a94b74d91970bb5b4eaeb349addc47/docs/dynamic/space.md
+use serde::{Deserialize, Serialize};
+
+use crate::dynamic::all::DynamicItem;
+use crate::dynamic::serde_utils::deserialize_u64_from_string_or_number;
+
+/// 用户空间动态响应数据
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DynamicSpaceData {
+    /// 是否还有下一页
+    pub has_more: bool,
+    /// 动态列表
+    pub items: Vec<DynamicItem>,
+    /// 下一页的 `offset`
+    pub offset: String,
+    /// 更新基线，空间动态一般为空字符串
+    #[serde(default)]
+    pub update_baseline: String,
+    /// 新动态数量，空间动态一般为 0
+    #[serde(default, deserialize_with = "deserialize_u64_from_string_or_number")]
+    pub update_num: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ApiEnvelope;
+    use crate::BpiResult;
+
+    #[test]
+    fn space_data_parses_feed_page_without_baseline() -> BpiResult<()> {
+        let bytes = br#"{
+            "code": 0,
+            "message": "0",
+            "ttl": 1,
+            "data": {
+                "has_more": true,
+                "items": [{
+                    "basic": {
+                        "comment_id_str": "1",
+                        "comment_type": 11,
+                        "like_icon": {},
+                        "rid_str": "1"
+                    },
+                    "id_str": "1099138163191840776",
+                    "modules": {},
+                    "type": "DYNAMIC_TYPE_DRAW",
+                    "visible": true
+                }],
+                "offset": "1099138163191840776"
+            }
+        }"#;
+
+        let payload = ApiEnvelope::<DynamicSpaceData>::from_slice(bytes)?.into_payload()?;
+
+        assert!(payload.has_more);
+        assert_eq!(payload.items.len(), 1);
+        assert_eq!(payload.offset, "1099138163191840776");
+        assert_eq!(payload.update_num, 0);
+        Ok(())
+    }
+}
@@ -1 +1,249 @@
-// https://github.com/Yuelioi/bilibili-API-collect/tree/cfc5fddcc8a94b74d91970bb5b4eaeb349addc47/docs/dynamic/topic.md
+use serde::{Deserialize, Serialize};
+
+use crate::dynamic::all::DynamicItem;
+
+/// 话题详情响应数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicDetailsData {
+    /// 话题顶部详情
+    pub top_details: TopicTopDetails,
+    /// 话题功能卡片，结构随活动变化
+    #[serde(default)]
+    pub functional_card: Option<serde_json::Value>,
+}
+
+/// 话题顶部详情
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicTopDetails {
+    /// 话题信息
+    pub topic_item: TopicItem,
+    /// 话题创建者，官方话题可能为 null
+    #[serde(default)]
+    pub topic_creator: Option<TopicCreator>,
+    /// 运营内容，结构随活动变化
+    #[serde(default)]
+    pub operation_content: Option<serde_json::Value>,
+    /// 当前账号是否有创建话题的权限
+    #[serde(default)]
+    pub has_create_jurisdiction: bool,
+    /// 文字颜色
+    #[serde(default)]
+    pub word_color: i64,
+    /// 是否关闭发布入口
+    #[serde(default)]
+    pub close_pub_layer_entry: bool,
+}
+
+/// 话题信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TopicItem {
+    /// 话题 ID
+    pub id: u64,
+    /// 话题名称
+    pub name: String,
+    /// 浏览数
+    #[serde(default)]
+    pub view: u64,
+    /// 讨论数
+    #[serde(default)]
+    pub discuss: u64,
+    /// 收藏数
+    #[serde(default)]
+    pub fav: u64,
+    /// 动态数
+    #[serde(default)]
+    pub dynamics: u64,
+    /// 点赞数
+    #[serde(default)]
+    pub like: u64,
+    /// 分享数
+    #[serde(default)]
+    pub share: u64,
+    /// 话题页链接
+    #[serde(default)]
+    pub jump_url: String,
+    /// 背景颜色
+    #[serde(default)]
+    pub back_color: String,
+    /// 话题描述
+    #[serde(default)]
+    pub description: String,
+    /// 分享图片 URL
+    #[serde(default)]
+    pub share_pic: String,
+    /// 分享链接
+    #[serde(default)]
+    pub share_url: String,
+    /// 当前账号是否已收藏
+    #[serde(default)]
+    pub is_fav: bool,
+    /// 当前账号是否已点赞
+    #[serde(default)]
+    pub is_like: bool,
+}
+
+/// 话题创建者
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicCreator {
+    /// 创建者 UID
+    pub uid: u64,
+    /// 创建者头像 URL
+    #[serde(default)]
+    pub face: String,
+    /// 创建者昵称
+    #[serde(default)]
+    pub name: String,
+}
+
+/// 话题下动态列表响应数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicCardsData {
+    /// 话题动态列表
+    pub topic_card_list: Option<TopicCardList>,
+    /// 相关话题，结构随话题变化
+    #[serde(default)]
+    pub related_topics: Option<serde_json::Value>,
+}
+
+/// 话题动态列表
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TopicCardList {
+    /// 是否还有下一页
+    pub has_more: bool,
+    /// 下一页的 `offset`
+    #[serde(default)]
+    pub offset: String,
+    /// 动态卡片列表
+    #[serde(default)]
+    pub items: Vec<TopicCard>,
+}
+
+/// 话题动态卡片
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicCard {
+    /// 动态条目，结构与已关注动态流相同
+    #[serde(default)]
+    pub dynamic_card_item: Option<DynamicItem>,
+    /// 卡片类型，如 "DYNAMIC"
+    #[serde(default)]
+    pub topic_type: String,
+}
+
+/// 话题搜索响应数据
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TopicSearchData {
+    /// 匹配的话题
+    #[serde(default)]
+    pub topic_items: Vec<TopicItem>,
+    /// 分页信息
+    #[serde(default)]
+    pub page_info: TopicSearchPageInfo,
+}
+
+/// 话题搜索分页信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TopicSearchPageInfo {
+    /// 下一页的 `offset`
+    #[serde(default)]
+    pub offset: i64,
+    /// 是否还有下一页
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ApiEnvelope, BpiResult};
+
+    #[test]
+    fn topic_details_parse_item_and_missing_creator() -> BpiResult<()> {
+        let bytes = br#"{
+            "code": 0,
+            "message": "0",
+            "data": {
+                "top_details": {
+                    "topic_item": {
+                        "id": 1001,
+                        "name": "topic",
+                        "view": 10,
+                        "discuss": 2,
+                        "dynamics": 3,
+                        "jump_url": "https://m.bilibili.com/topic-detail?topic_id=1001"
+                    },
+                    "topic_creator": null,
+                    "has_create_jurisdiction": false
+                }
+            }
+        }"#;
+
+        let payload = ApiEnvelope::<TopicDetailsData>::from_slice(bytes)?.into_payload()?;
+
+        assert_eq!(payload.top_details.topic_item.id, 1001);
+        assert_eq!(payload.top_details.topic_item.dynamics, 3);
+        assert!(payload.top_details.topic_creator.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn topic_cards_parse_dynamic_items() -> BpiResult<()> {
+        let bytes = br#"{
+            "code": 0,
+            "message": "0",
+            "data": {
+                "topic_card_list": {
+                    "has_more": true,
+                    "offset": "next",
+                    "items": [{
+                        "dynamic_card_item": {
+                            "basic": {
+                                "comment_id_str": "1",
+                                "comment_type": 11,
+                                "like_icon": {},
+                                "rid_str": "1"
+                            },
+                            "id_str": "1099138163191840776",
+                            "modules": {},
+                            "type": "DYNAMIC_TYPE_DRAW",
+                            "visible": true
+                        },
+                        "topic_type": "DYNAMIC"
+                    }]
+                }
+            }
+        }"#;
+
+        let payload = ApiEnvelope::<TopicCardsData>::from_slice(bytes)?.into_payload()?;
+        let list = payload.topic_card_list.unwrap_or_default();
+
+        assert!(list.has_more);
+        assert_eq!(list.offset, "next");
+        assert_eq!(
+            list.items[0]
+                .dynamic_card_item
+                .as_ref()
+                .map(|item| item.id_str.as_str()),
+            Some("1099138163191840776")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn topic_search_parses_items_and_page_info() -> BpiResult<()> {
+        let bytes = br#"{
+            "code": 0,
+            "message": "0",
+            "data": {
+                "topic_items": [{ "id": 1001, "name": "topic", "view": 10, "discuss": 2 }],
+                "page_info": { "offset": 20, "has_more": false }
+            }
+        }"#;
+
+        let payload = ApiEnvelope::<TopicSearchData>::from_slice(bytes)?.into_payload()?;
+
+        assert_eq!(payload.topic_items.len(), 1);
+        assert_eq!(payload.page_info.offset, 20);
+        assert!(!payload.page_info.has_more);
+        Ok(())
+    }
+}
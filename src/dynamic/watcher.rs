@@ -0,0 +1,530 @@
+//! 动态流新内容监听
+//!
+//! [`DynamicWatcher`] 为每个被监听的动态流维护基线，按自适应间隔轮询，只返回上次轮询后
+//! 新出现的 [`DynamicItem`]。监听进度保存在可序列化的 [`DynamicWatchCursor`] 中，
+//! 进程重启后可以从上次的位置继续。
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dynamic::DynamicClient;
+use crate::dynamic::all::DynamicItem;
+use crate::dynamic::params::{DynamicAllParams, DynamicCheckNewParams, DynamicUpUsersParams};
+use crate::ids::Mid;
+use crate::{BpiError, BpiResult};
+
+const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_INTERVAL: Duration = Duration::from_secs(600);
+/// 单次轮询中同一动态流最多向后追赶的页数，避免长时间离线后一次拉取过多。
+const MAX_CATCH_UP_PAGES: usize = 5;
+
+/// 被监听的动态流。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DynamicFeed {
+    /// 当前账号的已关注动态流。
+    Home,
+    /// 已关注动态流中指定 UP 主的动态。
+    Space(Mid),
+    /// `w_dyn_uplist` 返回的有新动态的已关注 UP 主集合。
+    UpUsers,
+}
+
+impl DynamicFeed {
+    /// 返回该动态流在 [`DynamicWatchCursor`] 中的键。
+    pub fn key(&self) -> String {
+        match self {
+            Self::Home => "home".to_string(),
+            Self::Space(mid) => format!("space:{mid}"),
+            Self::UpUsers => "up_users".to_string(),
+        }
+    }
+}
+
+/// 单个动态流的监听基线。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DynamicFeedCursor {
+    /// 最近一次 `feed/all` 返回的 `update_baseline`，仅首页动态流使用。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_baseline: Option<String>,
+    /// 已发出的最大动态 ID。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest_id: Option<u64>,
+    /// 基线建立时间，UNIX 秒级时间戳；没有 `latest_id` 时按发布时间判断新动态。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since_ts: Option<i64>,
+}
+
+impl DynamicFeedCursor {
+    fn is_new(&self, item: &DynamicItem) -> bool {
+        let Some(id) = item_id(item) else {
+            return false;
+        };
+
+        match (self.latest_id, self.since_ts) {
+            (Some(latest_id), _) => id > latest_id,
+            (None, Some(since_ts)) => item_pub_ts(item).is_some_and(|pub_ts| pub_ts > since_ts),
+            (None, None) => false,
+        }
+    }
+
+    fn is_initialized(&self) -> bool {
+        self.latest_id.is_some() || self.since_ts.is_some()
+    }
+
+    fn advance(&mut self, items: &[DynamicItem]) {
+        if let Some(max_id) = items.iter().filter_map(item_id).max() {
+            self.latest_id = Some(self.latest_id.map_or(max_id, |id| id.max(max_id)));
+        }
+    }
+}
+
+/// 可持久化的监听进度，按 [`DynamicFeed::key`] 保存每个动态流的基线。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DynamicWatchCursor {
+    #[serde(default)]
+    feeds: BTreeMap<String, DynamicFeedCursor>,
+}
+
+impl DynamicWatchCursor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 返回指定动态流的基线。
+    pub fn feed(&self, feed: &DynamicFeed) -> Option<&DynamicFeedCursor> {
+        self.feeds.get(&feed.key())
+    }
+
+    /// 返回全部动态流基线。
+    pub fn feeds(&self) -> &BTreeMap<String, DynamicFeedCursor> {
+        &self.feeds
+    }
+
+    fn entry(&mut self, key: String) -> &mut DynamicFeedCursor {
+        self.feeds.entry(key).or_default()
+    }
+}
+
+/// 按自适应间隔轮询一个或多个动态流，只返回新出现的动态。
+///
+/// 第一次轮询某个动态流时只建立基线，不返回已有动态。轮询发现新动态后间隔重置为
+/// 最小值；连续没有新动态或请求失败时间隔逐次翻倍，直到最大值。
+///
+/// ```no_run
+/// # async fn run() -> bpi_rs::BpiResult<()> {
+/// use bpi_rs::BpiClient;
+/// use bpi_rs::dynamic::{DynamicFeed, DynamicWatcher};
+/// use bpi_rs::ids::Mid;
+///
+/// let client = BpiClient::new()?;
+/// let mut watcher = DynamicWatcher::new(
+///     client.dynamic(),
+///     vec![DynamicFeed::Space(Mid::new(2)?), DynamicFeed::Home],
+/// )?;
+///
+/// loop {
+///     for item in watcher.next().await? {
+///         println!("{} {}", item.type_field, item.id_str);
+///     }
+///     let _saved = serde_json::to_string(watcher.cursor())?;
+/// }
+/// # }
+/// ```
+pub struct DynamicWatcher<'a> {
+    client: DynamicClient<'a>,
+    feeds: Vec<DynamicFeed>,
+    cursor: DynamicWatchCursor,
+    /// 本次轮询中的进度，全部动态流都成功后才写回 `cursor`
+    staged: DynamicWatchCursor,
+    min_interval: Duration,
+    max_interval: Duration,
+    interval: Duration,
+    polled: bool,
+}
+
+impl<'a> DynamicWatcher<'a> {
+    /// 创建监听器，`feeds` 不能为空。
+    pub fn new(client: DynamicClient<'a>, feeds: Vec<DynamicFeed>) -> BpiResult<Self> {
+        if feeds.is_empty() {
+            return Err(BpiError::invalid_parameter(
+                "feeds",
+                "at least one feed is required",
+            ));
+        }
+
+        Ok(Self {
+            client,
+            feeds,
+            cursor: DynamicWatchCursor::default(),
+            staged: DynamicWatchCursor::default(),
+            min_interval: DEFAULT_MIN_INTERVAL,
+            max_interval: DEFAULT_MAX_INTERVAL,
+            interval: DEFAULT_MIN_INTERVAL,
+            polled: false,
+        })
+    }
+
+    /// 从之前保存的进度继续监听。
+    pub fn with_cursor(mut self, cursor: DynamicWatchCursor) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    /// 设置轮询间隔范围。
+    pub fn with_interval(mut self, min: Duration, max: Duration) -> BpiResult<Self> {
+        if min.is_zero() {
+            return Err(BpiError::invalid_parameter(
+                "min_interval",
+                "value must be non-zero",
+            ));
+        }
+        if max < min {
+            return Err(BpiError::invalid_parameter(
+                "max_interval",
+                "value must not be less than min_interval",
+            ));
+        }
+
+        self.min_interval = min;
+        self.max_interval = max;
+        self.interval = min;
+        Ok(self)
+    }
+
+    /// 返回当前监听进度，可序列化后持久化。
+    pub fn cursor(&self) -> &DynamicWatchCursor {
+        &self.cursor
+    }
+
+    /// 取出监听进度。
+    pub fn into_cursor(self) -> DynamicWatchCursor {
+        self.cursor
+    }
+
+    /// 返回下一次轮询前的等待时间。
+    pub fn next_delay(&self) -> Duration {
+        self.interval
+    }
+
+    /// 等待自适应间隔后轮询一次；首次调用立即轮询。
+    pub async fn next(&mut self) -> BpiResult<Vec<DynamicItem>> {
+        if self.polled {
+            tokio::time::sleep(self.interval).await;
+        }
+        self.polled = true;
+
+        self.poll().await
+    }
+
+    /// 立即轮询全部动态流，按动态 ID 从旧到新返回新动态。
+    pub async fn poll(&mut self) -> BpiResult<Vec<DynamicItem>> {
+        let result = self.poll_feeds().await;
+        let found = result.as_ref().is_ok_and(|items| !items.is_empty());
+        self.adjust_interval(found);
+
+        let mut items = result?;
+        items.sort_by_key(|item| item_id(item).unwrap_or_default());
+        items.dedup_by(|a, b| a.id_str == b.id_str);
+        Ok(items)
+    }
+
+    /// 各动态流的进度先写入 `staged`，任一动态流失败或轮询被取消时 `cursor` 保持不变，
+    /// 下次轮询会重新返回这些动态。
+    async fn poll_feeds(&mut self) -> BpiResult<Vec<DynamicItem>> {
+        self.staged = self.cursor.clone();
+        let items = self.poll_staged().await?;
+        self.cursor = std::mem::take(&mut self.staged);
+        Ok(items)
+    }
+
+    async fn poll_staged(&mut self) -> BpiResult<Vec<DynamicItem>> {
+        let mut items = Vec::new();
+        for feed in self.feeds.clone() {
+            match feed {
+                DynamicFeed::Home => items.extend(self.poll_home().await?),
+                DynamicFeed::Space(mid) => {
+                    items.extend(self.poll_space(feed.key(), mid, None).await?);
+                }
+                DynamicFeed::UpUsers => items.extend(self.poll_up_users().await?),
+            }
+        }
+
+        Ok(items)
+    }
+
+    async fn poll_home(&mut self) -> BpiResult<Vec<DynamicItem>> {
+        let key = DynamicFeed::Home.key();
+        if let Some(baseline) = self.staged.entry(key.clone()).update_baseline.clone() {
+            let update = self
+                .client
+                .check_new(DynamicCheckNewParams::new(baseline)?)
+                .await?;
+            if update.update_num == 0 {
+                return Ok(Vec::new());
+            }
+        }
+
+        self.fetch_new(key, DynamicAllParams::new()).await
+    }
+
+    async fn poll_space(
+        &mut self,
+        key: String,
+        mid: Mid,
+        since_ts: Option<i64>,
+    ) -> BpiResult<Vec<DynamicItem>> {
+        let state = self.staged.entry(key.clone());
+        if !state.is_initialized() {
+            state.since_ts = since_ts;
+        }
+
+        self.fetch_new(key, DynamicAllParams::new().with_host_mid(mid))
+            .await
+    }
+
+    async fn poll_up_users(&mut self) -> BpiResult<Vec<DynamicItem>> {
+        let state = self.staged.entry(DynamicFeed::UpUsers.key());
+        let Some(since_ts) = state.since_ts else {
+            state.since_ts = Some(chrono::Utc::now().timestamp());
+            return Ok(Vec::new());
+        };
+
+        let users = self.client.up_users(DynamicUpUsersParams::new()).await?;
+        let mut items = Vec::new();
+        for user in users.items {
+            let Ok(mid) = Mid::new(user.user_profile.info.uid) else {
+                continue;
+            };
+            let key = DynamicFeed::Space(mid).key();
+            items.extend(self.poll_space(key, mid, Some(since_ts)).await?);
+        }
+
+        Ok(items)
+    }
+
+    async fn fetch_new(
+        &mut self,
+        key: String,
+        params: DynamicAllParams,
+    ) -> BpiResult<Vec<DynamicItem>> {
+        let mut state = self.staged.entry(key.clone()).clone();
+        let initialized = state.is_initialized();
+        let mut new_items = Vec::new();
+        let mut offset: Option<String> = None;
+        let mut baseline = None;
+
+        for _ in 0..MAX_CATCH_UP_PAGES {
+            let page_params = match &offset {
+                Some(offset) => params.clone().with_offset(offset.clone())?,
+                None => params.clone(),
+            };
+            let page = self.client.all(page_params).await?;
+            if baseline.is_none() {
+                baseline = Some(page.update_baseline.clone());
+            }
+
+            if !initialized {
+                state.advance(&page.items);
+                break;
+            }
+
+            let page_len = page.items.len();
+            let fresh: Vec<_> = page
+                .items
+                .into_iter()
+                .filter(|item| state.is_new(item))
+                .collect();
+            let exhausted = fresh.len() < page_len || !page.has_more || page.offset.is_empty();
+            new_items.extend(fresh);
+            if exhausted {
+                break;
+            }
+            offset = Some(page.offset);
+        }
+
+        state.advance(&new_items);
+        if let Some(baseline) = baseline.filter(|baseline| !baseline.is_empty()) {
+            state.update_baseline = Some(baseline);
+        }
+        if state.latest_id.is_none() && state.since_ts.is_none() {
+            state.since_ts = Some(chrono::Utc::now().timestamp());
+        }
+        *self.staged.entry(key) = state;
+
+        Ok(new_items)
+    }
+
+    fn adjust_interval(&mut self, found: bool) {
+        self.interval = if found {
+            self.min_interval
+        } else {
+            self.interval.saturating_mul(2).min(self.max_interval)
+        };
+    }
+}
+
+impl<'a> DynamicClient<'a> {
+    /// 创建动态流监听器。
+    pub fn watcher(&self, feeds: Vec<DynamicFeed>) -> BpiResult<DynamicWatcher<'a>> {
+        DynamicWatcher::new(*self, feeds)
+    }
+}
+
+fn item_id(item: &DynamicItem) -> Option<u64> {
+    item.id_str.parse().ok()
+}
+
+fn item_pub_ts(item: &DynamicItem) -> Option<i64> {
+    item.modules
+        .get("module_author")
+        .and_then(|author| author.get("pub_ts"))
+        .and_then(|pub_ts| {
+            pub_ts
+                .as_i64()
+                .or_else(|| pub_ts.as_str().and_then(|value| value.parse().ok()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BpiClient;
+
+    fn item(id: &str, pub_ts: i64) -> BpiResult<DynamicItem> {
+        let value = serde_json::json!({
+            "basic": {
+                "comment_id_str": "1",
+                "comment_type": 11,
+                "like_icon": {},
+                "rid_str": "1"
+            },
+            "id_str": id,
+            "modules": { "module_author": { "pub_ts": pub_ts } },
+            "type": "DYNAMIC_TYPE_WORD",
+            "visible": true
+        });
+        Ok(serde_json::from_value(value)?)
+    }
+
+    #[test]
+    fn feed_keys_are_stable_per_feed() -> BpiResult<()> {
+        assert_eq!(DynamicFeed::Home.key(), "home");
+        assert_eq!(DynamicFeed::Space(Mid::new(2)?).key(), "space:2");
+        assert_eq!(DynamicFeed::UpUsers.key(), "up_users");
+        Ok(())
+    }
+
+    #[test]
+    fn feed_cursor_treats_higher_ids_as_new() -> BpiResult<()> {
+        let cursor = DynamicFeedCursor {
+            latest_id: Some(100),
+            ..Default::default()
+        };
+
+        assert!(cursor.is_new(&item("101", 0)?));
+        assert!(!cursor.is_new(&item("100", 0)?));
+        assert!(!cursor.is_new(&item("99", i64::MAX)?));
+        assert!(!cursor.is_new(&item("not-a-number", 0)?));
+        Ok(())
+    }
+
+    #[test]
+    fn feed_cursor_falls_back_to_publish_time_without_latest_id() -> BpiResult<()> {
+        let cursor = DynamicFeedCursor {
+            since_ts: Some(1_700_000_000),
+            ..Default::default()
+        };
+
+        assert!(cursor.is_new(&item("1", 1_700_000_001)?));
+        assert!(!cursor.is_new(&item("2", 1_700_000_000)?));
+        assert!(!DynamicFeedCursor::default().is_new(&item("3", 0)?));
+        Ok(())
+    }
+
+    #[test]
+    fn feed_cursor_advance_keeps_maximum_id() -> BpiResult<()> {
+        let mut cursor = DynamicFeedCursor {
+            latest_id: Some(150),
+            ..Default::default()
+        };
+
+        cursor.advance(&[item("120", 0)?, item("180", 0)?]);
+        assert_eq!(cursor.latest_id, Some(180));
+
+        cursor.advance(&[item("160", 0)?]);
+        assert_eq!(cursor.latest_id, Some(180));
+        Ok(())
+    }
+
+    #[test]
+    fn watch_cursor_round_trips_through_json() -> BpiResult<()> {
+        let mut cursor = DynamicWatchCursor::new();
+        *cursor.entry(DynamicFeed::Home.key()) = DynamicFeedCursor {
+            update_baseline: Some("baseline".to_string()),
+            latest_id: Some(42),
+            since_ts: None,
+        };
+
+        let json = serde_json::to_string(&cursor)?;
+        assert_eq!(
+            json,
+            r#"{"feeds":{"home":{"update_baseline":"baseline","latest_id":42}}}"#
+        );
+        assert_eq!(serde_json::from_str::<DynamicWatchCursor>(&json)?, cursor);
+        assert_eq!(
+            cursor
+                .feed(&DynamicFeed::Home)
+                .and_then(|feed| feed.latest_id),
+            Some(42)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn watcher_rejects_empty_feed_list() -> BpiResult<()> {
+        let client = BpiClient::new()?;
+
+        assert!(matches!(
+            client.dynamic().watcher(Vec::new()),
+            Err(BpiError::InvalidParameter { field: "feeds", .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn watcher_interval_backs_off_until_new_items_arrive() -> BpiResult<()> {
+        let client = BpiClient::new()?;
+        let mut watcher = client
+            .dynamic()
+            .watcher(vec![DynamicFeed::Home])?
+            .with_interval(Duration::from_secs(10), Duration::from_secs(35))?;
+
+        watcher.adjust_interval(false);
+        assert_eq!(watcher.next_delay(), Duration::from_secs(20));
+        watcher.adjust_interval(false);
+        assert_eq!(watcher.next_delay(), Duration::from_secs(35));
+        watcher.adjust_interval(true);
+        assert_eq!(watcher.next_delay(), Duration::from_secs(10));
+        Ok(())
+    }
+
+    #[test]
+    fn watcher_rejects_inverted_interval_range() -> BpiResult<()> {
+        let client = BpiClient::new()?;
+        let result = client
+            .dynamic()
+            .watcher(vec![DynamicFeed::Home])?
+            .with_interval(Duration::from_secs(10), Duration::from_secs(5));
+
+        assert!(matches!(
+            result,
+            Err(BpiError::InvalidParameter {
+                field: "max_interval",
+                ..
+            })
+        ));
+        Ok(())
+    }
+}
@@ -5,6 +5,8 @@ mod client;
 pub mod info;
 pub mod list;
 pub mod params;
+mod resource;
+mod sync;
 
 pub use client::FavClient;
 pub use list::FavListDetailParams;
@@ -13,3 +15,8 @@ pub use params::{
     FavFolderEditParams, FavFolderInfoParams, FavResourceBatchDeleteParams, FavResourceCleanParams,
     FavResourceIdsParams, FavResourceInfosParams, FavResourceTransferParams,
 };
+pub use resource::FavResource;
+pub use sync::{
+    FAV_TRANSFER_MAX_BATCH, FavBatchTransferParams, FavBatchTransferReport, FavFolderMirror,
+    FavTransferBatch, FavTransferMode,
+};
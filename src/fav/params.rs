@@ -1,3 +1,4 @@
+use crate::fav::resource::{FavResource, join_resources};
 use crate::ids::{MediaId, Mid};
 use crate::{BpiError, BpiResult};
 
@@ -125,6 +126,11 @@ impl FavResourceInfosParams {
         })
     }
 
+    /// 使用类型化的资源标识创建参数。
+    pub fn from_resources(resources: impl IntoIterator<Item = FavResource>) -> BpiResult<Self> {
+        Self::new(join_resources(resources)?)
+    }
+
     pub fn with_platform(mut self, platform: impl Into<String>) -> BpiResult<Self> {
         self.platform = normalize_non_blank("platform", platform.into())?;
         Ok(self)
@@ -316,6 +322,16 @@ impl FavResourceTransferParams {
         })
     }
 
+    /// 使用类型化的资源标识创建参数。
+    pub fn from_resources(
+        src_media_id: MediaId,
+        tar_media_id: MediaId,
+        mid: Mid,
+        resources: impl IntoIterator<Item = FavResource>,
+    ) -> BpiResult<Self> {
+        Self::new(src_media_id, tar_media_id, mid, join_resources(resources)?)
+    }
+
     pub(crate) fn form_pairs(&self, csrf: &str) -> Vec<(&'static str, String)> {
         vec![
             ("src_media_id", self.src_media_id.to_string()),
@@ -343,6 +359,14 @@ impl FavResourceBatchDeleteParams {
         })
     }
 
+    /// 使用类型化的资源标识创建参数。
+    pub fn from_resources(
+        media_id: MediaId,
+        resources: impl IntoIterator<Item = FavResource>,
+    ) -> BpiResult<Self> {
+        Self::new(media_id, join_resources(resources)?)
+    }
+
     pub(crate) fn form_pairs(&self, csrf: &str) -> Vec<(&'static str, String)> {
         vec![
             ("media_id", self.media_id.to_string()),
@@ -565,6 +589,29 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn fav_resource_params_accept_typed_resources() -> BpiResult<()> {
+        let resources = [
+            FavResource::from(crate::ids::Aid::new(371494037)?),
+            FavResource::from(crate::ids::AudioId::new(15664)?),
+        ];
+
+        let infos = FavResourceInfosParams::from_resources(resources)?;
+        let delete = FavResourceBatchDeleteParams::from_resources(MediaId::new(1)?, resources)?;
+        let transfer = FavResourceTransferParams::from_resources(
+            MediaId::new(1)?,
+            MediaId::new(2)?,
+            Mid::new(3)?,
+            resources,
+        )?;
+
+        assert_eq!(infos.query_pairs()[0].1, "371494037:2,15664:12");
+        assert_eq!(delete.form_pairs("csrf")[1].1, "371494037:2,15664:12");
+        assert_eq!(transfer.form_pairs("csrf")[3].1, "371494037:2,15664:12");
+        assert!(FavResourceInfosParams::from_resources([]).is_err());
+        Ok(())
+    }
+
     #[test]
     fn fav_resource_ids_params_serializes_defaults() -> BpiResult<()> {
         let params = FavResourceIdsParams::new(MediaId::new(1052622027)?);
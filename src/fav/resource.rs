@@ -0,0 +1,174 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::fav::list::FavListMedia;
+use crate::ids::{Aid, AudioId, SeasonId};
+use crate::{BpiError, BpiResult};
+
+const TYPE_VIDEO: u8 = 2;
+const TYPE_AUDIO: u8 = 12;
+const TYPE_COLLECTION: u8 = 21;
+
+/// 收藏资源标识，`Display` 输出接口使用的 `id:type` 形式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FavResource {
+    /// 视频稿件，类型 2。
+    Video(Aid),
+    /// 音频，类型 12。
+    Audio(AudioId),
+    /// 视频合集，类型 21。
+    Collection(SeasonId),
+    /// 其他类型资源。
+    Other { id: u64, kind: u8 },
+}
+
+impl FavResource {
+    /// 根据资源 ID 和接口返回的资源类型创建标识。
+    pub fn new(id: u64, kind: u8) -> BpiResult<Self> {
+        match kind {
+            TYPE_VIDEO => Ok(Self::Video(Aid::new(id)?)),
+            TYPE_AUDIO => Ok(Self::Audio(AudioId::new(id)?)),
+            TYPE_COLLECTION => Ok(Self::Collection(SeasonId::new(id)?)),
+            _ if id == 0 => Err(BpiError::invalid_parameter(
+                "resources",
+                "id must be non-zero",
+            )),
+            _ => Ok(Self::Other { id, kind }),
+        }
+    }
+
+    /// 资源 ID。
+    pub fn id(self) -> u64 {
+        match self {
+            Self::Video(aid) => aid.get(),
+            Self::Audio(audio_id) => audio_id.get(),
+            Self::Collection(season_id) => season_id.get(),
+            Self::Other { id, .. } => id,
+        }
+    }
+
+    /// 接口使用的资源类型。
+    pub fn kind(self) -> u8 {
+        match self {
+            Self::Video(_) => TYPE_VIDEO,
+            Self::Audio(_) => TYPE_AUDIO,
+            Self::Collection(_) => TYPE_COLLECTION,
+            Self::Other { kind, .. } => kind,
+        }
+    }
+
+    /// 从收藏夹内容明细中的条目创建标识。
+    pub fn from_media(media: &FavListMedia) -> BpiResult<Self> {
+        Self::new(media.id, media.type_name)
+    }
+}
+
+impl fmt::Display for FavResource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.id(), self.kind())
+    }
+}
+
+impl FromStr for FavResource {
+    type Err = BpiError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || BpiError::invalid_parameter("resources", "resource must be `id:type`");
+        let (id, kind) = value.trim().split_once(':').ok_or_else(invalid)?;
+        let id = id.parse::<u64>().map_err(|_| invalid())?;
+        let kind = kind.parse::<u8>().map_err(|_| invalid())?;
+        Self::new(id, kind)
+    }
+}
+
+impl From<Aid> for FavResource {
+    fn from(aid: Aid) -> Self {
+        Self::Video(aid)
+    }
+}
+
+impl From<AudioId> for FavResource {
+    fn from(audio_id: AudioId) -> Self {
+        Self::Audio(audio_id)
+    }
+}
+
+impl From<SeasonId> for FavResource {
+    fn from(season_id: SeasonId) -> Self {
+        Self::Collection(season_id)
+    }
+}
+
+/// 把资源列表拼接为 `id:type,id:type`，列表为空时返回参数错误。
+pub(crate) fn join_resources(
+    resources: impl IntoIterator<Item = FavResource>,
+) -> BpiResult<String> {
+    let resources = resources
+        .into_iter()
+        .map(|resource| resource.to_string())
+        .collect::<Vec<_>>();
+    if resources.is_empty() {
+        return Err(BpiError::invalid_parameter(
+            "resources",
+            "at least one resource is required",
+        ));
+    }
+
+    Ok(resources.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fav_resource_formats_known_types() -> BpiResult<()> {
+        assert_eq!(
+            FavResource::from(Aid::new(371494037)?).to_string(),
+            "371494037:2"
+        );
+        assert_eq!(
+            FavResource::from(AudioId::new(15664)?).to_string(),
+            "15664:12"
+        );
+        assert_eq!(
+            FavResource::from(SeasonId::new(1234)?).to_string(),
+            "1234:21"
+        );
+        assert_eq!(FavResource::new(99, 24)?.to_string(), "99:24");
+        Ok(())
+    }
+
+    #[test]
+    fn fav_resource_parses_id_type_pairs() -> BpiResult<()> {
+        assert_eq!(
+            "371494037:2".parse::<FavResource>()?,
+            FavResource::Video(Aid::new(371494037)?)
+        );
+        assert!(matches!(
+            "371494037".parse::<FavResource>(),
+            Err(BpiError::InvalidParameter {
+                field: "resources",
+                ..
+            })
+        ));
+        assert!("0:24".parse::<FavResource>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn join_resources_rejects_empty_list() -> BpiResult<()> {
+        assert_eq!(
+            join_resources([
+                FavResource::from(Aid::new(1)?),
+                FavResource::from(AudioId::new(2)?)
+            ])?,
+            "1:2,2:12"
+        );
+        assert!(join_resources([]).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,367 @@
+use serde::{Deserialize, Serialize};
+
+use crate::fav::list::{FavListInfo, FavListMedia};
+use crate::fav::resource::FavResource;
+use crate::fav::{FavClient, FavListDetailParams, FavResourceTransferParams};
+use crate::ids::{MediaId, Mid};
+use crate::{BpiError, BpiResult};
+
+/// 单次复制或移动请求最多携带的资源数量。
+pub const FAV_TRANSFER_MAX_BATCH: usize = 50;
+
+/// 收藏夹明细接口单页最多返回的条目数。
+const LIST_DETAIL_PAGE_SIZE: u32 = 20;
+/// 失效条目的统一标题。
+const INVALID_MEDIA_TITLE: &str = "已失效视频";
+
+/// 由 [`FavClient::sync_folder`] 拉取的收藏夹本地镜像。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FavFolderMirror {
+    /// 收藏夹元数据，取自第一页。
+    pub info: FavListInfo,
+    /// 收藏夹中的全部条目，按接口返回顺序排列。
+    pub medias: Vec<FavListMedia>,
+}
+
+impl FavFolderMirror {
+    /// 全部条目的资源标识。
+    pub fn resources(&self) -> Vec<FavResource> {
+        self.medias
+            .iter()
+            .filter_map(|media| FavResource::from_media(media).ok())
+            .collect()
+    }
+
+    /// 已失效或已删除的条目。
+    pub fn invalid_medias(&self) -> impl Iterator<Item = &FavListMedia> {
+        self.medias.iter().filter(|media| is_invalid_media(media))
+    }
+
+    /// 已失效或已删除条目的资源标识。
+    pub fn invalid_resources(&self) -> Vec<FavResource> {
+        self.invalid_medias()
+            .filter_map(|media| FavResource::from_media(media).ok())
+            .collect()
+    }
+
+    /// 仍然有效条目的资源标识。
+    pub fn valid_resources(&self) -> Vec<FavResource> {
+        self.medias
+            .iter()
+            .filter(|media| !is_invalid_media(media))
+            .filter_map(|media| FavResource::from_media(media).ok())
+            .collect()
+    }
+}
+
+/// 批量转移资源的方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FavTransferMode {
+    /// 复制到目标收藏夹。
+    Copy,
+    /// 移动到目标收藏夹。
+    Move,
+}
+
+/// [`FavClient::transfer_resources_in_batches`] 的参数。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FavBatchTransferParams {
+    src_media_id: MediaId,
+    tar_media_id: MediaId,
+    mid: Mid,
+    resources: Vec<FavResource>,
+    mode: FavTransferMode,
+    batch_size: usize,
+}
+
+impl FavBatchTransferParams {
+    pub fn new(
+        src_media_id: MediaId,
+        tar_media_id: MediaId,
+        mid: Mid,
+        resources: impl IntoIterator<Item = FavResource>,
+        mode: FavTransferMode,
+    ) -> BpiResult<Self> {
+        let resources = resources.into_iter().collect::<Vec<_>>();
+        if resources.is_empty() {
+            return Err(BpiError::invalid_parameter(
+                "resources",
+                "at least one resource is required",
+            ));
+        }
+
+        Ok(Self {
+            src_media_id,
+            tar_media_id,
+            mid,
+            resources,
+            mode,
+            batch_size: FAV_TRANSFER_MAX_BATCH,
+        })
+    }
+
+    /// 设置每批资源数量，不能超过 [`FAV_TRANSFER_MAX_BATCH`]。
+    pub fn with_batch_size(mut self, batch_size: usize) -> BpiResult<Self> {
+        if batch_size == 0 || batch_size > FAV_TRANSFER_MAX_BATCH {
+            return Err(BpiError::invalid_parameter(
+                "batch_size",
+                "value must be between 1 and 50",
+            ));
+        }
+
+        self.batch_size = batch_size;
+        Ok(self)
+    }
+
+    fn batches(&self) -> impl Iterator<Item = &[FavResource]> {
+        self.resources.chunks(self.batch_size)
+    }
+}
+
+/// 一批资源的转移结果。
+#[derive(Debug)]
+pub struct FavTransferBatch {
+    /// 本批资源。
+    pub resources: Vec<FavResource>,
+    /// 失败原因；成功时为 `None`。
+    pub error: Option<BpiError>,
+}
+
+/// [`FavClient::transfer_resources_in_batches`] 的执行报告。
+#[derive(Debug, Default)]
+pub struct FavBatchTransferReport {
+    /// 已发送的批次。
+    pub batches: Vec<FavTransferBatch>,
+    /// 因登录失效或风控而未发送的资源。
+    pub skipped: Vec<FavResource>,
+}
+
+impl FavBatchTransferReport {
+    /// 失败批次中的资源和未发送的资源。
+    pub fn failed_resources(&self) -> Vec<FavResource> {
+        self.batches
+            .iter()
+            .filter(|batch| batch.error.is_some())
+            .flat_map(|batch| batch.resources.iter().copied())
+            .chain(self.skipped.iter().copied())
+            .collect()
+    }
+}
+
+impl<'a> FavClient<'a> {
+    /// 翻页拉取收藏夹全部条目，生成本地镜像。
+    pub async fn sync_folder(&self, media_id: MediaId) -> BpiResult<FavFolderMirror> {
+        let mut info = None;
+        let mut medias = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let params = FavListDetailParams::new(media_id)
+                .page_size(LIST_DETAIL_PAGE_SIZE)?
+                .page(page)?;
+            let data = self.list_detail(params).await?;
+            let has_more = data.has_more && !data.medias.is_empty();
+            info.get_or_insert(data.info);
+            medias.extend(data.medias);
+
+            if !has_more {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(FavFolderMirror {
+            info: info.ok_or_else(BpiError::missing_data)?,
+            medias,
+        })
+    }
+
+    /// 按批复制或移动资源。
+    ///
+    /// 单批的业务错误会记录在报告中并继续下一批；登录失效或触发风控时停止发送，
+    /// 剩余资源记入 [`FavBatchTransferReport::skipped`]。
+    pub async fn transfer_resources_in_batches(
+        &self,
+        params: FavBatchTransferParams,
+    ) -> BpiResult<FavBatchTransferReport> {
+        let mut report = FavBatchTransferReport::default();
+        let mut stopped = false;
+
+        for batch in params.batches() {
+            if stopped {
+                report.skipped.extend_from_slice(batch);
+                continue;
+            }
+
+            let transfer = FavResourceTransferParams::from_resources(
+                params.src_media_id,
+                params.tar_media_id,
+                params.mid,
+                batch.iter().copied(),
+            )?;
+            let result = match params.mode {
+                FavTransferMode::Copy => self.copy_resources(transfer).await,
+                FavTransferMode::Move => self.move_resources(transfer).await,
+            };
+            let error = result.err();
+            stopped = error
+                .as_ref()
+                .is_some_and(|err| err.requires_login() || err.is_risk_control());
+
+            report.batches.push(FavTransferBatch {
+                resources: batch.to_vec(),
+                error,
+            });
+        }
+
+        Ok(report)
+    }
+}
+
+/// 视频被 UP 主删除时 `attr` 为 9，其他原因失效时为 1。
+fn is_invalid_media(media: &FavListMedia) -> bool {
+    matches!(media.attr, 1 | 9) || media.title == INVALID_MEDIA_TITLE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ids::Aid;
+
+    fn media(id: u64, attr: u8, title: &str) -> BpiResult<FavListMedia> {
+        Ok(serde_json::from_value(serde_json::json!({
+            "id": id,
+            "type": 2,
+            "title": title,
+            "cover": "",
+            "intro": "",
+            "duration": 60,
+            "upper": { "mid": 1, "name": "up", "face": "" },
+            "attr": attr,
+            "cnt_info": { "collect": 0, "play": 0 },
+            "link": "",
+            "ctime": 0,
+            "pubtime": 0,
+            "fav_time": 0
+        }))?)
+    }
+
+    fn mirror(medias: Vec<FavListMedia>) -> BpiResult<FavFolderMirror> {
+        let info = serde_json::from_value(serde_json::json!({
+            "id": 1052622027,
+            "fid": 10526220,
+            "mid": 7792521,
+            "attr": 0,
+            "title": "默认收藏夹",
+            "cover": "",
+            "upper": { "mid": 7792521, "name": "user", "face": "" },
+            "cover_type": 2,
+            "cnt_info": { "collect": 0, "play": 0 },
+            "type": 11,
+            "intro": "",
+            "ctime": 0,
+            "mtime": 0,
+            "state": 0,
+            "fav_state": 0,
+            "like_state": 0,
+            "media_count": 3
+        }))?;
+
+        Ok(FavFolderMirror { info, medias })
+    }
+
+    #[test]
+    fn mirror_separates_invalid_medias() -> BpiResult<()> {
+        let mirror = mirror(vec![
+            media(1, 0, "正常视频")?,
+            media(2, 9, "已失效视频")?,
+            media(3, 1, "其他原因删除")?,
+        ])?;
+
+        assert_eq!(mirror.resources().len(), 3);
+        assert_eq!(
+            mirror.invalid_resources(),
+            vec![
+                FavResource::Video(Aid::new(2)?),
+                FavResource::Video(Aid::new(3)?)
+            ]
+        );
+        assert_eq!(
+            mirror.valid_resources(),
+            vec![FavResource::Video(Aid::new(1)?)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn batch_transfer_params_split_resources() -> BpiResult<()> {
+        let resources = (1..=5)
+            .map(|id| Aid::new(id).map(FavResource::from))
+            .collect::<BpiResult<Vec<_>>>()?;
+        let params = FavBatchTransferParams::new(
+            MediaId::new(1)?,
+            MediaId::new(2)?,
+            Mid::new(3)?,
+            resources,
+            FavTransferMode::Move,
+        )?
+        .with_batch_size(2)?;
+
+        assert_eq!(
+            params.batches().map(<[_]>::len).collect::<Vec<_>>(),
+            vec![2, 2, 1]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn batch_transfer_params_validate_batch_size() -> BpiResult<()> {
+        let params = FavBatchTransferParams::new(
+            MediaId::new(1)?,
+            MediaId::new(2)?,
+            Mid::new(3)?,
+            [FavResource::from(Aid::new(1)?)],
+            FavTransferMode::Copy,
+        )?;
+
+        assert!(params.clone().with_batch_size(0).is_err());
+        assert!(params.with_batch_size(FAV_TRANSFER_MAX_BATCH + 1).is_err());
+        assert!(
+            FavBatchTransferParams::new(
+                MediaId::new(1)?,
+                MediaId::new(2)?,
+                Mid::new(3)?,
+                [],
+                FavTransferMode::Copy,
+            )
+            .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn report_collects_failed_and_skipped_resources() -> BpiResult<()> {
+        let report = FavBatchTransferReport {
+            batches: vec![
+                FavTransferBatch {
+                    resources: vec![FavResource::from(Aid::new(1)?)],
+                    error: None,
+                },
+                FavTransferBatch {
+                    resources: vec![FavResource::from(Aid::new(2)?)],
+                    error: Some(BpiError::from_code(-101)),
+                },
+            ],
+            skipped: vec![FavResource::from(Aid::new(3)?)],
+        };
+
+        assert_eq!(
+            report.failed_resources(),
+            vec![
+                FavResource::from(Aid::new(2)?),
+                FavResource::from(Aid::new(3)?)
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,677 @@
+//! 分区房间列表与分区快照
+//!
+//! [`LiveClient::area_rooms`] 分页读取分区内正在直播的房间。[`LiveAreaCrawler`] 遍历选定分区的全部页，
+//! 生成带采集时间的 [`LiveAreaSnapshot`]；多次快照的 CSV 可以直接拼接，用于跟踪分区热度随时间的变化。
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::future::Future;
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::ids::RoomId;
+use crate::live::LiveClient;
+use crate::live::recommend::WatchedShow;
+use crate::utils::csv;
+use crate::{BilibiliRequest, BpiError, BpiResult};
+
+const AREA_ROOMS_ENDPOINT: &str =
+    "https://api.live.bilibili.com/xlive/web-interface/v1/second/getList";
+const DEFAULT_MAX_PAGES: u32 = 50;
+const DEFAULT_PAGE_DELAY: Duration = Duration::from_millis(500);
+
+/// 分区房间排序
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LiveAreaSort {
+    /// 综合排序
+    #[default]
+    Recommend,
+    /// 按人气
+    Online,
+    /// 按开播时间
+    LiveTime,
+}
+
+impl LiveAreaSort {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Recommend => "",
+            Self::Online => "online",
+            Self::LiveTime => "live_time",
+        }
+    }
+}
+
+/// 分区房间列表参数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveAreaRoomsParams {
+    parent_area_id: u64,
+    area_id: u64,
+    sort: LiveAreaSort,
+    page: u32,
+}
+
+impl LiveAreaRoomsParams {
+    /// `area_id` 为 0 时列出整个父分区。
+    pub fn new(parent_area_id: u64, area_id: u64) -> BpiResult<Self> {
+        if parent_area_id == 0 {
+            return Err(BpiError::invalid_parameter(
+                "parent_area_id",
+                "value must be non-zero",
+            ));
+        }
+
+        Ok(Self {
+            parent_area_id,
+            area_id,
+            sort: LiveAreaSort::default(),
+            page: 1,
+        })
+    }
+
+    pub fn with_sort(mut self, sort: LiveAreaSort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// 页码，从 1 开始。
+    pub fn with_page(mut self, page: u32) -> BpiResult<Self> {
+        if page == 0 {
+            return Err(BpiError::invalid_parameter("page", "page starts at 1"));
+        }
+
+        self.page = page;
+        Ok(self)
+    }
+
+    pub fn parent_area_id(&self) -> u64 {
+        self.parent_area_id
+    }
+
+    pub fn area_id(&self) -> u64 {
+        self.area_id
+    }
+
+    pub fn page(&self) -> u32 {
+        self.page
+    }
+
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("platform", "web".to_string()),
+            ("parent_area_id", self.parent_area_id.to_string()),
+            ("area_id", self.area_id.to_string()),
+            ("sort_type", self.sort.as_str().to_string()),
+            ("page", self.page.to_string()),
+        ]
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct LiveAreaRoom {
+    /// 直播间ID
+    pub roomid: i64,
+    /// 主播用户mid
+    pub uid: i64,
+    /// 直播间标题
+    pub title: String,
+    /// 主播用户名
+    pub uname: String,
+    /// 人气值
+    #[serde(default)]
+    pub online: i64,
+    /// 封面URL
+    #[serde(default)]
+    pub cover: String,
+    /// 父分区ID
+    #[serde(default)]
+    pub parent_id: i64,
+    /// 父分区名称
+    #[serde(default)]
+    pub parent_name: String,
+    /// 分区ID
+    #[serde(default)]
+    pub area_id: i64,
+    /// 分区名称
+    #[serde(default)]
+    pub area_name: String,
+    /// 看过人数
+    #[serde(default)]
+    pub watched_show: Option<WatchedShow>,
+}
+
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct LiveAreaTag {
+    /// 标签ID
+    pub id: i64,
+    /// 标签名称
+    pub name: String,
+    /// 对应的排序方式
+    #[serde(default)]
+    pub sort_type: String,
+}
+
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct LiveAreaRoomsData {
+    /// 房间列表
+    #[serde(default)]
+    pub list: Option<Vec<LiveAreaRoom>>,
+    /// 分区标签
+    #[serde(default)]
+    pub new_tags: Option<Vec<LiveAreaTag>>,
+    /// 房间总数
+    #[serde(default)]
+    pub count: i64,
+    /// 是否还有下一页，1：有
+    #[serde(default)]
+    pub has_more: i32,
+}
+
+impl LiveAreaRoomsData {
+    pub fn is_last_page(&self) -> bool {
+        self.has_more == 0 || self.list.as_ref().is_none_or(Vec::is_empty)
+    }
+}
+
+/// 快照中的一个房间
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiveAreaRoomRecord {
+    /// 采集时间
+    pub taken_at: DateTime<Local>,
+    pub parent_area_id: i64,
+    pub parent_area_name: String,
+    pub area_id: i64,
+    pub area_name: String,
+    pub room_id: i64,
+    pub uid: i64,
+    pub uname: String,
+    pub title: String,
+    /// 人气值
+    pub online: i64,
+    /// 看过人数，未开启展示时为空
+    pub watched: Option<i64>,
+    /// 直播间标签，未采集标签时为空
+    pub tags: Vec<String>,
+}
+
+impl LiveAreaRoomRecord {
+    fn new(taken_at: DateTime<Local>, room: LiveAreaRoom) -> Self {
+        Self {
+            taken_at,
+            parent_area_id: room.parent_id,
+            parent_area_name: room.parent_name,
+            area_id: room.area_id,
+            area_name: room.area_name,
+            room_id: room.roomid,
+            uid: room.uid,
+            uname: room.uname,
+            title: room.title,
+            online: room.online,
+            watched: room
+                .watched_show
+                .filter(|show| show.switch)
+                .map(|show| i64::from(show.num)),
+            tags: Vec::new(),
+        }
+    }
+}
+
+/// 单个分区的汇总
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiveAreaSummary {
+    pub parent_area_id: i64,
+    pub parent_area_name: String,
+    pub area_id: i64,
+    pub area_name: String,
+    /// 正在直播的房间数
+    pub rooms: u64,
+    pub total_online: i64,
+    pub total_watched: i64,
+}
+
+/// 一次分区快照
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiveAreaSnapshot {
+    /// 开始采集的时间
+    pub taken_at: DateTime<Local>,
+    pub rooms: Vec<LiveAreaRoomRecord>,
+    /// 读取失败的页，该分区之后的页不再读取
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed: Vec<LiveAreaPageFailure>,
+}
+
+/// 快照中读取失败的一页
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiveAreaPageFailure {
+    pub parent_area_id: u64,
+    pub area_id: u64,
+    pub page: u32,
+    /// 错误信息
+    pub error: String,
+}
+
+impl LiveAreaSnapshot {
+    /// 是否全部页都读取成功。
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    /// 序列化为 JSON。
+    pub fn to_json(&self) -> BpiResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// 房间 CSV，首列为采集时间，多次快照去掉表头后可以直接追加。标签以 `|` 分隔。
+    pub fn rooms_csv(&self) -> String {
+        let mut csv = String::from(
+            "taken_at,parent_area_id,parent_area_name,area_id,area_name,room_id,uid,uname,title,online,watched,tags\n",
+        );
+        for room in &self.rooms {
+            csv::push_row(
+                &mut csv,
+                &[
+                    room.taken_at.to_rfc3339(),
+                    room.parent_area_id.to_string(),
+                    room.parent_area_name.clone(),
+                    room.area_id.to_string(),
+                    room.area_name.clone(),
+                    room.room_id.to_string(),
+                    room.uid.to_string(),
+                    room.uname.clone(),
+                    room.title.clone(),
+                    room.online.to_string(),
+                    room.watched.map(|n| n.to_string()).unwrap_or_default(),
+                    room.tags.join("|"),
+                ],
+            );
+        }
+
+        csv
+    }
+
+    /// 按子分区汇总，按人气降序。
+    pub fn area_summaries(&self) -> Vec<LiveAreaSummary> {
+        let mut areas: BTreeMap<i64, LiveAreaSummary> = BTreeMap::new();
+        for room in &self.rooms {
+            let summary = areas
+                .entry(room.area_id)
+                .or_insert_with(|| LiveAreaSummary {
+                    parent_area_id: room.parent_area_id,
+                    parent_area_name: room.parent_area_name.clone(),
+                    area_id: room.area_id,
+                    area_name: room.area_name.clone(),
+                    rooms: 0,
+                    total_online: 0,
+                    total_watched: 0,
+                });
+            summary.rooms += 1;
+            summary.total_online += room.online;
+            summary.total_watched += room.watched.unwrap_or_default();
+        }
+
+        let mut summaries: Vec<_> = areas.into_values().collect();
+        summaries.sort_by_key(|summary| std::cmp::Reverse(summary.total_online));
+        summaries
+    }
+
+    /// 分区汇总 CSV。
+    pub fn areas_csv(&self) -> String {
+        let taken_at = self.taken_at.to_rfc3339();
+        let mut csv = String::from(
+            "taken_at,parent_area_id,parent_area_name,area_id,area_name,rooms,total_online,total_watched\n",
+        );
+        for area in self.area_summaries() {
+            csv::push_row(
+                &mut csv,
+                &[
+                    taken_at.clone(),
+                    area.parent_area_id.to_string(),
+                    area.parent_area_name,
+                    area.area_id.to_string(),
+                    area.area_name,
+                    area.rooms.to_string(),
+                    area.total_online.to_string(),
+                    area.total_watched.to_string(),
+                ],
+            );
+        }
+
+        csv
+    }
+}
+
+/// 分区房间采集器。
+pub struct LiveAreaCrawler<'a> {
+    client: LiveClient<'a>,
+    areas: Vec<(u64, u64)>,
+    sort: LiveAreaSort,
+    max_pages: u32,
+    page_delay: Duration,
+    room_tags: bool,
+}
+
+impl<'a> LiveAreaCrawler<'a> {
+    fn new(client: LiveClient<'a>) -> Self {
+        Self {
+            client,
+            areas: Vec::new(),
+            sort: LiveAreaSort::Online,
+            max_pages: DEFAULT_MAX_PAGES,
+            page_delay: DEFAULT_PAGE_DELAY,
+            room_tags: false,
+        }
+    }
+
+    /// 加入要采集的分区，`area_id` 为 0 时采集整个父分区。
+    pub fn with_area(mut self, parent_area_id: u64, area_id: u64) -> BpiResult<Self> {
+        LiveAreaRoomsParams::new(parent_area_id, area_id)?;
+
+        if !self.areas.contains(&(parent_area_id, area_id)) {
+            self.areas.push((parent_area_id, area_id));
+        }
+        Ok(self)
+    }
+
+    /// 排序方式，默认按人气。
+    pub fn with_sort(mut self, sort: LiveAreaSort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// 每个分区最多读取的页数，默认 50 页。
+    pub fn with_max_pages(mut self, max_pages: u32) -> BpiResult<Self> {
+        if max_pages == 0 {
+            return Err(BpiError::invalid_parameter(
+                "max_pages",
+                "value must be non-zero",
+            ));
+        }
+
+        self.max_pages = max_pages;
+        Ok(self)
+    }
+
+    /// 两次请求之间的间隔，默认 500 毫秒。
+    pub fn with_page_delay(mut self, delay: Duration) -> Self {
+        self.page_delay = delay;
+        self
+    }
+
+    /// 是否逐个读取直播间信息以采集标签，默认不采集；开启后每个房间多一次请求。
+    pub fn with_room_tags(mut self, room_tags: bool) -> Self {
+        self.room_tags = room_tags;
+        self
+    }
+
+    /// 采集一次全部分区。翻页时房间可能因人气变化在相邻页重复出现，同一房间只保留第一次出现的记录。
+    pub async fn snapshot(&self) -> BpiResult<LiveAreaSnapshot> {
+        if self.areas.is_empty() {
+            return Err(BpiError::invalid_parameter(
+                "areas",
+                "at least one area is required",
+            ));
+        }
+
+        let mut snapshot = self
+            .collect_pages(|params| self.client.area_rooms(params))
+            .await?;
+
+        if self.room_tags {
+            for room in &mut snapshot.rooms {
+                tokio::time::sleep(self.page_delay).await;
+                let room_id = RoomId::new(u64::try_from(room.room_id).unwrap_or(0))?;
+                match self.client.room_info(room_id).await {
+                    Ok(info) => room.tags = split_tags(&info.tags),
+                    Err(err) => {
+                        tracing::warn!(room_id = room.room_id, error = %err, "读取直播间标签失败");
+                    }
+                }
+            }
+        }
+
+        Ok(snapshot)
+    }
+
+    /// 按分区依次翻页，`fetch` 读取单页。某页失败时记录在快照中并跳到下一个分区，已采集的房间保留。
+    async fn collect_pages<F, Fut>(&self, fetch: F) -> BpiResult<LiveAreaSnapshot>
+    where
+        F: Fn(LiveAreaRoomsParams) -> Fut,
+        Fut: Future<Output = BpiResult<LiveAreaRoomsData>>,
+    {
+        let taken_at = Local::now();
+        let mut seen = BTreeSet::new();
+        let mut rooms = Vec::new();
+        let mut failed = Vec::new();
+        let mut requested = false;
+        for &(parent_area_id, area_id) in &self.areas {
+            for page in 1..=self.max_pages {
+                if requested {
+                    tokio::time::sleep(self.page_delay).await;
+                }
+                requested = true;
+
+                let params = LiveAreaRoomsParams::new(parent_area_id, area_id)?
+                    .with_sort(self.sort)
+                    .with_page(page)?;
+                let data = match fetch(params).await {
+                    Ok(data) => data,
+                    Err(err) => {
+                        tracing::warn!(parent_area_id, area_id, page, error = %err, "读取分区房间失败");
+                        failed.push(LiveAreaPageFailure {
+                            parent_area_id,
+                            area_id,
+                            page,
+                            error: err.to_string(),
+                        });
+                        break;
+                    }
+                };
+                let last = data.is_last_page();
+                rooms.extend(
+                    data.list
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|room| seen.insert(room.roomid))
+                        .map(|room| LiveAreaRoomRecord::new(taken_at, room)),
+                );
+                if last {
+                    break;
+                }
+            }
+        }
+
+        Ok(LiveAreaSnapshot {
+            taken_at,
+            rooms,
+            failed,
+        })
+    }
+}
+
+fn split_tags(tags: &str) -> Vec<String> {
+    tags.split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+impl<'a> LiveClient<'a> {
+    /// 获取分区内正在直播的房间。
+    pub async fn area_rooms(&self, params: LiveAreaRoomsParams) -> BpiResult<LiveAreaRoomsData> {
+        let query = self.client.get_wbi_sign2(params.query_pairs()).await?;
+
+        self.client
+            .get(AREA_ROOMS_ENDPOINT)
+            .with_bilibili_headers()
+            .query(&query)
+            .send_bpi_payload("live.area_rooms")
+            .await
+    }
+
+    /// 创建分区房间采集器。
+    pub fn area_crawler(&self) -> LiveAreaCrawler<'a> {
+        LiveAreaCrawler::new(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BpiClient;
+    use serde_json::json;
+
+    fn page() -> BpiResult<LiveAreaRoomsData> {
+        Ok(serde_json::from_value(json!({
+            "list": [
+                {
+                    "roomid": 1001,
+                    "uid": 2001,
+                    "title": "标题, 带逗号",
+                    "uname": "<redacted-uname>",
+                    "online": 5000,
+                    "parent_id": 6,
+                    "parent_name": "单机游戏",
+                    "area_id": 236,
+                    "area_name": "主机游戏",
+                    "watched_show": {
+                        "switch": true,
+                        "num": 1200,
+                        "text_small": "1200",
+                        "text_large": "1200人看过",
+                        "icon": "",
+                        "icon_location": 0,
+                        "icon_web": ""
+                    }
+                },
+                {
+                    "roomid": 1002,
+                    "uid": 2002,
+                    "title": "<redacted-title>",
+                    "uname": "<redacted-uname>",
+                    "online": 300,
+                    "parent_id": 6,
+                    "parent_name": "单机游戏",
+                    "area_id": 235,
+                    "area_name": "其他单机"
+                }
+            ],
+            "new_tags": [{ "id": 0, "name": "全部", "sort_type": "" }],
+            "count": 2,
+            "has_more": 0
+        }))?)
+    }
+
+    #[test]
+    fn area_rooms_params_build_query() -> BpiResult<()> {
+        let params = LiveAreaRoomsParams::new(6, 236)?
+            .with_sort(LiveAreaSort::LiveTime)
+            .with_page(3)?;
+
+        assert_eq!(
+            params.query_pairs(),
+            vec![
+                ("platform", "web".to_string()),
+                ("parent_area_id", "6".to_string()),
+                ("area_id", "236".to_string()),
+                ("sort_type", "live_time".to_string()),
+                ("page", "3".to_string()),
+            ]
+        );
+        assert!(LiveAreaRoomsParams::new(0, 236).is_err());
+        assert!(LiveAreaRoomsParams::new(6, 0)?.with_page(0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn snapshot_exports_rooms_and_area_summaries() -> BpiResult<()> {
+        let data = page()?;
+        assert!(data.is_last_page());
+
+        let taken_at = DateTime::parse_from_rfc3339("2026-01-01T20:00:00+08:00")
+            .map_err(|e| BpiError::parse(e.to_string()))?
+            .with_timezone(&Local);
+        let mut rooms: Vec<_> = data
+            .list
+            .unwrap_or_default()
+            .into_iter()
+            .map(|room| LiveAreaRoomRecord::new(taken_at, room))
+            .collect();
+        rooms[0].tags = split_tags("主机, 动作,");
+        let snapshot = LiveAreaSnapshot {
+            taken_at,
+            rooms,
+            failed: Vec::new(),
+        };
+
+        assert_eq!(snapshot.rooms[0].watched, Some(1200));
+        assert_eq!(snapshot.rooms[1].watched, None);
+
+        let csv = snapshot.rooms_csv();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].contains("\"标题, 带逗号\""));
+        assert!(lines[1].ends_with(",5000,1200,主机|动作"));
+
+        let summaries = snapshot.area_summaries();
+        assert_eq!(summaries[0].area_id, 236);
+        assert_eq!(summaries[0].total_watched, 1200);
+        assert_eq!(summaries[1].rooms, 1);
+        assert_eq!(snapshot.areas_csv().lines().count(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn crawler_keeps_rooms_when_a_page_fails() -> Result<(), Box<dyn std::error::Error>> {
+        let client = BpiClient::new()?;
+        let crawler = client
+            .live()
+            .area_crawler()
+            .with_area(6, 236)?
+            .with_area(9, 0)?
+            .with_page_delay(Duration::ZERO);
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()?;
+
+        let snapshot = runtime.block_on(crawler.collect_pages(|params| async move {
+            let pairs = params.query_pairs();
+            if pairs.contains(&("parent_area_id", "9".to_string())) {
+                Err(BpiError::network("stub failure"))
+            } else {
+                page()
+            }
+        }))?;
+
+        assert_eq!(snapshot.rooms.len(), 2);
+        assert!(!snapshot.is_complete());
+        assert_eq!(
+            (
+                snapshot.failed[0].parent_area_id,
+                snapshot.failed[0].area_id,
+                snapshot.failed[0].page
+            ),
+            (9, 0, 1)
+        );
+        assert!(snapshot.failed[0].error.contains("stub failure"));
+        let restored: LiveAreaSnapshot = serde_json::from_str(&snapshot.to_json()?)?;
+        assert_eq!(restored.failed, snapshot.failed);
+        Ok(())
+    }
+
+    #[test]
+    fn crawler_requires_valid_areas() -> BpiResult<()> {
+        let client = BpiClient::new()?;
+        let crawler = client
+            .live()
+            .area_crawler()
+            .with_area(6, 236)?
+            .with_area(6, 236)?;
+        assert_eq!(crawler.areas, vec![(6, 236)]);
+        assert!(client.live().area_crawler().with_area(0, 1).is_err());
+        assert!(crawler.with_max_pages(0).is_err());
+        Ok(())
+    }
+}
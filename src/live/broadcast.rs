@@ -0,0 +1,435 @@
+//! 开播会话
+//!
+//! [`BroadcastSession`] 把开播流程串成一个会话：校验分区、按需预设标题和封面、开播并取得 RTMP 推流地址，
+//! 直播中可以修改标题、封面和标签。会话结束时调用 [`BroadcastSession::stop`] 关播；会话被 drop 或所在的
+//! future 被取消时，会在当前 tokio 运行时中后台发送关播请求，保证不会遗留直播中的房间。
+
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::ids::RoomId;
+use crate::live::LiveClient;
+use crate::live::live_area::{LiveParentArea, LiveSubArea};
+use crate::live::manage::{AuditInfo, RtmpInfo, StartLiveData, StopLiveData};
+use crate::live::room_init::LiveRoom;
+use crate::{BilibiliRequest, BpiError, BpiResult};
+
+/// 关播使用的平台，与网页 link 中心开播对应。
+const BROADCAST_PLATFORM: &str = "pc_link";
+/// 开播接口在需要人脸认证时返回的错误码。
+const FACE_AUTH_CODE: i32 = 60024;
+
+/// 开播流程错误
+#[derive(Debug, Error)]
+pub enum BroadcastError {
+    /// 分区不存在
+    #[error("直播分区不存在: {area_id}")]
+    AreaNotFound { area_id: u64 },
+
+    /// 分区已锁定，不能开播
+    #[error("直播分区已锁定: {name} ({area_id})")]
+    AreaLocked { area_id: u64, name: String },
+
+    /// 目标分区需要人脸认证，`qr` 为认证页面地址
+    #[error("开播需要人脸认证")]
+    FaceAuthRequired { qr: Option<String> },
+
+    /// 标题未通过审核或正在审核
+    #[error("标题未通过审核: {}", .0.audit_title_reason)]
+    TitleAudit(AuditInfo),
+
+    /// 开播成功但没有拿到推流地址
+    #[error("开播响应缺少推流地址")]
+    MissingRtmp,
+
+    #[error(transparent)]
+    Api(#[from] BpiError),
+}
+
+impl BroadcastError {
+    fn from_api(err: BpiError) -> Self {
+        if err.code() == Some(FACE_AUTH_CODE) {
+            Self::FaceAuthRequired { qr: None }
+        } else {
+            Self::Api(err)
+        }
+    }
+}
+
+pub type BroadcastResult<T> = Result<T, BroadcastError>;
+
+/// 开播参数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BroadcastParams {
+    room_id: RoomId,
+    area_id: u64,
+    title: Option<String>,
+    cover: Option<String>,
+    tags: Vec<String>,
+}
+
+impl BroadcastParams {
+    pub fn new(room_id: RoomId, area_id: u64) -> BpiResult<Self> {
+        if area_id == 0 {
+            return Err(BpiError::invalid_parameter(
+                "area_id",
+                "value must be non-zero",
+            ));
+        }
+
+        Ok(Self {
+            room_id,
+            area_id,
+            title: None,
+            cover: None,
+            tags: Vec::new(),
+        })
+    }
+
+    /// 开播前设置的标题。
+    pub fn with_title(mut self, title: impl Into<String>) -> BpiResult<Self> {
+        self.title = Some(validate_text("title", title.into())?);
+        Ok(self)
+    }
+
+    /// 开播前设置的封面 URL，需要先上传到 B 站图床。
+    ///
+    /// 封面走的是开播前信息接口，直播中的房间更新接口不接受封面，所以开播会话不提供改封面，
+    /// 需要换封面时请在开播前设置。
+    pub fn with_cover(mut self, cover: impl Into<String>) -> BpiResult<Self> {
+        self.cover = Some(validate_text("cover", cover.into())?);
+        Ok(self)
+    }
+
+    /// 开播前添加的标签，可以多次调用。
+    pub fn with_tag(mut self, tag: impl Into<String>) -> BpiResult<Self> {
+        self.tags.push(validate_text("tag", tag.into())?);
+        Ok(self)
+    }
+}
+
+/// 直播中的开播会话，可以修改标题和标签；封面只能在开播前通过 [`BroadcastParams::with_cover`] 设置。
+pub struct BroadcastSession<'a> {
+    client: LiveClient<'a>,
+    room: LiveRoom,
+    area_id: u64,
+    start: StartLiveData,
+    rtmp: RtmpInfo,
+    stopped: bool,
+}
+
+impl<'a> BroadcastSession<'a> {
+    pub fn room(&self) -> LiveRoom {
+        self.room
+    }
+
+    pub fn area_id(&self) -> u64 {
+        self.area_id
+    }
+
+    /// 推流地址和推流码。
+    pub fn rtmp(&self) -> &RtmpInfo {
+        &self.rtmp
+    }
+
+    /// 本场直播的 live_key。
+    pub fn live_key(&self) -> &str {
+        &self.start.live_key
+    }
+
+    /// 开播接口的原始响应。
+    pub fn start_data(&self) -> &StartLiveData {
+        &self.start
+    }
+
+    /// 直播中修改标题。
+    pub async fn update_title(&self, title: &str) -> BroadcastResult<()> {
+        let title = validate_text("title", title.to_string())?;
+        update_room(self.client, self.room.room_id, Some(&title), None, None).await
+    }
+
+    /// 直播中添加标签。
+    pub async fn add_tag(&self, tag: &str) -> BroadcastResult<()> {
+        let tag = validate_text("tag", tag.to_string())?;
+        update_room(self.client, self.room.room_id, None, Some(&tag), None).await
+    }
+
+    /// 直播中删除标签。
+    pub async fn remove_tag(&self, tag: &str) -> BroadcastResult<()> {
+        let tag = validate_text("tag", tag.to_string())?;
+        update_room(self.client, self.room.room_id, None, None, Some(&tag)).await
+    }
+
+    /// 关播。请求失败或被取消时，会话 drop 时仍会在后台再次发送关播请求。
+    pub async fn stop(mut self) -> BroadcastResult<StopLiveData> {
+        let data = self
+            .client
+            .live_stop(self.room.room_id, BROADCAST_PLATFORM)
+            .await?;
+        self.stopped = true;
+        Ok(data)
+    }
+}
+
+impl Drop for BroadcastSession<'_> {
+    fn drop(&mut self) {
+        if self.stopped {
+            return;
+        }
+
+        let room_id = self.room.room_id;
+        let request = match self.client.live_stop_request(room_id, BROADCAST_PLATFORM) {
+            Ok(request) => request,
+            Err(err) => {
+                tracing::warn!("直播间 {room_id} 未能自动关播: {err}");
+                return;
+            }
+        };
+
+        match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => {
+                runtime.spawn(async move {
+                    if let Err(err) = request.send_bpi_payload::<Value>("live.stop").await {
+                        tracing::warn!("直播间 {room_id} 自动关播失败: {err}");
+                    }
+                });
+            }
+            Err(_) => tracing::warn!("直播间 {room_id} 未在 tokio 运行时中 drop，未能自动关播"),
+        }
+    }
+}
+
+fn validate_text(field: &'static str, value: String) -> BpiResult<String> {
+    let value = value.trim().to_string();
+    if value.is_empty() {
+        return Err(BpiError::invalid_parameter(
+            field,
+            "value must not be empty",
+        ));
+    }
+
+    Ok(value)
+}
+
+/// 在分区列表中查找可以开播的子分区。
+fn find_area(areas: &[LiveParentArea], area_id: u64) -> BroadcastResult<&LiveSubArea> {
+    let area = areas
+        .iter()
+        .flat_map(|parent| &parent.list)
+        .find(|area| area.id.parse::<u64>().ok() == Some(area_id))
+        .ok_or(BroadcastError::AreaNotFound { area_id })?;
+
+    if area.lock_status == "1" {
+        return Err(BroadcastError::AreaLocked {
+            area_id,
+            name: area.name.clone(),
+        });
+    }
+
+    Ok(area)
+}
+
+fn check_audit(audit: Option<AuditInfo>) -> BroadcastResult<()> {
+    match audit {
+        Some(audit) if !audit.is_passed() => Err(BroadcastError::TitleAudit(audit)),
+        _ => Ok(()),
+    }
+}
+
+fn check_start(start: &StartLiveData) -> BroadcastResult<()> {
+    if start.need_face_auth {
+        return Err(BroadcastError::FaceAuthRequired {
+            qr: start
+                .qr
+                .as_str()
+                .filter(|qr| !qr.is_empty())
+                .map(str::to_string),
+        });
+    }
+
+    Ok(())
+}
+
+async fn update_room(
+    client: LiveClient<'_>,
+    room_id: RoomId,
+    title: Option<&str>,
+    add_tag: Option<&str>,
+    del_tag: Option<&str>,
+) -> BroadcastResult<()> {
+    let data = client
+        .live_update_room_info(room_id, title, None, add_tag, del_tag)
+        .await?;
+    check_audit(data.audit_info)
+}
+
+impl<'a> LiveClient<'a> {
+    /// 校验分区并开播，返回带推流地址的开播会话。
+    ///
+    /// 开播前会按参数设置标题、封面和标签。需要人脸认证或标题未通过审核时返回对应的
+    /// [`BroadcastError`]，此时不会开播。
+    pub async fn start_broadcast(
+        &self,
+        params: BroadcastParams,
+    ) -> BroadcastResult<BroadcastSession<'a>> {
+        let room = self.resolve_room(params.room_id).await?;
+        let areas = self.area_list().await?;
+        find_area(&areas, params.area_id)?;
+
+        if let Some(title) = &params.title {
+            update_room(*self, room.room_id, Some(title), None, None).await?;
+        }
+        for tag in &params.tags {
+            update_room(*self, room.room_id, None, Some(tag), None).await?;
+        }
+        if let Some(cover) = &params.cover {
+            check_audit(
+                self.live_update_pre_live_info(None, Some(cover))
+                    .await?
+                    .audit_info,
+            )?;
+        }
+
+        let start = self
+            .live_web_center_start(room.room_id, params.area_id)
+            .await
+            .map_err(BroadcastError::from_api)?;
+        check_start(&start)?;
+
+        let rtmp = start.rtmp.clone();
+        let mut session = BroadcastSession {
+            client: *self,
+            room,
+            area_id: params.area_id,
+            start,
+            rtmp: RtmpInfo {
+                addr: String::new(),
+                code: String::new(),
+            },
+            stopped: false,
+        };
+
+        // 开播响应不带推流地址时再从 link 中心获取，失败时 session 被 drop 并自动关播
+        session.rtmp = match rtmp {
+            Some(rtmp) => rtmp,
+            None => self.live_fetch_web_up_stream_addr().await?.addr,
+        };
+        if session.rtmp.addr.is_empty() || session.rtmp.code.is_empty() {
+            return Err(BroadcastError::MissingRtmp);
+        }
+
+        Ok(session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn areas() -> Vec<LiveParentArea> {
+        serde_json::from_value(serde_json::json!([{
+            "id": 9,
+            "name": "虚拟主播",
+            "list": [
+                {
+                    "id": "371", "parent_id": "9", "old_area_id": "0", "name": "虚拟日常",
+                    "act_id": "0", "pk_status": "0", "hot_status": 0, "lock_status": "0",
+                    "pic": "", "parent_name": "虚拟主播", "area_type": 1
+                },
+                {
+                    "id": "372", "parent_id": "9", "old_area_id": "0", "name": "已锁定",
+                    "act_id": "0", "pk_status": "0", "hot_status": 0, "lock_status": "1",
+                    "pic": "", "parent_name": "虚拟主播", "area_type": 1
+                }
+            ]
+        }]))
+        .expect("area fixture should parse")
+    }
+
+    fn start_data(need_face_auth: bool, qr: &str) -> StartLiveData {
+        serde_json::from_value(serde_json::json!({
+            "change": 1,
+            "status": "LIVE",
+            "rtmp": { "addr": "rtmp://example/live/", "code": "stream-key" },
+            "live_key": "live-key",
+            "sub_session_key": "sub-session",
+            "need_face_auth": need_face_auth,
+            "room_type": 0,
+            "protocols": [],
+            "notice": {},
+            "qr": qr,
+            "service_source": "",
+            "rtmp_backup": [],
+            "up_stream_extra": {}
+        }))
+        .expect("start fixture should parse")
+    }
+
+    #[test]
+    fn broadcast_params_reject_empty_values() -> BpiResult<()> {
+        let room_id = RoomId::new(3_818_081)?;
+
+        assert!(matches!(
+            BroadcastParams::new(room_id, 0).unwrap_err(),
+            BpiError::InvalidParameter {
+                field: "area_id",
+                ..
+            }
+        ));
+        assert!(matches!(
+            BroadcastParams::new(room_id, 371)?
+                .with_title("  ")
+                .unwrap_err(),
+            BpiError::InvalidParameter { field: "title", .. }
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn area_validation_reports_missing_and_locked_areas() {
+        let areas = areas();
+
+        assert_eq!(
+            find_area(&areas, 371).map(|area| area.name.as_str()).ok(),
+            Some("虚拟日常")
+        );
+        assert!(matches!(
+            find_area(&areas, 999),
+            Err(BroadcastError::AreaNotFound { area_id: 999 })
+        ));
+        assert!(matches!(
+            find_area(&areas, 372),
+            Err(BroadcastError::AreaLocked { area_id: 372, .. })
+        ));
+    }
+
+    #[test]
+    fn start_and_audit_branches_map_to_typed_errors() {
+        assert!(check_start(&start_data(false, "")).is_ok());
+        assert!(matches!(
+            check_start(&start_data(true, "https://example/face-auth")),
+            Err(BroadcastError::FaceAuthRequired { qr: Some(qr) }) if qr == "https://example/face-auth"
+        ));
+        assert!(matches!(
+            BroadcastError::from_api(BpiError::from_code(FACE_AUTH_CODE)),
+            BroadcastError::FaceAuthRequired { qr: None }
+        ));
+        assert!(matches!(
+            BroadcastError::from_api(BpiError::from_code(-101)),
+            BroadcastError::Api(_)
+        ));
+
+        let audit = |status| AuditInfo {
+            audit_title_reason: "<redacted-reason>".to_string(),
+            audit_title_status: status,
+            audit_title: None,
+            update_title: None,
+        };
+        assert!(check_audit(None).is_ok());
+        assert!(check_audit(Some(audit(0))).is_ok());
+        assert!(matches!(
+            check_audit(Some(audit(2))),
+            Err(BroadcastError::TitleAudit(info)) if info.audit_title_status == 2
+        ));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::ids::RoomId;
 use crate::live::danmaku::LiveDanmuInfoData;
 use crate::live::emoticons::EmoticonData;
 use crate::live::follow_up_live::{FollowUpLiveData, LiveWebListData};
@@ -101,8 +102,10 @@ impl<'a> LiveClient<'a> {
             .await
     }
 
-    /// 按真实房间 ID 获取公开房间信息。
-    pub async fn room_info(&self, room_id: i64) -> BpiResult<RoomInfoData> {
+    /// 获取公开房间信息，短号会先解析为真实房间号。
+    pub async fn room_info(&self, room_id: RoomId) -> BpiResult<RoomInfoData> {
+        let room_id = self.real_room_id(room_id).await?;
+
         self.client
             .get(ROOM_INFO_ENDPOINT)
             .with_bilibili_headers()
@@ -111,15 +114,16 @@ impl<'a> LiveClient<'a> {
             .await
     }
 
-    /// 按真实房间 ID 获取直播流 URL。
+    /// 获取直播流 URL，短号会先解析为真实房间号。
     pub async fn stream(
         &self,
-        cid: i64,
+        room_id: RoomId,
         platform: Option<&str>,
         quality: Option<i32>,
         qn: Option<i32>,
     ) -> BpiResult<LiveStreamData> {
-        let mut query = vec![("cid", cid.to_string())];
+        let room_id = self.real_room_id(room_id).await?;
+        let mut query = vec![("cid", room_id.to_string())];
 
         if let Some(platform) = platform {
             query.push(("platform", platform.to_string()));
@@ -171,10 +175,11 @@ impl<'a> LiveClient<'a> {
     /// 获取直播间礼物面板。
     pub async fn room_gift_list(
         &self,
-        room_id: i64,
+        room_id: RoomId,
         area_parent_id: Option<i32>,
         area_id: Option<i32>,
     ) -> BpiResult<RoomGiftData> {
+        let room_id = self.real_room_id(room_id).await?;
         let mut query = vec![
             ("room_id", room_id.to_string()),
             ("platform", "web".to_string()),
@@ -207,7 +212,8 @@ impl<'a> LiveClient<'a> {
     }
 
     /// 获取直播 WebSocket 弹幕 token 和主机信息。
-    pub async fn danmu_info(&self, room_id: u64, info_type: u8) -> BpiResult<LiveDanmuInfoData> {
+    pub async fn danmu_info(&self, room_id: RoomId, info_type: u8) -> BpiResult<LiveDanmuInfoData> {
+        let room_id = self.real_room_id(room_id).await?;
         let query = self
             .client
             .get_wbi_sign2(vec![
@@ -225,7 +231,9 @@ impl<'a> LiveClient<'a> {
     }
 
     /// 获取直播间表情包。
-    pub async fn emoticons(&self, room_id: i64, platform: &str) -> BpiResult<EmoticonData> {
+    pub async fn emoticons(&self, room_id: RoomId, platform: &str) -> BpiResult<EmoticonData> {
+        let room_id = self.real_room_id(room_id).await?;
+
         self.client
             .get(EMOTICONS_ENDPOINT)
             .with_bilibili_headers()
@@ -238,7 +246,8 @@ impl<'a> LiveClient<'a> {
     }
 
     /// 获取直播间抽奖信息。
-    pub async fn lottery_info(&self, room_id: i64) -> BpiResult<LotteryInfoData> {
+    pub async fn lottery_info(&self, room_id: RoomId) -> BpiResult<LotteryInfoData> {
+        let room_id = self.real_room_id(room_id).await?;
         let query = self
             .client
             .get_wbi_sign2(vec![("roomid", room_id.to_string())])
@@ -335,18 +344,18 @@ impl<'a> LiveClient<'a> {
             .await
     }
 
-    /// 获取直播间的大航海成员。
+    /// 获取直播间的大航海成员，主播 mid 由房间号解析得到。
     pub async fn guard_list(
         &self,
-        room_id: i64,
-        ruid: i64,
+        room_id: RoomId,
         page: Option<i32>,
         page_size: Option<i32>,
         typ: Option<i32>,
     ) -> BpiResult<GuardListData> {
+        let room = self.resolve_room(room_id).await?;
         let query = [
-            ("roomid", room_id.to_string()),
-            ("ruid", ruid.to_string()),
+            ("roomid", room.room_id.to_string()),
+            ("ruid", room.uid.to_string()),
             ("page", page.unwrap_or(1).to_string()),
             ("page_size", page_size.unwrap_or(20).to_string()),
             ("typ", typ.unwrap_or(5).to_string()),
@@ -365,6 +374,8 @@ impl<'a> LiveClient<'a> {
         &self,
         params: LiveSilentUserListParams,
     ) -> BpiResult<SilentUserListData> {
+        let room_id = self.real_room_id(params.room_id()).await?;
+        let params = params.with_room_id(room_id);
         let csrf = self.client.csrf().unwrap_or_default();
         let form = params.form_pairs(&csrf);
 
@@ -397,6 +408,8 @@ impl<'a> LiveClient<'a> {
         &self,
         params: LiveShieldKeywordListParams,
     ) -> BpiResult<ShieldKeywordListData> {
+        let room_id = self.real_room_id(params.room_id()).await?;
+        let params = params.with_room_id(room_id);
         let csrf = self.client.csrf().unwrap_or_default();
         let form = params.form_pairs(&csrf);
 
@@ -410,6 +423,9 @@ impl<'a> LiveClient<'a> {
 
     /// 发送用于直播遥测的 Web 心跳。
     pub async fn web_heart_beat(&self, params: LiveWebHeartBeatParams) -> BpiResult<HeartBeatData> {
+        let room_id = self.real_room_id(params.room_id()).await?;
+        let params = params.with_room_id(room_id);
+
         self.client
             .get(WEB_HEART_BEAT_ENDPOINT)
             .with_bilibili_headers()
@@ -601,8 +617,13 @@ mod tests {
         let live = client.live();
 
         assert_area_list_future(live.area_list());
-        assert_room_info_future(live.room_info(23_174_842));
-        assert_stream_future(live.stream(14_073_662, Some("web"), None, Some(10_000)));
+        assert_room_info_future(live.room_info(RoomId::new(23_174_842)?));
+        assert_stream_future(live.stream(
+            RoomId::new(14_073_662)?,
+            Some("web"),
+            None,
+            Some(10_000),
+        ));
         assert_recommend_future(live.recommend());
         assert_version_future(live.version());
         Ok(())
@@ -614,16 +635,16 @@ mod tests {
         let live = client.live();
 
         assert_gift_types_future(live.gift_types());
-        assert_room_gift_list_future(live.room_gift_list(23_174_842, None, None));
+        assert_room_gift_list_future(live.room_gift_list(RoomId::new(23_174_842)?, None, None));
         assert_blind_gift_info_future(live.blind_gift_info(32_251));
-        assert_danmu_info_future(live.danmu_info(21_733_448, 0));
-        assert_emoticons_future(live.emoticons(14_047, "pc"));
-        assert_lottery_info_future(live.lottery_info(23_174_842));
+        assert_danmu_info_future(live.danmu_info(RoomId::new(21_733_448)?, 0));
+        assert_emoticons_future(live.emoticons(RoomId::new(14_047)?, "pc"));
+        assert_lottery_info_future(live.lottery_info(RoomId::new(23_174_842)?));
         assert_my_medals_future(live.my_medals(1, 10));
         assert_follow_up_list_future(live.follow_up_list(Some(1), Some(2), Some(1), Some(true)));
         assert_follow_up_web_list_future(live.follow_up_web_list(Some(false)));
         assert_replay_list_future(live.replay_list(Some(1), Some(2)));
-        assert_guard_list_future(live.guard_list(23_174_842, 504_140_200, None, None, None));
+        assert_guard_list_future(live.guard_list(RoomId::new(23_174_842)?, None, None, None));
         assert_silent_users_future(
             live.silent_users(
                 LiveSilentUserListParams::new(RoomId::new(3_818_081)?).page_size(10)?,
@@ -635,7 +656,9 @@ mod tests {
         assert_shield_keywords_future(
             live.shield_keywords(LiveShieldKeywordListParams::new(RoomId::new(3_818_081)?)),
         );
-        assert_web_heart_beat_future(live.web_heart_beat(LiveWebHeartBeatParams::new(23_174_842)?));
+        assert_web_heart_beat_future(
+            live.web_heart_beat(LiveWebHeartBeatParams::new(RoomId::new(23_174_842)?)),
+        );
 
         let source = include_str!("client.rs");
         let payload_helper = concat!(".send_", "bpi_payload");
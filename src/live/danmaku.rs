@@ -1,11 +1,18 @@
 // --- 弹幕发送响应数据结构体 ---
 
-use crate::BilibiliRequest;
-use crate::BpiResult;
+use crate::err::error::ErrorCategory;
+use crate::ids::{Mid, RoomId};
 use crate::live::LiveClient;
+use crate::live::emoticons::EmoticonItem;
+use crate::request::send_bpi_envelope;
+use crate::{ApiEnvelope, BilibiliRequest, BpiError, BpiResult};
 use chrono::Utc;
 use reqwest::multipart::Form;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+const USER_ROOM_INFO_ENDPOINT: &str =
+    "https://api.live.bilibili.com/xlive/web-room/v1/index/getInfoByUser";
 
 /// 弹幕发送响应数据
 
@@ -36,6 +43,314 @@ pub struct LiveDanmuInfoData {
     pub host_list: Vec<LiveDanmuInfoHost>,
 }
 
+/// 弹幕位置
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LiveDanmuMode {
+    /// 滚动
+    #[default]
+    Scroll,
+    /// 底部
+    Bottom,
+    /// 顶部
+    Top,
+}
+
+impl LiveDanmuMode {
+    fn code(&self) -> u8 {
+        match self {
+            Self::Scroll => 1,
+            Self::Bottom => 4,
+            Self::Top => 5,
+        }
+    }
+}
+
+/// 弹幕内容
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LiveDanmuContent {
+    /// 文字弹幕
+    Text(String),
+    /// 表情弹幕，值为表情的 `emoticon_unique`
+    Emoticon(String),
+}
+
+/// 发送弹幕参数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveDanmuParams {
+    room_id: RoomId,
+    content: LiveDanmuContent,
+    color: u32,
+    font_size: u32,
+    mode: LiveDanmuMode,
+    bubble: u32,
+    reply_mid: Option<Mid>,
+}
+
+impl LiveDanmuParams {
+    /// 文字弹幕，默认白色、25 号字、滚动。
+    pub fn text(room_id: RoomId, text: impl Into<String>) -> BpiResult<Self> {
+        let text = text.into();
+        if text.trim().is_empty() {
+            return Err(BpiError::invalid_parameter(
+                "msg",
+                "message must not be empty",
+            ));
+        }
+
+        Ok(Self::new(room_id, LiveDanmuContent::Text(text)))
+    }
+
+    /// 表情弹幕，表情来自 [`LiveClient::emoticons`]，未解锁的表情会被拒绝。
+    pub fn emoticon(room_id: RoomId, emoticon: &EmoticonItem) -> BpiResult<Self> {
+        if emoticon.perm != 1 {
+            return Err(BpiError::invalid_parameter(
+                "emoticon",
+                "emoticon is locked for current user",
+            ));
+        }
+        if emoticon.emoticon_unique.is_empty() {
+            return Err(BpiError::invalid_parameter(
+                "emoticon",
+                "emoticon_unique must not be empty",
+            ));
+        }
+
+        Ok(Self::new(
+            room_id,
+            LiveDanmuContent::Emoticon(emoticon.emoticon_unique.clone()),
+        ))
+    }
+
+    fn new(room_id: RoomId, content: LiveDanmuContent) -> Self {
+        Self {
+            room_id,
+            content,
+            color: 16777215,
+            font_size: 25,
+            mode: LiveDanmuMode::Scroll,
+            bubble: 0,
+            reply_mid: None,
+        }
+    }
+
+    /// 十进制颜色值
+    pub fn with_color(mut self, color: u32) -> BpiResult<Self> {
+        if color > 0xFF_FF_FF {
+            return Err(BpiError::invalid_parameter(
+                "color",
+                "color must be a 24-bit RGB value",
+            ));
+        }
+
+        self.color = color;
+        Ok(self)
+    }
+
+    pub fn with_font_size(mut self, font_size: u32) -> BpiResult<Self> {
+        if font_size == 0 {
+            return Err(BpiError::invalid_parameter(
+                "fontsize",
+                "value must be non-zero",
+            ));
+        }
+
+        self.font_size = font_size;
+        Ok(self)
+    }
+
+    pub fn with_mode(mut self, mode: LiveDanmuMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// 气泡样式，0 为无气泡，其余为已拥有的气泡 id。
+    pub fn with_bubble(mut self, bubble: u32) -> Self {
+        self.bubble = bubble;
+        self
+    }
+
+    /// 回复指定用户，弹幕前会显示 @昵称。
+    pub fn with_reply(mut self, mid: Mid) -> Self {
+        self.reply_mid = Some(mid);
+        self
+    }
+
+    pub fn content(&self) -> &LiveDanmuContent {
+        &self.content
+    }
+
+    pub(crate) fn room_id(&self) -> RoomId {
+        self.room_id
+    }
+
+    pub(crate) fn with_room_id(mut self, room_id: RoomId) -> Self {
+        self.room_id = room_id;
+        self
+    }
+
+    /// 把文字弹幕按长度上限拆成多条，只有第一条保留回复对象；表情弹幕不拆分。
+    pub(crate) fn split(self, max_chars: usize) -> Vec<Self> {
+        let LiveDanmuContent::Text(text) = &self.content else {
+            return vec![self];
+        };
+
+        split_message(text, max_chars)
+            .into_iter()
+            .enumerate()
+            .map(|(index, chunk)| Self {
+                content: LiveDanmuContent::Text(chunk),
+                reply_mid: if index == 0 { self.reply_mid } else { None },
+                ..self.clone()
+            })
+            .collect()
+    }
+
+    pub(crate) fn form_pairs(&self, csrf: &str, rnd: i64) -> Vec<(&'static str, String)> {
+        let (msg, dm_type) = match &self.content {
+            LiveDanmuContent::Text(text) => (text.clone(), 0),
+            LiveDanmuContent::Emoticon(unique) => (unique.clone(), 1),
+        };
+
+        let mut form = vec![
+            ("csrf", csrf.to_string()),
+            ("roomid", self.room_id.to_string()),
+            ("msg", msg),
+            ("rnd", rnd.to_string()),
+            ("bubble", self.bubble.to_string()),
+            ("mode", self.mode.code().to_string()),
+            ("dm_type", dm_type.to_string()),
+            ("color", self.color.to_string()),
+            ("fontsize", self.font_size.to_string()),
+            ("statistics", r#"{"appId":100,"platform":5}"#.to_string()),
+            ("csrf_token", csrf.to_string()), // 文档中提到 csrf_token 和 csrf 相同
+        ];
+        if let Some(mid) = self.reply_mid {
+            form.push(("reply_mid", mid.to_string()));
+            form.push(("reply_attr", "0".to_string()));
+        }
+        form
+    }
+}
+
+/// 按字符数拆分弹幕，优先在空白处断开。
+pub(crate) fn split_message(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let mut chunks = Vec::new();
+    let mut rest: Vec<char> = text.trim().chars().collect();
+
+    while rest.len() > max_chars {
+        let cut = rest[..=max_chars]
+            .iter()
+            .rposition(|c| c.is_whitespace())
+            .filter(|position| *position > 0)
+            .unwrap_or(max_chars);
+        let chunk: String = rest[..cut].iter().collect();
+        chunks.push(chunk.trim_end().to_string());
+        rest = rest[cut..]
+            .iter()
+            .collect::<String>()
+            .trim_start()
+            .chars()
+            .collect();
+    }
+    if !rest.is_empty() {
+        chunks.push(rest.into_iter().collect());
+    }
+
+    chunks
+}
+
+/// 弹幕发送失败原因
+#[derive(Debug, Error)]
+pub enum LiveDanmuError {
+    /// 弹幕被系统屏蔽（响应消息 `f`）
+    #[error("弹幕被系统屏蔽")]
+    Filtered,
+
+    /// 弹幕命中直播间屏蔽词（响应消息 `k`）
+    #[error("弹幕命中直播间屏蔽词")]
+    ShieldKeyword,
+
+    /// 发送频率过快
+    #[error("弹幕发送频率过快")]
+    RateLimited,
+
+    /// 重复弹幕
+    #[error("重复弹幕")]
+    Duplicate,
+
+    /// 没有发送权限，例如被禁言或直播间开启了发言限制
+    #[error("没有发送弹幕的权限: {message}")]
+    Forbidden { message: String },
+
+    /// 超过直播间的弹幕长度上限
+    #[error("弹幕超过长度上限 {max}")]
+    TooLong { len: usize, max: usize },
+
+    #[error(transparent)]
+    Api(#[from] BpiError),
+}
+
+impl LiveDanmuError {
+    fn from_api(err: BpiError) -> Self {
+        match &err {
+            BpiError::Api {
+                code: -403,
+                message,
+                ..
+            } => Self::Forbidden {
+                message: message.clone(),
+            },
+            BpiError::Api { code: 10030, .. } => Self::RateLimited,
+            BpiError::Api { code: 10031, .. } => Self::Duplicate,
+            _ => Self::Api(err),
+        }
+    }
+
+    /// 频率过快或网络错误等暂时性失败，稍后重发可能成功；屏蔽、重复、无权限等为永久拒绝。
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RateLimited => true,
+            Self::Api(err) => err.category() == ErrorCategory::Network,
+            _ => false,
+        }
+    }
+
+    /// 成功响应中的 `message` 也可能表示弹幕被屏蔽。
+    fn from_success_message(message: &str) -> Option<Self> {
+        match message {
+            "f" => Some(Self::Filtered),
+            "k" => Some(Self::ShieldKeyword),
+            _ => None,
+        }
+    }
+}
+
+/// 当前用户在直播间的弹幕配置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LiveDanmuProperty {
+    /// 弹幕长度上限
+    #[serde(default)]
+    pub length: u32,
+    /// 默认颜色
+    #[serde(default)]
+    pub color: u32,
+    /// 默认位置
+    #[serde(default)]
+    pub mode: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LiveUserRoomProperty {
+    pub danmu: LiveDanmuProperty,
+}
+
+/// 当前用户在直播间的信息（`getInfoByUser`），这里只保留弹幕配置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LiveUserRoomInfoData {
+    pub property: LiveUserRoomProperty,
+}
+
 impl<'a> LiveClient<'a> {
     /// 发送直播间弹幕
     ///
@@ -46,42 +361,73 @@ impl<'a> LiveClient<'a> {
     /// * `font_size` - 字体大小，默认 25
     pub async fn live_send_danmu(
         &self,
-        room_id: u64,
+        room_id: RoomId,
         message: &str,
         color: Option<u32>,
         font_size: Option<u32>,
     ) -> BpiResult<SendDanmuData> {
-        let csrf = self.client.csrf()?;
-        let now = Utc::now().timestamp();
-
-        // 使用 Form 构建 application/x-www-form-urlencoded 请求体
-        let mut form = Form::new()
-            .text("csrf", csrf.clone())
-            .text("roomid", room_id.to_string())
-            .text("msg", message.to_string())
-            .text("rnd", now.to_string())
-            .text("bubble", "0")
-            .text("mode", "1")
-            .text("statistics", r#"{"appId":100,"platform":5}"#)
-            .text("csrf_token", csrf); // 文档中提到 csrf_token 和 csrf 相同
-
+        let mut params = LiveDanmuParams::text(room_id, message)?;
         if let Some(c) = color {
-            form = form.text("color", c.to_string());
-        } else {
-            form = form.text("color", "16777215"); // 默认白色
+            params = params.with_color(c)?;
         }
-
         if let Some(s) = font_size {
-            form = form.text("fontsize", s.to_string());
-        } else {
-            form = form.text("fontsize", "25"); // 默认 25
+            params = params.with_font_size(s)?;
         }
 
-        self.client
-            .post("https://api.live.bilibili.com/msg/send")
-            .multipart(form)
-            .send_bpi_payload("live.danmu.send")
+        self.send_danmu_envelope(params).await?.into_payload()
+    }
+
+    /// 按参数发送一条弹幕，屏蔽、频率限制和权限问题返回对应的 [`LiveDanmuError`]。
+    pub async fn live_send_danmu_with(
+        &self,
+        params: LiveDanmuParams,
+    ) -> Result<SendDanmuData, LiveDanmuError> {
+        let envelope = self
+            .send_danmu_envelope(params)
             .await
+            .map_err(LiveDanmuError::from_api)?;
+
+        if let Some(err) = LiveDanmuError::from_success_message(&envelope.message) {
+            return Err(err);
+        }
+        Ok(envelope.into_payload()?)
+    }
+
+    async fn send_danmu_envelope(
+        &self,
+        params: LiveDanmuParams,
+    ) -> BpiResult<ApiEnvelope<SendDanmuData>> {
+        let csrf = self.client.csrf()?;
+        let room_id = self.real_room_id(params.room_id()).await?;
+        let params = params.with_room_id(room_id);
+
+        // 使用 multipart 表单，与网页端一致
+        let form = params
+            .form_pairs(&csrf, Utc::now().timestamp())
+            .into_iter()
+            .fold(Form::new(), |form, (key, value)| form.text(key, value));
+
+        send_bpi_envelope(
+            self.client
+                .post("https://api.live.bilibili.com/msg/send")
+                .multipart(form),
+            "live.danmu.send",
+        )
+        .await
+    }
+
+    /// 获取当前用户在直播间的弹幕配置，其中包含弹幕长度上限。需要登录。
+    pub async fn danmu_property(&self, room_id: RoomId) -> BpiResult<LiveDanmuProperty> {
+        let room_id = self.real_room_id(room_id).await?;
+
+        let data: LiveUserRoomInfoData = self
+            .client
+            .get(USER_ROOM_INFO_ENDPOINT)
+            .with_bilibili_headers()
+            .query(&[("room_id", room_id.to_string()), ("from", "0".to_string())])
+            .send_bpi_payload("live.user_room_info")
+            .await?;
+        Ok(data.property.danmu)
     }
 }
 
@@ -147,6 +493,99 @@ mod tests {
         Ok(())
     }
 
+    fn emoticon(perm: i32) -> EmoticonItem {
+        serde_json::from_value(serde_json::json!({
+            "bulge_display": 0,
+            "descript": "",
+            "emoji": "[dog]",
+            "emoticon_id": 1,
+            "emoticon_unique": "official_101",
+            "emoticon_value_type": 0,
+            "height": 60,
+            "identity": 0,
+            "in_player_area": 1,
+            "is_dynamic": 0,
+            "perm": perm,
+            "unlock_need_gift": 0,
+            "unlock_need_level": 0,
+            "unlock_show_color": "",
+            "unlock_show_image": "",
+            "unlock_show_text": "",
+            "url": "",
+            "width": 60
+        }))
+        .expect("emoticon fixture should parse")
+    }
+
+    #[test]
+    fn danmu_params_build_emoticon_reply_and_mode_forms() -> BpiResult<()> {
+        let room_id = RoomId::new(3_818_081)?;
+        let form = LiveDanmuParams::emoticon(room_id, &emoticon(1))?
+            .with_mode(LiveDanmuMode::Top)
+            .with_bubble(5)
+            .with_reply(Mid::new(1_000_002)?)
+            .form_pairs("csrf", 1_700_000_000);
+
+        assert!(form.contains(&("msg", "official_101".to_string())));
+        assert!(form.contains(&("dm_type", "1".to_string())));
+        assert!(form.contains(&("mode", "5".to_string())));
+        assert!(form.contains(&("bubble", "5".to_string())));
+        assert!(form.contains(&("reply_mid", "1000002".to_string())));
+        assert!(matches!(
+            LiveDanmuParams::emoticon(room_id, &emoticon(0)).unwrap_err(),
+            BpiError::InvalidParameter {
+                field: "emoticon",
+                ..
+            }
+        ));
+        assert!(LiveDanmuParams::text(room_id, " ").is_err());
+        assert!(
+            LiveDanmuParams::text(room_id, "hi")?
+                .with_color(0x1_00_00_00)
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn split_message_prefers_whitespace_and_counts_chars() {
+        assert_eq!(
+            split_message("hello world again", 11),
+            vec!["hello world", "again"]
+        );
+        assert_eq!(split_message("一二三四五", 2), vec!["一二", "三四", "五"]);
+        assert_eq!(split_message("短", 20), vec!["短"]);
+    }
+
+    #[test]
+    fn danmu_rejections_map_to_typed_errors() {
+        assert!(matches!(
+            LiveDanmuError::from_api(BpiError::from_code_message(-403, "<redacted>".to_string())),
+            LiveDanmuError::Forbidden { .. }
+        ));
+        assert!(matches!(
+            LiveDanmuError::from_api(BpiError::from_code(10030)),
+            LiveDanmuError::RateLimited
+        ));
+        assert!(matches!(
+            LiveDanmuError::from_api(BpiError::from_code(10031)),
+            LiveDanmuError::Duplicate
+        ));
+        assert!(matches!(
+            LiveDanmuError::from_api(BpiError::from_code(-101)),
+            LiveDanmuError::Api(_)
+        ));
+        assert!(matches!(
+            LiveDanmuError::from_success_message("k"),
+            Some(LiveDanmuError::ShieldKeyword)
+        ));
+        assert!(matches!(
+            LiveDanmuError::from_success_message("f"),
+            Some(LiveDanmuError::Filtered)
+        ));
+        assert!(LiveDanmuError::from_success_message("").is_none());
+    }
+
     fn local_probe_body(profile: &str) -> Option<serde_json::Value> {
         let path = format!(
             "target/bpi-probe-runs/live/room-interaction-read/danmu-info/{profile}.response.json"
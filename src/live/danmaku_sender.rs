@@ -0,0 +1,344 @@
+//! 弹幕发送队列
+//!
+//! [`LiveDanmakuSender`] 按直播间的弹幕长度上限拆分长消息，并按固定间隔依次发送，避免触发频率限制；
+//! 服务端仍返回频率过快时会退避重试，重试用尽或网络错误时弹幕留在队列中等待下次发送。
+//! 被屏蔽、无权限等永久失败的弹幕被丢弃，并以 [`LiveDanmuError`] 返回。
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::ids::RoomId;
+use crate::live::LiveClient;
+use crate::live::danmaku::{LiveDanmuContent, LiveDanmuError, LiveDanmuParams, SendDanmuData};
+use crate::{BpiError, BpiResult};
+
+/// 默认发送间隔。直播间弹幕配置（`getInfoByUser` 的 `property.danmu`）只返回长度、颜色和位置，
+/// 不返回发送间隔，因此使用固定值，可用 [`LiveDanmakuSender::with_interval`] 调整。
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+/// 未获取到直播间配置时使用的长度上限，与 UL 20 以下用户一致。
+const DEFAULT_MAX_LENGTH: usize = 20;
+const DEFAULT_MAX_RETRIES: u32 = 2;
+
+/// 一次 [`LiveDanmakuSender::flush`] 的结果。
+#[derive(Debug, Default)]
+pub struct LiveDanmakuFlush {
+    /// 已发出的弹幕，按发送顺序排列
+    pub sent: Vec<SendDanmuData>,
+    /// 导致发送中止的错误，全部发出时为 `None`
+    pub error: Option<LiveDanmuError>,
+}
+
+impl LiveDanmakuFlush {
+    /// 队列是否全部发出。
+    pub fn is_complete(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// 有错误时返回错误，丢弃已发出的结果。
+    pub fn into_result(self) -> Result<Vec<SendDanmuData>, LiveDanmuError> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.sent),
+        }
+    }
+}
+
+/// 直播间弹幕发送队列。
+pub struct LiveDanmakuSender<'a> {
+    client: LiveClient<'a>,
+    room_id: RoomId,
+    max_length: usize,
+    interval: Duration,
+    max_retries: u32,
+    split: bool,
+    queue: VecDeque<LiveDanmuParams>,
+    last_sent: Option<Instant>,
+}
+
+impl<'a> LiveDanmakuSender<'a> {
+    fn new(client: LiveClient<'a>, room_id: RoomId, max_length: usize) -> Self {
+        Self {
+            client,
+            room_id,
+            max_length: if max_length == 0 {
+                DEFAULT_MAX_LENGTH
+            } else {
+                max_length
+            },
+            interval: DEFAULT_INTERVAL,
+            max_retries: DEFAULT_MAX_RETRIES,
+            split: true,
+            queue: VecDeque::new(),
+            last_sent: None,
+        }
+    }
+
+    /// 两条弹幕之间的最短间隔，默认 1 秒。
+    pub fn with_interval(mut self, interval: Duration) -> BpiResult<Self> {
+        if interval.is_zero() {
+            return Err(BpiError::invalid_parameter(
+                "interval",
+                "interval must be non-zero",
+            ));
+        }
+
+        self.interval = interval;
+        Ok(self)
+    }
+
+    /// 覆盖直播间返回的弹幕长度上限。
+    pub fn with_max_length(mut self, max_length: usize) -> BpiResult<Self> {
+        if max_length == 0 {
+            return Err(BpiError::invalid_parameter(
+                "max_length",
+                "value must be non-zero",
+            ));
+        }
+
+        self.max_length = max_length;
+        Ok(self)
+    }
+
+    /// 频率过快时的最大重试次数，默认 2 次。
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// 是否拆分超长的文字弹幕，默认拆分；关闭后超长弹幕返回 [`LiveDanmuError::TooLong`]。
+    pub fn with_split(mut self, split: bool) -> Self {
+        self.split = split;
+        self
+    }
+
+    pub fn room_id(&self) -> RoomId {
+        self.room_id
+    }
+
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// 队列中待发送的弹幕数。
+    pub fn pending(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+
+    /// 加入队列，返回拆分后的条数。参数中的房间号会被替换为发送器的房间号。
+    pub fn enqueue(&mut self, params: LiveDanmuParams) -> Result<usize, LiveDanmuError> {
+        let params = params.with_room_id(self.room_id);
+        if let LiveDanmuContent::Text(text) = params.content() {
+            let len = text.trim().chars().count();
+            if !self.split && len > self.max_length {
+                return Err(LiveDanmuError::TooLong {
+                    len,
+                    max: self.max_length,
+                });
+            }
+        }
+
+        let chunks = params.split(self.max_length);
+        let count = chunks.len();
+        self.queue.extend(chunks);
+        Ok(count)
+    }
+
+    /// 把文字加入队列。
+    pub fn say(&mut self, text: &str) -> Result<usize, LiveDanmuError> {
+        self.enqueue(LiveDanmuParams::text(self.room_id, text)?)
+    }
+
+    /// 加入队列并立即发送整个队列。
+    ///
+    /// 只有加入队列失败时返回 `Err`，发送中的失败记录在 [`LiveDanmakuFlush::error`] 中。
+    pub async fn send(
+        &mut self,
+        params: LiveDanmuParams,
+    ) -> Result<LiveDanmakuFlush, LiveDanmuError> {
+        self.enqueue(params)?;
+        Ok(self.flush().await)
+    }
+
+    /// 按间隔依次发送队列中的弹幕。
+    ///
+    /// 某条发送失败时停止发送，其余弹幕留在队列中，可以再次调用继续发送。失败的一条在
+    /// [`LiveDanmuError::is_retryable`] 时放回队首，否则丢弃；失败前已发出的弹幕仍在
+    /// [`LiveDanmakuFlush::sent`] 中返回。
+    pub async fn flush(&mut self) -> LiveDanmakuFlush {
+        let mut flush = LiveDanmakuFlush::default();
+
+        while let Some(params) = self.queue.pop_front() {
+            match self.send_one(params.clone()).await {
+                Ok(data) => flush.sent.push(data),
+                Err(err) => {
+                    self.settle_failure(params, &err);
+                    flush.error = Some(err);
+                    break;
+                }
+            }
+        }
+
+        flush
+    }
+
+    fn settle_failure(&mut self, params: LiveDanmuParams, err: &LiveDanmuError) {
+        if err.is_retryable() {
+            self.queue.push_front(params);
+        } else {
+            tracing::warn!("直播间 {} 弹幕被拒绝，已丢弃: {err}", self.room_id);
+        }
+    }
+
+    async fn send_one(&mut self, params: LiveDanmuParams) -> Result<SendDanmuData, LiveDanmuError> {
+        let mut attempt = 0;
+        loop {
+            if let Some(last) = self.last_sent {
+                tokio::time::sleep_until(last + self.interval).await;
+            }
+
+            let result = self.client.live_send_danmu_with(params.clone()).await;
+            self.last_sent = Some(Instant::now());
+
+            match result {
+                Err(LiveDanmuError::RateLimited) if attempt < self.max_retries => {
+                    attempt += 1;
+                    tracing::warn!(
+                        "直播间 {} 弹幕发送过快，第 {attempt} 次退避重试",
+                        self.room_id
+                    );
+                    tokio::time::sleep(self.interval * attempt).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl<'a> LiveClient<'a> {
+    /// 创建弹幕发送队列，长度上限取自当前用户在直播间的弹幕配置。需要登录。
+    pub async fn danmaku_sender(&self, room_id: RoomId) -> BpiResult<LiveDanmakuSender<'a>> {
+        let room_id = self.real_room_id(room_id).await?;
+        let property = self.danmu_property(room_id).await?;
+
+        Ok(LiveDanmakuSender::new(
+            *self,
+            room_id,
+            property.length as usize,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BpiClient;
+    use crate::ids::Mid;
+
+    #[test]
+    fn sender_splits_long_text_and_keeps_reply_on_first_chunk() -> BpiResult<()> {
+        let client = BpiClient::new()?;
+        let room_id = RoomId::new(3_818_081)?;
+        let sender = LiveDanmakuSender::new(client.live(), room_id, 0);
+        assert_eq!(sender.max_length(), DEFAULT_MAX_LENGTH);
+
+        let mut sender = sender.with_max_length(10)?;
+        let params = LiveDanmuParams::text(RoomId::new(6)?, "一二三四五六七八九十一二三")?
+            .with_reply(Mid::new(1_000_002)?);
+        assert_eq!(sender.enqueue(params).map_err(|err| err.to_string()), Ok(2));
+        assert_eq!(sender.say("短消息").map_err(|err| err.to_string()), Ok(1));
+        assert_eq!(sender.pending(), 3);
+
+        let queued: Vec<_> = sender
+            .queue
+            .iter()
+            .map(|params| params.form_pairs("csrf", 0))
+            .collect();
+        assert!(queued[0].contains(&("roomid", "3818081".to_string())));
+        assert!(queued[0].contains(&("msg", "一二三四五六七八九十".to_string())));
+        assert!(queued[0].contains(&("reply_mid", "1000002".to_string())));
+        assert!(queued[1].contains(&("msg", "一二三".to_string())));
+        assert!(!queued[1].iter().any(|(key, _)| *key == "reply_mid"));
+
+        sender.clear();
+        assert_eq!(sender.pending(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn flush_result_keeps_sent_danmaku_alongside_error() -> Result<(), Box<dyn std::error::Error>> {
+        let data: SendDanmuData = serde_json::from_value(serde_json::json!({
+            "mode_info": {"mode": 0, "show_player_type": 0, "extra": "{}"},
+            "dm_v2": null
+        }))?;
+        let flush = LiveDanmakuFlush {
+            sent: vec![data],
+            error: Some(LiveDanmuError::RateLimited),
+        };
+
+        assert!(!flush.is_complete());
+        assert_eq!(flush.sent.len(), 1);
+        assert!(matches!(
+            flush.into_result(),
+            Err(LiveDanmuError::RateLimited)
+        ));
+        assert!(LiveDanmakuFlush::default().into_result()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn retryable_failures_stay_at_the_front_of_the_queue() -> BpiResult<()> {
+        let client = BpiClient::new()?;
+        let mut sender = LiveDanmakuSender::new(client.live(), RoomId::new(3_818_081)?, 20);
+        sender
+            .say("第二条")
+            .map_err(|err| BpiError::parse(err.to_string()))?;
+        let first = LiveDanmuParams::text(sender.room_id(), "第一条")
+            .map_err(|err| BpiError::parse(err.to_string()))?;
+
+        sender.settle_failure(first.clone(), &LiveDanmuError::RateLimited);
+        sender.settle_failure(
+            first.clone(),
+            &LiveDanmuError::Api(BpiError::network("timeout")),
+        );
+        assert_eq!(sender.pending(), 3);
+        assert!(
+            sender.queue[0]
+                .form_pairs("csrf", 0)
+                .contains(&("msg", "第一条".to_string()))
+        );
+
+        for err in [
+            LiveDanmuError::Filtered,
+            LiveDanmuError::ShieldKeyword,
+            LiveDanmuError::Duplicate,
+            LiveDanmuError::Forbidden {
+                message: "禁言".to_string(),
+            },
+            LiveDanmuError::Api(BpiError::from_code(-400)),
+        ] {
+            sender.settle_failure(first.clone(), &err);
+        }
+        assert_eq!(sender.pending(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn sender_rejects_long_text_when_split_disabled() -> BpiResult<()> {
+        let client = BpiClient::new()?;
+        let mut sender =
+            LiveDanmakuSender::new(client.live(), RoomId::new(3_818_081)?, 5).with_split(false);
+
+        assert!(matches!(
+            sender.say("一二三四五六"),
+            Err(LiveDanmuError::TooLong { len: 6, max: 5 })
+        ));
+        assert_eq!(sender.pending(), 0);
+        Ok(())
+    }
+}
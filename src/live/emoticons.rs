@@ -99,6 +99,7 @@ pub struct EmoticonData {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ids::RoomId;
     use crate::probe::contract::HttpMethod;
     use crate::probe::endpoint_contract::EndpointContract;
     use crate::{ApiEnvelope, BpiClient, BpiError, BpiResult};
@@ -113,7 +114,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_live_emoticons() -> Result<(), Box<BpiError>> {
         let bpi = BpiClient::new().expect("client should build");
-        let data = bpi.live().emoticons(14047, "pc").await?;
+        let data = bpi.live().emoticons(RoomId::new(14047)?, "pc").await?;
 
         assert!(!data.data.is_empty());
         Ok(())
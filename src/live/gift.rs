@@ -79,6 +79,7 @@ pub struct BlindGiftData {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ids::RoomId;
     use crate::probe::contract::HttpMethod;
     use crate::probe::endpoint_contract::EndpointContract;
     use crate::{ApiEnvelope, BpiClient, BpiError, BpiResult};
@@ -103,7 +104,10 @@ mod tests {
     #[tokio::test]
     async fn test_get_room_gift_list() -> Result<(), Box<BpiError>> {
         let bpi = BpiClient::new().expect("client should build");
-        let data = bpi.live().room_gift_list(23174842, None, None).await?;
+        let data = bpi
+            .live()
+            .room_gift_list(RoomId::new(23174842)?, None, None)
+            .await?;
 
         if let Some(gift_config) = data.gift_config {
             assert!(!gift_config.base_config.list.is_empty());
@@ -169,6 +169,7 @@ pub struct GuardListData {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ids::RoomId;
     use crate::probe::contract::HttpMethod;
     use crate::probe::endpoint_contract::EndpointContract;
     use crate::{ApiEnvelope, BpiClient, BpiError, BpiResult};
@@ -185,7 +186,7 @@ mod tests {
         let bpi = BpiClient::new().expect("client should build");
         let data = bpi
             .live()
-            .guard_list(23174842, 504140200, None, None, None)
+            .guard_list(RoomId::new(23174842)?, None, None, None)
             .await?;
 
         assert!(!data.list.is_empty());
@@ -122,6 +122,7 @@ pub struct RoomInfoData {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ids::RoomId;
     use crate::probe::contract::HttpMethod;
     use crate::probe::endpoint_contract::EndpointContract;
     use crate::{ApiEnvelope, BpiClient, BpiError, BpiResult};
@@ -136,7 +137,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_room_info() -> Result<(), Box<BpiError>> {
         let bpi = BpiClient::new().expect("client should build");
-        let data = bpi.live().room_info(23174842).await?;
+        let data = bpi.live().room_info(RoomId::new(23174842)?).await?;
 
         assert_eq!(data.room_id, 23174842);
         Ok(())
@@ -0,0 +1,702 @@
+//! 直播营收账本
+//!
+//! [`GiftLedger`] 记录信息流中的礼物、醒目留言和上舰事件，结合礼物面板配置和盲盒概率详情补全价格，
+//! 按场次和用户汇总流水，并导出 JSON / CSV 报表。
+//!
+//! 金额统一以金瓜子计：1000 金瓜子为 1 元，醒目留言的元价格会换算为金瓜子。银瓜子礼物没有实际流水，
+//! 单独统计。盲盒礼物区分观众实际花费（盲盒价格）和爆出礼物的价值，主播流水按爆出礼物价值计算。
+
+use std::collections::{BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::BpiResult;
+use crate::ids::{Mid, RoomId};
+use crate::live::LiveClient;
+use crate::live::gift::{BlindGiftData, GiftConfig};
+use crate::live::message_stream::{LiveGiftEvent, LiveRevenueEvent};
+use crate::utils::csv;
+
+/// 1 元对应的金瓜子数。
+pub const GOLD_PER_YUAN: i64 = 1000;
+
+const SILVER_COIN: &str = "silver";
+
+/// 账本条目类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerEntryKind {
+    Gift,
+    BlindGift,
+    SuperChat,
+    Guard,
+}
+
+/// 补全价格后的账本条目
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    /// 场次序号，从 0 开始
+    pub session: usize,
+    /// 时间戳，单位秒
+    pub timestamp: i64,
+    pub uid: Option<Mid>,
+    pub uname: String,
+    pub kind: LedgerEntryKind,
+    /// 礼物 id，盲盒为爆出的礼物 id，醒目留言为留言 id
+    pub item_id: i64,
+    pub item_name: String,
+    pub num: u32,
+    /// 盲盒 id
+    #[serde(default)]
+    pub blind_box_id: Option<i64>,
+    /// 大航海等级
+    #[serde(default)]
+    pub guard_level: Option<u32>,
+    /// 观众实际花费，单位金瓜子
+    pub paid_gold: i64,
+    /// 主播侧流水，单位金瓜子
+    pub value_gold: i64,
+    /// 银瓜子数
+    pub silver: i64,
+}
+
+/// 流水汇总
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevenueSummary {
+    /// 第一条记录的时间戳
+    pub started_at: Option<i64>,
+    /// 最后一条记录的时间戳
+    pub ended_at: Option<i64>,
+    /// 事件数
+    pub events: u32,
+    /// 普通金瓜子礼物流水
+    pub gift_gold: i64,
+    /// 盲盒花费
+    pub blind_paid_gold: i64,
+    /// 盲盒爆出礼物价值
+    pub blind_value_gold: i64,
+    /// 银瓜子
+    pub silver: i64,
+    /// 醒目留言流水
+    pub super_chat_gold: i64,
+    /// 醒目留言条数
+    pub super_chat_count: u32,
+    /// 大航海流水
+    pub guard_gold: i64,
+    /// 总督月数
+    pub governor_months: u32,
+    /// 提督月数
+    pub admiral_months: u32,
+    /// 舰长月数
+    pub captain_months: u32,
+    /// 观众总花费
+    pub paid_gold: i64,
+    /// 主播总流水
+    pub total_gold: i64,
+}
+
+impl RevenueSummary {
+    fn add(&mut self, entry: &LedgerEntry) {
+        self.started_at = Some(
+            self.started_at
+                .map_or(entry.timestamp, |at| at.min(entry.timestamp)),
+        );
+        self.ended_at = Some(
+            self.ended_at
+                .map_or(entry.timestamp, |at| at.max(entry.timestamp)),
+        );
+        self.events += 1;
+        self.silver += entry.silver;
+        self.paid_gold += entry.paid_gold;
+        self.total_gold += entry.value_gold;
+
+        match entry.kind {
+            LedgerEntryKind::Gift => self.gift_gold += entry.value_gold,
+            LedgerEntryKind::BlindGift => {
+                self.blind_paid_gold += entry.paid_gold;
+                self.blind_value_gold += entry.value_gold;
+            }
+            LedgerEntryKind::SuperChat => {
+                self.super_chat_gold += entry.value_gold;
+                self.super_chat_count += 1;
+            }
+            LedgerEntryKind::Guard => {
+                self.guard_gold += entry.value_gold;
+                match entry.guard_level {
+                    Some(1) => self.governor_months += entry.num,
+                    Some(2) => self.admiral_months += entry.num,
+                    Some(3) => self.captain_months += entry.num,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// 主播总流水，单位元。
+    pub fn total_yuan(&self) -> f64 {
+        self.total_gold as f64 / GOLD_PER_YUAN as f64
+    }
+}
+
+/// 单个用户的贡献
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserRevenue {
+    /// 信息流隐去 uid 时为 `None`，按昵称归并
+    pub uid: Option<Mid>,
+    pub uname: String,
+    pub events: u32,
+    pub gift_gold: i64,
+    pub super_chat_gold: i64,
+    pub guard_gold: i64,
+    pub paid_gold: i64,
+    pub total_gold: i64,
+}
+
+/// 账本报表
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GiftLedgerReport {
+    pub room_id: Option<RoomId>,
+    pub total: RevenueSummary,
+    pub sessions: Vec<RevenueSummary>,
+    /// 按主播流水降序
+    pub users: Vec<UserRevenue>,
+}
+
+impl GiftLedgerReport {
+    /// 序列化为 JSON。
+    pub fn to_json(&self) -> BpiResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// 用户贡献 CSV，金额单位为金瓜子。
+    pub fn users_csv(&self) -> String {
+        let mut csv = String::from(
+            "uid,uname,events,gift_gold,super_chat_gold,guard_gold,paid_gold,total_gold\n",
+        );
+        for user in &self.users {
+            csv::push_row(
+                &mut csv,
+                &[
+                    user.uid.map(|uid| uid.to_string()).unwrap_or_default(),
+                    user.uname.clone(),
+                    user.events.to_string(),
+                    user.gift_gold.to_string(),
+                    user.super_chat_gold.to_string(),
+                    user.guard_gold.to_string(),
+                    user.paid_gold.to_string(),
+                    user.total_gold.to_string(),
+                ],
+            );
+        }
+
+        csv
+    }
+
+    /// 场次汇总 CSV，金额单位为金瓜子。
+    pub fn sessions_csv(&self) -> String {
+        let mut csv = String::from(
+            "session,started_at,ended_at,events,gift_gold,blind_paid_gold,blind_value_gold,silver,super_chat_gold,super_chat_count,guard_gold,governor_months,admiral_months,captain_months,paid_gold,total_gold\n",
+        );
+        for (index, session) in self.sessions.iter().enumerate() {
+            csv::push_row(
+                &mut csv,
+                &[
+                    index.to_string(),
+                    session
+                        .started_at
+                        .map(|at| at.to_string())
+                        .unwrap_or_default(),
+                    session
+                        .ended_at
+                        .map(|at| at.to_string())
+                        .unwrap_or_default(),
+                    session.events.to_string(),
+                    session.gift_gold.to_string(),
+                    session.blind_paid_gold.to_string(),
+                    session.blind_value_gold.to_string(),
+                    session.silver.to_string(),
+                    session.super_chat_gold.to_string(),
+                    session.super_chat_count.to_string(),
+                    session.guard_gold.to_string(),
+                    session.governor_months.to_string(),
+                    session.admiral_months.to_string(),
+                    session.captain_months.to_string(),
+                    session.paid_gold.to_string(),
+                    session.total_gold.to_string(),
+                ],
+            );
+        }
+
+        csv
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GiftPrice {
+    price: i64,
+    coin_type: String,
+}
+
+#[derive(Debug, Clone)]
+struct BlindBoxPrice {
+    price: i64,
+    outcomes: HashMap<i64, i64>,
+}
+
+/// 直播营收账本。
+///
+/// 账本保存原始事件，价格在汇总时才补全，所以先记录事件、之后再补充盲盒详情也会生效。
+#[derive(Debug, Clone, Default)]
+pub struct GiftLedger {
+    room_id: Option<RoomId>,
+    gifts: HashMap<i64, GiftPrice>,
+    blind_boxes: HashMap<i64, BlindBoxPrice>,
+    events: Vec<(usize, LiveRevenueEvent)>,
+    session: usize,
+}
+
+impl GiftLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_room_id(mut self, room_id: RoomId) -> Self {
+        self.room_id = Some(room_id);
+        self
+    }
+
+    /// 使用礼物面板配置补全礼物价格。
+    pub fn with_gift_config(mut self, config: &GiftConfig) -> Self {
+        self.add_gift_config(config);
+        self
+    }
+
+    pub fn add_gift_config(&mut self, config: &GiftConfig) {
+        for gift in &config.list {
+            self.gifts.insert(
+                gift.id,
+                GiftPrice {
+                    price: gift.price,
+                    coin_type: gift.coin_type.clone(),
+                },
+            );
+        }
+    }
+
+    /// 使用盲盒概率详情补全盲盒价格和爆出礼物价值。
+    pub fn add_blind_gift(&mut self, box_id: i64, data: &BlindGiftData) {
+        self.blind_boxes.insert(
+            box_id,
+            BlindBoxPrice {
+                price: data.blind_price,
+                outcomes: data
+                    .gifts
+                    .iter()
+                    .map(|gift| (gift.gift_id, gift.price))
+                    .collect(),
+            },
+        );
+    }
+
+    /// 开始新的场次，之后记录的事件归入新场次。当前场次没有事件时不会新建。
+    pub fn start_session(&mut self) {
+        if self
+            .events
+            .last()
+            .is_some_and(|(session, _)| *session == self.session)
+        {
+            self.session += 1;
+        }
+    }
+
+    pub fn record(&mut self, event: LiveRevenueEvent) {
+        self.events.push((self.session, event));
+    }
+
+    /// 解析并记录信息流命令，返回命令是否为营收事件。
+    pub fn record_command(&mut self, command: &serde_json::Value) -> BpiResult<bool> {
+        let Some(event) = LiveRevenueEvent::from_command(command)? else {
+            return Ok(false);
+        };
+        self.record(event);
+        Ok(true)
+    }
+
+    /// 出现过但缺少价格的盲盒 id，可以用 [`LiveClient::blind_gift_info`] 查询后补充。
+    pub fn missing_blind_boxes(&self) -> Vec<i64> {
+        self.events
+            .iter()
+            .filter_map(|(_, event)| match event {
+                LiveRevenueEvent::Gift(gift) => gift.blind_gift.as_ref().map(|blind| (gift, blind)),
+                _ => None,
+            })
+            .filter(|(gift, blind)| {
+                !self.blind_boxes.contains_key(&blind.original_gift_id)
+                    && (gift.price <= 0 || blind.original_gift_price <= 0)
+            })
+            .map(|(_, blind)| blind.original_gift_id)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// 补全价格后的全部条目。
+    pub fn entries(&self) -> Vec<LedgerEntry> {
+        self.events
+            .iter()
+            .map(|(session, event)| self.entry(*session, event))
+            .collect()
+    }
+
+    pub fn summary(&self) -> RevenueSummary {
+        let mut summary = RevenueSummary::default();
+        for entry in self.entries() {
+            summary.add(&entry);
+        }
+        summary
+    }
+
+    /// 按场次汇总，没有事件的场次不会出现。
+    pub fn session_summaries(&self) -> Vec<RevenueSummary> {
+        let mut sessions: Vec<RevenueSummary> = Vec::new();
+        let mut current = None;
+        for entry in self.entries() {
+            if current != Some(entry.session) {
+                current = Some(entry.session);
+                sessions.push(RevenueSummary::default());
+            }
+            if let Some(summary) = sessions.last_mut() {
+                summary.add(&entry);
+            }
+        }
+        sessions
+    }
+
+    /// 按用户汇总，按主播流水降序。
+    pub fn users(&self) -> Vec<UserRevenue> {
+        let mut users: Vec<UserRevenue> = Vec::new();
+        let mut index: HashMap<(Option<Mid>, String), usize> = HashMap::new();
+
+        for entry in self.entries() {
+            let key = match entry.uid {
+                Some(uid) => (Some(uid), String::new()),
+                None => (None, entry.uname.clone()),
+            };
+            let position = *index.entry(key).or_insert_with(|| {
+                users.push(UserRevenue {
+                    uid: entry.uid,
+                    uname: entry.uname.clone(),
+                    events: 0,
+                    gift_gold: 0,
+                    super_chat_gold: 0,
+                    guard_gold: 0,
+                    paid_gold: 0,
+                    total_gold: 0,
+                });
+                users.len() - 1
+            });
+
+            let user = &mut users[position];
+            if !entry.uname.is_empty() {
+                user.uname = entry.uname.clone();
+            }
+            user.events += 1;
+            user.paid_gold += entry.paid_gold;
+            user.total_gold += entry.value_gold;
+            match entry.kind {
+                LedgerEntryKind::Gift | LedgerEntryKind::BlindGift => {
+                    user.gift_gold += entry.value_gold
+                }
+                LedgerEntryKind::SuperChat => user.super_chat_gold += entry.value_gold,
+                LedgerEntryKind::Guard => user.guard_gold += entry.value_gold,
+            }
+        }
+
+        users.sort_by_key(|user| std::cmp::Reverse(user.total_gold));
+        users
+    }
+
+    pub fn report(&self) -> GiftLedgerReport {
+        GiftLedgerReport {
+            room_id: self.room_id,
+            total: self.summary(),
+            sessions: self.session_summaries(),
+            users: self.users(),
+        }
+    }
+
+    fn entry(&self, session: usize, event: &LiveRevenueEvent) -> LedgerEntry {
+        match event {
+            LiveRevenueEvent::Gift(gift) => self.gift_entry(session, gift),
+            LiveRevenueEvent::SuperChat(super_chat) => {
+                let gold = super_chat.price * GOLD_PER_YUAN;
+                LedgerEntry {
+                    session,
+                    timestamp: super_chat.start_time,
+                    uid: super_chat.uid,
+                    uname: super_chat.uname.clone(),
+                    kind: LedgerEntryKind::SuperChat,
+                    item_id: super_chat.id,
+                    item_name: "醒目留言".to_string(),
+                    num: 1,
+                    blind_box_id: None,
+                    guard_level: None,
+                    paid_gold: gold,
+                    value_gold: gold,
+                    silver: 0,
+                }
+            }
+            LiveRevenueEvent::Guard(guard) => {
+                let gold = guard.price * i64::from(guard.num);
+                LedgerEntry {
+                    session,
+                    timestamp: guard.start_time,
+                    uid: guard.uid,
+                    uname: guard.uname.clone(),
+                    kind: LedgerEntryKind::Guard,
+                    item_id: guard.gift_id,
+                    item_name: guard.gift_name.clone(),
+                    num: guard.num,
+                    blind_box_id: None,
+                    guard_level: Some(guard.guard_level),
+                    paid_gold: gold,
+                    value_gold: gold,
+                    silver: 0,
+                }
+            }
+        }
+    }
+
+    fn gift_entry(&self, session: usize, gift: &LiveGiftEvent) -> LedgerEntry {
+        let num = i64::from(gift.num);
+        let config = self.gifts.get(&gift.gift_id);
+        let coin_type = if gift.coin_type.is_empty() {
+            config
+                .map(|config| config.coin_type.as_str())
+                .unwrap_or_default()
+        } else {
+            gift.coin_type.as_str()
+        };
+
+        let mut entry = LedgerEntry {
+            session,
+            timestamp: gift.timestamp,
+            uid: gift.uid,
+            uname: gift.uname.clone(),
+            kind: LedgerEntryKind::Gift,
+            item_id: gift.gift_id,
+            item_name: gift.gift_name.clone(),
+            num: gift.num,
+            blind_box_id: None,
+            guard_level: None,
+            paid_gold: 0,
+            value_gold: 0,
+            silver: 0,
+        };
+
+        if let Some(blind) = &gift.blind_gift {
+            let box_price = self.blind_boxes.get(&blind.original_gift_id);
+            let paid = positive(blind.original_gift_price)
+                .or_else(|| box_price.and_then(|price| positive(price.price)))
+                .or_else(|| {
+                    self.gifts
+                        .get(&blind.original_gift_id)
+                        .and_then(|config| positive(config.price))
+                })
+                .unwrap_or_default();
+            let value = positive(gift.price)
+                .or_else(|| box_price.and_then(|price| price.outcomes.get(&gift.gift_id).copied()))
+                .or_else(|| config.map(|config| config.price))
+                .unwrap_or_default();
+
+            entry.kind = LedgerEntryKind::BlindGift;
+            entry.blind_box_id = Some(blind.original_gift_id);
+            entry.paid_gold = paid * num;
+            entry.value_gold = value * num;
+            return entry;
+        }
+
+        let price = positive(gift.price)
+            .or_else(|| config.map(|config| config.price))
+            .unwrap_or_default();
+        if coin_type == SILVER_COIN {
+            entry.silver = price * num;
+        } else {
+            entry.paid_gold = price * num;
+            entry.value_gold = price * num;
+        }
+        entry
+    }
+}
+
+fn positive(value: i64) -> Option<i64> {
+    (value > 0).then_some(value)
+}
+
+impl<'a> LiveClient<'a> {
+    /// 创建直播间营收账本，并载入礼物面板中的礼物价格。
+    ///
+    /// 新版礼物面板只返回 `gift_data`，此时价格以信息流事件中携带的为准。
+    pub async fn gift_ledger(&self, room_id: RoomId) -> BpiResult<GiftLedger> {
+        let room_id = self.real_room_id(room_id).await?;
+        let gifts = self.room_gift_list(room_id, None, None).await?;
+
+        let mut ledger = GiftLedger::new().with_room_id(room_id);
+        if let Some(config) = gifts.gift_config {
+            ledger.add_gift_config(&config.base_config);
+        }
+        Ok(ledger)
+    }
+
+    /// 查询账本中缺少价格的盲盒详情并补充，返回补充的盲盒数。
+    pub async fn fill_blind_gifts(&self, ledger: &mut GiftLedger) -> BpiResult<usize> {
+        let missing = ledger.missing_blind_boxes();
+        for box_id in &missing {
+            let data = self.blind_gift_info(*box_id).await?;
+            ledger.add_blind_gift(*box_id, &data);
+        }
+        Ok(missing.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::live::gift::{BlindGiftItem, GiftItem};
+    use crate::live::message_stream::{LiveBlindGiftInfo, LiveGuardBuyEvent, LiveSuperChatEvent};
+
+    fn mid(uid: u64) -> Option<Mid> {
+        Mid::new(uid).ok()
+    }
+
+    fn gift(uid: u64, gift_id: i64, num: u32, price: i64, coin_type: &str) -> LiveRevenueEvent {
+        LiveRevenueEvent::Gift(LiveGiftEvent {
+            uid: mid(uid),
+            uname: format!("user-{uid}"),
+            gift_id,
+            gift_name: "<redacted-gift>".to_string(),
+            num,
+            price,
+            coin_type: coin_type.to_string(),
+            timestamp: 1_700_000_000,
+            blind_gift: None,
+        })
+    }
+
+    fn gift_item(id: i64, price: i64, coin_type: &str) -> GiftItem {
+        GiftItem {
+            id,
+            name: "<redacted-gift>".to_string(),
+            price,
+            r#type: 0,
+            coin_type: coin_type.to_string(),
+            effect: 0,
+            stay_time: 0,
+            animation_frame_num: 0,
+            desc: String::new(),
+            img_basic: String::new(),
+            gif: String::new(),
+        }
+    }
+
+    #[test]
+    fn ledger_prices_gifts_from_events_and_config() {
+        let mut ledger = GiftLedger::new().with_gift_config(&GiftConfig {
+            list: vec![gift_item(1, 100, "gold"), gift_item(2, 1, "silver")],
+        });
+        ledger.record(gift(1_000_002, 1, 3, 0, ""));
+        ledger.record(gift(1_000_002, 2, 10, 0, ""));
+        ledger.record(gift(1_000_003, 3, 1, 5200, "gold"));
+
+        let summary = ledger.summary();
+        assert_eq!(summary.gift_gold, 5500);
+        assert_eq!(summary.silver, 10);
+        assert_eq!(summary.total_gold, 5500);
+        assert_eq!(summary.total_yuan(), 5.5);
+    }
+
+    #[test]
+    fn blind_gifts_use_box_price_and_outcome_value() {
+        let mut ledger = GiftLedger::new();
+        ledger.record(LiveRevenueEvent::Gift(LiveGiftEvent {
+            blind_gift: Some(LiveBlindGiftInfo {
+                original_gift_id: 32251,
+                original_gift_name: "<redacted-box>".to_string(),
+                original_gift_price: 0,
+            }),
+            ..match gift(1_000_002, 32132, 2, 0, "gold") {
+                LiveRevenueEvent::Gift(gift) => gift,
+                _ => unreachable!(),
+            }
+        }));
+        assert_eq!(ledger.missing_blind_boxes(), vec![32251]);
+
+        ledger.add_blind_gift(
+            32251,
+            &BlindGiftData {
+                note_text: String::new(),
+                blind_price: 10000,
+                blind_gift_name: "<redacted-box>".to_string(),
+                gifts: vec![BlindGiftItem {
+                    gift_id: 32132,
+                    price: 12000,
+                    gift_name: "<redacted-gift>".to_string(),
+                    gift_img: String::new(),
+                    chance: "10%".to_string(),
+                }],
+            },
+        );
+        assert!(ledger.missing_blind_boxes().is_empty());
+
+        let summary = ledger.summary();
+        assert_eq!(summary.blind_paid_gold, 20000);
+        assert_eq!(summary.blind_value_gold, 24000);
+        assert_eq!(summary.paid_gold, 20000);
+        assert_eq!(summary.total_gold, 24000);
+    }
+
+    #[test]
+    fn ledger_splits_sessions_and_ranks_users() -> BpiResult<()> {
+        let mut ledger = GiftLedger::new().with_room_id(RoomId::new(3_818_081)?);
+        ledger.record(gift(1_000_002, 1, 1, 1000, "gold"));
+        ledger.record(LiveRevenueEvent::SuperChat(LiveSuperChatEvent {
+            id: 9001,
+            uid: mid(1_000_003),
+            uname: "user-1000003".to_string(),
+            price: 30,
+            message: "<redacted-text>".to_string(),
+            start_time: 1_700_000_100,
+        }));
+        ledger.start_session();
+        ledger.start_session();
+        ledger.record(LiveRevenueEvent::Guard(LiveGuardBuyEvent {
+            uid: mid(1_000_002),
+            uname: "user-1000002".to_string(),
+            guard_level: 3,
+            num: 2,
+            price: 198_000,
+            gift_id: 10003,
+            gift_name: "舰长".to_string(),
+            start_time: 1_700_086_400,
+        }));
+
+        let report = ledger.report();
+        assert_eq!(report.sessions.len(), 2);
+        assert_eq!(report.sessions[0].super_chat_gold, 30_000);
+        assert_eq!(report.sessions[0].super_chat_count, 1);
+        assert_eq!(report.sessions[1].captain_months, 2);
+        assert_eq!(report.sessions[1].guard_gold, 396_000);
+        assert_eq!(report.total.total_gold, 427_000);
+
+        assert_eq!(report.users[0].uid, mid(1_000_002));
+        assert_eq!(report.users[0].total_gold, 397_000);
+        assert_eq!(report.users[1].super_chat_gold, 30_000);
+
+        let csv = report.users_csv();
+        assert!(csv.starts_with("uid,uname,events,"));
+        assert!(csv.contains("1000002,user-1000002,2,1000,0,396000,397000,397000\n"));
+        assert_eq!(report.sessions_csv().lines().count(), 3);
+        assert!(report.to_json()?.contains("\"captain_months\": 2"));
+        Ok(())
+    }
+}
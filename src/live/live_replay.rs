@@ -1,5 +1,29 @@
+//! 直播回放
+//!
+//! 当前账号的直播回放可以通过 [`LiveClient::replay`] 取得 [`LiveReplay`]：回放信息加上 HLS 播放地址，
+//! 可以交给 [`LiveRecorder::record_replay`](crate::live::LiveRecorder::record_replay) 下载存档；
+//! 回放弹幕通过 [`LiveClient::replay_danmaku_all`] 逐页读取。
+
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
+use crate::ids::RoomId;
+use crate::live::LiveClient;
+use crate::live::live_stream::{LiveStreamCandidate, LiveStreamKind};
+use crate::{BilibiliRequest, BpiError, BpiResult};
+
+const REPLAY_STREAM_ENDPOINT: &str =
+    "https://api.live.bilibili.com/xlive/web-room/v1/videoService/GetUserSliceStream";
+const REPLAY_DANMAKU_ENDPOINT: &str =
+    "https://api.live.bilibili.com/xlive/web-room/v1/videoService/GetUserSliceDanmaku";
+const REPLAY_PAGE_SIZE: i32 = 20;
+const REPLAY_MAX_PAGES: i32 = 50;
+/// 回放弹幕最多读取的页数，避免接口一直返回 `has_more` 时无限循环。
+const DANMAKU_MAX_PAGES: u32 = 1000;
+/// 回放只提供原画。
+const REPLAY_QN: u32 = 10000;
+
 #[derive(Debug, Serialize, Clone, Deserialize)]
 pub struct LiveInfo {
     /// 直播标题
@@ -78,12 +102,193 @@ pub struct ReplayListData {
     pub pagination: Pagination,
 }
 
+impl ReplayInfo {
+    /// 回放对应的直播间。
+    pub fn room_id(&self) -> BpiResult<RoomId> {
+        RoomId::new(u64::try_from(self.room_id).unwrap_or(0))
+    }
+
+    /// 直播时长。
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(u64::try_from(self.video_info.duration).unwrap_or(0))
+    }
+
+    fn slice_query(&self) -> BpiResult<Vec<(&'static str, String)>> {
+        if self.live_key.is_empty() {
+            return Err(BpiError::invalid_parameter(
+                "live_key",
+                "replay has no live_key",
+            ));
+        }
+
+        Ok(vec![
+            ("live_key", self.live_key.clone()),
+            ("start_time", self.start_time.to_string()),
+            ("end_time", self.end_time.to_string()),
+        ])
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct ReplayStreamItem {
+    /// HLS 播放列表地址
+    pub stream: String,
+}
+
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct ReplayPlayUrlData {
+    /// 播放地址，各项为不同节点
+    #[serde(default)]
+    pub list: Option<Vec<ReplayStreamItem>>,
+}
+
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct ReplayDanmaku {
+    /// 发送者mid
+    #[serde(default)]
+    pub uid: i64,
+    /// 发送者昵称
+    #[serde(default)]
+    pub nickname: String,
+    /// 弹幕内容
+    pub text: String,
+    /// 相对直播开始的时间（毫秒）
+    #[serde(default)]
+    pub ts: i64,
+    /// 弹幕模式，1：滚动，4：底部，5：顶部
+    #[serde(default)]
+    pub dm_mode: i32,
+    /// 弹幕颜色
+    #[serde(default)]
+    pub dm_color: i64,
+    /// 字号
+    #[serde(default)]
+    pub dm_fontsize: i32,
+}
+
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct ReplayDanmakuData {
+    /// 本页弹幕
+    #[serde(default)]
+    pub dm_info: Option<Vec<ReplayDanmaku>>,
+    /// 是否还有下一页
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+/// 可播放的直播回放
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiveReplay {
+    pub info: ReplayInfo,
+    /// HLS 播放列表地址，各项为不同节点
+    pub urls: Vec<String>,
+}
+
+impl LiveReplay {
+    /// 作为 HLS 直播流交给录制器下载；实际封装格式在下载时按播放列表确定。
+    pub fn candidate(&self) -> Option<LiveStreamCandidate> {
+        if self.urls.is_empty() {
+            return None;
+        }
+
+        Some(LiveStreamCandidate {
+            kind: LiveStreamKind::hls_ts(),
+            qn: REPLAY_QN,
+            urls: self.urls.clone(),
+        })
+    }
+}
+
+impl<'a> LiveClient<'a> {
+    /// 在当前账号的回放列表中查找回放。需要登录。
+    pub async fn replay_detail(&self, replay_id: i64) -> BpiResult<ReplayInfo> {
+        for page in 1..=REPLAY_MAX_PAGES {
+            let data = self.replay_list(Some(page), Some(REPLAY_PAGE_SIZE)).await?;
+            let replays = data.replay_info.unwrap_or_default();
+            let exhausted = replays.len() < REPLAY_PAGE_SIZE as usize;
+            if let Some(replay) = replays
+                .into_iter()
+                .find(|replay| replay.replay_id == replay_id)
+            {
+                return Ok(replay);
+            }
+            if exhausted {
+                break;
+            }
+        }
+
+        Err(BpiError::invalid_parameter(
+            "replay_id",
+            "replay not found in replay list",
+        ))
+    }
+
+    /// 获取回放的 HLS 播放地址。需要登录。
+    pub async fn replay_play_url(&self, replay: &ReplayInfo) -> BpiResult<ReplayPlayUrlData> {
+        self.client
+            .get(REPLAY_STREAM_ENDPOINT)
+            .with_bilibili_headers()
+            .query(&replay.slice_query()?)
+            .send_bpi_payload("live.replay_play_url")
+            .await
+    }
+
+    /// 获取回放详情和播放地址。需要登录。
+    pub async fn replay(&self, replay_id: i64) -> BpiResult<LiveReplay> {
+        let info = self.replay_detail(replay_id).await?;
+        let data = self.replay_play_url(&info).await?;
+        let urls = data
+            .list
+            .unwrap_or_default()
+            .into_iter()
+            .map(|item| item.stream)
+            .filter(|url| !url.is_empty())
+            .collect();
+
+        Ok(LiveReplay { info, urls })
+    }
+
+    /// 获取一页回放弹幕，`index` 从 0 开始。需要登录。
+    pub async fn replay_danmaku(
+        &self,
+        replay: &ReplayInfo,
+        index: u32,
+    ) -> BpiResult<ReplayDanmakuData> {
+        let mut query = replay.slice_query()?;
+        query.push(("index", index.to_string()));
+
+        self.client
+            .get(REPLAY_DANMAKU_ENDPOINT)
+            .with_bilibili_headers()
+            .query(&query)
+            .send_bpi_payload("live.replay_danmaku")
+            .await
+    }
+
+    /// 读取回放的全部弹幕，按时间排序。需要登录。
+    pub async fn replay_danmaku_all(&self, replay: &ReplayInfo) -> BpiResult<Vec<ReplayDanmaku>> {
+        let mut danmaku = Vec::new();
+        for index in 0..DANMAKU_MAX_PAGES {
+            let data = self.replay_danmaku(replay, index).await?;
+            let page = data.dm_info.unwrap_or_default();
+            let empty = page.is_empty();
+            danmaku.extend(page);
+            if !data.has_more || empty {
+                break;
+            }
+        }
+
+        danmaku.sort_by_key(|item| item.ts);
+        Ok(danmaku)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::probe::contract::HttpMethod;
     use crate::probe::endpoint_contract::EndpointContract;
-    use crate::{ApiEnvelope, BpiClient, BpiResult};
+    use crate::{ApiEnvelope, BpiClient};
 
     fn contract() -> BpiResult<EndpointContract> {
         EndpointContract::from_slice(include_bytes!(
@@ -183,4 +388,85 @@ mod tests {
         }
         Ok(())
     }
+
+    fn replay(live_key: &str) -> BpiResult<ReplayInfo> {
+        Ok(serde_json::from_value(serde_json::json!({
+            "replay_id": 1001,
+            "live_info": {
+                "title": "<redacted-title>",
+                "cover": "",
+                "live_time": 1_700_000_000,
+                "live_type": 0
+            },
+            "video_info": {
+                "replay_status": 1,
+                "estimated_time": "",
+                "duration": 3600,
+                "download_url": null,
+                "alert_code": null,
+                "alert_message": null
+            },
+            "alarm_info": {
+                "code": 0,
+                "message": "",
+                "cur_time": 1_700_010_000,
+                "is_ban_publish": false
+            },
+            "room_id": 3_818_081,
+            "live_key": live_key,
+            "start_time": 1_700_000_000,
+            "end_time": 1_700_003_600
+        }))?)
+    }
+
+    #[test]
+    fn replay_builds_slice_query_and_hls_candidate() -> BpiResult<()> {
+        let info = replay("<redacted-live-key>")?;
+        assert_eq!(info.room_id()?.get(), 3_818_081);
+        assert_eq!(info.duration(), Duration::from_secs(3600));
+        assert_eq!(
+            info.slice_query()?,
+            vec![
+                ("live_key", "<redacted-live-key>".to_string()),
+                ("start_time", "1700000000".to_string()),
+                ("end_time", "1700003600".to_string()),
+            ]
+        );
+        assert!(matches!(
+            replay("")?.slice_query(),
+            Err(BpiError::InvalidParameter {
+                field: "live_key",
+                ..
+            })
+        ));
+
+        let mut replay = LiveReplay {
+            info,
+            urls: Vec::new(),
+        };
+        assert!(replay.candidate().is_none());
+        replay.urls = vec!["https://example.invalid/replay/index.m3u8".to_string()];
+        let candidate = replay.candidate().expect("replay with urls is playable");
+        assert_eq!(candidate.kind, LiveStreamKind::hls_ts());
+        assert_eq!(candidate.urls, replay.urls);
+        Ok(())
+    }
+
+    #[test]
+    fn replay_danmaku_page_parses_with_missing_fields() -> BpiResult<()> {
+        let data: ReplayDanmakuData = serde_json::from_value(serde_json::json!({
+            "dm_info": [
+                { "uid": 1_000_002, "nickname": "<redacted-uname>", "text": "晚上好", "ts": 1200, "dm_mode": 1, "dm_color": 16_777_215, "dm_fontsize": 25 },
+                { "text": "来了", "ts": 800 }
+            ],
+            "has_more": true
+        }))?;
+
+        let items = data.dm_info.unwrap_or_default();
+        assert!(data.has_more);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].uid, 0);
+        assert_eq!(items[1].ts, 800);
+        Ok(())
+    }
 }
@@ -1,5 +1,12 @@
 use serde::{Deserialize, Serialize};
 
+use crate::ids::RoomId;
+use crate::live::LiveClient;
+use crate::{BilibiliRequest, BpiError, BpiResult};
+
+const ROOM_PLAY_INFO_ENDPOINT: &str =
+    "https://api.live.bilibili.com/xlive/web-room/v2/index/getRoomPlayInfo";
+
 #[derive(Debug, Serialize, Clone, Deserialize)]
 pub struct QualityDescription {
     /// 画质代码
@@ -34,6 +41,431 @@ pub struct LiveStreamData {
     pub durl: Vec<LiveStreamUrl>,
 }
 
+/// 直播流协议。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LiveProtocol {
+    /// `http_stream`，通常搭配 FLV
+    HttpStream,
+    /// `http_hls`，搭配 TS 或 fMP4
+    HttpHls,
+}
+
+impl LiveProtocol {
+    fn code(self) -> u8 {
+        match self {
+            Self::HttpStream => 0,
+            Self::HttpHls => 1,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::HttpStream => "http_stream",
+            Self::HttpHls => "http_hls",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "http_stream" => Some(Self::HttpStream),
+            "http_hls" => Some(Self::HttpHls),
+            _ => None,
+        }
+    }
+}
+
+/// 直播流封装格式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LiveFormat {
+    Flv,
+    Ts,
+    Fmp4,
+}
+
+impl LiveFormat {
+    fn code(self) -> u8 {
+        match self {
+            Self::Flv => 0,
+            Self::Ts => 1,
+            Self::Fmp4 => 2,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Flv => "flv",
+            Self::Ts => "ts",
+            Self::Fmp4 => "fmp4",
+        }
+    }
+
+    /// 录制文件的扩展名。
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Flv => "flv",
+            Self::Ts => "ts",
+            Self::Fmp4 => "m4s",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "flv" => Some(Self::Flv),
+            "ts" => Some(Self::Ts),
+            "fmp4" => Some(Self::Fmp4),
+            _ => None,
+        }
+    }
+}
+
+/// 直播流视频编码。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LiveCodec {
+    Avc,
+    Hevc,
+}
+
+impl LiveCodec {
+    fn code(self) -> u8 {
+        match self {
+            Self::Avc => 0,
+            Self::Hevc => 1,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Avc => "avc",
+            Self::Hevc => "hevc",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "avc" => Some(Self::Avc),
+            "hevc" => Some(Self::Hevc),
+            _ => None,
+        }
+    }
+}
+
+/// 直播流的协议、格式、编码组合。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LiveStreamKind {
+    pub protocol: LiveProtocol,
+    pub format: LiveFormat,
+    pub codec: LiveCodec,
+}
+
+impl LiveStreamKind {
+    pub fn new(protocol: LiveProtocol, format: LiveFormat, codec: LiveCodec) -> Self {
+        Self {
+            protocol,
+            format,
+            codec,
+        }
+    }
+
+    /// `http_stream` + FLV + AVC，兼容性最好。
+    pub fn flv() -> Self {
+        Self::new(LiveProtocol::HttpStream, LiveFormat::Flv, LiveCodec::Avc)
+    }
+
+    /// `http_hls` + fMP4 + AVC。
+    pub fn hls_fmp4() -> Self {
+        Self::new(LiveProtocol::HttpHls, LiveFormat::Fmp4, LiveCodec::Avc)
+    }
+
+    /// `http_hls` + TS + AVC。
+    pub fn hls_ts() -> Self {
+        Self::new(LiveProtocol::HttpHls, LiveFormat::Ts, LiveCodec::Avc)
+    }
+
+    pub fn with_codec(mut self, codec: LiveCodec) -> Self {
+        self.codec = codec;
+        self
+    }
+}
+
+/// `/xlive/web-room/v2/index/getRoomPlayInfo` 的参数。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LivePlayInfoParams {
+    room_id: RoomId,
+    protocols: Vec<LiveProtocol>,
+    formats: Vec<LiveFormat>,
+    codecs: Vec<LiveCodec>,
+    qn: u32,
+    platform: String,
+}
+
+impl LivePlayInfoParams {
+    /// 默认请求全部协议、格式和编码，画质为原画。
+    pub fn new(room_id: RoomId) -> Self {
+        Self {
+            room_id,
+            protocols: vec![LiveProtocol::HttpStream, LiveProtocol::HttpHls],
+            formats: vec![LiveFormat::Flv, LiveFormat::Ts, LiveFormat::Fmp4],
+            codecs: vec![LiveCodec::Avc, LiveCodec::Hevc],
+            qn: 10000,
+            platform: "web".to_string(),
+        }
+    }
+
+    pub fn with_protocols(mut self, protocols: &[LiveProtocol]) -> BpiResult<Self> {
+        self.protocols = non_empty("protocol", protocols)?;
+        Ok(self)
+    }
+
+    pub fn with_formats(mut self, formats: &[LiveFormat]) -> BpiResult<Self> {
+        self.formats = non_empty("format", formats)?;
+        Ok(self)
+    }
+
+    pub fn with_codecs(mut self, codecs: &[LiveCodec]) -> BpiResult<Self> {
+        self.codecs = non_empty("codec", codecs)?;
+        Ok(self)
+    }
+
+    /// 画质代码，如 10000 原画、400 蓝光、250 超清、150 高清。
+    pub fn with_qn(mut self, qn: u32) -> BpiResult<Self> {
+        if qn == 0 {
+            return Err(BpiError::invalid_parameter("qn", "qn must be positive"));
+        }
+        self.qn = qn;
+        Ok(self)
+    }
+
+    pub fn with_platform(mut self, platform: impl Into<String>) -> BpiResult<Self> {
+        let platform = platform.into();
+        if platform.trim().is_empty() {
+            return Err(BpiError::invalid_parameter(
+                "platform",
+                "platform must not be blank",
+            ));
+        }
+        self.platform = platform;
+        Ok(self)
+    }
+
+    pub(crate) fn room_id(&self) -> RoomId {
+        self.room_id
+    }
+
+    pub(crate) fn with_room_id(mut self, room_id: RoomId) -> Self {
+        self.room_id = room_id;
+        self
+    }
+
+    pub(crate) fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        fn join(codes: impl Iterator<Item = u8>) -> String {
+            codes
+                .map(|code| code.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        }
+
+        vec![
+            ("room_id", self.room_id.to_string()),
+            ("protocol", join(self.protocols.iter().map(|p| p.code()))),
+            ("format", join(self.formats.iter().map(|f| f.code()))),
+            ("codec", join(self.codecs.iter().map(|c| c.code()))),
+            ("qn", self.qn.to_string()),
+            ("platform", self.platform.clone()),
+            ("ptype", "8".to_string()),
+            ("dolby", "5".to_string()),
+            ("panorama", "1".to_string()),
+        ]
+    }
+}
+
+fn non_empty<T: Copy>(field: &'static str, values: &[T]) -> BpiResult<Vec<T>> {
+    if values.is_empty() {
+        return Err(BpiError::invalid_parameter(
+            field,
+            "at least one value must be provided",
+        ));
+    }
+    Ok(values.to_vec())
+}
+
+/// 直播流 CDN 节点
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct LiveUrlInfo {
+    /// 节点域名
+    pub host: String,
+    /// URL 参数
+    #[serde(default)]
+    pub extra: String,
+    /// 有效时间，单位秒
+    #[serde(default)]
+    pub stream_ttl: u64,
+}
+
+/// 某一编码的直播流
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct LivePlayCodec {
+    /// avc / hevc
+    pub codec_name: String,
+    /// 当前画质代码
+    pub current_qn: u32,
+    /// 可选画质代码
+    #[serde(default)]
+    pub accept_qn: Vec<u32>,
+    /// 路径
+    pub base_url: String,
+    /// CDN 节点
+    #[serde(default)]
+    pub url_info: Vec<LiveUrlInfo>,
+}
+
+impl LivePlayCodec {
+    /// 全部 CDN 节点的完整 URL，按接口返回顺序排列。
+    pub fn urls(&self) -> Vec<String> {
+        self.url_info
+            .iter()
+            .map(|info| format!("{}{}{}", info.host, self.base_url, info.extra))
+            .collect()
+    }
+}
+
+/// 某一封装格式的直播流
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct LivePlayFormat {
+    /// flv / ts / fmp4
+    pub format_name: String,
+    #[serde(default)]
+    pub codec: Vec<LivePlayCodec>,
+}
+
+/// 某一协议的直播流
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct LivePlayStream {
+    /// http_stream / http_hls
+    pub protocol_name: String,
+    #[serde(default)]
+    pub format: Vec<LivePlayFormat>,
+}
+
+/// 画质说明
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct LiveQnDesc {
+    pub qn: u32,
+    pub desc: String,
+}
+
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct LivePlayUrl {
+    pub cid: u64,
+    #[serde(default)]
+    pub g_qn_desc: Vec<LiveQnDesc>,
+    #[serde(default)]
+    pub stream: Vec<LivePlayStream>,
+}
+
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct LivePlayUrlInfo {
+    pub playurl: LivePlayUrl,
+}
+
+/// 直播间播放信息
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct LivePlayInfoData {
+    /// 直播间长号
+    pub room_id: u64,
+    /// 直播间短号
+    #[serde(default)]
+    pub short_id: u64,
+    /// 主播 mid
+    pub uid: u64,
+    /// 直播状态 0: 未开播 1: 直播中 2: 轮播中
+    pub live_status: i32,
+    /// 开播时间戳
+    #[serde(default)]
+    pub live_time: i64,
+    /// 未开播时为空
+    #[serde(default)]
+    pub playurl_info: Option<LivePlayUrlInfo>,
+}
+
+/// 选中的直播流
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiveStreamCandidate {
+    pub kind: LiveStreamKind,
+    /// 画质代码
+    pub qn: u32,
+    /// 各 CDN 节点的 URL
+    pub urls: Vec<String>,
+}
+
+impl LivePlayInfoData {
+    pub fn is_live(&self) -> bool {
+        self.live_status == 1
+    }
+
+    /// 接口返回的全部直播流，跳过无法识别或没有节点的组合。
+    pub fn candidates(&self) -> Vec<LiveStreamCandidate> {
+        let Some(info) = &self.playurl_info else {
+            return Vec::new();
+        };
+
+        let mut candidates = Vec::new();
+        for stream in &info.playurl.stream {
+            let Some(protocol) = LiveProtocol::from_name(&stream.protocol_name) else {
+                continue;
+            };
+            for format in &stream.format {
+                let Some(format_kind) = LiveFormat::from_name(&format.format_name) else {
+                    continue;
+                };
+                for codec in &format.codec {
+                    let Some(codec_kind) = LiveCodec::from_name(&codec.codec_name) else {
+                        continue;
+                    };
+                    let urls = codec.urls();
+                    if urls.is_empty() {
+                        continue;
+                    }
+                    candidates.push(LiveStreamCandidate {
+                        kind: LiveStreamKind::new(protocol, format_kind, codec_kind),
+                        qn: codec.current_qn,
+                        urls,
+                    });
+                }
+            }
+        }
+        candidates
+    }
+
+    /// 按偏好顺序选择第一个可用的直播流。
+    pub fn select(&self, preferences: &[LiveStreamKind]) -> Option<LiveStreamCandidate> {
+        let candidates = self.candidates();
+        preferences.iter().find_map(|kind| {
+            candidates
+                .iter()
+                .find(|candidate| candidate.kind == *kind)
+                .cloned()
+        })
+    }
+}
+
+impl<'a> LiveClient<'a> {
+    /// 获取直播间播放信息（v2），可按协议、格式、编码筛选直播流。
+    pub async fn room_play_info(&self, params: LivePlayInfoParams) -> BpiResult<LivePlayInfoData> {
+        let room_id = self.real_room_id(params.room_id()).await?;
+        let params = params.with_room_id(room_id);
+
+        self.client
+            .get(ROOM_PLAY_INFO_ENDPOINT)
+            .with_bilibili_headers()
+            .query(&params.query_pairs())
+            .send_bpi_payload("live.room_play_info")
+            .await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -53,12 +485,101 @@ mod tests {
         let bpi = BpiClient::new().expect("client should build");
         let data = bpi
             .live()
-            .stream(14073662, Some("web"), None, Some(10000))
+            .stream(
+                RoomId::new(14073662).expect("room id"),
+                Some("web"),
+                None,
+                Some(10000),
+            )
             .await
             .unwrap();
         tracing::info!("{:?}", data);
     }
 
+    fn play_info_fixture() -> LivePlayInfoData {
+        serde_json::from_value(serde_json::json!({
+            "room_id": 1000,
+            "short_id": 0,
+            "uid": 2000,
+            "live_status": 1,
+            "live_time": 1700000000,
+            "playurl_info": {"playurl": {
+                "cid": 1000,
+                "g_qn_desc": [{"qn": 10000, "desc": "原画"}],
+                "stream": [
+                    {"protocol_name": "http_stream", "format": [{
+                        "format_name": "flv",
+                        "codec": [{
+                            "codec_name": "avc",
+                            "current_qn": 10000,
+                            "accept_qn": [10000],
+                            "base_url": "/live-bvc/1000.flv?",
+                            "url_info": [
+                                {"host": "https://cn-a.example.com", "extra": "expires=1", "stream_ttl": 3600},
+                                {"host": "https://cn-b.example.com", "extra": "expires=1", "stream_ttl": 3600}
+                            ]
+                        }]
+                    }]},
+                    {"protocol_name": "http_hls", "format": [{
+                        "format_name": "fmp4",
+                        "codec": [
+                            {"codec_name": "hevc", "current_qn": 10000, "base_url": "/live-bvc/1000/index.m3u8?", "url_info": [{"host": "https://cn-c.example.com", "extra": ""}]},
+                            {"codec_name": "av1", "current_qn": 10000, "base_url": "/x", "url_info": [{"host": "https://cn-c.example.com"}]}
+                        ]
+                    }]}
+                ]
+            }}
+        }))
+        .expect("fixture should parse")
+    }
+
+    #[test]
+    fn play_info_params_serialize_selection() -> BpiResult<()> {
+        let pairs = LivePlayInfoParams::new(RoomId::new(1000)?)
+            .with_protocols(&[LiveProtocol::HttpHls])?
+            .with_formats(&[LiveFormat::Ts, LiveFormat::Fmp4])?
+            .with_codecs(&[LiveCodec::Hevc])?
+            .with_qn(400)?
+            .query_pairs();
+
+        assert!(pairs.contains(&("protocol", "1".to_string())));
+        assert!(pairs.contains(&("format", "1,2".to_string())));
+        assert!(pairs.contains(&("codec", "1".to_string())));
+        assert!(pairs.contains(&("qn", "400".to_string())));
+        assert!(
+            LivePlayInfoParams::new(RoomId::new(1000)?)
+                .with_codecs(&[])
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn play_info_selects_preferred_stream_with_all_cdn_hosts() {
+        let data = play_info_fixture();
+
+        assert_eq!(data.candidates().len(), 2);
+        let flv = data
+            .select(&[LiveStreamKind::hls_ts(), LiveStreamKind::flv()])
+            .expect("flv stream is available");
+        assert_eq!(flv.kind, LiveStreamKind::flv());
+        assert_eq!(
+            flv.urls,
+            vec![
+                "https://cn-a.example.com/live-bvc/1000.flv?expires=1",
+                "https://cn-b.example.com/live-bvc/1000.flv?expires=1",
+            ]
+        );
+        let hevc = data
+            .select(&[LiveStreamKind::hls_fmp4().with_codec(LiveCodec::Hevc)])
+            .expect("hevc stream is available");
+        assert_eq!(
+            hevc.urls,
+            vec!["https://cn-c.example.com/live-bvc/1000/index.m3u8?"]
+        );
+        assert!(data.select(&[LiveStreamKind::hls_ts()]).is_none());
+    }
+
     #[test]
     fn live_stream_contract_matches_endpoint_request() -> BpiResult<()> {
         let contract = contract()?;
@@ -0,0 +1,868 @@
+//! 人气红包与天选时刻
+//!
+//! 参与人气红包和天选时刻前按抽奖声明的参与条件（关注主播、粉丝勋章、大航海、赠送礼物）检查当前账号，
+//! 不满足且 [`LotteryJoinPolicy`] 未放行时直接返回 [`LotteryError`]，不会发出参与请求。
+//! [`LotteryMonitor`] 轮询直播间抽奖信息，报告开始和结束的抽奖以及开奖结果，并标出当前账号是否中奖。
+
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::ids::{Mid, RoomId};
+use crate::live::LiveClient;
+use crate::live::message_stream::command_name;
+use crate::live::redpocket::{AnchorLottery, LotteryInfoData, PopularityRedPocket};
+use crate::live::watch::find_medal;
+use crate::{BilibiliRequest, BpiError, BpiResult};
+
+const RED_POCKET_DRAW_ENDPOINT: &str =
+    "https://api.live.bilibili.com/xlive/lottery-interface/v1/popularityRedPocket/RedPocketDraw";
+const ANCHOR_JOIN_ENDPOINT: &str =
+    "https://api.live.bilibili.com/xlive/lottery-interface/v1/Anchor/Join";
+const RED_POCKET_SPM_ID: &str = "444.8.red_envelope.extract";
+const RELATION_ENDPOINT: &str = "https://api.bilibili.com/x/relation";
+/// 关系属性：已关注、互相关注
+const FOLLOWING_ATTRIBUTES: [u8; 2] = [2, 6];
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(30);
+const ANCHOR_LOT_AWARD_CMD: &str = "ANCHOR_LOT_AWARD";
+const RED_POCKET_WINNER_CMD: &str = "POPULARITY_RED_POCKET_WINNER_LIST";
+
+/// 抽奖参与错误
+#[derive(Debug, Error)]
+pub enum LotteryError {
+    /// 抽奖已结束
+    #[error("抽奖已结束: {lot_id}")]
+    Closed { lot_id: i64 },
+
+    /// 需要关注主播
+    #[error("参与需要关注主播 {anchor}")]
+    FollowRequired { anchor: Mid },
+
+    /// 粉丝勋章等级不足
+    #[error("参与需要 {required} 级粉丝勋章，当前 {current} 级")]
+    MedalRequired { required: i32, current: i32 },
+
+    /// 需要大航海身份
+    #[error("参与需要大航海身份")]
+    GuardRequired { required: i32 },
+
+    /// 需要赠送的礼物超过允许花费的金瓜子
+    #[error("参与需要花费 {required} 金瓜子，超过上限 {limit}")]
+    GoldLimit { required: u64, limit: u64 },
+
+    #[error(transparent)]
+    Api(#[from] BpiError),
+}
+
+pub type LotteryResult<T> = Result<T, LotteryError>;
+
+/// `x/relation` 返回的与目标用户的关系。
+#[derive(Debug, Clone, Deserialize)]
+struct RelationData {
+    #[serde(default)]
+    attribute: u8,
+}
+
+impl RelationData {
+    fn is_following(&self) -> bool {
+        FOLLOWING_ATTRIBUTES.contains(&self.attribute)
+    }
+}
+
+/// 抽奖参与条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LotteryRequirement {
+    None,
+    /// 关注主播
+    Follow,
+    /// 佩戴不低于 `level` 级的主播粉丝勋章
+    Medal {
+        level: i32,
+    },
+    /// 大航海身份；`level` 为可接受的最低等级（1 总督，2 提督，3 舰长），0 表示任意
+    Guard {
+        level: i32,
+    },
+    /// 本地无法检查的条件，例如用户等级，由服务端判断
+    Other {
+        kind: i32,
+        value: i32,
+    },
+}
+
+impl LotteryRequirement {
+    /// 天选时刻的参与条件。
+    pub fn anchor(lottery: &AnchorLottery) -> Self {
+        match lottery.require_type {
+            0 => Self::None,
+            1 => Self::Follow,
+            2 => Self::Medal {
+                level: lottery.require_value,
+            },
+            3 => Self::Guard {
+                level: lottery.require_value,
+            },
+            kind => Self::Other {
+                kind,
+                value: lottery.require_value,
+            },
+        }
+    }
+
+    /// 人气红包的参与条件。
+    pub fn red_pocket(pocket: &PopularityRedPocket) -> Self {
+        match pocket.join_requirement {
+            0 => Self::None,
+            1 => Self::Follow,
+            kind => Self::Other { kind, value: 0 },
+        }
+    }
+}
+
+/// 参与抽奖时允许的额外操作。默认不允许自动关注、不允许花费金瓜子。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LotteryJoinPolicy {
+    allow_follow: bool,
+    max_gold: u64,
+}
+
+impl LotteryJoinPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 未关注主播时是否仍然参与；参与后服务端会自动关注主播。
+    pub fn with_allow_follow(mut self, allow_follow: bool) -> Self {
+        self.allow_follow = allow_follow;
+        self
+    }
+
+    /// 参与天选时刻时最多花费的金瓜子。
+    pub fn with_max_gold(mut self, max_gold: u64) -> Self {
+        self.max_gold = max_gold;
+        self
+    }
+
+    pub fn allow_follow(&self) -> bool {
+        self.allow_follow
+    }
+
+    pub fn max_gold(&self) -> u64 {
+        self.max_gold
+    }
+}
+
+/// 参与人气红包响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedPocketJoinData {
+    /// 参与状态
+    #[serde(default)]
+    pub join_status: i32,
+    #[serde(default)]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
+}
+
+/// 参与天选时刻响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchorJoinData {
+    /// 花费的金瓜子
+    #[serde(default)]
+    pub gold: i64,
+    /// 花费的银瓜子
+    #[serde(default)]
+    pub silver: i64,
+    #[serde(default)]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
+}
+
+/// 抽奖类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LotteryKind {
+    RedPocket,
+    Anchor,
+}
+
+/// 中奖用户
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LotteryWinner {
+    pub uid: Option<Mid>,
+    pub uname: String,
+    pub award_name: String,
+}
+
+/// 开奖结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LotteryDraw {
+    pub kind: LotteryKind,
+    pub lot_id: i64,
+    pub winners: Vec<LotteryWinner>,
+    /// 当前账号的中奖记录
+    pub mine: Option<LotteryWinner>,
+}
+
+impl LotteryDraw {
+    fn new(kind: LotteryKind, lot_id: i64, winners: Vec<LotteryWinner>, me: Option<Mid>) -> Self {
+        let mine = me.and_then(|me| {
+            winners
+                .iter()
+                .find(|winner| winner.uid == Some(me))
+                .cloned()
+        });
+
+        Self {
+            kind,
+            lot_id,
+            winners,
+            mine,
+        }
+    }
+
+    fn anchor(lottery: &AnchorLottery, me: Option<Mid>) -> Self {
+        let winners = lottery
+            .award_users
+            .iter()
+            .flatten()
+            .map(|user| LotteryWinner {
+                uid: to_mid(user.uid),
+                uname: user.uname.clone(),
+                award_name: lottery.award_name.clone(),
+            })
+            .collect();
+
+        Self::new(LotteryKind::Anchor, lottery.id, winners, me)
+    }
+
+    /// 解析 `POPULARITY_RED_POCKET_WINNER_LIST`。
+    ///
+    /// `winner_info` 每项为数组，依次是 uid、昵称、用户信息 id 和奖品礼物 id；奖品名称取自 `awards`。
+    fn red_pocket(data: &Value, me: Option<Mid>) -> BpiResult<Self> {
+        let lot_id = data
+            .get("lot_id")
+            .and_then(Value::as_i64)
+            .ok_or_else(|| BpiError::parse(format!("{RED_POCKET_WINNER_CMD} 缺少 lot_id")))?;
+
+        let winners = data
+            .get("winner_info")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_array)
+            .map(|winner| {
+                let award_name = winner
+                    .get(3)
+                    .and_then(Value::as_i64)
+                    .and_then(|gift_id| {
+                        data.get("awards")?
+                            .get(gift_id.to_string())?
+                            .get("award_name")?
+                            .as_str()
+                    })
+                    .unwrap_or_default();
+
+                LotteryWinner {
+                    uid: winner.first().and_then(Value::as_i64).and_then(to_mid),
+                    uname: winner
+                        .get(1)
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    award_name: award_name.to_string(),
+                }
+            })
+            .collect();
+
+        Ok(Self::new(LotteryKind::RedPocket, lot_id, winners, me))
+    }
+
+    /// 当前账号是否中奖。
+    pub fn won(&self) -> bool {
+        self.mine.is_some()
+    }
+}
+
+/// 抽奖监控事件
+#[derive(Debug, Clone)]
+pub enum LotteryEvent {
+    /// 出现新的人气红包
+    RedPocketStarted(PopularityRedPocket),
+    /// 人气红包从直播间消失
+    RedPocketEnded { lot_id: i64 },
+    /// 出现新的天选时刻
+    AnchorStarted(AnchorLottery),
+    /// 开奖
+    Drawn(LotteryDraw),
+}
+
+/// 直播间抽奖监控。
+pub struct LotteryMonitor<'a> {
+    client: LiveClient<'a>,
+    room_id: RoomId,
+    me: Option<Mid>,
+    interval: Duration,
+    polled: bool,
+    red_pockets: BTreeSet<i64>,
+    anchor: Option<i64>,
+    drawn: BTreeSet<(LotteryKind, i64)>,
+    history: Vec<LotteryDraw>,
+}
+
+impl<'a> LotteryMonitor<'a> {
+    fn new(client: LiveClient<'a>, room_id: RoomId, me: Option<Mid>) -> Self {
+        Self {
+            client,
+            room_id,
+            me,
+            interval: DEFAULT_INTERVAL,
+            polled: false,
+            red_pockets: BTreeSet::new(),
+            anchor: None,
+            drawn: BTreeSet::new(),
+            history: Vec::new(),
+        }
+    }
+
+    /// 轮询间隔，默认 30 秒。
+    pub fn with_interval(mut self, interval: Duration) -> BpiResult<Self> {
+        if interval.is_zero() {
+            return Err(BpiError::invalid_parameter(
+                "interval",
+                "interval must be non-zero",
+            ));
+        }
+
+        self.interval = interval;
+        Ok(self)
+    }
+
+    /// 判断中奖时使用的账号，默认取客户端登录的账号。
+    pub fn with_account(mut self, uid: Mid) -> Self {
+        self.me = Some(uid);
+        self
+    }
+
+    pub fn room_id(&self) -> RoomId {
+        self.room_id
+    }
+
+    /// 已观察到的开奖结果。
+    pub fn history(&self) -> &[LotteryDraw] {
+        &self.history
+    }
+
+    /// 当前账号中奖的开奖结果。
+    pub fn won(&self) -> impl Iterator<Item = &LotteryDraw> {
+        self.history.iter().filter(|draw| draw.won())
+    }
+
+    /// 等待轮询间隔后轮询一次；首次调用立即轮询。
+    pub async fn next(&mut self) -> BpiResult<Vec<LotteryEvent>> {
+        if self.polled {
+            tokio::time::sleep(self.interval).await;
+        }
+        self.polled = true;
+
+        self.poll().await
+    }
+
+    /// 立即读取直播间抽奖信息。
+    pub async fn poll(&mut self) -> BpiResult<Vec<LotteryEvent>> {
+        let info = self.client.lottery_info(self.room_id).await?;
+        self.observe_info(&info)
+    }
+
+    /// 对比上次的抽奖信息，返回变化。
+    pub fn observe_info(&mut self, info: &LotteryInfoData) -> BpiResult<Vec<LotteryEvent>> {
+        let mut events = Vec::new();
+
+        let pockets = info.popularity_red_pocket.as_deref().unwrap_or_default();
+        let current: BTreeSet<i64> = pockets.iter().map(|pocket| pocket.lot_id).collect();
+        events.extend(
+            self.red_pockets
+                .difference(&current)
+                .map(|&lot_id| LotteryEvent::RedPocketEnded { lot_id }),
+        );
+        events.extend(
+            pockets
+                .iter()
+                .filter(|pocket| !self.red_pockets.contains(&pocket.lot_id))
+                .cloned()
+                .map(LotteryEvent::RedPocketStarted),
+        );
+        self.red_pockets = current;
+
+        let anchor = info.anchor_lottery()?;
+        if let Some(lottery) = &anchor {
+            if self.anchor != Some(lottery.id) && lottery.is_open() {
+                events.push(LotteryEvent::AnchorStarted(lottery.clone()));
+            }
+            if lottery
+                .award_users
+                .as_ref()
+                .is_some_and(|users| !users.is_empty())
+            {
+                events.extend(self.record(LotteryDraw::anchor(lottery, self.me)));
+            }
+        }
+        self.anchor = anchor.map(|lottery| lottery.id);
+
+        Ok(events)
+    }
+
+    /// 处理直播信息流中的开奖消息（`ANCHOR_LOT_AWARD`、`POPULARITY_RED_POCKET_WINNER_LIST`），
+    /// 其他消息返回 `None`。同一场抽奖只报告一次。
+    pub fn observe_command(&mut self, command: &Value) -> BpiResult<Option<LotteryEvent>> {
+        let Some(name) = command_name(command) else {
+            return Ok(None);
+        };
+        if name != ANCHOR_LOT_AWARD_CMD && name != RED_POCKET_WINNER_CMD {
+            return Ok(None);
+        }
+
+        let data = command
+            .get("data")
+            .ok_or_else(|| BpiError::parse(format!("{name} 缺少 data")))?;
+        let draw = if name == ANCHOR_LOT_AWARD_CMD {
+            LotteryDraw::anchor(&AnchorLottery::deserialize(data)?, self.me)
+        } else {
+            LotteryDraw::red_pocket(data, self.me)?
+        };
+
+        Ok(self.record(draw))
+    }
+
+    fn record(&mut self, draw: LotteryDraw) -> Option<LotteryEvent> {
+        if !self.drawn.insert((draw.kind, draw.lot_id)) {
+            return None;
+        }
+
+        if let Some(mine) = &draw.mine {
+            tracing::info!(
+                "直播间 {} 抽奖 {} 中奖: {}",
+                self.room_id,
+                draw.lot_id,
+                mine.award_name
+            );
+        }
+        self.history.push(draw.clone());
+        Some(LotteryEvent::Drawn(draw))
+    }
+}
+
+impl<'a> LiveClient<'a> {
+    /// 参与人气红包。参与时服务端会自动发送红包弹幕并关注主播。需要登录。
+    pub async fn join_red_pocket(
+        &self,
+        room_id: RoomId,
+        pocket: &PopularityRedPocket,
+        policy: LotteryJoinPolicy,
+    ) -> LotteryResult<RedPocketJoinData> {
+        if pocket.end_time > 0 && pocket.current_time >= pocket.end_time {
+            return Err(LotteryError::Closed {
+                lot_id: pocket.lot_id,
+            });
+        }
+
+        let room = self.resolve_room(room_id).await?;
+        self.check_lottery_requirement(room.uid, LotteryRequirement::red_pocket(pocket), policy)
+            .await?;
+
+        let csrf = self.client.csrf()?;
+        let form = [
+            ("ruid", room.uid.to_string()),
+            ("room_id", room.room_id.to_string()),
+            ("lot_id", pocket.lot_id.to_string()),
+            ("spm_id", RED_POCKET_SPM_ID.to_string()),
+            ("jump_from", String::new()),
+            ("session_id", String::new()),
+            ("csrf_token", csrf.clone()),
+            ("csrf", csrf),
+            ("visit_id", String::new()),
+        ];
+
+        Ok(self
+            .client
+            .post(RED_POCKET_DRAW_ENDPOINT)
+            .with_bilibili_headers()
+            .form(&form)
+            .send_bpi_payload("live.join_red_pocket")
+            .await?)
+    }
+
+    /// 参与天选时刻。需要赠送礼物时花费不能超过 `policy` 允许的金瓜子。需要登录。
+    pub async fn join_anchor_lottery(
+        &self,
+        room_id: RoomId,
+        lottery: &AnchorLottery,
+        policy: LotteryJoinPolicy,
+    ) -> LotteryResult<AnchorJoinData> {
+        if !lottery.is_open() {
+            return Err(LotteryError::Closed { lot_id: lottery.id });
+        }
+
+        let gold = lottery.gold_cost();
+        if gold > policy.max_gold {
+            return Err(LotteryError::GoldLimit {
+                required: gold,
+                limit: policy.max_gold,
+            });
+        }
+
+        let room = self.resolve_room(room_id).await?;
+        self.check_lottery_requirement(room.uid, LotteryRequirement::anchor(lottery), policy)
+            .await?;
+
+        let csrf = self.client.csrf()?;
+        let mut form = vec![
+            ("id", lottery.id.to_string()),
+            ("roomid", room.room_id.to_string()),
+            ("platform", "pc".to_string()),
+            ("csrf_token", csrf.clone()),
+            ("csrf", csrf),
+            ("visit_id", String::new()),
+        ];
+        if gold > 0 {
+            form.push(("gift_id", lottery.gift_id.to_string()));
+            form.push(("gift_num", lottery.gift_num.to_string()));
+        }
+
+        Ok(self
+            .client
+            .post(ANCHOR_JOIN_ENDPOINT)
+            .with_bilibili_headers()
+            .form(&form)
+            .send_bpi_payload("live.join_anchor_lottery")
+            .await?)
+    }
+
+    /// 当前账号是否关注了主播，直接查询与主播的关系。需要登录。
+    pub async fn is_following(&self, anchor: Mid) -> BpiResult<bool> {
+        let data: RelationData = self
+            .client
+            .get(RELATION_ENDPOINT)
+            .with_bilibili_headers()
+            .query(&[("fid", anchor.to_string())])
+            .send_bpi_payload("live.relation")
+            .await?;
+        Ok(data.is_following())
+    }
+
+    /// 创建直播间抽奖监控，中奖判断使用客户端登录的账号。
+    pub async fn lottery_monitor(&self, room_id: RoomId) -> BpiResult<LotteryMonitor<'a>> {
+        let room_id = self.real_room_id(room_id).await?;
+        let me = self
+            .client
+            .get_account()
+            .and_then(|account| account.dede_user_id.parse::<u64>().ok())
+            .and_then(|uid| Mid::new(uid).ok());
+
+        Ok(LotteryMonitor::new(*self, room_id, me))
+    }
+
+    async fn check_lottery_requirement(
+        &self,
+        anchor: Mid,
+        requirement: LotteryRequirement,
+        policy: LotteryJoinPolicy,
+    ) -> LotteryResult<()> {
+        match requirement {
+            LotteryRequirement::None | LotteryRequirement::Other { .. } => Ok(()),
+            LotteryRequirement::Follow => {
+                if policy.allow_follow || self.is_following(anchor).await? {
+                    Ok(())
+                } else {
+                    Err(LotteryError::FollowRequired { anchor })
+                }
+            }
+            LotteryRequirement::Medal { level } => {
+                let current = find_medal(self, anchor)
+                    .await?
+                    .map_or(0, |medal| medal.level);
+                if current < level {
+                    return Err(LotteryError::MedalRequired {
+                        required: level,
+                        current,
+                    });
+                }
+                Ok(())
+            }
+            LotteryRequirement::Guard { level } => {
+                let guard = find_medal(self, anchor)
+                    .await?
+                    .map_or(0, |medal| medal.guard_level);
+                if !guard_satisfies(guard, level) {
+                    return Err(LotteryError::GuardRequired { required: level });
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// 大航海等级数字越小身份越高，0 表示没有大航海身份。
+fn guard_satisfies(guard: i32, required: i32) -> bool {
+    guard > 0 && (required <= 0 || guard <= required)
+}
+
+fn to_mid(uid: i64) -> Option<Mid> {
+    u64::try_from(uid).ok().and_then(|uid| Mid::new(uid).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BpiClient;
+    use crate::session::{Account, AccountProfile};
+    use serde_json::json;
+
+    #[test]
+    fn relation_attribute_decides_following() -> BpiResult<()> {
+        for (attribute, following) in [(0, false), (2, true), (6, true), (128, false)] {
+            let data: RelationData = serde_json::from_value(json!({
+                "mid": 1_000_002,
+                "attribute": attribute,
+                "mtime": 0,
+                "tag": null,
+                "special": 0
+            }))?;
+            assert_eq!(data.is_following(), following, "attribute {attribute}");
+        }
+        Ok(())
+    }
+
+    fn anchor_lottery(value: Value) -> BpiResult<AnchorLottery> {
+        Ok(serde_json::from_value(value)?)
+    }
+
+    fn monitor(client: &BpiClient) -> BpiResult<LotteryMonitor<'_>> {
+        Ok(LotteryMonitor::new(
+            client.live(),
+            RoomId::new(3_818_081)?,
+            Some(Mid::new(1_000_002)?),
+        ))
+    }
+
+    fn info(value: Value) -> BpiResult<LotteryInfoData> {
+        Ok(serde_json::from_value(value)?)
+    }
+
+    fn red_pocket(lot_id: i64) -> Value {
+        json!({
+            "lot_id": lot_id,
+            "sender_uid": 1_000_001,
+            "sender_name": "<redacted-uname>",
+            "sender_face": "",
+            "join_requirement": 1,
+            "danmu": "老板大气！点点红包抽礼物",
+            "awards": [],
+            "start_time": 1_700_000_000,
+            "end_time": 1_700_000_180,
+            "last_time": 180,
+            "remove_time": 1_700_000_195,
+            "replace_time": 1_700_000_190,
+            "current_time": 1_700_000_060,
+            "lot_status": 1,
+            "h5_url": "",
+            "user_status": 2,
+            "lot_config_id": 3,
+            "total_price": 1600
+        })
+    }
+
+    #[test]
+    fn requirements_map_from_lottery_fields() -> BpiResult<()> {
+        let lottery = anchor_lottery(json!({
+            "id": 100,
+            "status": 1,
+            "require_type": 2,
+            "require_value": 5,
+            "gift_id": 31036,
+            "gift_num": 2,
+            "gift_price": 100
+        }))?;
+        assert_eq!(
+            LotteryRequirement::anchor(&lottery),
+            LotteryRequirement::Medal { level: 5 }
+        );
+        assert_eq!(lottery.gold_cost(), 200);
+
+        let free = anchor_lottery(json!({ "id": 101, "require_type": 4, "require_value": 20 }))?;
+        assert_eq!(
+            LotteryRequirement::anchor(&free),
+            LotteryRequirement::Other { kind: 4, value: 20 }
+        );
+        assert_eq!(free.gold_cost(), 0);
+        assert!(!free.is_open());
+
+        assert!(guard_satisfies(3, 0));
+        assert!(guard_satisfies(2, 3));
+        assert!(!guard_satisfies(3, 2));
+        assert!(!guard_satisfies(0, 0));
+        Ok(())
+    }
+
+    #[test]
+    fn join_anchor_lottery_rejects_gold_and_closed_lotteries_before_request()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let client = BpiClient::new()?;
+        let live = client.live();
+        let room_id = RoomId::new(3_818_081)?;
+        let paid = anchor_lottery(json!({
+            "id": 100,
+            "status": 1,
+            "gift_id": 31036,
+            "gift_num": 1,
+            "gift_price": 1000
+        }))?;
+        let closed = anchor_lottery(json!({ "id": 101, "status": 2 }))?;
+
+        let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+        let result = runtime.block_on(live.join_anchor_lottery(
+            room_id,
+            &paid,
+            LotteryJoinPolicy::new().with_max_gold(500),
+        ));
+        assert!(matches!(
+            result,
+            Err(LotteryError::GoldLimit {
+                required: 1000,
+                limit: 500
+            })
+        ));
+
+        let result =
+            runtime.block_on(live.join_anchor_lottery(room_id, &closed, LotteryJoinPolicy::new()));
+        assert!(matches!(result, Err(LotteryError::Closed { lot_id: 101 })));
+        Ok(())
+    }
+
+    #[test]
+    fn monitor_reports_started_ended_and_drawn_lotteries_once() -> BpiResult<()> {
+        let client = BpiClient::new()?;
+        let mut monitor = monitor(&client)?;
+
+        let events = monitor.observe_info(&info(json!({
+            "popularity_red_pocket": [red_pocket(7)],
+            "activity_box_info": null,
+            "anchor": {
+                "id": 100,
+                "status": 1,
+                "award_name": "<redacted-award>",
+                "award_users": null
+            }
+        }))?)?;
+        assert!(matches!(
+            events.as_slice(),
+            [
+                LotteryEvent::RedPocketStarted(pocket),
+                LotteryEvent::AnchorStarted(lottery)
+            ] if pocket.lot_id == 7 && lottery.id == 100
+        ));
+
+        let drawn = info(json!({
+            "popularity_red_pocket": null,
+            "activity_box_info": null,
+            "anchor": {
+                "id": 100,
+                "status": 2,
+                "award_name": "<redacted-award>",
+                "award_users": [
+                    { "uid": 1_000_003, "uname": "<redacted-uname>", "num": 1 },
+                    { "uid": 1_000_002, "uname": "<redacted-uname>", "num": 1 }
+                ]
+            }
+        }))?;
+        let events = monitor.observe_info(&drawn)?;
+        assert!(matches!(
+            events.as_slice(),
+            [
+                LotteryEvent::RedPocketEnded { lot_id: 7 },
+                LotteryEvent::Drawn(draw)
+            ] if draw.kind == LotteryKind::Anchor && draw.winners.len() == 2 && draw.won()
+        ));
+        assert!(monitor.observe_info(&drawn)?.is_empty());
+        assert_eq!(monitor.won().count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn monitor_parses_award_commands() -> BpiResult<()> {
+        let client = BpiClient::new()?;
+        let mut monitor = monitor(&client)?;
+
+        let red_pocket = json!({
+            "cmd": "POPULARITY_RED_POCKET_WINNER_LIST",
+            "data": {
+                "lot_id": 7,
+                "winner_info": [
+                    [1_000_002, "<redacted-uname>", 1, 31212],
+                    [1_000_004, "<redacted-uname>", 2, 31213]
+                ],
+                "awards": {
+                    "31212": { "award_name": "打call", "award_type": 1 },
+                    "31213": { "award_name": "牛哇", "award_type": 1 }
+                }
+            }
+        });
+        let Some(LotteryEvent::Drawn(draw)) = monitor.observe_command(&red_pocket)? else {
+            panic!("red pocket winner list should be reported");
+        };
+        assert_eq!(draw.kind, LotteryKind::RedPocket);
+        assert_eq!(
+            draw.mine.map(|mine| mine.award_name).as_deref(),
+            Some("打call")
+        );
+        assert!(monitor.observe_command(&red_pocket)?.is_none());
+
+        let anchor = json!({
+            "cmd": "ANCHOR_LOT_AWARD",
+            "data": {
+                "id": 100,
+                "award_name": "<redacted-award>",
+                "award_users": [{ "uid": 1_000_003, "uname": "<redacted-uname>", "num": 1 }]
+            }
+        });
+        let Some(LotteryEvent::Drawn(draw)) = monitor.observe_command(&anchor)? else {
+            panic!("anchor award should be reported");
+        };
+        assert!(!draw.won());
+        assert!(
+            monitor
+                .observe_command(&json!({ "cmd": "DANMU_MSG", "info": [] }))?
+                .is_none()
+        );
+        assert_eq!(monitor.history().len(), 2);
+        Ok(())
+    }
+
+    #[ignore = "live mutating test; requires BPI_MUTATING_TEST=1 plus BPI_LIVE_ROOM_ID with an open free 天选"]
+    #[tokio::test]
+    async fn live_join_anchor_lottery_from_env() -> Result<(), Box<dyn std::error::Error>> {
+        if std::env::var("BPI_MUTATING_TEST").ok().as_deref() != Some("1") {
+            eprintln!("skipping live mutating test; set BPI_MUTATING_TEST=1 and BPI_LIVE_ROOM_ID");
+            return Ok(());
+        }
+
+        let room_id = RoomId::new(std::env::var("BPI_LIVE_ROOM_ID")?.parse()?)?;
+        let account = Account::load_test_account_profile(AccountProfile::Normal)?;
+        let client = BpiClient::builder().account(account).build()?;
+        let live = client.live();
+
+        let info = live.lottery_info(room_id).await?;
+        match info.anchor_lottery()? {
+            Some(lottery) => {
+                let data = live
+                    .join_anchor_lottery(room_id, &lottery, LotteryJoinPolicy::new())
+                    .await?;
+                eprintln!("joined anchor lottery {}: {data:?}", lottery.id);
+            }
+            None => eprintln!("no anchor lottery in room {room_id}"),
+        }
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::BilibiliRequest;
 use crate::BpiResult;
+use crate::ids::RoomId;
 use crate::live::LiveClient;
 use reqwest::multipart::Form;
 use serde::{Deserialize, Serialize};
@@ -31,18 +32,35 @@ pub struct UpdateRoomData {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AuditInfo {
     pub audit_title_reason: String,
+    /// 标题审核状态，0 为通过
     pub audit_title_status: u8,
     pub audit_title: Option<String>,
     pub update_title: Option<String>,
 }
 
+impl AuditInfo {
+    /// 标题是否已通过审核。
+    pub fn is_passed(&self) -> bool {
+        self.audit_title_status == 0
+    }
+}
+
 /// RTMP 推流地址信息
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RtmpInfo {
+    /// 推流服务器地址
     pub addr: String,
+    /// 推流码
     pub code: String,
 }
 
+impl RtmpInfo {
+    /// 服务器地址和推流码拼接后的完整推流地址。
+    pub fn push_url(&self) -> String {
+        format!("{}{}", self.addr, self.code)
+    }
+}
+
 /// 网页 link 中心推流地址（FetchWebUpStreamAddr）
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct WebUpStreamAddrData {
@@ -125,13 +143,14 @@ impl<'a> LiveClient<'a> {
     /// * `del_tag` - 要删除的标签，可选
     pub async fn live_update_room_info(
         &self,
-        room_id: u64,
+        room_id: RoomId,
         title: Option<&str>,
         area_id: Option<u64>,
         add_tag: Option<&str>,
         del_tag: Option<&str>,
     ) -> BpiResult<UpdateRoomData> {
         let csrf = self.client.csrf()?;
+        let room_id = self.real_room_id(room_id).await?;
         let mut form = Form::new()
             .text("room_id", room_id.to_string())
             .text("csrf", csrf.clone())
@@ -172,10 +191,11 @@ impl<'a> LiveClient<'a> {
     /// 网页 link 中心开始直播（第三方软件开播，WBI 签名）
     pub async fn live_web_center_start(
         &self,
-        room_id: u64,
+        room_id: RoomId,
         area_v2: u64,
     ) -> BpiResult<StartLiveData> {
         let csrf = self.client.csrf()?;
+        let room_id = self.real_room_id(room_id).await?;
         let form = web_live_center_start_query(room_id, area_v2, &csrf);
         let signed = self.client.get_wbi_sign2(form).await?;
 
@@ -191,11 +211,12 @@ impl<'a> LiveClient<'a> {
     #[allow(dead_code)]
     async fn live_start_legacy(
         &self,
-        room_id: u64,
+        room_id: RoomId,
         area_v2: u64,
         platform: &str,
     ) -> BpiResult<StartLiveData> {
         let csrf = self.client.csrf()?;
+        let room_id = self.real_room_id(room_id).await?;
         let form = Form::new()
             .text("room_id", room_id.to_string())
             .text("area_v2", area_v2.to_string())
@@ -215,7 +236,22 @@ impl<'a> LiveClient<'a> {
     /// # 参数
     /// * `room_id` - 直播间 ID
     /// * `platform` - 直播平台，如 "pc_link"
-    pub async fn live_stop(&self, room_id: u64, platform: &str) -> BpiResult<StopLiveData> {
+    pub async fn live_stop(&self, room_id: RoomId, platform: &str) -> BpiResult<StopLiveData> {
+        let room_id = self.real_room_id(room_id).await?;
+
+        self.live_stop_request(room_id, platform)?
+            .send_bpi_payload("live.stop")
+            .await
+    }
+
+    /// 关闭直播请求，`room_id` 需要是真实房间号。
+    ///
+    /// 请求不借用客户端，[`crate::live::broadcast::BroadcastSession`] 在 drop 时用它在后台关播。
+    pub(crate) fn live_stop_request(
+        &self,
+        room_id: RoomId,
+        platform: &str,
+    ) -> BpiResult<reqwest::RequestBuilder> {
         let csrf = self.client.csrf()?;
         let form = Form::new()
             .text("platform", platform.to_string())
@@ -223,11 +259,10 @@ impl<'a> LiveClient<'a> {
             .text("csrf", csrf.clone())
             .text("csrf_token", csrf);
 
-        self.client
+        Ok(self
+            .client
             .post("https://api.live.bilibili.com/room/v1/Room/stopLive")
-            .multipart(form)
-            .send_bpi_payload("live.stop")
-            .await
+            .multipart(form))
     }
 
     /// 预更新直播间信息
@@ -265,19 +300,14 @@ impl<'a> LiveClient<'a> {
     /// 更新直播间公告
     ///
     /// # 参数
-    /// * `room_id` - 直播间 ID
-    /// * `uid` - 用户ID
+    /// * `room_id` - 直播间 ID，主播 mid 由房间号解析得到
     /// * `content` - 公告内容
-    pub async fn live_update_room_news(
-        &self,
-        room_id: u64,
-        uid: u64,
-        content: &str,
-    ) -> BpiResult<Value> {
+    pub async fn live_update_room_news(&self, room_id: RoomId, content: &str) -> BpiResult<Value> {
         let csrf = self.client.csrf()?;
+        let room = self.resolve_room(room_id).await?;
         let form = Form::new()
-            .text("room_id", room_id.to_string())
-            .text("uid", uid.to_string())
+            .text("room_id", room.room_id.to_string())
+            .text("uid", room.uid.to_string())
             .text("content", content.to_string())
             .text("csrf", csrf.clone())
             .text("csrf_token", csrf);
@@ -300,7 +330,7 @@ fn web_up_stream_addr_form(csrf: &str) -> Vec<(&'static str, String)> {
 }
 
 fn web_live_center_start_query(
-    room_id: u64,
+    room_id: RoomId,
     area_v2: u64,
     csrf: &str,
 ) -> Vec<(&'static str, String)> {
@@ -401,9 +431,9 @@ mod tests {
     }
 
     #[test]
-    fn web_center_start_query_contains_wbi_signed_inputs() {
+    fn web_center_start_query_contains_wbi_signed_inputs() -> BpiResult<()> {
         assert_eq!(
-            web_live_center_start_query(4_354_019, 309, "csrf-token"),
+            web_live_center_start_query(RoomId::new(4_354_019)?, 309, "csrf-token"),
             vec![
                 ("room_id", "4354019".to_string()),
                 ("platform", "pc".to_string()),
@@ -413,6 +443,7 @@ mod tests {
                 ("csrf_token", "csrf-token".to_string()),
             ]
         );
+        Ok(())
     }
 
     #[test]
@@ -464,4 +495,14 @@ mod tests {
         assert_eq!(payload.live_key, "live-key");
         Ok(())
     }
+
+    #[test]
+    fn rtmp_push_url_joins_server_and_stream_key() {
+        let rtmp = RtmpInfo {
+            addr: "rtmp://example/live/".to_string(),
+            code: "stream-key".to_string(),
+        };
+
+        assert_eq!(rtmp.push_url(), "rtmp://example/live/stream-key");
+    }
 }
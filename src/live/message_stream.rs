@@ -1 +1,428 @@
 // https://github.com/Yuelioi/bilibili-API-collect/tree/cfc5fddcc8a94b74d91970bb5b4eaeb349addc47/docs/live/message_stream.md
+
+//! 直播信息流消息
+//!
+//! 信息流连接本身（`getDanmuInfo` 返回的 host 与 token）由调用方维护，这里只解析解包后的命令 JSON：
+//! `DANMU_MSG` 解析为交给 [`crate::live::moderator::RoomModerator`] 的弹幕事件，`SEND_GIFT`、
+//! `SUPER_CHAT_MESSAGE` 和 `GUARD_BUY` 解析为交给 [`crate::live::ledger::GiftLedger`] 的营收事件。
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use crate::ids::{Mid, RoomId};
+use crate::{BpiError, BpiResult};
+
+const DANMU_MSG_CMD: &str = "DANMU_MSG";
+const SEND_GIFT_CMD: &str = "SEND_GIFT";
+const SUPER_CHAT_CMD: &str = "SUPER_CHAT_MESSAGE";
+const GUARD_BUY_CMD: &str = "GUARD_BUY";
+
+/// 弹幕发送者佩戴的粉丝勋章
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiveDanmakuMedal {
+    /// 勋章等级
+    pub level: u32,
+    /// 勋章名
+    pub name: String,
+    /// 勋章所属直播间
+    #[serde(default)]
+    pub room_id: Option<RoomId>,
+    /// 勋章所属主播 mid
+    #[serde(default)]
+    pub anchor_uid: Option<Mid>,
+}
+
+/// 直播弹幕事件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiveDanmakuEvent {
+    /// 发送者 mid，未登录连接时服务器会隐去为 0，此时为 `None`
+    #[serde(default)]
+    pub uid: Option<Mid>,
+    /// 发送者昵称
+    pub uname: String,
+    /// 弹幕内容
+    pub text: String,
+    /// 发送时间戳，单位毫秒
+    pub timestamp_ms: i64,
+    /// 是否为房管
+    #[serde(default)]
+    pub is_admin: bool,
+    /// 用户直播等级
+    #[serde(default)]
+    pub user_level: u32,
+    /// 大航海等级 0: 无 1: 总督 2: 提督 3: 舰长
+    #[serde(default)]
+    pub guard_level: u32,
+    /// 佩戴的粉丝勋章
+    #[serde(default)]
+    pub medal: Option<LiveDanmakuMedal>,
+}
+
+impl LiveDanmakuEvent {
+    /// 解析信息流中的一条命令，非 `DANMU_MSG` 命令返回 `None`。
+    ///
+    /// 部分命令名会带有后缀（如 `DANMU_MSG:4:0:2:2:2:0`），同样按弹幕处理。
+    pub fn from_command(command: &Value) -> BpiResult<Option<Self>> {
+        if command_name(command) != Some(DANMU_MSG_CMD) {
+            return Ok(None);
+        }
+
+        let info = command
+            .get("info")
+            .and_then(Value::as_array)
+            .ok_or_else(|| BpiError::parse("DANMU_MSG 缺少 info 数组"))?;
+        let meta = array_at(info, 0)?;
+        let user = array_at(info, 2)?;
+
+        let medal = info
+            .get(3)
+            .and_then(Value::as_array)
+            .filter(|medal| !medal.is_empty())
+            .map(|medal| LiveDanmakuMedal {
+                level: u64_at(medal, 0) as u32,
+                name: str_at(medal, 1).to_string(),
+                room_id: RoomId::new(u64_at(medal, 3)).ok(),
+                anchor_uid: Mid::new(u64_at(medal, 12)).ok(),
+            });
+
+        Ok(Some(Self {
+            uid: Mid::new(u64_at(user, 0)).ok(),
+            uname: str_at(user, 1).to_string(),
+            text: info
+                .get(1)
+                .and_then(Value::as_str)
+                .ok_or_else(|| BpiError::parse("DANMU_MSG 缺少弹幕内容"))?
+                .to_string(),
+            timestamp_ms: meta.get(4).and_then(Value::as_i64).unwrap_or_default(),
+            is_admin: u64_at(user, 2) == 1,
+            user_level: info
+                .get(4)
+                .and_then(Value::as_array)
+                .map(|level| u64_at(level, 0) as u32)
+                .unwrap_or_default(),
+            guard_level: info.get(7).and_then(Value::as_u64).unwrap_or_default() as u32,
+            medal,
+        }))
+    }
+
+    /// 当前佩戴的、属于指定主播的勋章等级；未佩戴或佩戴其他主播的勋章时为 `None`。
+    pub fn medal_level_for(&self, anchor: Mid) -> Option<u32> {
+        self.medal
+            .as_ref()
+            .filter(|medal| medal.anchor_uid.is_none_or(|uid| uid == anchor))
+            .map(|medal| medal.level)
+    }
+}
+
+/// 盲盒礼物的原始盲盒信息
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiveBlindGiftInfo {
+    /// 盲盒礼物 id
+    pub original_gift_id: i64,
+    /// 盲盒名字
+    #[serde(default)]
+    pub original_gift_name: String,
+    /// 盲盒单价，单位金瓜子（1000 金瓜子为 1 元）
+    #[serde(default)]
+    pub original_gift_price: i64,
+}
+
+/// 礼物事件（`SEND_GIFT`）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiveGiftEvent {
+    /// 赠送者 mid
+    #[serde(default, deserialize_with = "deserialize_optional_mid")]
+    pub uid: Option<Mid>,
+    /// 赠送者昵称
+    #[serde(default)]
+    pub uname: String,
+    /// 礼物 id，盲盒为爆出的礼物 id
+    #[serde(rename = "giftId")]
+    pub gift_id: i64,
+    /// 礼物名字
+    #[serde(rename = "giftName", default)]
+    pub gift_name: String,
+    /// 数量
+    pub num: u32,
+    /// 单价，金瓜子礼物单位为金瓜子，银瓜子礼物单位为银瓜子；盲盒为爆出礼物的价格
+    #[serde(default)]
+    pub price: i64,
+    /// 货币类型 gold / silver
+    #[serde(default)]
+    pub coin_type: String,
+    /// 发送时间戳，单位秒
+    #[serde(default)]
+    pub timestamp: i64,
+    /// 盲盒信息，非盲盒礼物为 `None`
+    #[serde(default)]
+    pub blind_gift: Option<LiveBlindGiftInfo>,
+}
+
+/// 醒目留言事件（`SUPER_CHAT_MESSAGE`）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiveSuperChatEvent {
+    /// 醒目留言 id
+    pub id: i64,
+    /// 发送者 mid
+    #[serde(default, deserialize_with = "deserialize_optional_mid")]
+    pub uid: Option<Mid>,
+    /// 发送者昵称，来自 `user_info.uname`
+    #[serde(default)]
+    pub uname: String,
+    /// 价格，单位元
+    pub price: i64,
+    /// 留言内容
+    #[serde(default)]
+    pub message: String,
+    /// 开始展示时间戳，单位秒
+    #[serde(default)]
+    pub start_time: i64,
+}
+
+/// 上舰事件（`GUARD_BUY`）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiveGuardBuyEvent {
+    /// 购买者 mid
+    #[serde(default, deserialize_with = "deserialize_optional_mid")]
+    pub uid: Option<Mid>,
+    /// 购买者昵称
+    #[serde(default, alias = "username")]
+    pub uname: String,
+    /// 大航海等级 1: 总督 2: 提督 3: 舰长
+    pub guard_level: u32,
+    /// 购买月数
+    pub num: u32,
+    /// 单价，单位金瓜子
+    pub price: i64,
+    /// 礼物 id
+    #[serde(default)]
+    pub gift_id: i64,
+    /// 礼物名字
+    #[serde(default)]
+    pub gift_name: String,
+    /// 开始时间戳，单位秒
+    #[serde(default)]
+    pub start_time: i64,
+}
+
+/// 直播间营收事件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LiveRevenueEvent {
+    Gift(LiveGiftEvent),
+    SuperChat(LiveSuperChatEvent),
+    Guard(LiveGuardBuyEvent),
+}
+
+impl LiveRevenueEvent {
+    /// 解析信息流中的一条命令，非营收命令返回 `None`。
+    pub fn from_command(command: &Value) -> BpiResult<Option<Self>> {
+        let Some(name) = command_name(command) else {
+            return Ok(None);
+        };
+        if ![SEND_GIFT_CMD, SUPER_CHAT_CMD, GUARD_BUY_CMD].contains(&name) {
+            return Ok(None);
+        }
+
+        let data = command
+            .get("data")
+            .cloned()
+            .ok_or_else(|| BpiError::parse(format!("{name} 缺少 data")))?;
+
+        Ok(Some(match name {
+            SEND_GIFT_CMD => Self::Gift(serde_json::from_value(data)?),
+            SUPER_CHAT_CMD => {
+                let uname = data
+                    .pointer("/user_info/uname")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                let mut event: LiveSuperChatEvent = serde_json::from_value(data)?;
+                event.uname = uname;
+                Self::SuperChat(event)
+            }
+            _ => Self::Guard(serde_json::from_value(data)?),
+        }))
+    }
+}
+
+/// 命令名，去掉 `DANMU_MSG:4:0:2:2:2:0` 这类后缀。
+pub(crate) fn command_name(command: &Value) -> Option<&str> {
+    command
+        .get("cmd")
+        .and_then(Value::as_str)
+        .and_then(|cmd| cmd.split(':').next())
+}
+
+/// 信息流中 uid 为 0 表示已隐去。
+fn deserialize_optional_mid<'de, D>(deserializer: D) -> Result<Option<Mid>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<u64>::deserialize(deserializer)?.and_then(|uid| Mid::new(uid).ok()))
+}
+
+fn array_at(info: &[Value], index: usize) -> BpiResult<&[Value]> {
+    info.get(index)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .ok_or_else(|| BpiError::parse(format!("DANMU_MSG info[{index}] 不是数组")))
+}
+
+fn u64_at(values: &[Value], index: usize) -> u64 {
+    values
+        .get(index)
+        .and_then(Value::as_u64)
+        .unwrap_or_default()
+}
+
+fn str_at(values: &[Value], index: usize) -> &str {
+    values
+        .get(index)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn danmu_msg(cmd: &str, medal: Value) -> Value {
+        serde_json::json!({
+            "cmd": cmd,
+            "info": [
+                [0, 1, 25, 16777215, 1700000000123_i64, 1700000000, 0, "abcd1234", 0, 0, 0, "", 0, "{}", "{}"],
+                "<redacted-text>",
+                [1000002, "<redacted-user>", 1, 0, 0, 10000, 1, ""],
+                medal,
+                [12, 0, 6406234, ">50000"],
+                ["", ""],
+                0,
+                3,
+                null
+            ]
+        })
+    }
+
+    #[test]
+    fn danmu_msg_parses_sender_text_and_medal() -> BpiResult<()> {
+        let event = LiveDanmakuEvent::from_command(&danmu_msg(
+            "DANMU_MSG:4:0:2:2:2:0",
+            serde_json::json!([
+                21,
+                "<redacted-medal>",
+                "<redacted-anchor>",
+                3818081,
+                1725515,
+                "",
+                0,
+                6809855,
+                1725515,
+                5414290,
+                3,
+                1,
+                1000001
+            ]),
+        ))?
+        .expect("DANMU_MSG should parse as danmaku");
+
+        assert_eq!(event.uid, Some(Mid::new(1_000_002)?));
+        assert_eq!(event.uname, "<redacted-user>");
+        assert_eq!(event.text, "<redacted-text>");
+        assert_eq!(event.timestamp_ms, 1_700_000_000_123);
+        assert!(event.is_admin);
+        assert_eq!(event.user_level, 12);
+        assert_eq!(event.guard_level, 3);
+        assert_eq!(event.medal_level_for(Mid::new(1_000_001)?), Some(21));
+        assert_eq!(event.medal_level_for(Mid::new(1_000_009)?), None);
+        Ok(())
+    }
+
+    #[test]
+    fn danmu_msg_without_medal_or_uid_and_other_commands() -> BpiResult<()> {
+        let mut command = danmu_msg("DANMU_MSG", serde_json::json!([]));
+        command["info"][2][0] = serde_json::json!(0);
+        let event = LiveDanmakuEvent::from_command(&command)?.expect("danmaku");
+
+        assert_eq!(event.uid, None);
+        assert_eq!(event.medal, None);
+        assert!(
+            LiveDanmakuEvent::from_command(&serde_json::json!({"cmd": "SEND_GIFT"}))?.is_none()
+        );
+        assert!(LiveDanmakuEvent::from_command(&serde_json::json!({"cmd": "DANMU_MSG"})).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn revenue_commands_parse_gift_super_chat_and_guard() -> BpiResult<()> {
+        let gift = LiveRevenueEvent::from_command(&serde_json::json!({
+            "cmd": "SEND_GIFT",
+            "data": {
+                "uid": 1000002,
+                "uname": "<redacted-user>",
+                "giftId": 32132,
+                "giftName": "<redacted-gift>",
+                "num": 2,
+                "price": 12000,
+                "coin_type": "gold",
+                "total_coin": 10000,
+                "timestamp": 1700000000,
+                "blind_gift": {
+                    "original_gift_id": 32251,
+                    "original_gift_name": "<redacted-box>",
+                    "original_gift_price": 10000,
+                    "gift_action": "爆出"
+                }
+            }
+        }))?;
+        let Some(LiveRevenueEvent::Gift(gift)) = gift else {
+            panic!("SEND_GIFT should parse as gift");
+        };
+        assert_eq!(gift.uid, Some(Mid::new(1_000_002)?));
+        assert_eq!(gift.num, 2);
+        assert_eq!(
+            gift.blind_gift.map(|blind| blind.original_gift_price),
+            Some(10000)
+        );
+
+        let super_chat = LiveRevenueEvent::from_command(&serde_json::json!({
+            "cmd": "SUPER_CHAT_MESSAGE",
+            "data": {
+                "id": 9001,
+                "uid": 1000003,
+                "price": 30,
+                "message": "<redacted-text>",
+                "start_time": 1700000100,
+                "user_info": { "uname": "<redacted-user>" }
+            }
+        }))?;
+        let Some(LiveRevenueEvent::SuperChat(super_chat)) = super_chat else {
+            panic!("SUPER_CHAT_MESSAGE should parse as super chat");
+        };
+        assert_eq!(super_chat.price, 30);
+        assert_eq!(super_chat.uname, "<redacted-user>");
+
+        let guard = LiveRevenueEvent::from_command(&serde_json::json!({
+            "cmd": "GUARD_BUY",
+            "data": {
+                "uid": 0,
+                "username": "<redacted-user>",
+                "guard_level": 3,
+                "num": 1,
+                "price": 198000,
+                "gift_id": 10003,
+                "gift_name": "舰长",
+                "start_time": 1700000200
+            }
+        }))?;
+        let Some(LiveRevenueEvent::Guard(guard)) = guard else {
+            panic!("GUARD_BUY should parse as guard");
+        };
+        assert_eq!(guard.uid, None);
+        assert_eq!(guard.uname, "<redacted-user>");
+        assert!(
+            LiveRevenueEvent::from_command(&serde_json::json!({"cmd": "DANMU_MSG"}))?.is_none()
+        );
+        Ok(())
+    }
+}
@@ -1,22 +1,61 @@
 //! 直播
 
+pub mod area_rooms;
+pub mod broadcast;
 pub mod client;
 pub mod danmaku;
+pub mod danmaku_sender;
 pub mod emoticons;
 pub mod follow_up_live;
 pub mod gift;
 pub mod guard;
 pub mod info;
+pub mod ledger;
 pub mod live_area;
 pub mod live_bill;
 pub mod live_replay;
 pub mod live_stream;
+pub mod lottery;
 pub mod manage;
 pub mod message_stream;
+pub mod moderator;
 pub mod recommend;
+pub mod recorder;
 pub mod redpocket;
 pub mod report;
+pub mod room_init;
 pub mod silent_user_manage;
 pub mod user;
+pub mod watch;
 
+pub use area_rooms::{
+    LiveAreaCrawler, LiveAreaPageFailure, LiveAreaRoom, LiveAreaRoomRecord, LiveAreaRoomsData,
+    LiveAreaRoomsParams, LiveAreaSnapshot, LiveAreaSort, LiveAreaSummary,
+};
+pub use broadcast::{BroadcastError, BroadcastParams, BroadcastResult, BroadcastSession};
 pub use client::LiveClient;
+pub use danmaku::{LiveDanmuContent, LiveDanmuError, LiveDanmuMode, LiveDanmuParams};
+pub use danmaku_sender::{LiveDanmakuFlush, LiveDanmakuSender};
+pub use ledger::{
+    GiftLedger, GiftLedgerReport, LedgerEntry, LedgerEntryKind, RevenueSummary, UserRevenue,
+};
+pub use live_replay::{LiveReplay, ReplayDanmaku};
+pub use live_stream::{
+    LiveCodec, LiveFormat, LivePlayInfoData, LivePlayInfoParams, LiveProtocol, LiveStreamCandidate,
+    LiveStreamKind,
+};
+pub use lottery::{
+    AnchorJoinData, LotteryDraw, LotteryError, LotteryEvent, LotteryJoinPolicy, LotteryKind,
+    LotteryMonitor, LotteryRequirement, LotteryResult, LotteryWinner, RedPocketJoinData,
+};
+pub use message_stream::{
+    LiveBlindGiftInfo, LiveDanmakuEvent, LiveDanmakuMedal, LiveGiftEvent, LiveGuardBuyEvent,
+    LiveRevenueEvent, LiveSuperChatEvent,
+};
+pub use moderator::{
+    ModerationAction, ModerationOutcome, ModerationReason, ModerationRecord, ModerationRules,
+    RoomModerator,
+};
+pub use recorder::{LiveRecorder, LiveRecording, LiveRecordingMeta};
+pub use room_init::{LiveRoom, RoomInitData};
+pub use watch::{LiveMedalProgress, LiveWatchHandle, LiveWatchSession, LiveWatchStats};
@@ -0,0 +1,685 @@
+//! 直播间自动管理
+//!
+//! [`RoomModerator`] 按 [`ModerationRules`] 检查弹幕事件：关键词正则、单个用户在时间窗口内的发言频率、
+//! 本直播间粉丝勋章等级下限。命中后通过已有的禁言、拉黑接口处理，同一用户在冷却时间内不会重复处理，
+//! 每次处理都会写入审计日志。`dry_run` 模式下只记录审计日志，不调用接口。
+//!
+//! 弹幕来源由调用方提供，可以是任何产出 [`LiveDanmakuEvent`] 的流，例如自行维护的信息流连接。
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use futures_util::{Stream, StreamExt};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::ids::{Mid, RoomId};
+use crate::live::LiveClient;
+use crate::live::message_stream::LiveDanmakuEvent;
+use crate::live::room_init::LiveRoom;
+use crate::live::silent_user_manage::{LiveBannedUserListParams, LiveSilentUserListParams};
+use crate::{BpiError, BpiResult};
+
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(60);
+const EXISTING_PAGE_SIZE: u32 = 50;
+const EXISTING_MAX_PAGES: u32 = 20;
+const PRUNE_EVERY: u64 = 1024;
+
+/// 命中规则后执行的处理。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ModerationAction {
+    /// 禁言，`hour` 为 -1 表示永久，0 表示本场直播，其余为小时数
+    Silence { hour: i32 },
+    /// 拉黑
+    Ban,
+}
+
+impl ModerationAction {
+    pub fn silence(hour: i32) -> BpiResult<Self> {
+        if hour < -1 {
+            return Err(BpiError::invalid_parameter(
+                "hour",
+                "hour must be -1, 0 or positive",
+            ));
+        }
+
+        Ok(Self::Silence { hour })
+    }
+
+    /// 处理后该用户是否不会再发言，这类用户不需要等冷却结束后再次处理。
+    fn is_permanent(&self) -> bool {
+        matches!(self, Self::Ban | Self::Silence { hour: -1 })
+    }
+}
+
+/// 命中的规则。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ModerationReason {
+    /// 弹幕匹配关键词正则
+    Keyword { pattern: String },
+    /// 时间窗口内发言次数超过上限
+    Spam { count: u32, window_secs: u64 },
+    /// 未佩戴本直播间勋章或勋章等级不足
+    MedalLevel { level: Option<u32>, required: u32 },
+}
+
+#[derive(Debug, Clone)]
+struct KeywordRule {
+    pattern: Regex,
+    action: ModerationAction,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SpamRule {
+    max_messages: u32,
+    window: Duration,
+    action: ModerationAction,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct MedalRule {
+    min_level: u32,
+    action: ModerationAction,
+}
+
+/// 自动管理规则。
+///
+/// 检查顺序为关键词、勋章等级、发言频率，命中第一条即停止。房管、主播和豁免用户不参与检查。
+#[derive(Debug, Clone)]
+pub struct ModerationRules {
+    keywords: Vec<KeywordRule>,
+    spam: Option<SpamRule>,
+    medal: Option<MedalRule>,
+    cooldown: Duration,
+    exempt: HashSet<Mid>,
+}
+
+impl Default for ModerationRules {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ModerationRules {
+    pub fn new() -> Self {
+        Self {
+            keywords: Vec::new(),
+            spam: None,
+            medal: None,
+            cooldown: DEFAULT_COOLDOWN,
+            exempt: HashSet::new(),
+        }
+    }
+
+    /// 添加关键词正则，可以多次调用。
+    pub fn with_keyword(mut self, pattern: &str, action: ModerationAction) -> BpiResult<Self> {
+        let pattern = Regex::new(pattern)
+            .map_err(|_| BpiError::invalid_parameter("keyword", "keyword must be a valid regex"))?;
+        self.keywords.push(KeywordRule { pattern, action });
+        Ok(self)
+    }
+
+    /// 同一用户在 `window` 内发言超过 `max_messages` 条时处理。
+    pub fn with_spam_limit(
+        mut self,
+        max_messages: u32,
+        window: Duration,
+        action: ModerationAction,
+    ) -> BpiResult<Self> {
+        if max_messages == 0 {
+            return Err(BpiError::invalid_parameter(
+                "max_messages",
+                "value must be non-zero",
+            ));
+        }
+        if window.is_zero() {
+            return Err(BpiError::invalid_parameter(
+                "window",
+                "window must be non-zero",
+            ));
+        }
+
+        self.spam = Some(SpamRule {
+            max_messages,
+            window,
+            action,
+        });
+        Ok(self)
+    }
+
+    /// 未佩戴本直播间勋章或勋章等级低于 `min_level` 时处理。
+    pub fn with_min_medal_level(
+        mut self,
+        min_level: u32,
+        action: ModerationAction,
+    ) -> BpiResult<Self> {
+        if min_level == 0 {
+            return Err(BpiError::invalid_parameter(
+                "min_level",
+                "value must be non-zero",
+            ));
+        }
+
+        self.medal = Some(MedalRule { min_level, action });
+        Ok(self)
+    }
+
+    /// 同一用户两次处理之间的最短间隔，默认 60 秒。
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// 豁免用户，可以多次调用。
+    pub fn with_exempt(mut self, uid: Mid) -> Self {
+        self.exempt.insert(uid);
+        self
+    }
+}
+
+/// 处理结果。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ModerationOutcome {
+    /// dry-run 模式，未调用接口
+    DryRun,
+    /// 已调用接口处理
+    Applied,
+    /// 调用接口失败
+    Failed { error: String },
+}
+
+/// 审计日志条目。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModerationRecord {
+    pub at: DateTime<Local>,
+    pub room_id: RoomId,
+    pub uid: Mid,
+    pub uname: String,
+    /// 触发处理的弹幕
+    pub text: String,
+    pub reason: ModerationReason,
+    pub action: ModerationAction,
+    pub outcome: ModerationOutcome,
+}
+
+/// 直播间自动管理器。
+pub struct RoomModerator<'a> {
+    client: LiveClient<'a>,
+    room: LiveRoom,
+    rules: ModerationRules,
+    dry_run: bool,
+    messages: HashMap<Mid, VecDeque<Instant>>,
+    cooldowns: HashMap<Mid, Instant>,
+    handled: HashSet<Mid>,
+    seen: u64,
+    audit: Vec<ModerationRecord>,
+}
+
+impl<'a> RoomModerator<'a> {
+    fn new(client: LiveClient<'a>, room: LiveRoom, rules: ModerationRules) -> Self {
+        Self {
+            client,
+            room,
+            rules,
+            dry_run: false,
+            messages: HashMap::new(),
+            cooldowns: HashMap::new(),
+            handled: HashSet::new(),
+            seen: 0,
+            audit: Vec::new(),
+        }
+    }
+
+    /// 只记录审计日志，不调用禁言、拉黑接口。
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn room(&self) -> LiveRoom {
+        self.room
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    pub fn audit_log(&self) -> &[ModerationRecord] {
+        &self.audit
+    }
+
+    /// 取出并清空审计日志。
+    pub fn take_audit_log(&mut self) -> Vec<ModerationRecord> {
+        std::mem::take(&mut self.audit)
+    }
+
+    /// 读取直播间已有的禁言和拉黑列表，列表中的用户不会再被处理，返回读取到的用户数。
+    ///
+    /// 需要当前账号为主播或房管。
+    pub async fn load_existing(&mut self) -> BpiResult<usize> {
+        let before = self.handled.len();
+
+        for page in 1..=EXISTING_MAX_PAGES {
+            let data = self
+                .client
+                .silent_users(
+                    LiveSilentUserListParams::new(self.room.room_id)
+                        .page(page)?
+                        .page_size(EXISTING_PAGE_SIZE)?,
+                )
+                .await?;
+            self.handled
+                .extend(data.data.iter().filter_map(|user| mid(user.tuid)));
+            if data.data.is_empty() || page >= data.total_page.max(1) as u32 {
+                break;
+            }
+        }
+
+        let mut fetched = 0;
+        for page in 1..=EXISTING_MAX_PAGES {
+            let data = self
+                .client
+                .banned_users(
+                    LiveBannedUserListParams::new(self.room.uid)
+                        .page(page)?
+                        .page_size(EXISTING_PAGE_SIZE)?,
+                )
+                .await?;
+            fetched += data.data.len();
+            self.handled
+                .extend(data.data.iter().filter_map(|user| mid(user.uid)));
+            if data.data.is_empty() || fetched >= data.total.max(0) as usize {
+                break;
+            }
+        }
+
+        Ok(self.handled.len() - before)
+    }
+
+    /// 处理一条弹幕，命中规则并且不在冷却中时返回写入审计日志的记录。
+    ///
+    /// 接口调用失败不会中断处理，失败原因记录在 [`ModerationOutcome::Failed`] 中。
+    pub async fn handle(&mut self, event: &LiveDanmakuEvent) -> Option<ModerationRecord> {
+        let (uid, reason, action) = self.evaluate(event, Instant::now())?;
+
+        let outcome = if self.dry_run {
+            ModerationOutcome::DryRun
+        } else {
+            let result = match action {
+                ModerationAction::Silence { hour } => {
+                    self.client
+                        .live_add_silent_user(
+                            self.room.room_id,
+                            uid,
+                            hour,
+                            Some(event.text.clone()),
+                        )
+                        .await
+                }
+                ModerationAction::Ban => {
+                    self.client
+                        .live_add_banned_user(self.room.room_id, uid)
+                        .await
+                }
+            };
+            match result {
+                Ok(_) => {
+                    if action.is_permanent() {
+                        self.handled.insert(uid);
+                    }
+                    ModerationOutcome::Applied
+                }
+                Err(err) => {
+                    tracing::warn!("直播间 {} 处理用户 {uid} 失败: {err}", self.room.room_id);
+                    ModerationOutcome::Failed {
+                        error: err.to_string(),
+                    }
+                }
+            }
+        };
+
+        let record = ModerationRecord {
+            at: Local::now(),
+            room_id: self.room.room_id,
+            uid,
+            uname: event.uname.clone(),
+            text: event.text.clone(),
+            reason,
+            action,
+            outcome,
+        };
+        self.audit.push(record.clone());
+        Some(record)
+    }
+
+    /// 持续处理弹幕流直到流结束，来源返回错误时停止并返回该错误。返回本次写入审计日志的条数。
+    pub async fn run<S>(&mut self, events: S) -> BpiResult<usize>
+    where
+        S: Stream<Item = BpiResult<LiveDanmakuEvent>>,
+    {
+        let mut events = std::pin::pin!(events);
+        let mut records = 0;
+
+        while let Some(event) = events.next().await {
+            if self.handle(&event?).await.is_some() {
+                records += 1;
+            }
+        }
+
+        Ok(records)
+    }
+
+    /// 检查规则并更新发言记录和冷却，不调用接口。
+    fn evaluate(
+        &mut self,
+        event: &LiveDanmakuEvent,
+        now: Instant,
+    ) -> Option<(Mid, ModerationReason, ModerationAction)> {
+        let uid = event.uid?;
+        if event.is_admin
+            || uid == self.room.uid
+            || self.rules.exempt.contains(&uid)
+            || self.handled.contains(&uid)
+        {
+            return None;
+        }
+
+        let count = self.record_message(uid, now);
+        let (reason, action) = self.matched_rule(event, count)?;
+
+        if self
+            .cooldowns
+            .get(&uid)
+            .is_some_and(|last| now.duration_since(*last) < self.rules.cooldown)
+        {
+            return None;
+        }
+        self.cooldowns.insert(uid, now);
+        self.messages.remove(&uid);
+
+        Some((uid, reason, action))
+    }
+
+    fn matched_rule(
+        &self,
+        event: &LiveDanmakuEvent,
+        count: u32,
+    ) -> Option<(ModerationReason, ModerationAction)> {
+        if let Some(rule) = self
+            .rules
+            .keywords
+            .iter()
+            .find(|rule| rule.pattern.is_match(&event.text))
+        {
+            return Some((
+                ModerationReason::Keyword {
+                    pattern: rule.pattern.as_str().to_string(),
+                },
+                rule.action,
+            ));
+        }
+
+        if let Some(rule) = self.rules.medal {
+            let level = event.medal_level_for(self.room.uid);
+            if level.is_none_or(|level| level < rule.min_level) {
+                return Some((
+                    ModerationReason::MedalLevel {
+                        level,
+                        required: rule.min_level,
+                    },
+                    rule.action,
+                ));
+            }
+        }
+
+        if let Some(rule) = self.rules.spam
+            && count > rule.max_messages
+        {
+            return Some((
+                ModerationReason::Spam {
+                    count,
+                    window_secs: rule.window.as_secs(),
+                },
+                rule.action,
+            ));
+        }
+
+        None
+    }
+
+    /// 记录一次发言，返回窗口内的发言次数。
+    fn record_message(&mut self, uid: Mid, now: Instant) -> u32 {
+        let Some(rule) = self.rules.spam else {
+            return 0;
+        };
+
+        self.seen += 1;
+        if self.seen % PRUNE_EVERY == 0 {
+            self.messages.retain(|_, times| {
+                times
+                    .back()
+                    .is_some_and(|last| now.duration_since(*last) < rule.window)
+            });
+        }
+
+        let times = self.messages.entry(uid).or_default();
+        times.push_back(now);
+        while times
+            .front()
+            .is_some_and(|first| now.duration_since(*first) >= rule.window)
+        {
+            times.pop_front();
+        }
+        times.len() as u32
+    }
+}
+
+fn mid(uid: i64) -> Option<Mid> {
+    u64::try_from(uid).ok().and_then(|uid| Mid::new(uid).ok())
+}
+
+impl<'a> LiveClient<'a> {
+    /// 创建直播间自动管理器，短号会先解析为真实房间号。
+    pub async fn moderator(
+        &self,
+        room_id: RoomId,
+        rules: ModerationRules,
+    ) -> BpiResult<RoomModerator<'a>> {
+        let room = self.resolve_room(room_id).await?;
+        Ok(RoomModerator::new(*self, room, rules))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BpiClient;
+    use crate::live::message_stream::LiveDanmakuMedal;
+
+    fn room() -> LiveRoom {
+        LiveRoom {
+            room_id: RoomId::new(3_818_081).expect("room id"),
+            short_id: None,
+            uid: Mid::new(1_000_001).expect("anchor"),
+        }
+    }
+
+    fn event(uid: u64, text: &str, medal_level: Option<u32>) -> LiveDanmakuEvent {
+        LiveDanmakuEvent {
+            uid: Mid::new(uid).ok(),
+            uname: "<redacted-user>".to_string(),
+            text: text.to_string(),
+            timestamp_ms: 0,
+            is_admin: false,
+            user_level: 0,
+            guard_level: 0,
+            medal: medal_level.map(|level| LiveDanmakuMedal {
+                level,
+                name: "<redacted-medal>".to_string(),
+                room_id: None,
+                anchor_uid: Some(room().uid),
+            }),
+        }
+    }
+
+    #[test]
+    fn moderation_rules_reject_invalid_values() {
+        assert!(matches!(
+            ModerationRules::new()
+                .with_keyword("(", ModerationAction::Ban)
+                .unwrap_err(),
+            BpiError::InvalidParameter {
+                field: "keyword",
+                ..
+            }
+        ));
+        assert!(
+            ModerationRules::new()
+                .with_spam_limit(0, Duration::from_secs(10), ModerationAction::Ban)
+                .is_err()
+        );
+        assert!(ModerationAction::silence(-2).is_err());
+    }
+
+    #[test]
+    fn moderator_applies_keyword_medal_and_spam_rules_with_cooldown() -> BpiResult<()> {
+        let client = BpiClient::new()?;
+        let rules = ModerationRules::new()
+            .with_keyword("加群|vx", ModerationAction::Ban)?
+            .with_min_medal_level(3, ModerationAction::silence(0)?)?
+            .with_spam_limit(2, Duration::from_secs(10), ModerationAction::silence(1)?)?
+            .with_cooldown(Duration::from_secs(30))
+            .with_exempt(Mid::new(1_000_009)?);
+        let mut moderator = RoomModerator::new(client.live(), room(), rules);
+        let start = Instant::now();
+
+        let (_, reason, action) = moderator
+            .evaluate(&event(1_000_002, "加群领福利", Some(10)), start)
+            .expect("keyword should match");
+        assert_eq!(
+            reason,
+            ModerationReason::Keyword {
+                pattern: "加群|vx".to_string()
+            }
+        );
+        assert_eq!(action, ModerationAction::Ban);
+
+        let (_, reason, _) = moderator
+            .evaluate(&event(1_000_003, "hello", Some(1)), start)
+            .expect("medal level should match");
+        assert_eq!(
+            reason,
+            ModerationReason::MedalLevel {
+                level: Some(1),
+                required: 3
+            }
+        );
+        assert!(
+            moderator
+                .evaluate(
+                    &event(1_000_003, "hello", None),
+                    start + Duration::from_secs(5)
+                )
+                .is_none()
+        );
+        assert!(
+            moderator
+                .evaluate(
+                    &event(1_000_003, "hello", None),
+                    start + Duration::from_secs(31)
+                )
+                .is_some()
+        );
+
+        for offset in 0..2 {
+            assert!(
+                moderator
+                    .evaluate(
+                        &event(1_000_004, "hi", Some(5)),
+                        start + Duration::from_secs(offset)
+                    )
+                    .is_none()
+            );
+        }
+        let (_, reason, action) = moderator
+            .evaluate(
+                &event(1_000_004, "hi", Some(5)),
+                start + Duration::from_secs(2),
+            )
+            .expect("third message in window should be spam");
+        assert_eq!(
+            reason,
+            ModerationReason::Spam {
+                count: 3,
+                window_secs: 10
+            }
+        );
+        assert_eq!(action, ModerationAction::Silence { hour: 1 });
+
+        assert!(
+            moderator
+                .evaluate(&event(1_000_009, "加群", None), start)
+                .is_none()
+        );
+        assert!(
+            moderator
+                .evaluate(&event(1_000_001, "加群", None), start)
+                .is_none()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn spam_window_slides() -> BpiResult<()> {
+        let client = BpiClient::new()?;
+        let rules = ModerationRules::new().with_spam_limit(
+            1,
+            Duration::from_secs(5),
+            ModerationAction::Ban,
+        )?;
+        let mut moderator = RoomModerator::new(client.live(), room(), rules);
+        let start = Instant::now();
+
+        assert!(
+            moderator
+                .evaluate(&event(1_000_002, "a", None), start)
+                .is_none()
+        );
+        assert!(
+            moderator
+                .evaluate(&event(1_000_002, "b", None), start + Duration::from_secs(6))
+                .is_none()
+        );
+        assert!(
+            moderator
+                .evaluate(&event(1_000_002, "c", None), start + Duration::from_secs(7))
+                .is_some()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn dry_run_records_audit_without_calling_api() -> Result<(), Box<dyn std::error::Error>> {
+        let client = BpiClient::new()?;
+        let rules = ModerationRules::new().with_keyword("广告", ModerationAction::Ban)?;
+        let mut moderator = RoomModerator::new(client.live(), room(), rules).with_dry_run(true);
+        let events = futures_util::stream::iter(vec![
+            Ok(event(1_000_002, "正常弹幕", None)),
+            Ok(event(1_000_002, "广告", None)),
+        ]);
+
+        let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+
+        assert_eq!(runtime.block_on(moderator.run(events))?, 1);
+        let audit = moderator.take_audit_log();
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].outcome, ModerationOutcome::DryRun);
+        assert_eq!(audit[0].uid, Mid::new(1_000_002)?);
+        assert!(moderator.audit_log().is_empty());
+        Ok(())
+    }
+}
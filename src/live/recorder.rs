@@ -0,0 +1,1005 @@
+//! 直播录制
+//!
+//! [`LiveRecorder`] 轮询直播间状态，开播后通过 `getRoomPlayInfo` 选择直播流并写入本地文件。
+//! FLV 在关键帧处切分，每个分段都会重新写入文件头和编码参数；HLS 按切片切分，fMP4 会在每个分段开头写入初始化切片。
+//! 每个分段旁边会写入同名 `.json` 文件，记录标题、分区和开始时间。
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::time::Instant;
+
+use crate::ids::RoomId;
+use crate::live::LiveClient;
+use crate::live::info::RoomInfoData;
+use crate::live::live_replay::{LiveReplay, ReplayInfo};
+use crate::live::live_stream::{
+    LiveFormat, LivePlayInfoParams, LiveProtocol, LiveStreamCandidate, LiveStreamKind,
+};
+use crate::{BilibiliRequest, BpiError, BpiResult};
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(30);
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(3);
+const DEFAULT_STALL_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_RETRIES: u32 = 5;
+
+/// 直播录制器。
+pub struct LiveRecorder<'a> {
+    client: LiveClient<'a>,
+    room_id: RoomId,
+    output_dir: PathBuf,
+    preferences: Vec<LiveStreamKind>,
+    qn: u32,
+    segment_duration: Option<Duration>,
+    segment_size: Option<u64>,
+    poll_interval: Duration,
+    retry_delay: Duration,
+    stall_timeout: Duration,
+    max_retries: u32,
+    started: bool,
+}
+
+impl<'a> LiveRecorder<'a> {
+    /// 录制到 `output_dir`，默认优先 FLV，其次 HLS fMP4、HLS TS，画质为原画，不切分文件。
+    pub fn new(client: LiveClient<'a>, room_id: RoomId, output_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            client,
+            room_id,
+            output_dir: output_dir.into(),
+            preferences: vec![
+                LiveStreamKind::flv(),
+                LiveStreamKind::hls_fmp4(),
+                LiveStreamKind::hls_ts(),
+            ],
+            qn: 10000,
+            segment_duration: None,
+            segment_size: None,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            retry_delay: DEFAULT_RETRY_DELAY,
+            stall_timeout: DEFAULT_STALL_TIMEOUT,
+            max_retries: DEFAULT_MAX_RETRIES,
+            started: false,
+        }
+    }
+
+    /// 直播流偏好，按顺序选择第一个可用的组合。
+    pub fn with_preferences(mut self, preferences: &[LiveStreamKind]) -> BpiResult<Self> {
+        if preferences.is_empty() {
+            return Err(BpiError::invalid_parameter(
+                "preferences",
+                "at least one stream kind must be provided",
+            ));
+        }
+        self.preferences = preferences.to_vec();
+        Ok(self)
+    }
+
+    pub fn with_qn(mut self, qn: u32) -> BpiResult<Self> {
+        if qn == 0 {
+            return Err(BpiError::invalid_parameter("qn", "qn must be positive"));
+        }
+        self.qn = qn;
+        Ok(self)
+    }
+
+    /// 每个分段的最长时长。
+    pub fn with_segment_duration(mut self, duration: Duration) -> BpiResult<Self> {
+        if duration.is_zero() {
+            return Err(BpiError::invalid_parameter(
+                "segment_duration",
+                "duration must be positive",
+            ));
+        }
+        self.segment_duration = Some(duration);
+        Ok(self)
+    }
+
+    /// 每个分段的最大字节数，分段在达到上限后的下一个关键帧或切片处切分。
+    pub fn with_segment_size(mut self, bytes: u64) -> BpiResult<Self> {
+        if bytes == 0 {
+            return Err(BpiError::invalid_parameter(
+                "segment_size",
+                "size must be positive",
+            ));
+        }
+        self.segment_size = Some(bytes);
+        Ok(self)
+    }
+
+    /// 未开播时查询直播状态的间隔。
+    pub fn with_poll_interval(mut self, interval: Duration) -> BpiResult<Self> {
+        if interval.is_zero() {
+            return Err(BpiError::invalid_parameter(
+                "poll_interval",
+                "interval must be positive",
+            ));
+        }
+        self.poll_interval = interval;
+        Ok(self)
+    }
+
+    /// 所有 CDN 节点都失败后，重新获取直播流的最大连续次数。
+    pub fn with_max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// 直播流超过该时长没有数据时切换到下一个 CDN 节点。
+    pub fn with_stall_timeout(mut self, timeout: Duration) -> BpiResult<Self> {
+        if timeout.is_zero() {
+            return Err(BpiError::invalid_parameter(
+                "stall_timeout",
+                "timeout must be positive",
+            ));
+        }
+        self.stall_timeout = timeout;
+        Ok(self)
+    }
+
+    pub fn room_id(&self) -> RoomId {
+        self.room_id
+    }
+
+    /// 等待开播，首次调用立即查询，之后每次查询前等待轮询间隔。
+    pub async fn wait_live(&mut self) -> BpiResult<RoomInfoData> {
+        loop {
+            if self.started {
+                tokio::time::sleep(self.poll_interval).await;
+            }
+            self.started = true;
+
+            let info = self.room_info().await?;
+            if info.live_status == 1 {
+                return Ok(info);
+            }
+        }
+    }
+
+    /// 等待开播并录制一场直播，下播后返回写入的分段。
+    pub async fn record(&mut self) -> BpiResult<LiveRecording> {
+        let info = self.wait_live().await?;
+        self.record_live(info).await
+    }
+
+    /// 录制正在进行的直播，直到下播。
+    pub async fn record_live(&mut self, info: RoomInfoData) -> BpiResult<LiveRecording> {
+        tokio::fs::create_dir_all(&self.output_dir)
+            .await
+            .map_err(|e| BpiError::parse(format!("创建录制目录失败: {}", e)))?;
+
+        let mut recording = LiveRecording::default();
+        let mut failures = 0;
+        loop {
+            let outcome = match self.select_stream().await {
+                Ok(Some(candidate)) => {
+                    let mut writer = SegmentWriter::new(
+                        &self.output_dir,
+                        LiveRecordingMeta::new(&info, &candidate),
+                        self.segment_duration,
+                        self.segment_size,
+                    );
+                    let outcome = match candidate.kind.protocol {
+                        LiveProtocol::HttpStream => {
+                            self.download_flv(&candidate, &mut writer).await
+                        }
+                        LiveProtocol::HttpHls => self.download_hls(&candidate, &mut writer).await,
+                    };
+                    let closed = writer.close().await;
+                    if writer.received_data() {
+                        failures = 0;
+                    }
+                    recording.segments.extend(writer.into_segments());
+                    closed.and(outcome)
+                }
+                Ok(None) => Err(BpiError::unsupported_response("直播间没有符合偏好的直播流")),
+                Err(err) => Err(err),
+            };
+
+            if let Err(err) = outcome {
+                failures += 1;
+                tracing::warn!(room_id = %self.room_id, failures, error = %err, "直播流中断");
+                if failures > self.max_retries {
+                    return Err(err);
+                }
+            }
+            if self.room_info().await?.live_status != 1 {
+                return Ok(recording);
+            }
+            tokio::time::sleep(self.retry_delay).await;
+        }
+    }
+
+    /// 下载直播回放，按录制器的分段设置写入 `output_dir`，完成后返回写入的分段。
+    pub async fn record_replay(&self, replay: &LiveReplay) -> BpiResult<LiveRecording> {
+        let Some(mut candidate) = replay.candidate() else {
+            return Err(BpiError::unsupported_response("回放没有可用的播放地址"));
+        };
+        tokio::fs::create_dir_all(&self.output_dir)
+            .await
+            .map_err(|e| BpiError::parse(format!("创建录制目录失败: {}", e)))?;
+
+        candidate.kind.format = self.detect_hls_format(&candidate.urls[0]).await?;
+        let room = self.client.resolve_room(replay.info.room_id()?).await?;
+        let mut writer = SegmentWriter::new(
+            &self.output_dir,
+            LiveRecordingMeta::from_replay(&replay.info, room.uid.get(), &candidate),
+            self.segment_duration,
+            self.segment_size,
+        );
+        let outcome = self.download_hls(&candidate, &mut writer).await;
+        let closed = writer.close().await;
+        closed.and(outcome)?;
+
+        Ok(LiveRecording {
+            segments: writer.into_segments(),
+        })
+    }
+
+    /// 有初始化切片的播放列表为 fMP4，否则为 TS。
+    async fn detect_hls_format(&self, url: &str) -> BpiResult<LiveFormat> {
+        let mut playlist = HlsPlaylist::parse(&self.fetch_text(url).await?, url)?;
+        if let Some(variant) = playlist.variant {
+            playlist = HlsPlaylist::parse(&self.fetch_text(&variant).await?, &variant)?;
+        }
+
+        Ok(if playlist.map.is_some() {
+            LiveFormat::Fmp4
+        } else {
+            LiveFormat::Ts
+        })
+    }
+
+    async fn room_info(&self) -> BpiResult<RoomInfoData> {
+        self.client.room_info(self.room_id).await
+    }
+
+    async fn select_stream(&self) -> BpiResult<Option<LiveStreamCandidate>> {
+        let data = self
+            .client
+            .room_play_info(LivePlayInfoParams::new(self.room_id).with_qn(self.qn)?)
+            .await?;
+        Ok(data.select(&self.preferences))
+    }
+
+    /// 依次尝试各 CDN 节点，正常结束时返回 `Ok`。
+    async fn download_flv(
+        &self,
+        candidate: &LiveStreamCandidate,
+        writer: &mut SegmentWriter,
+    ) -> BpiResult<()> {
+        let mut last_error = None;
+        for url in &candidate.urls {
+            match self.download_flv_from(url, writer).await {
+                Ok(()) => return Ok(()),
+                Err(err) => {
+                    tracing::warn!(url = %url, error = %err, "FLV 节点失败，切换下一个节点");
+                    last_error = Some(err);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| BpiError::unsupported_response("没有可用的直播流节点")))
+    }
+
+    async fn download_flv_from(&self, url: &str, writer: &mut SegmentWriter) -> BpiResult<()> {
+        let response = self
+            .client
+            .client
+            .get(url)
+            .with_bilibili_headers()
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(BpiError::http(response.status().as_u16()));
+        }
+
+        // 新连接会重新发送 FLV 头，从新分段开始写入
+        writer.finish_segment().await?;
+        let mut reader = FlvTagReader::default();
+        let mut stream = response.bytes_stream();
+        loop {
+            let chunk = match tokio::time::timeout(self.stall_timeout, stream.next()).await {
+                Ok(Some(chunk)) => chunk?,
+                Ok(None) => return Ok(()),
+                Err(_) => return Err(BpiError::network("直播流长时间没有数据")),
+            };
+            for tag in reader.push(&chunk)? {
+                if tag.keyframe && writer.should_rotate() {
+                    writer.finish_segment().await?;
+                }
+                if !writer.is_open() {
+                    if !reader.is_ready() {
+                        continue;
+                    }
+                    writer.open(&reader.init_bytes()).await?;
+                    if tag.is_init() {
+                        continue;
+                    }
+                }
+                writer.write(&tag.bytes).await?;
+            }
+        }
+    }
+
+    async fn download_hls(
+        &self,
+        candidate: &LiveStreamCandidate,
+        writer: &mut SegmentWriter,
+    ) -> BpiResult<()> {
+        let mut last_error = None;
+        let mut last_sequence = None;
+        for url in &candidate.urls {
+            match self
+                .download_hls_from(url, candidate.kind.format, writer, &mut last_sequence)
+                .await
+            {
+                Ok(()) => return Ok(()),
+                Err(err) => {
+                    tracing::warn!(url = %url, error = %err, "HLS 节点失败，切换下一个节点");
+                    last_error = Some(err);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| BpiError::unsupported_response("没有可用的直播流节点")))
+    }
+
+    async fn download_hls_from(
+        &self,
+        playlist_url: &str,
+        format: LiveFormat,
+        writer: &mut SegmentWriter,
+        last_sequence: &mut Option<u64>,
+    ) -> BpiResult<()> {
+        let mut playlist_url = playlist_url.to_string();
+        let mut init: Option<(String, Vec<u8>)> = None;
+        let mut idle_since = Instant::now();
+        loop {
+            let text = self.fetch_text(&playlist_url).await?;
+            let playlist = HlsPlaylist::parse(&text, &playlist_url)?;
+            if let Some(variant) = playlist.variant {
+                playlist_url = variant;
+                continue;
+            }
+
+            if format == LiveFormat::Fmp4
+                && let Some(map) = &playlist.map
+                && init.as_ref().is_none_or(|(uri, _)| uri != map)
+            {
+                let bytes = self.fetch_bytes(map).await?;
+                init = Some((map.clone(), bytes));
+                // 初始化切片变化后需要新分段
+                writer.finish_segment().await?;
+            }
+
+            let mut received = false;
+            for segment in &playlist.segments {
+                if last_sequence.is_some_and(|last| segment.sequence <= last) {
+                    continue;
+                }
+                let bytes = self.fetch_bytes(&segment.url).await?;
+                if writer.should_rotate() {
+                    writer.finish_segment().await?;
+                }
+                if !writer.is_open() {
+                    let header = init.as_ref().map(|(_, bytes)| bytes.as_slice());
+                    writer.open(header.unwrap_or_default()).await?;
+                }
+                writer.write(&bytes).await?;
+                *last_sequence = Some(segment.sequence);
+                received = true;
+            }
+
+            if playlist.ended {
+                return Ok(());
+            }
+            let target = Duration::from_secs_f64(playlist.target_duration.max(1.0));
+            if received {
+                idle_since = Instant::now();
+            } else if idle_since.elapsed() > self.stall_timeout.max(target * 3) {
+                return Err(BpiError::network("HLS 播放列表长时间没有更新"));
+            }
+            tokio::time::sleep(target / 2).await;
+        }
+    }
+
+    async fn fetch_bytes(&self, url: &str) -> BpiResult<Vec<u8>> {
+        let bytes = self
+            .client
+            .client
+            .get(url)
+            .with_bilibili_headers()
+            .send_request("live.recorder.fetch")
+            .await?;
+        Ok(bytes.to_vec())
+    }
+
+    async fn fetch_text(&self, url: &str) -> BpiResult<String> {
+        let bytes = self.fetch_bytes(url).await?;
+        String::from_utf8(bytes).map_err(|e| BpiError::parse(format!("播放列表不是 UTF-8: {}", e)))
+    }
+}
+
+impl<'a> LiveClient<'a> {
+    /// 创建直播录制器。
+    pub fn recorder(&self, room_id: RoomId, output_dir: impl Into<PathBuf>) -> LiveRecorder<'a> {
+        LiveRecorder::new(*self, room_id, output_dir)
+    }
+}
+
+/// 一场直播的录制结果。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LiveRecording {
+    pub segments: Vec<LiveRecordingMeta>,
+}
+
+/// 分段文件旁的 `.json` 元数据。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiveRecordingMeta {
+    pub room_id: i64,
+    pub uid: i64,
+    pub title: String,
+    pub area_name: String,
+    pub parent_area_name: String,
+    /// 开播时间，来自直播间信息
+    pub live_time: String,
+    /// 分段序号，从 0 开始
+    pub index: u32,
+    /// 分段文件路径
+    pub file: PathBuf,
+    pub kind: LiveStreamKind,
+    pub qn: u32,
+    /// 分段开始写入的时间
+    pub started_at: DateTime<Local>,
+    /// 分段结束写入的时间，写入中为空
+    #[serde(default)]
+    pub ended_at: Option<DateTime<Local>>,
+    /// 已写入的字节数
+    #[serde(default)]
+    pub size: u64,
+}
+
+impl LiveRecordingMeta {
+    fn new(info: &RoomInfoData, candidate: &LiveStreamCandidate) -> Self {
+        Self {
+            room_id: info.room_id,
+            uid: info.uid,
+            title: info.title.clone(),
+            area_name: info.area_name.clone(),
+            parent_area_name: info.parent_area_name.clone(),
+            live_time: info.live_time.clone(),
+            index: 0,
+            file: PathBuf::new(),
+            kind: candidate.kind,
+            qn: candidate.qn,
+            started_at: Local::now(),
+            ended_at: None,
+            size: 0,
+        }
+    }
+
+    fn from_replay(replay: &ReplayInfo, uid: u64, candidate: &LiveStreamCandidate) -> Self {
+        let live_time = DateTime::from_timestamp(replay.start_time, 0)
+            .map(|time| {
+                time.with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_default();
+
+        Self {
+            room_id: replay.room_id,
+            uid: i64::try_from(uid).unwrap_or_default(),
+            title: replay.live_info.title.clone(),
+            area_name: String::new(),
+            parent_area_name: String::new(),
+            live_time,
+            index: 0,
+            file: PathBuf::new(),
+            kind: candidate.kind,
+            qn: candidate.qn,
+            started_at: Local::now(),
+            ended_at: None,
+            size: 0,
+        }
+    }
+
+    /// 分段文件名，形如 `房间号_开始时间_序号.扩展名`。
+    pub fn file_name(&self) -> String {
+        format!(
+            "{}_{}_{:03}.{}",
+            self.room_id,
+            self.started_at.format("%Y%m%d_%H%M%S"),
+            self.index,
+            self.kind.format.extension()
+        )
+    }
+
+    /// 元数据文件路径。
+    pub fn sidecar_path(&self) -> PathBuf {
+        let mut path = self.file.clone().into_os_string();
+        path.push(".json");
+        PathBuf::from(path)
+    }
+}
+
+/// 按时长或大小切分的分段写入器。
+struct SegmentWriter {
+    dir: PathBuf,
+    template: LiveRecordingMeta,
+    max_duration: Option<Duration>,
+    max_size: Option<u64>,
+    current: Option<(tokio::fs::File, LiveRecordingMeta, Instant)>,
+    next_index: u32,
+    received: bool,
+    segments: Vec<LiveRecordingMeta>,
+}
+
+impl SegmentWriter {
+    fn new(
+        dir: &Path,
+        template: LiveRecordingMeta,
+        max_duration: Option<Duration>,
+        max_size: Option<u64>,
+    ) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            template,
+            max_duration,
+            max_size,
+            current: None,
+            next_index: 0,
+            received: false,
+            segments: Vec::new(),
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        self.current.is_some()
+    }
+
+    fn received_data(&self) -> bool {
+        self.received
+    }
+
+    fn should_rotate(&self) -> bool {
+        self.current.as_ref().is_some_and(|(_, meta, opened)| {
+            exceeds_limits(
+                meta.size,
+                opened.elapsed(),
+                self.max_size,
+                self.max_duration,
+            )
+        })
+    }
+
+    async fn open(&mut self, header: &[u8]) -> BpiResult<()> {
+        self.finish_segment().await?;
+
+        let mut meta = self.template.clone();
+        meta.index = self.next_index;
+        meta.started_at = Local::now();
+        meta.file = self.dir.join(meta.file_name());
+        self.next_index += 1;
+
+        let mut file = tokio::fs::File::create(&meta.file)
+            .await
+            .map_err(|e| BpiError::parse(format!("创建录制文件失败: {}", e)))?;
+        file.write_all(header)
+            .await
+            .map_err(|e| BpiError::parse(format!("写入录制文件失败: {}", e)))?;
+        meta.size = header.len() as u64;
+        write_sidecar(&meta).await?;
+        tracing::info!(file = %meta.file.display(), "开始写入直播分段");
+
+        self.current = Some((file, meta, Instant::now()));
+        Ok(())
+    }
+
+    async fn write(&mut self, bytes: &[u8]) -> BpiResult<()> {
+        let Some((file, meta, _)) = &mut self.current else {
+            return Err(BpiError::parse("录制分段尚未打开"));
+        };
+        file.write_all(bytes)
+            .await
+            .map_err(|e| BpiError::parse(format!("写入录制文件失败: {}", e)))?;
+        meta.size += bytes.len() as u64;
+        self.received = true;
+        Ok(())
+    }
+
+    async fn finish_segment(&mut self) -> BpiResult<()> {
+        let Some((mut file, mut meta, _)) = self.current.take() else {
+            return Ok(());
+        };
+        file.flush()
+            .await
+            .map_err(|e| BpiError::parse(format!("写入录制文件失败: {}", e)))?;
+        meta.ended_at = Some(Local::now());
+        write_sidecar(&meta).await?;
+        self.segments.push(meta);
+        Ok(())
+    }
+
+    async fn close(&mut self) -> BpiResult<()> {
+        self.finish_segment().await
+    }
+
+    fn into_segments(self) -> Vec<LiveRecordingMeta> {
+        self.segments
+    }
+}
+
+fn exceeds_limits(
+    size: u64,
+    elapsed: Duration,
+    max_size: Option<u64>,
+    max_duration: Option<Duration>,
+) -> bool {
+    max_size.is_some_and(|max| size >= max) || max_duration.is_some_and(|max| elapsed >= max)
+}
+
+async fn write_sidecar(meta: &LiveRecordingMeta) -> BpiResult<()> {
+    let json = serde_json::to_vec_pretty(meta)?;
+    tokio::fs::write(meta.sidecar_path(), json)
+        .await
+        .map_err(|e| BpiError::parse(format!("写入录制元数据失败: {}", e)))
+}
+
+/// 完整的 FLV tag，包含其后的 PreviousTagSize。
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FlvTag {
+    bytes: Vec<u8>,
+    keyframe: bool,
+    script: bool,
+    sequence_header: bool,
+}
+
+impl FlvTag {
+    /// 是否属于每个分段开头都要重复写入的部分。
+    fn is_init(&self) -> bool {
+        self.script || self.sequence_header
+    }
+}
+
+/// 增量解析 FLV 流，记录文件头、元数据和编码参数，用于在关键帧处开启新分段。
+#[derive(Debug, Default)]
+struct FlvTagReader {
+    buffer: Vec<u8>,
+    header: Option<Vec<u8>>,
+    script: Option<Vec<u8>>,
+    video_header: Option<Vec<u8>>,
+    audio_header: Option<Vec<u8>>,
+}
+
+const FLV_HEADER_LEN: usize = 13;
+const FLV_TAG_HEADER_LEN: usize = 11;
+
+impl FlvTagReader {
+    fn push(&mut self, data: &[u8]) -> BpiResult<Vec<FlvTag>> {
+        self.buffer.extend_from_slice(data);
+
+        if self.header.is_none() {
+            if self.buffer.len() < FLV_HEADER_LEN {
+                return Ok(Vec::new());
+            }
+            if &self.buffer[..3] != b"FLV" {
+                return Err(BpiError::parse("直播流不是 FLV 格式"));
+            }
+            self.header = Some(self.buffer.drain(..FLV_HEADER_LEN).collect());
+        }
+
+        let mut tags = Vec::new();
+        while self.buffer.len() >= FLV_TAG_HEADER_LEN {
+            let data_size =
+                u32::from_be_bytes([0, self.buffer[1], self.buffer[2], self.buffer[3]]) as usize;
+            let total = FLV_TAG_HEADER_LEN + data_size + 4;
+            if self.buffer.len() < total {
+                break;
+            }
+
+            let bytes: Vec<u8> = self.buffer.drain(..total).collect();
+            let tag = classify_flv_tag(bytes);
+            if tag.script && self.script.is_none() {
+                self.script = Some(tag.bytes.clone());
+            }
+            if tag.sequence_header {
+                match tag.bytes[0] & 0x1f {
+                    9 => self.video_header = Some(tag.bytes.clone()),
+                    _ => self.audio_header = Some(tag.bytes.clone()),
+                }
+            }
+            tags.push(tag);
+        }
+        Ok(tags)
+    }
+
+    /// 已收到视频编码参数，可以开始写入分段。
+    fn is_ready(&self) -> bool {
+        self.header.is_some() && self.video_header.is_some()
+    }
+
+    /// 每个分段开头写入的内容：文件头、元数据和编码参数。
+    fn init_bytes(&self) -> Vec<u8> {
+        [
+            &self.header,
+            &self.script,
+            &self.video_header,
+            &self.audio_header,
+        ]
+        .into_iter()
+        .flatten()
+        .flat_map(|bytes| bytes.iter().copied())
+        .collect()
+    }
+}
+
+fn classify_flv_tag(bytes: Vec<u8>) -> FlvTag {
+    let tag_type = bytes[0] & 0x1f;
+    let data = &bytes[FLV_TAG_HEADER_LEN..bytes.len() - 4];
+    let (keyframe, sequence_header) = match (tag_type, data) {
+        // Enhanced FLV：高位为 1 时低 4 位是 PacketType，0 表示 SequenceStart
+        (9, [first, ..]) if first & 0x80 != 0 => ((first >> 4) & 0x07 == 1, first & 0x0f == 0),
+        (9, [first, packet_type, ..]) => (first >> 4 == 1, *packet_type == 0),
+        // AAC 的 AACPacketType 为 0 表示 AudioSpecificConfig
+        (8, [first, packet_type, ..]) => (false, first >> 4 == 10 && *packet_type == 0),
+        _ => (false, false),
+    };
+
+    FlvTag {
+        keyframe: keyframe && !sequence_header,
+        script: tag_type == 18,
+        sequence_header,
+        bytes,
+    }
+}
+
+/// HLS 媒体播放列表中的切片。
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HlsSegment {
+    sequence: u64,
+    url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+struct HlsPlaylist {
+    target_duration: f64,
+    /// fMP4 初始化切片
+    map: Option<String>,
+    segments: Vec<HlsSegment>,
+    ended: bool,
+    /// 主播放列表中的第一个子播放列表
+    variant: Option<String>,
+}
+
+impl HlsPlaylist {
+    fn parse(text: &str, base_url: &str) -> BpiResult<Self> {
+        let base = reqwest::Url::parse(base_url)
+            .map_err(|e| BpiError::parse(format!("播放列表地址无效: {}", e)))?;
+        let resolve = |uri: &str| {
+            base.join(uri)
+                .map(String::from)
+                .map_err(|e| BpiError::parse(format!("切片地址无效: {}", e)))
+        };
+
+        let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+        if lines.next() != Some("#EXTM3U") {
+            return Err(BpiError::parse("不是 M3U8 播放列表"));
+        }
+
+        let mut playlist = Self::default();
+        let mut sequence = 0;
+        let mut variant = false;
+        for line in lines {
+            if let Some(value) = line.strip_prefix("#EXT-X-TARGETDURATION:") {
+                playlist.target_duration = value.parse().unwrap_or_default();
+            } else if let Some(value) = line.strip_prefix("#EXT-X-MEDIA-SEQUENCE:") {
+                sequence = value.parse().unwrap_or_default();
+            } else if let Some(value) = line.strip_prefix("#EXT-X-MAP:") {
+                if let Some(uri) = attribute(value, "URI") {
+                    playlist.map = Some(resolve(uri)?);
+                }
+            } else if line.starts_with("#EXT-X-STREAM-INF") {
+                variant = true;
+            } else if line == "#EXT-X-ENDLIST" {
+                playlist.ended = true;
+            } else if !line.starts_with('#') {
+                if variant {
+                    playlist.variant = Some(resolve(line)?);
+                    break;
+                }
+                playlist.segments.push(HlsSegment {
+                    sequence,
+                    url: resolve(line)?,
+                });
+                sequence += 1;
+            }
+        }
+        Ok(playlist)
+    }
+}
+
+fn attribute<'a>(list: &'a str, name: &str) -> Option<&'a str> {
+    list.split(',').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        (key.trim() == name).then(|| value.trim().trim_matches('"'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flv_tag(tag_type: u8, data: &[u8]) -> Vec<u8> {
+        let size = data.len() as u32;
+        let mut bytes = vec![tag_type];
+        bytes.extend_from_slice(&size.to_be_bytes()[1..]);
+        bytes.extend_from_slice(&[0; 7]);
+        bytes.extend_from_slice(data);
+        bytes.extend_from_slice(&(size + 11).to_be_bytes());
+        bytes
+    }
+
+    fn flv_header() -> Vec<u8> {
+        vec![b'F', b'L', b'V', 1, 5, 0, 0, 0, 9, 0, 0, 0, 0]
+    }
+
+    #[test]
+    fn flv_reader_splits_tags_across_chunks_and_keeps_init_bytes() -> BpiResult<()> {
+        let script = flv_tag(18, b"onMetaData");
+        let video_header = flv_tag(9, &[0x17, 0, 0, 0, 0]);
+        let audio_header = flv_tag(8, &[0xaf, 0, 0x12]);
+        let keyframe = flv_tag(9, &[0x17, 1, 0, 0, 0, 0xaa]);
+        let inter = flv_tag(9, &[0x27, 1, 0, 0, 0, 0xbb]);
+        let stream = [
+            flv_header(),
+            script.clone(),
+            video_header.clone(),
+            audio_header.clone(),
+            keyframe.clone(),
+            inter.clone(),
+        ]
+        .concat();
+
+        let mut reader = FlvTagReader::default();
+        let mut tags = Vec::new();
+        for chunk in stream.chunks(7) {
+            tags.extend(reader.push(chunk)?);
+        }
+
+        assert_eq!(tags.len(), 5);
+        assert!(tags[0].is_init() && tags[1].is_init() && tags[2].is_init());
+        assert!(tags[3].keyframe && !tags[4].keyframe);
+        assert_eq!(tags[3].bytes, keyframe);
+        assert!(reader.is_ready());
+        assert_eq!(
+            reader.init_bytes(),
+            [flv_header(), script, video_header, audio_header].concat()
+        );
+        assert!(FlvTagReader::default().push(b"NOT A FLV FILE").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn flv_reader_understands_enhanced_hevc_tags() {
+        let sequence_start = classify_flv_tag(flv_tag(9, &[0x90, b'h', b'v', b'c', b'1']));
+        let coded_key = classify_flv_tag(flv_tag(9, &[0x91, b'h', b'v', b'c', b'1', 0]));
+
+        assert!(sequence_start.sequence_header && !sequence_start.keyframe);
+        assert!(coded_key.keyframe && !coded_key.sequence_header);
+    }
+
+    #[test]
+    fn hls_playlist_resolves_segments_and_init_map() -> BpiResult<()> {
+        let playlist = HlsPlaylist::parse(
+            "#EXTM3U\n#EXT-X-VERSION:7\n#EXT-X-TARGETDURATION:2\n#EXT-X-MEDIA-SEQUENCE:100\n\
+             #EXT-X-MAP:URI=\"h1700000000.m4s\"\n#EXTINF:1.00,\n101.m4s\n#EXTINF:1.00,\n102.m4s?a=1\n",
+            "https://cn-a.example.com/live-bvc/1000/index.m3u8?expires=1",
+        )?;
+
+        assert_eq!(playlist.target_duration, 2.0);
+        assert_eq!(
+            playlist.map.as_deref(),
+            Some("https://cn-a.example.com/live-bvc/1000/h1700000000.m4s")
+        );
+        assert_eq!(
+            playlist.segments,
+            vec![
+                HlsSegment {
+                    sequence: 100,
+                    url: "https://cn-a.example.com/live-bvc/1000/101.m4s".to_string(),
+                },
+                HlsSegment {
+                    sequence: 101,
+                    url: "https://cn-a.example.com/live-bvc/1000/102.m4s?a=1".to_string(),
+                },
+            ]
+        );
+        assert!(!playlist.ended);
+        Ok(())
+    }
+
+    #[test]
+    fn hls_master_playlist_points_to_first_variant() -> BpiResult<()> {
+        let playlist = HlsPlaylist::parse(
+            "#EXTM3U\n#EXT-X-STREAM-INF:BANDWIDTH=1\nhttps://cn-b.example.com/a.m3u8\n",
+            "https://cn-a.example.com/index.m3u8",
+        )?;
+
+        assert_eq!(
+            playlist.variant.as_deref(),
+            Some("https://cn-b.example.com/a.m3u8")
+        );
+        assert!(HlsPlaylist::parse("not a playlist", "https://a.example.com/").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn segments_rotate_on_size_or_duration() {
+        let minute = Duration::from_secs(60);
+
+        assert!(!exceeds_limits(10, minute, None, None));
+        assert!(exceeds_limits(10, Duration::ZERO, Some(10), None));
+        assert!(!exceeds_limits(9, minute, Some(10), Some(minute * 2)));
+        assert!(exceeds_limits(9, minute * 2, Some(10), Some(minute * 2)));
+    }
+
+    #[test]
+    fn recording_meta_names_segment_and_sidecar() -> BpiResult<()> {
+        let meta: LiveRecordingMeta = serde_json::from_value(serde_json::json!({
+            "room_id": 1000,
+            "uid": 2000,
+            "title": "测试直播",
+            "area_name": "单机游戏",
+            "parent_area_name": "游戏",
+            "live_time": "2026-01-01 20:00:00",
+            "index": 2,
+            "file": "rec/1000_20260101_200000_002.flv",
+            "kind": {"protocol": "http_stream", "format": "flv", "codec": "avc"},
+            "qn": 10000,
+            "started_at": "2026-01-01T20:00:00+08:00"
+        }))?;
+
+        assert!(meta.file_name().starts_with("1000_"));
+        assert!(meta.file_name().ends_with("_002.flv"));
+        assert_eq!(
+            meta.sidecar_path(),
+            PathBuf::from("rec/1000_20260101_200000_002.flv.json")
+        );
+        assert_eq!(meta.ended_at, None);
+        Ok(())
+    }
+
+    #[test]
+    fn replay_meta_uses_replay_title_and_start_time() -> BpiResult<()> {
+        let replay: ReplayInfo = serde_json::from_value(serde_json::json!({
+            "replay_id": 1001,
+            "live_info": { "title": "回放标题", "cover": "", "live_time": 1_700_000_000, "live_type": 0 },
+            "video_info": { "replay_status": 1, "estimated_time": "", "duration": 3600 },
+            "alarm_info": { "code": 0, "message": "", "cur_time": 0, "is_ban_publish": false },
+            "room_id": 1000,
+            "live_key": "<redacted-live-key>",
+            "start_time": 1_700_000_000,
+            "end_time": 1_700_003_600
+        }))?;
+        let candidate = LiveStreamCandidate {
+            kind: LiveStreamKind::hls_fmp4(),
+            qn: 10000,
+            urls: vec!["https://example.invalid/index.m3u8".to_string()],
+        };
+
+        let meta = LiveRecordingMeta::from_replay(&replay, 2000, &candidate);
+        assert_eq!((meta.room_id, meta.uid), (1000, 2000));
+        assert_eq!(meta.title, "回放标题");
+        let expected = DateTime::from_timestamp(1_700_000_000, 0)
+            .expect("valid timestamp")
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+        assert_eq!(meta.live_time, expected);
+        assert!(meta.file_name().ends_with("_000.m4s"));
+        Ok(())
+    }
+}
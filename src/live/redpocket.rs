@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::BpiResult;
+
 // ================= 数据结构 =================
 
 #[derive(Debug, Serialize, Clone, Deserialize)]
@@ -62,6 +64,87 @@ pub struct ActivityBoxInfo {
     pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
+/// 天选时刻中奖用户
+#[derive(Debug, Serialize, Clone, Deserialize, PartialEq, Eq)]
+pub struct AnchorLotteryWinner {
+    /// 用户uid
+    pub uid: i64,
+    /// 用户昵称
+    #[serde(default)]
+    pub uname: String,
+    /// 中奖数量
+    #[serde(default)]
+    pub num: i32,
+}
+
+/// 天选时刻
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct AnchorLottery {
+    /// 天选id
+    pub id: i64,
+    /// 直播间id
+    #[serde(default)]
+    pub room_id: i64,
+    /// 状态，1：进行中，2：已开奖
+    #[serde(default)]
+    pub status: i32,
+    /// 奖品名称
+    #[serde(default)]
+    pub award_name: String,
+    /// 奖品数量
+    #[serde(default)]
+    pub award_num: i32,
+    /// 参与时自动发送的弹幕内容
+    #[serde(default)]
+    pub danmu: String,
+    /// 参与条件类型，0：无，1：关注主播，2：粉丝勋章，3：大航海，4：直播用户等级，5：主站等级
+    #[serde(default)]
+    pub require_type: i32,
+    /// 参与条件等级
+    #[serde(default)]
+    pub require_value: i32,
+    /// 参与条件说明
+    #[serde(default)]
+    pub require_text: String,
+    /// 参与需要赠送的礼物id，0 表示免费参与
+    #[serde(default)]
+    pub gift_id: i64,
+    /// 参与需要赠送的礼物名称
+    #[serde(default)]
+    pub gift_name: String,
+    /// 参与需要赠送的礼物数量
+    #[serde(default)]
+    pub gift_num: i32,
+    /// 礼物单价（金瓜子）
+    #[serde(default)]
+    pub gift_price: i64,
+    /// 剩余时间（秒）
+    #[serde(default)]
+    pub time: i64,
+    /// 当前时间
+    #[serde(default)]
+    pub current_time: i64,
+    /// 中奖用户，开奖前为空
+    #[serde(default)]
+    pub award_users: Option<Vec<AnchorLotteryWinner>>,
+}
+
+impl AnchorLottery {
+    /// 是否仍可参与。
+    pub fn is_open(&self) -> bool {
+        self.status == 1
+    }
+
+    /// 参与需要花费的金瓜子。
+    pub fn gold_cost(&self) -> u64 {
+        if self.gift_id == 0 {
+            return 0;
+        }
+
+        u64::try_from(self.gift_price.saturating_mul(i64::from(self.gift_num))).unwrap_or(0)
+    }
+}
+
 #[derive(Debug, Serialize, Clone, Deserialize)]
 pub struct LotteryInfoData {
     /// 人气红包信息
@@ -74,12 +157,23 @@ pub struct LotteryInfoData {
     pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
+impl LotteryInfoData {
+    /// 解析 `anchor` 分区中的天选时刻，没有进行中的天选时返回 `None`。
+    pub fn anchor_lottery(&self) -> BpiResult<Option<AnchorLottery>> {
+        match self.extra.get("anchor") {
+            None | Some(serde_json::Value::Null) => Ok(None),
+            Some(value) => Ok(Some(AnchorLottery::deserialize(value)?)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ids::RoomId;
     use crate::probe::contract::HttpMethod;
     use crate::probe::endpoint_contract::EndpointContract;
-    use crate::{ApiEnvelope, BpiClient, BpiError, BpiResult};
+    use crate::{ApiEnvelope, BpiClient, BpiError};
 
     fn contract() -> BpiResult<EndpointContract> {
         EndpointContract::from_slice(include_bytes!(
@@ -91,7 +185,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_live_lottery_info() -> Result<(), Box<BpiError>> {
         let bpi = BpiClient::new().expect("client should build");
-        bpi.live().lottery_info(23174842).await?;
+        bpi.live().lottery_info(RoomId::new(23174842)?).await?;
 
         // 注意：直播间可能没有红包，所以不做额外断言
         Ok(())
@@ -139,6 +233,7 @@ mod tests {
         .into_payload()?;
         assert!(payload.popularity_red_pocket.is_none());
         assert!(payload.extra.contains_key("activity_box"));
+        assert!(payload.anchor_lottery()?.is_none());
         Ok(())
     }
 
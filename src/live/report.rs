@@ -1,6 +1,7 @@
 use base64::{Engine as _, engine::general_purpose};
 use serde::{Deserialize, Serialize};
 
+use crate::ids::RoomId;
 use crate::{BpiError, BpiResult};
 
 // ================= 数据结构 =================
@@ -13,20 +14,18 @@ pub struct HeartBeatData {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LiveWebHeartBeatParams {
-    room_id: i64,
+    room_id: RoomId,
     next_interval: i32,
     platform: String,
 }
 
 impl LiveWebHeartBeatParams {
-    pub fn new(room_id: i64) -> BpiResult<Self> {
-        let params = Self {
+    pub fn new(room_id: RoomId) -> Self {
+        Self {
             room_id,
             next_interval: 60,
             platform: "web".to_string(),
-        };
-        params.validate()?;
-        Ok(params)
+        }
     }
 
     pub fn next_interval(mut self, next_interval: i32) -> BpiResult<Self> {
@@ -41,6 +40,15 @@ impl LiveWebHeartBeatParams {
         Ok(self)
     }
 
+    pub(crate) fn room_id(&self) -> RoomId {
+        self.room_id
+    }
+
+    pub(crate) fn with_room_id(mut self, room_id: RoomId) -> Self {
+        self.room_id = room_id;
+        self
+    }
+
     pub(crate) fn query_pairs(&self) -> Vec<(&'static str, String)> {
         let heart_beat_data = format!("{}|{}|1|0", self.next_interval, self.room_id);
         vec![
@@ -50,13 +58,6 @@ impl LiveWebHeartBeatParams {
     }
 
     fn validate(&self) -> BpiResult<()> {
-        if self.room_id <= 0 {
-            return Err(BpiError::invalid_parameter(
-                "room_id",
-                "room_id must be positive",
-            ));
-        }
-
         if self.next_interval <= 0 {
             return Err(BpiError::invalid_parameter(
                 "next_interval",
@@ -94,7 +95,7 @@ mod tests {
         let bpi = BpiClient::new().expect("client should build");
         let data = bpi
             .live()
-            .web_heart_beat(LiveWebHeartBeatParams::new(23174842)?)
+            .web_heart_beat(LiveWebHeartBeatParams::new(RoomId::new(23174842)?))
             .await?;
 
         assert!(data.next_interval > 0);
@@ -103,7 +104,7 @@ mod tests {
 
     #[test]
     fn live_web_heart_beat_params_serializes_default_query() -> BpiResult<()> {
-        let params = LiveWebHeartBeatParams::new(23174842)?;
+        let params = LiveWebHeartBeatParams::new(RoomId::new(23174842)?);
 
         assert_eq!(
             params.query_pairs(),
@@ -115,23 +116,10 @@ mod tests {
         Ok(())
     }
 
-    #[test]
-    fn live_web_heart_beat_params_rejects_invalid_room_id() {
-        let err = LiveWebHeartBeatParams::new(0).unwrap_err();
-
-        assert!(matches!(
-            err,
-            BpiError::InvalidParameter {
-                field: "room_id",
-                ..
-            }
-        ));
-    }
-
     #[test]
     fn live_web_heart_beat_contract_matches_endpoint_request() -> BpiResult<()> {
         let contract = contract()?;
-        let params = LiveWebHeartBeatParams::new(23174842)?;
+        let params = LiveWebHeartBeatParams::new(RoomId::new(23174842)?);
 
         assert_eq!(contract.name, "live.web_heart_beat");
         assert_eq!(contract.request.method, HttpMethod::Get);
@@ -0,0 +1,222 @@
+//! 直播间号解析
+//!
+//! 直播间可能有短号（如 6、1017），多数直播接口只接受真实房间号。[`LiveClient::resolve_room`]
+//! 通过 `room_init` 把短号或长号解析为真实房间号和主播 mid，并缓存在客户端中。
+
+use std::collections::HashMap;
+use std::sync::{PoisonError, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::ids::{Mid, RoomId};
+use crate::live::LiveClient;
+use crate::{BilibiliRequest, BpiResult};
+
+const ROOM_INIT_ENDPOINT: &str = "https://api.live.bilibili.com/room/v1/Room/room_init";
+
+/// 直播间初始化信息
+#[derive(Debug, Serialize, Clone, Deserialize)]
+pub struct RoomInitData {
+    /// 真实房间号
+    pub room_id: u64,
+    /// 短号，没有短号时为 0
+    #[serde(default)]
+    pub short_id: u64,
+    /// 主播 mid
+    pub uid: u64,
+    /// 直播状态 0: 未开播 1: 直播中 2: 轮播中
+    #[serde(default)]
+    pub live_status: i32,
+    /// 开播时间戳
+    #[serde(default)]
+    pub live_time: i64,
+    /// 是否隐藏
+    #[serde(default)]
+    pub is_hidden: bool,
+    /// 是否锁定
+    #[serde(default)]
+    pub is_locked: bool,
+    /// 是否竖屏
+    #[serde(default)]
+    pub is_portrait: bool,
+    /// 是否加密
+    #[serde(default)]
+    pub encrypted: bool,
+    /// 加密房间是否已验证密码
+    #[serde(default)]
+    pub pwd_verified: bool,
+}
+
+impl RoomInitData {
+    pub fn room(&self) -> BpiResult<LiveRoom> {
+        Ok(LiveRoom {
+            room_id: RoomId::new(self.room_id)?,
+            short_id: RoomId::new(self.short_id).ok(),
+            uid: Mid::new(self.uid)?,
+        })
+    }
+}
+
+/// 解析后的直播间。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LiveRoom {
+    /// 真实房间号
+    pub room_id: RoomId,
+    /// 短号
+    pub short_id: Option<RoomId>,
+    /// 主播 mid
+    pub uid: Mid,
+}
+
+/// 直播间号缓存，短号和真实房间号都指向同一个直播间。
+#[derive(Debug, Default)]
+pub(crate) struct LiveRoomCache {
+    rooms: RwLock<HashMap<RoomId, LiveRoom>>,
+}
+
+impl LiveRoomCache {
+    // 缓存里只有互相独立的房间号映射，持锁线程 panic 也不会留下半写的条目，
+    // 所以锁中毒时直接沿用内部数据。
+    pub(crate) fn get(&self, room_id: RoomId) -> Option<LiveRoom> {
+        self.rooms
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&room_id)
+            .copied()
+    }
+
+    pub(crate) fn insert(&self, room: LiveRoom) {
+        let mut rooms = self.rooms.write().unwrap_or_else(PoisonError::into_inner);
+        rooms.insert(room.room_id, room);
+        if let Some(short_id) = room.short_id {
+            rooms.insert(short_id, room);
+        }
+    }
+
+    pub(crate) fn clear(&self) {
+        self.rooms
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
+
+impl<'a> LiveClient<'a> {
+    /// 获取直播间初始化信息，短号和长号都可以查询，结果会写入直播间号缓存。
+    pub async fn room_init(&self, room_id: RoomId) -> BpiResult<RoomInitData> {
+        let data: RoomInitData = self
+            .client
+            .get(ROOM_INIT_ENDPOINT)
+            .with_bilibili_headers()
+            .query(&[("id", room_id.to_string())])
+            .send_bpi_payload("live.room_init")
+            .await?;
+        self.client.live_room_cache().insert(data.room()?);
+
+        Ok(data)
+    }
+
+    /// 把短号或长号解析为真实房间号和主播 mid，优先使用缓存。
+    pub async fn resolve_room(&self, room_id: RoomId) -> BpiResult<LiveRoom> {
+        if let Some(room) = self.client.live_room_cache().get(room_id) {
+            return Ok(room);
+        }
+
+        self.room_init(room_id).await?.room()
+    }
+
+    /// 清空直播间号缓存。
+    pub fn clear_room_cache(&self) {
+        self.client.live_room_cache().clear();
+    }
+
+    /// 真实房间号，需要真实房间号的直播接口在发请求前都会先经过这里。
+    pub(crate) async fn real_room_id(&self, room_id: RoomId) -> BpiResult<RoomId> {
+        Ok(self.resolve_room(room_id).await?.room_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn init_data(short_id: u64) -> RoomInitData {
+        serde_json::from_value(serde_json::json!({
+            "room_id": 5440,
+            "short_id": short_id,
+            "uid": 9617619,
+            "need_p2p": 0,
+            "is_hidden": false,
+            "is_locked": false,
+            "is_portrait": false,
+            "live_status": 1,
+            "hidden_till": 0,
+            "lock_till": 0,
+            "encrypted": false,
+            "pwd_verified": false,
+            "live_time": 1700000000,
+            "room_shield": 0,
+            "is_sp": 0,
+            "special_type": 0
+        }))
+        .expect("fixture should parse")
+    }
+
+    #[test]
+    fn room_init_maps_short_id_to_real_room_and_anchor() -> BpiResult<()> {
+        let room = init_data(6).room()?;
+
+        assert_eq!(room.room_id, RoomId::new(5440)?);
+        assert_eq!(room.short_id, Some(RoomId::new(6)?));
+        assert_eq!(room.uid, Mid::new(9617619)?);
+        assert_eq!(init_data(0).room()?.short_id, None);
+        Ok(())
+    }
+
+    #[test]
+    fn room_cache_answers_for_both_short_and_real_ids() -> BpiResult<()> {
+        let cache = LiveRoomCache::default();
+        let room = init_data(6).room()?;
+        cache.insert(room);
+
+        assert_eq!(cache.get(RoomId::new(6)?), Some(room));
+        assert_eq!(cache.get(RoomId::new(5440)?), Some(room));
+        assert_eq!(cache.get(RoomId::new(1017)?), None);
+        cache.clear();
+        assert_eq!(cache.get(RoomId::new(6)?), None);
+        Ok(())
+    }
+
+    #[test]
+    fn room_cache_keeps_working_after_lock_poisoning() -> BpiResult<()> {
+        let cache = LiveRoomCache::default();
+        let room = init_data(6).room()?;
+        cache.insert(room);
+
+        let poisoned = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _guard = cache
+                .rooms
+                .write()
+                .expect("lock should not be poisoned yet");
+            panic!("poison the room cache");
+        }));
+        assert!(poisoned.is_err());
+        assert!(cache.rooms.is_poisoned());
+
+        assert_eq!(cache.get(RoomId::new(6)?), Some(room));
+        cache.clear();
+        assert_eq!(cache.get(RoomId::new(5440)?), None);
+        Ok(())
+    }
+
+    #[test]
+    fn clients_keep_separate_room_caches() -> BpiResult<()> {
+        let first = crate::BpiClient::new()?;
+        let second = crate::BpiClient::new()?;
+        first.live_room_cache().insert(init_data(6).room()?);
+
+        assert!(first.live_room_cache().get(RoomId::new(6)?).is_some());
+        assert!(second.live_room_cache().get(RoomId::new(6)?).is_none());
+        Ok(())
+    }
+}
@@ -128,6 +128,15 @@ impl LiveSilentUserListParams {
         }
     }
 
+    pub(crate) fn room_id(&self) -> RoomId {
+        self.room_id
+    }
+
+    pub(crate) fn with_room_id(mut self, room_id: RoomId) -> Self {
+        self.room_id = room_id;
+        self
+    }
+
     pub fn page(mut self, page: u32) -> BpiResult<Self> {
         self.page = validate_positive_u32("pn", page)?;
         Ok(self)
@@ -200,6 +209,15 @@ impl LiveShieldKeywordListParams {
         Self { room_id }
     }
 
+    pub(crate) fn room_id(&self) -> RoomId {
+        self.room_id
+    }
+
+    pub(crate) fn with_room_id(mut self, room_id: RoomId) -> Self {
+        self.room_id = room_id;
+        self
+    }
+
     pub(crate) fn form_pairs(&self, csrf: &str) -> Vec<(&'static str, String)> {
         vec![
             ("room_id", self.room_id.to_string()),
@@ -231,17 +249,18 @@ where
 
 impl<'a> LiveClient<'a> {
     /// 禁言观众
-    /// tuid: 用户uid
+    /// tuid: 用户 mid
     /// hour: -1永久 0本场直播
     /// msg: 禁言理由，一般为禁言的弹幕，选填
     pub async fn live_add_silent_user(
         &self,
-        room_id: i64,
-        tuid: i64,
+        room_id: RoomId,
+        tuid: Mid,
         hour: i32,
         msg: Option<String>,
     ) -> BpiResult<Option<serde_json::Value>> {
         let csrf = self.client.csrf()?;
+        let room_id = self.real_room_id(room_id).await?;
 
         let form = vec![
             ("room_id", room_id.to_string()),
@@ -276,13 +295,14 @@ impl<'a> LiveClient<'a> {
     ///
     pub async fn live_del_block_user(
         &self,
-        roomid: i64,
-        tuid: i64,
+        room_id: RoomId,
+        tuid: Mid,
     ) -> BpiResult<Option<serde_json::Value>> {
         let csrf = self.client.csrf()?;
+        let room_id = self.real_room_id(room_id).await?;
 
         let form = vec![
-            ("room_id", roomid.to_string()),
+            ("room_id", room_id.to_string()),
             ("tuid", tuid.to_string()),
             ("csrf_token", csrf.clone()),
             ("csrf", csrf),
@@ -295,19 +315,18 @@ impl<'a> LiveClient<'a> {
             .await
     }
 
-    /// 拉黑观众
-    /// anchor_id：主播uid
+    /// 拉黑观众，主播 mid 由房间号解析得到
     pub async fn live_add_banned_user(
         &self,
-        room_id: i64,
-        anchor_id: i64,
-        tuid: i64,
+        room_id: RoomId,
+        tuid: Mid,
     ) -> BpiResult<Option<serde_json::Value>> {
         let csrf = self.client.csrf()?;
+        let room = self.resolve_room(room_id).await?;
 
         let form = vec![
             ("tuid", tuid.to_string()),
-            ("anchor_id", anchor_id.to_string()),
+            ("anchor_id", room.uid.to_string()),
             ("spmid", "444.8.0.0".to_string()),
             ("csrf_token", csrf.clone()),
             ("csrf", csrf),
@@ -316,25 +335,27 @@ impl<'a> LiveClient<'a> {
 
         self.client
             .post("https://api.live.bilibili.com/xlive/app-ucenter/v2/xbanned/banned/AddBlack")
-            .header("Referer", format!("https://live.bilibili.com/{}", room_id))
+            .header(
+                "Referer",
+                format!("https://live.bilibili.com/{}", room.room_id),
+            )
             .form(&form)
             .send_bpi_optional_payload("live.banned_user.add")
             .await
     }
 
-    /// 解除拉黑
-    /// anchor_id：主播uid
+    /// 解除拉黑，主播 mid 由房间号解析得到
     pub async fn live_del_banned_user(
         &self,
-        room_id: i64,
-        anchor_id: i64,
-        tuid: i64,
+        room_id: RoomId,
+        tuid: Mid,
     ) -> BpiResult<Option<serde_json::Value>> {
         let csrf = self.client.csrf()?;
+        let room = self.resolve_room(room_id).await?;
 
         let form = vec![
             ("tuid", tuid.to_string()),
-            ("anchor_id", anchor_id.to_string()),
+            ("anchor_id", room.uid.to_string()),
             ("spmid", "444.8.0.0".to_string()),
             ("csrf_token", csrf.clone()),
             ("csrf", csrf),
@@ -345,7 +366,10 @@ impl<'a> LiveClient<'a> {
 
         self.client
             .post("https://api.live.bilibili.com/xlive/app-ucenter/v2/xbanned/banned/DelBlack")
-            .header("Referer", format!("https://live.bilibili.com/{}", room_id))
+            .header(
+                "Referer",
+                format!("https://live.bilibili.com/{}", room.room_id),
+            )
             .form(&form)
             .send_bpi_optional_payload("live.banned_user.delete")
             .await
@@ -355,10 +379,11 @@ impl<'a> LiveClient<'a> {
     ///
     pub async fn live_add_shield_keyword(
         &self,
-        room_id: i64,
+        room_id: RoomId,
         keyword: String,
     ) -> BpiResult<Option<serde_json::Value>> {
         let csrf = self.client.csrf()?;
+        let room_id = self.real_room_id(room_id).await?;
 
         let form = vec![
             ("keyword", keyword),
@@ -382,10 +407,11 @@ impl<'a> LiveClient<'a> {
     ///
     pub async fn live_del_shield_keyword(
         &self,
-        room_id: i64,
+        room_id: RoomId,
         keyword: String,
     ) -> BpiResult<Option<serde_json::Value>> {
         let csrf = self.client.csrf()?;
+        let room_id = self.real_room_id(room_id).await?;
 
         let form = vec![
             ("keyword", keyword),
@@ -0,0 +1,508 @@
+//! 直播观看心跳
+//!
+//! 粉丝勋章的观看亲密度依赖 `x25Kn` 的 E/X 心跳：先发送一次 `E` 进入直播间，之后按服务器返回的间隔
+//! 发送 `X`。每次 `X` 的签名 `s` 由上一次响应的 `secret_rule` 决定，依次用对应的 HMAC 算法对上一步
+//! 的十六进制结果再签名。
+//!
+//! [`LiveWatchSession`] 维护这一过程并累计观看时长，可以自行调用 [`LiveWatchSession::next`] 驱动，
+//! 也可以在客户端为 `'static` 时用 [`LiveWatchSession::spawn`] 放到后台运行。
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Local, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::ids::{Mid, RoomId};
+use crate::live::LiveClient;
+use crate::live::user::FansMedalItem;
+use crate::{BilibiliRequest, BpiError, BpiResult};
+
+const HEARTBEAT_E_ENDPOINT: &str =
+    "https://live-trace.bilibili.com/xlive/data-interface/v1/x25Kn/E";
+const HEARTBEAT_X_ENDPOINT: &str =
+    "https://live-trace.bilibili.com/xlive/data-interface/v1/x25Kn/X";
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+const MEDAL_PAGE_SIZE: i32 = 50;
+const MEDAL_MAX_PAGES: i32 = 20;
+
+/// E/X 心跳响应
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LiveHeartbeatData {
+    /// 服务器时间戳，下一次心跳的 `ets`
+    pub timestamp: i64,
+    /// 下一次心跳间隔，单位秒
+    pub heartbeat_interval: u64,
+    /// 下一次心跳的签名密钥
+    pub secret_key: String,
+    /// 下一次心跳的签名算法顺序
+    pub secret_rule: Vec<u8>,
+    #[serde(default)]
+    pub patch_status: i32,
+}
+
+/// 参与签名的心跳字段，字段顺序即签名时的 JSON 顺序。
+#[derive(Debug, Clone, Serialize)]
+struct HeartbeatSignPayload<'a> {
+    platform: &'a str,
+    parent_id: u64,
+    area_id: u64,
+    seq_id: u64,
+    room_id: u64,
+    buvid: &'a str,
+    uuid: &'a str,
+    ets: i64,
+    time: u64,
+    ts: i64,
+}
+
+/// 按 `secret_rule` 依次计算 HMAC：0 MD5、1 SHA1、2 SHA256、3 SHA224、4 SHA512、5 SHA384。
+fn heartbeat_signature(data: &str, key: &str, rule: &[u8]) -> BpiResult<String> {
+    fn hmac_hex<M: Mac + hmac::digest::KeyInit>(key: &str, data: &str) -> String {
+        let mut mac =
+            <M as Mac>::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any length");
+        mac.update(data.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    rule.iter().try_fold(data.to_string(), |data, step| {
+        Ok(match step {
+            0 => hmac_hex::<Hmac<md_5::Md5>>(key, &data),
+            1 => hmac_hex::<Hmac<sha1::Sha1>>(key, &data),
+            2 => hmac_hex::<Hmac<sha2::Sha256>>(key, &data),
+            3 => hmac_hex::<Hmac<sha2::Sha224>>(key, &data),
+            4 => hmac_hex::<Hmac<sha2::Sha512>>(key, &data),
+            5 => hmac_hex::<Hmac<sha2::Sha384>>(key, &data),
+            _ => {
+                return Err(BpiError::unsupported_response(format!(
+                    "未知的心跳签名算法: {step}"
+                )));
+            }
+        })
+    })
+}
+
+/// 观看统计。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiveWatchStats {
+    pub room_id: RoomId,
+    /// 会话开始时间
+    pub started_at: DateTime<Local>,
+    /// 服务器已确认的观看时长
+    pub watched: Duration,
+    /// 成功的心跳次数，包含进入直播间的 E 心跳
+    pub heartbeats: u32,
+    /// 最近一次成功心跳的时间
+    #[serde(default)]
+    pub last_heartbeat_at: Option<DateTime<Local>>,
+    /// 最近一次心跳失败的原因
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+/// 粉丝勋章亲密度变化。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiveMedalProgress {
+    /// 会话开始时的今日亲密度
+    pub today_feed_before: i32,
+    /// 当前的今日亲密度
+    pub today_feed: i32,
+    /// 每日上限
+    pub day_limit: i32,
+    pub level: i32,
+    pub intimacy: i32,
+    pub next_intimacy: i32,
+}
+
+impl LiveMedalProgress {
+    pub fn gained(&self) -> i32 {
+        self.today_feed - self.today_feed_before
+    }
+
+    pub fn reached_day_limit(&self) -> bool {
+        self.day_limit > 0 && self.today_feed >= self.day_limit
+    }
+}
+
+/// 直播观看心跳会话。
+pub struct LiveWatchSession<'a> {
+    client: LiveClient<'a>,
+    room_id: RoomId,
+    anchor: Mid,
+    parent_area_id: u64,
+    area_id: u64,
+    buvid: String,
+    uuid: String,
+    seq: u64,
+    last: Option<LiveHeartbeatData>,
+    medal: Option<FansMedalItem>,
+    stats: Arc<Mutex<LiveWatchStats>>,
+}
+
+impl<'a> LiveWatchSession<'a> {
+    /// 创建会话，读取直播间分区和当前账号在该直播间的粉丝勋章，需要登录。
+    pub async fn new(client: LiveClient<'a>, room_id: RoomId) -> BpiResult<Self> {
+        let account = client
+            .client
+            .get_account()
+            .ok_or_else(BpiError::auth_required)?;
+        if account.buvid3.trim().is_empty() {
+            return Err(BpiError::auth("观看心跳需要 buvid3 Cookie"));
+        }
+
+        let room = client.resolve_room(room_id).await?;
+        let info = client.room_info(room.room_id).await?;
+        let medal = find_medal(&client, room.uid).await?;
+
+        Ok(Self {
+            client,
+            room_id: room.room_id,
+            anchor: room.uid,
+            parent_area_id: u64::try_from(info.parent_area_id).unwrap_or_default(),
+            area_id: u64::try_from(info.area_id).unwrap_or_default(),
+            buvid: account.buvid3,
+            uuid: uuid::Uuid::new_v4().to_string(),
+            seq: 0,
+            last: None,
+            medal,
+            stats: Arc::new(Mutex::new(LiveWatchStats {
+                room_id: room.room_id,
+                started_at: Local::now(),
+                watched: Duration::ZERO,
+                heartbeats: 0,
+                last_heartbeat_at: None,
+                last_error: None,
+            })),
+        })
+    }
+
+    pub fn room_id(&self) -> RoomId {
+        self.room_id
+    }
+
+    /// 会话开始时该直播间的粉丝勋章，没有勋章时为空。
+    pub fn medal(&self) -> Option<&FansMedalItem> {
+        self.medal.as_ref()
+    }
+
+    pub fn stats(&self) -> LiveWatchStats {
+        self.stats
+            .lock()
+            .expect("watch stats mutex poisoned")
+            .clone()
+    }
+
+    /// 距离下一次心跳的等待时间。
+    pub fn next_delay(&self) -> Duration {
+        self.last
+            .as_ref()
+            .map(|last| Duration::from_secs(last.heartbeat_interval.max(1)))
+            .unwrap_or(DEFAULT_INTERVAL)
+    }
+
+    /// 等待心跳间隔后发送下一次心跳，首次调用立即发送 E 心跳。
+    pub async fn next(&mut self) -> BpiResult<LiveWatchStats> {
+        if self.last.is_some() {
+            tokio::time::sleep(self.next_delay()).await;
+        }
+        self.beat().await
+    }
+
+    /// 立即发送一次心跳。
+    pub async fn beat(&mut self) -> BpiResult<LiveWatchStats> {
+        // 失败时保留上一次的响应，下一次重试仍用它签名
+        let result = match self.last.clone() {
+            None => self.enter().await.map(|data| (data, Duration::ZERO)),
+            Some(last) => self.heartbeat(&last).await,
+        };
+
+        let mut stats = self.stats.lock().expect("watch stats mutex poisoned");
+        match result {
+            Ok((data, watched)) => {
+                self.last = Some(data);
+                stats.watched += watched;
+                stats.heartbeats += 1;
+                stats.last_heartbeat_at = Some(Local::now());
+                stats.last_error = None;
+                Ok(stats.clone())
+            }
+            Err(err) => {
+                stats.last_error = Some(err.to_string());
+                Err(err)
+            }
+        }
+    }
+
+    /// 重新读取粉丝勋章，返回会话开始以来的今日亲密度变化。
+    pub async fn medal_progress(&self) -> BpiResult<Option<LiveMedalProgress>> {
+        let Some(before) = &self.medal else {
+            return Ok(None);
+        };
+        let Some(now) = find_medal(&self.client, self.anchor).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(LiveMedalProgress {
+            today_feed_before: before.today_feed,
+            today_feed: now.today_feed,
+            day_limit: now.day_limit,
+            level: now.level,
+            intimacy: now.intimacy,
+            next_intimacy: now.next_intimacy,
+        }))
+    }
+
+    async fn enter(&mut self) -> BpiResult<LiveHeartbeatData> {
+        let csrf = self.client.client.csrf()?;
+        let mut form = self.common_form(0, csrf);
+        form.extend([
+            ("is_patch", "0".to_string()),
+            ("heart_beat", "[]".to_string()),
+        ]);
+
+        let data = self
+            .client
+            .client
+            .post(HEARTBEAT_E_ENDPOINT)
+            .with_bilibili_headers()
+            .form(&form)
+            .send_bpi_payload("live.watch.enter")
+            .await?;
+        self.seq = 0;
+
+        Ok(data)
+    }
+
+    async fn heartbeat(
+        &mut self,
+        last: &LiveHeartbeatData,
+    ) -> BpiResult<(LiveHeartbeatData, Duration)> {
+        let csrf = self.client.client.csrf()?;
+        let seq = self.seq + 1;
+        let ts = Utc::now().timestamp_millis();
+        let payload = serde_json::to_string(&HeartbeatSignPayload {
+            platform: "web",
+            parent_id: self.parent_area_id,
+            area_id: self.area_id,
+            seq_id: seq,
+            room_id: self.room_id.get(),
+            buvid: &self.buvid,
+            uuid: &self.uuid,
+            ets: last.timestamp,
+            time: last.heartbeat_interval,
+            ts,
+        })?;
+        let signature = heartbeat_signature(&payload, &last.secret_key, &last.secret_rule)?;
+
+        let mut form = self.common_form(seq, csrf);
+        form.retain(|(key, _)| *key != "ts");
+        form.extend([
+            ("s", signature),
+            ("ets", last.timestamp.to_string()),
+            ("benchmark", last.secret_key.clone()),
+            ("time", last.heartbeat_interval.to_string()),
+            ("ts", ts.to_string()),
+        ]);
+
+        let data = self
+            .client
+            .client
+            .post(HEARTBEAT_X_ENDPOINT)
+            .with_bilibili_headers()
+            .form(&form)
+            .send_bpi_payload("live.watch.heartbeat")
+            .await?;
+        self.seq = seq;
+
+        Ok((data, Duration::from_secs(last.heartbeat_interval)))
+    }
+
+    fn common_form(&self, seq: u64, csrf: String) -> Vec<(&'static str, String)> {
+        vec![
+            (
+                "id",
+                format!(
+                    "[{},{},{},{}]",
+                    self.parent_area_id, self.area_id, seq, self.room_id
+                ),
+            ),
+            (
+                "device",
+                serde_json::json!([self.buvid, self.uuid]).to_string(),
+            ),
+            ("ruid", self.anchor.to_string()),
+            ("ts", Utc::now().timestamp_millis().to_string()),
+            ("ua", self.client.client.user_agent().to_string()),
+            ("csrf_token", csrf.clone()),
+            ("csrf", csrf),
+            ("visit_id", String::new()),
+        ]
+    }
+}
+
+impl LiveWatchSession<'static> {
+    /// 在后台持续发送心跳，直到调用 [`LiveWatchHandle::stop`]。
+    ///
+    /// 单次心跳失败只记录在统计中，下一次心跳会重试；连续失败 `max_failures` 次后停止。
+    pub fn spawn(mut self, max_failures: u32) -> LiveWatchHandle {
+        let stats = Arc::clone(&self.stats);
+        let (stop, mut stopped) = watch::channel(false);
+        let task = tokio::spawn(async move {
+            let mut failures = 0;
+            loop {
+                tokio::select! {
+                    _ = stopped.changed() => return Ok(self.stats()),
+                    result = self.next() => match result {
+                        Ok(_) => failures = 0,
+                        Err(err) => {
+                            failures += 1;
+                            tracing::warn!(room_id = %self.room_id, failures, error = %err, "观看心跳失败");
+                            if failures >= max_failures.max(1) {
+                                return Err(err);
+                            }
+                        }
+                    },
+                }
+            }
+        });
+
+        LiveWatchHandle { stats, stop, task }
+    }
+}
+
+/// 后台观看心跳的句柄。
+pub struct LiveWatchHandle {
+    stats: Arc<Mutex<LiveWatchStats>>,
+    stop: watch::Sender<bool>,
+    task: JoinHandle<BpiResult<LiveWatchStats>>,
+}
+
+impl LiveWatchHandle {
+    /// 当前观看统计。
+    pub fn stats(&self) -> LiveWatchStats {
+        self.stats
+            .lock()
+            .expect("watch stats mutex poisoned")
+            .clone()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    /// 停止心跳并返回最终统计。
+    pub async fn stop(self) -> BpiResult<LiveWatchStats> {
+        let _ = self.stop.send(true);
+        self.task
+            .await
+            .map_err(|e| BpiError::parse(format!("观看心跳任务异常退出: {}", e)))?
+    }
+}
+
+impl<'a> LiveClient<'a> {
+    /// 创建直播观看心跳会话，需要登录。
+    pub async fn watch_session(&self, room_id: RoomId) -> BpiResult<LiveWatchSession<'a>> {
+        LiveWatchSession::new(*self, room_id).await
+    }
+
+    /// 为粉丝勋章对应的直播间创建观看心跳会话。
+    pub async fn watch_medal(&self, medal: &FansMedalItem) -> BpiResult<LiveWatchSession<'a>> {
+        let room_id = u64::try_from(medal.roomid)
+            .map_err(|_| BpiError::invalid_parameter("roomid", "medal has no live room"))?;
+        LiveWatchSession::new(*self, RoomId::new(room_id)?).await
+    }
+}
+
+/// 在已拥有的粉丝勋章中查找某位主播的勋章。
+pub(crate) async fn find_medal(
+    client: &LiveClient<'_>,
+    anchor: Mid,
+) -> BpiResult<Option<FansMedalItem>> {
+    for page in 1..=MEDAL_MAX_PAGES {
+        let data = client.my_medals(page, MEDAL_PAGE_SIZE).await?;
+        if let Some(medal) = data
+            .items
+            .into_iter()
+            .find(|medal| u64::try_from(medal.target_id).ok() == Some(anchor.get()))
+        {
+            return Ok(Some(medal));
+        }
+        if page >= data.page_info.total_page {
+            break;
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload() -> String {
+        serde_json::to_string(&HeartbeatSignPayload {
+            platform: "web",
+            parent_id: 6,
+            area_id: 283,
+            seq_id: 1,
+            room_id: 5440,
+            buvid: "buvid-test",
+            uuid: "uuid-test",
+            ets: 1_700_000_000,
+            time: 60,
+            ts: 1_700_000_060_000,
+        })
+        .expect("payload should serialize")
+    }
+
+    #[test]
+    fn sign_payload_keeps_field_order() {
+        assert_eq!(
+            payload(),
+            r#"{"platform":"web","parent_id":6,"area_id":283,"seq_id":1,"room_id":5440,"buvid":"buvid-test","uuid":"uuid-test","ets":1700000000,"time":60,"ts":1700000060000}"#
+        );
+    }
+
+    #[test]
+    fn heartbeat_signature_chains_hmac_by_secret_rule() -> BpiResult<()> {
+        assert_eq!(
+            heartbeat_signature(&payload(), "secret", &[2, 5, 1, 4])?,
+            "3057d75321b623b389f56ea3db4440f0db976b7f21b9a6d18655c617b1c82d62\
+             bd8820154865ca6956f0742d5e78bddcc39d3f5f94f10399dde398077ea1b2a4"
+        );
+        assert_eq!(
+            heartbeat_signature(&payload(), "secret", &[0, 3])?,
+            "6c36422147a19459c0bc7bb56fbb65b636140577c3ace86e19df79b7"
+        );
+        assert!(heartbeat_signature(&payload(), "secret", &[9]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn heartbeat_response_parses_secret_rule() -> BpiResult<()> {
+        let data: LiveHeartbeatData = serde_json::from_str(
+            r#"{"timestamp":1700000000,"heartbeat_interval":60,"secret_key":"seacasdgyijfhofiuxoannn","secret_rule":[2,5,1,4],"patch_status":2}"#,
+        )?;
+
+        assert_eq!(data.secret_rule, vec![2, 5, 1, 4]);
+        assert_eq!(data.heartbeat_interval, 60);
+        Ok(())
+    }
+
+    #[test]
+    fn medal_progress_reports_gain_and_day_limit() {
+        let progress = LiveMedalProgress {
+            today_feed_before: 100,
+            today_feed: 1500,
+            day_limit: 1500,
+            level: 12,
+            intimacy: 3000,
+            next_intimacy: 5000,
+        };
+
+        assert_eq!(progress.gained(), 1400);
+        assert!(progress.reached_day_limit());
+    }
+}
@@ -1,12 +1,18 @@
-use crate::message::msg::{ReplyFeedData, UnreadCountData};
+use crate::message::msg::{
+    AtFeedData, LikeFeedData, ReplyFeedData, SystemNotifyData, UnreadCountData,
+};
 use crate::message::params::{
-    MessageReplyFeedParams, MessageSingleUnreadParams, MessageUnreadCountParams,
+    MessageAtFeedParams, MessageLikeFeedParams, MessageReplyFeedParams, MessageSingleUnreadParams,
+    MessageSystemNotifyParams, MessageUnreadCountParams,
 };
 use crate::message::private_msg::SingleUnreadData;
 use crate::{BilibiliRequest, BpiClient, BpiResult};
 
 const UNREAD_COUNT_ENDPOINT: &str = "https://api.vc.bilibili.com/x/im/web/msgfeed/unread";
 const REPLY_FEED_ENDPOINT: &str = "https://api.bilibili.com/x/msgfeed/reply";
+const AT_FEED_ENDPOINT: &str = "https://api.bilibili.com/x/msgfeed/at";
+const LIKE_FEED_ENDPOINT: &str = "https://api.bilibili.com/x/msgfeed/like";
+const SYSTEM_NOTIFY_ENDPOINT: &str = "https://message.bilibili.com/x/sys-msg/query_user_notify";
 const SINGLE_UNREAD_ENDPOINT: &str =
     "https://api.vc.bilibili.com/session_svr/v1/session_svr/single_unread";
 
@@ -57,6 +63,38 @@ impl<'a> MessageClient<'a> {
             .await
     }
 
+    /// 获取@我的通知流条目。
+    pub async fn at_feed(&self, params: MessageAtFeedParams) -> BpiResult<AtFeedData> {
+        self.client
+            .get(AT_FEED_ENDPOINT)
+            .query(&params.query_pairs())
+            .send_bpi_payload("message.at_feed")
+            .await
+    }
+
+    /// 获取收到的赞通知流条目，多人点赞同一对象会合并为一条。
+    pub async fn like_feed(&self, params: MessageLikeFeedParams) -> BpiResult<LikeFeedData> {
+        self.client
+            .get(LIKE_FEED_ENDPOINT)
+            .query(&params.query_pairs())
+            .send_bpi_payload("message.like_feed")
+            .await
+    }
+
+    /// 获取系统通知。
+    pub async fn system_notify(
+        &self,
+        params: MessageSystemNotifyParams,
+    ) -> BpiResult<SystemNotifyData> {
+        let csrf = self.client.csrf()?;
+
+        self.client
+            .get(SYSTEM_NOTIFY_ENDPOINT)
+            .query(&params.query_pairs(csrf))
+            .send_bpi_payload("message.system_notify")
+            .await
+    }
+
     /// 获取未读私信计数。
     pub async fn single_unread(
         &self,
@@ -2,13 +2,25 @@
 
 mod client;
 pub mod msg;
+pub mod notification;
 pub mod params;
 pub mod private_msg;
 pub mod private_msg_content;
 pub mod settings;
 
 pub use client::MessageClient;
+pub use msg::NotificationSource;
+pub use notification::{
+    Notification, NotificationCursor, NotificationFeedCursor, NotificationKind, NotificationStream,
+};
 pub use params::{
-    MessageReplyFeedParams, MessageSingleUnreadParams, MessageUnreadCountParams, SingleUnreadType,
+    MessageAtFeedParams, MessageLikeFeedParams, MessageReplyFeedParams, MessageSingleUnreadParams,
+    MessageSystemNotifyParams, MessageUnreadCountParams, SingleUnreadType,
+};
+pub use private_msg::{
+    MessageImageUploadData, MessageImageUploadParams, MessageSendParams, MessageSession,
+    MessageSessionListType, MessageSessionMsgsParams, MessageSessionsParams, MessageTalkerType,
+    MessageUpdateAckParams, PrivateMessage, SessionListData, SessionMsgsData,
 };
-pub use private_msg::MessageSendParams;
+pub use private_msg_content::PrivateMessageContent;
+pub use settings::{MessageNotifyMode, MessageSettingsData, MessageSettingsParams};
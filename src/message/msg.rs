@@ -1,10 +1,17 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[cfg(feature = "comment")]
+use crate::comment::CommentTarget;
+use crate::ids::{Aid, Cvid, DynamicId};
 
 // --- API 结构体 ---
 
 /// 未读消息数
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct UnreadCountData {
+    #[serde(default)]
+    pub at: u32, // 未读@我的数
     pub coin: u32, // 未读投币数
     #[serde(default)]
     pub danmu: u32, // 未读弹幕数
@@ -87,6 +94,292 @@ pub struct AtUserDetail {
     pub follow: bool,
 }
 
+impl ReplyDetail {
+    /// 回复所在的评论及其评论区。
+    pub fn source(&self) -> NotificationSource {
+        NotificationSource::from_parts(
+            &self.reply_type,
+            self.business_id,
+            self.subject_id,
+            self.source_id,
+            self.root_id,
+            &self.uri,
+        )
+    }
+}
+
+/// "@我的"信息
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AtFeedData {
+    pub cursor: ReplyCursor,
+    #[serde(default)]
+    pub items: Vec<AtItem>,
+}
+
+/// 单条@通知
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AtItem {
+    pub id: u64,
+    pub user: ReplyUser,
+    pub item: AtDetail,
+    pub at_time: u64,
+}
+
+/// @通知详情
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AtDetail {
+    /// 来源类型 reply: 评论 dynamic: 动态
+    #[serde(rename = "type")]
+    pub at_type: String,
+    pub business: String,
+    pub business_id: u32,
+    pub title: String,
+    pub image: String,
+    pub uri: String,
+    pub source_content: String,
+    pub source_id: u64,
+    pub target_id: u64,
+    pub root_id: u64,
+    pub subject_id: u64,
+    pub native_url: String,
+    pub at_details: Vec<AtUserDetail>,
+}
+
+impl AtDetail {
+    /// @所在的评论、动态或稿件。
+    pub fn source(&self) -> NotificationSource {
+        NotificationSource::from_parts(
+            &self.at_type,
+            self.business_id,
+            self.subject_id,
+            self.source_id,
+            self.root_id,
+            &self.uri,
+        )
+    }
+}
+
+/// "收到的赞"信息
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LikeFeedData {
+    /// 最近的点赞，不参与分页
+    #[serde(default)]
+    pub latest: Option<LikeLatest>,
+    pub total: LikeTotal,
+}
+
+impl LikeFeedData {
+    /// 合并最近和分页列表中的点赞通知，按 ID 去重并按点赞时间从新到旧排列。
+    pub fn merged_items(&self) -> Vec<&LikeItem> {
+        let mut items: Vec<&LikeItem> = self
+            .latest
+            .iter()
+            .flat_map(|latest| latest.items.iter())
+            .chain(self.total.items.iter())
+            .collect();
+        items.sort_by(|a, b| b.like_time.cmp(&a.like_time).then(b.id.cmp(&a.id)));
+        items.dedup_by_key(|item| item.id);
+        items
+    }
+}
+
+/// 最近的点赞
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LikeLatest {
+    pub items: Vec<LikeItem>,
+    pub last_view_at: u64,
+}
+
+/// 分页的点赞列表
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LikeTotal {
+    pub cursor: ReplyCursor,
+    #[serde(default)]
+    pub items: Vec<LikeItem>,
+}
+
+/// 单条点赞通知，同一对象的多次点赞会合并为一条
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LikeItem {
+    pub id: u64,
+    /// 最近点赞的用户，最多返回数人
+    #[serde(default)]
+    pub users: Vec<ReplyUser>,
+    pub item: LikeDetail,
+    /// 点赞总人数
+    #[serde(default)]
+    pub counts: u32,
+    pub like_time: u64,
+    #[serde(default)]
+    pub notice_state: u32,
+}
+
+impl LikeItem {
+    /// 是否由多人点赞合并而成。
+    pub fn is_merged(&self) -> bool {
+        self.counts > 1 || self.users.len() > 1
+    }
+
+    /// 未在 `users` 中列出的其他点赞人数。
+    pub fn other_likers(&self) -> u32 {
+        self.counts.saturating_sub(self.users.len() as u32)
+    }
+}
+
+/// 点赞通知详情
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LikeDetail {
+    /// 被点赞对象的 ID，评论为 rpid，视频为 aid，动态为动态 ID
+    pub item_id: u64,
+    pub pid: u64,
+    /// 被点赞对象类型 reply: 评论 video: 视频 dynamic: 动态 article: 专栏
+    #[serde(rename = "type")]
+    pub like_type: String,
+    pub business: String,
+    pub business_id: u32,
+    pub reply_business_id: u32,
+    pub title: String,
+    pub desc: String,
+    pub image: String,
+    pub uri: String,
+    pub native_uri: String,
+    pub detail_name: String,
+    pub ctime: u64,
+}
+
+impl LikeDetail {
+    /// 被点赞的评论、动态或稿件。
+    pub fn source(&self) -> NotificationSource {
+        if self.like_type == "reply" {
+            if let Some((business_id, oid)) = comment_area_from_uri(&self.native_uri) {
+                return NotificationSource::Comment {
+                    business_id,
+                    oid,
+                    rpid: self.item_id,
+                    root_id: 0,
+                };
+            }
+        }
+
+        NotificationSource::from_parts(
+            &self.like_type,
+            self.reply_business_id,
+            self.item_id,
+            self.item_id,
+            0,
+            &self.uri,
+        )
+    }
+}
+
+/// 系统通知列表
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SystemNotifyData {
+    #[serde(default)]
+    pub system_notify_list: Option<Vec<SystemNotifyItem>>,
+}
+
+impl SystemNotifyData {
+    pub fn items(&self) -> &[SystemNotifyItem] {
+        self.system_notify_list.as_deref().unwrap_or_default()
+    }
+}
+
+/// 单条系统通知
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SystemNotifyItem {
+    pub id: u64,
+    /// 翻页游标
+    pub cursor: u64,
+    #[serde(rename = "type", default)]
+    pub notify_type: u32,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub content: String,
+    /// 通知时间，格式为 `YYYY-MM-DD hh:mm:ss`
+    #[serde(default)]
+    pub time_at: String,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, Value>,
+}
+
+/// 通知指向的原始内容。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationSource {
+    /// 视频稿件
+    Video(Aid),
+    /// 动态
+    Dynamic(DynamicId),
+    /// 专栏
+    Article(Cvid),
+    /// 评论，`business_id` 为评论区类型，`oid` 为评论区 ID
+    Comment {
+        business_id: u32,
+        oid: u64,
+        rpid: u64,
+        root_id: u64,
+    },
+    /// 无法识别的来源，保留业务名和跳转地址
+    Unknown { business: String, uri: String },
+}
+
+impl NotificationSource {
+    fn from_parts(
+        kind: &str,
+        business_id: u32,
+        subject_id: u64,
+        source_id: u64,
+        root_id: u64,
+        uri: &str,
+    ) -> Self {
+        let source = match kind {
+            "reply" if subject_id != 0 && business_id != 0 => Some(Self::Comment {
+                business_id,
+                oid: subject_id,
+                rpid: source_id,
+                root_id,
+            }),
+            "video" => Aid::new(subject_id).ok().map(Self::Video),
+            "dynamic" | "album" => DynamicId::new(subject_id.to_string())
+                .ok()
+                .filter(|_| subject_id != 0)
+                .map(Self::Dynamic),
+            "article" => Cvid::new(subject_id).ok().map(Self::Article),
+            _ => None,
+        };
+
+        source.unwrap_or_else(|| Self::Unknown {
+            business: kind.to_string(),
+            uri: uri.to_string(),
+        })
+    }
+
+    /// 评论来源所在的评论区。
+    #[cfg(feature = "comment")]
+    pub fn comment_target(&self) -> Option<CommentTarget> {
+        match self {
+            Self::Comment {
+                business_id, oid, ..
+            } => CommentTarget::new(i32::try_from(*business_id).ok()?, i64::try_from(*oid).ok()?)
+                .ok(),
+            _ => None,
+        }
+    }
+}
+
+/// 从 `bilibili://comment/detail/{type}/{oid}/{rpid}` 形式的地址中取出评论区。
+fn comment_area_from_uri(uri: &str) -> Option<(u32, u64)> {
+    let rest = uri.split_once("comment/detail/")?.1;
+    let mut parts = rest.split('/');
+    let business_id = parts.next()?.parse().ok()?;
+    let oid = parts.next()?.parse().ok()?;
+    Some((business_id, oid))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -330,4 +623,56 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn like_feed_merges_latest_and_paged_items() -> BpiResult<()> {
+        let data: LikeFeedData = serde_json::from_value(serde_json::json!({
+            "latest": {
+                "items": [{"id": 2, "users": [], "item": {"item_id": 5, "type": "video"}, "counts": 1, "like_time": 20}],
+                "last_view_at": 0
+            },
+            "total": {
+                "cursor": {"is_end": true, "id": null, "time": null},
+                "items": [
+                    {"id": 2, "users": [], "item": {"item_id": 5, "type": "video"}, "counts": 1, "like_time": 20},
+                    {"id": 1, "users": [], "item": {"item_id": 7, "type": "dynamic"}, "counts": 4, "like_time": 10}
+                ]
+            }
+        }))?;
+        let items = data.merged_items();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            items[0].item.source(),
+            NotificationSource::Video(Aid::new(5)?)
+        );
+        assert!(items[1].is_merged());
+        assert_eq!(items[1].other_likers(), 4);
+        assert!(matches!(
+            items[1].item.source(),
+            NotificationSource::Dynamic(_)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn at_item_points_back_to_comment_area() -> BpiResult<()> {
+        let item: AtItem = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "user": {"mid": 2, "nickname": "u", "avatar": "", "follow": false, "fans": 0, "mid_link": ""},
+            "item": {"type": "reply", "business_id": 17, "subject_id": 300, "source_id": 400, "root_id": 0},
+            "at_time": 1700000000
+        }))?;
+
+        assert_eq!(
+            item.item.source(),
+            NotificationSource::Comment {
+                business_id: 17,
+                oid: 300,
+                rpid: 400,
+                root_id: 0,
+            }
+        );
+        Ok(())
+    }
 }
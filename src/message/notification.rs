@@ -0,0 +1,491 @@
+//! 通知中心聚合
+//!
+//! [`NotificationStream`] 轮询未读计数，只拉取有未读的通知流，并按每个通知流保存的
+//! 基线返回新出现的 [`Notification`]。进度保存在可序列化的 [`NotificationCursor`] 中。
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::message::MessageClient;
+use crate::message::msg::{
+    AtItem, LikeItem, NotificationSource, ReplyItem, SystemNotifyItem, UnreadCountData,
+};
+use crate::message::params::{
+    MessageAtFeedParams, MessageLikeFeedParams, MessageReplyFeedParams, MessageSystemNotifyParams,
+    MessageUnreadCountParams,
+};
+use crate::{BpiError, BpiResult};
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+/// 单次轮询中同一通知流最多向后追赶的页数。
+const MAX_CATCH_UP_PAGES: usize = 3;
+
+/// 通知流类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NotificationKind {
+    /// 回复我的
+    Reply,
+    /// @我的
+    At,
+    /// 收到的赞
+    Like,
+    /// 系统通知
+    System,
+}
+
+impl NotificationKind {
+    /// 全部通知流。
+    pub const ALL: [Self; 4] = [Self::Reply, Self::At, Self::Like, Self::System];
+
+    /// 返回该通知流在 [`NotificationCursor`] 中的键。
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Reply => "reply",
+            Self::At => "at",
+            Self::Like => "like",
+            Self::System => "system",
+        }
+    }
+
+    /// 返回未读计数中对应该通知流的数量。
+    pub fn unread(self, counts: &UnreadCountData) -> u32 {
+        match self {
+            Self::Reply => counts.recv_reply,
+            Self::At => counts.at,
+            Self::Like => counts.recv_like,
+            Self::System => counts.sys_msg,
+        }
+    }
+}
+
+/// 通知中心的单条通知。
+#[derive(Debug, Clone)]
+pub enum Notification {
+    Reply(ReplyItem),
+    At(AtItem),
+    Like(LikeItem),
+    System(SystemNotifyItem),
+}
+
+impl Notification {
+    pub fn kind(&self) -> NotificationKind {
+        match self {
+            Self::Reply(_) => NotificationKind::Reply,
+            Self::At(_) => NotificationKind::At,
+            Self::Like(_) => NotificationKind::Like,
+            Self::System(_) => NotificationKind::System,
+        }
+    }
+
+    pub fn id(&self) -> u64 {
+        match self {
+            Self::Reply(item) => item.id,
+            Self::At(item) => item.id,
+            Self::Like(item) => item.id,
+            Self::System(item) => item.id,
+        }
+    }
+
+    /// 通知时间 UNIX 秒级时间戳；系统通知只返回格式化时间，此处为 0。
+    pub fn time(&self) -> u64 {
+        match self {
+            Self::Reply(item) => item.reply_time,
+            Self::At(item) => item.at_time,
+            Self::Like(item) => item.like_time,
+            Self::System(_) => 0,
+        }
+    }
+
+    /// 通知指向的原始内容，系统通知没有来源。
+    pub fn source(&self) -> Option<NotificationSource> {
+        match self {
+            Self::Reply(item) => Some(item.item.source()),
+            Self::At(item) => Some(item.item.source()),
+            Self::Like(item) => Some(item.item.source()),
+            Self::System(_) => None,
+        }
+    }
+
+    fn position(&self) -> (u64, u64) {
+        (self.time(), self.id())
+    }
+}
+
+/// 单个通知流的监听基线。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationFeedCursor {
+    /// 已发出的最新通知时间。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest_time: Option<u64>,
+    /// 已发出的最新通知 ID。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest_id: Option<u64>,
+    /// 上一次轮询时的未读数。
+    #[serde(default)]
+    pub last_unread: u32,
+}
+
+impl NotificationFeedCursor {
+    fn is_initialized(&self) -> bool {
+        self.latest_id.is_some()
+    }
+
+    fn is_new(&self, notification: &Notification) -> bool {
+        let latest = (
+            self.latest_time.unwrap_or_default(),
+            self.latest_id.unwrap_or_default(),
+        );
+        notification.position() > latest
+    }
+
+    fn advance(&mut self, notifications: &[Notification]) {
+        let Some((time, id)) = notifications.iter().map(Notification::position).max() else {
+            return;
+        };
+        let latest = (
+            self.latest_time.unwrap_or_default(),
+            self.latest_id.unwrap_or_default(),
+        );
+        if !self.is_initialized() || (time, id) > latest {
+            self.latest_time = Some(time);
+            self.latest_id = Some(id);
+        }
+    }
+}
+
+/// 可持久化的通知中心进度，按 [`NotificationKind::key`] 保存每个通知流的基线。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationCursor {
+    #[serde(default)]
+    feeds: BTreeMap<String, NotificationFeedCursor>,
+}
+
+impl NotificationCursor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 返回指定通知流的基线。
+    pub fn feed(&self, kind: NotificationKind) -> Option<&NotificationFeedCursor> {
+        self.feeds.get(kind.key())
+    }
+
+    fn entry(&mut self, kind: NotificationKind) -> &mut NotificationFeedCursor {
+        self.feeds.entry(kind.key().to_string()).or_default()
+    }
+}
+
+/// 按固定间隔轮询未读计数，只拉取有未读的通知流并按进度去重返回新通知。
+///
+/// 第一次轮询某个通知流时只建立基线，不返回已有通知。多人点赞合并的通知在有新的点赞时
+/// 会以更新后的内容再次返回。
+///
+/// ```no_run
+/// # async fn run() -> bpi_rs::BpiResult<()> {
+/// use bpi_rs::BpiClient;
+/// use bpi_rs::message::{NotificationKind, NotificationStream};
+///
+/// let client = BpiClient::new()?;
+/// let mut stream = NotificationStream::new(client.message(), NotificationKind::ALL.to_vec())?;
+///
+/// loop {
+///     for notification in stream.next().await? {
+///         println!("{:?} {:?}", notification.kind(), notification.source());
+///     }
+///     let _saved = serde_json::to_string(stream.cursor())?;
+/// }
+/// # }
+/// ```
+pub struct NotificationStream<'a> {
+    client: MessageClient<'a>,
+    kinds: Vec<NotificationKind>,
+    cursor: NotificationCursor,
+    interval: Duration,
+    polled: bool,
+}
+
+impl<'a> NotificationStream<'a> {
+    /// 创建通知流，`kinds` 不能为空。
+    pub fn new(client: MessageClient<'a>, mut kinds: Vec<NotificationKind>) -> BpiResult<Self> {
+        kinds.sort();
+        kinds.dedup();
+        if kinds.is_empty() {
+            return Err(BpiError::invalid_parameter(
+                "kinds",
+                "at least one notification kind is required",
+            ));
+        }
+
+        Ok(Self {
+            client,
+            kinds,
+            cursor: NotificationCursor::default(),
+            interval: DEFAULT_INTERVAL,
+            polled: false,
+        })
+    }
+
+    /// 从之前保存的进度继续。
+    pub fn with_cursor(mut self, cursor: NotificationCursor) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    /// 设置轮询间隔。
+    pub fn with_interval(mut self, interval: Duration) -> BpiResult<Self> {
+        if interval.is_zero() {
+            return Err(BpiError::invalid_parameter(
+                "interval",
+                "value must be non-zero",
+            ));
+        }
+
+        self.interval = interval;
+        Ok(self)
+    }
+
+    /// 返回当前进度，可序列化后持久化。
+    pub fn cursor(&self) -> &NotificationCursor {
+        &self.cursor
+    }
+
+    /// 取出进度。
+    pub fn into_cursor(self) -> NotificationCursor {
+        self.cursor
+    }
+
+    /// 等待轮询间隔后轮询一次；首次调用立即轮询。
+    pub async fn next(&mut self) -> BpiResult<Vec<Notification>> {
+        if self.polled {
+            tokio::time::sleep(self.interval).await;
+        }
+        self.polled = true;
+
+        self.poll().await
+    }
+
+    /// 立即轮询一次，按通知流分组、组内从旧到新返回新通知。
+    pub async fn poll(&mut self) -> BpiResult<Vec<Notification>> {
+        let counts = self
+            .client
+            .unread_count(MessageUnreadCountParams::new())
+            .await?;
+
+        // 进度先写入副本，全部通知流成功后才提交，避免某个通知流失败时丢失已取到的通知
+        let mut staged = self.cursor.clone();
+        let mut notifications = Vec::new();
+        for kind in self.kinds.clone() {
+            let unread = kind.unread(&counts);
+            let mut state = staged.entry(kind).clone();
+            if state.is_initialized() && !needs_pull(unread) {
+                staged.entry(kind).last_unread = unread;
+                continue;
+            }
+
+            let mut fresh = self.fetch_new(kind, &mut state).await?;
+            state.last_unread = unread;
+            *staged.entry(kind) = state;
+
+            fresh.sort_by_key(Notification::position);
+            notifications.extend(fresh);
+        }
+
+        self.cursor = staged;
+        Ok(notifications)
+    }
+
+    async fn fetch_new(
+        &self,
+        kind: NotificationKind,
+        state: &mut NotificationFeedCursor,
+    ) -> BpiResult<Vec<Notification>> {
+        let initialized = state.is_initialized();
+        let mut page = Some(FeedPage::first(kind));
+        let mut new_items = Vec::new();
+
+        for _ in 0..MAX_CATCH_UP_PAGES {
+            let Some(current) = page.take() else {
+                break;
+            };
+            let (items, next) = self.fetch_page(current).await?;
+
+            if !initialized {
+                state.advance(&items);
+                if !state.is_initialized() {
+                    // 空的通知流以零点为基线，之后出现的通知都视为新通知。
+                    state.latest_time = Some(0);
+                    state.latest_id = Some(0);
+                }
+                return Ok(Vec::new());
+            }
+
+            let page_len = items.len();
+            let fresh: Vec<_> = items
+                .into_iter()
+                .filter(|item| state.is_new(item))
+                .collect();
+            let exhausted = fresh.len() < page_len;
+            new_items.extend(fresh);
+            if exhausted {
+                break;
+            }
+            page = next;
+        }
+
+        state.advance(&new_items);
+        Ok(new_items)
+    }
+
+    async fn fetch_page(&self, page: FeedPage) -> BpiResult<(Vec<Notification>, Option<FeedPage>)> {
+        Ok(match page {
+            FeedPage::Reply(params) => {
+                let data = self.client.reply_feed(params.clone()).await?;
+                let next = params.next_page(&data.cursor).map(FeedPage::Reply);
+                let items = data.items.into_iter().map(Notification::Reply).collect();
+                (items, next)
+            }
+            FeedPage::At(params) => {
+                let data = self.client.at_feed(params.clone()).await?;
+                let next = params.next_page(&data.cursor).map(FeedPage::At);
+                let items = data.items.into_iter().map(Notification::At).collect();
+                (items, next)
+            }
+            FeedPage::Like(params) => {
+                let data = self.client.like_feed(params.clone()).await?;
+                let next = params.next_page(&data.total.cursor).map(FeedPage::Like);
+                let items = data
+                    .merged_items()
+                    .into_iter()
+                    .cloned()
+                    .map(Notification::Like)
+                    .collect();
+                (items, next)
+            }
+            FeedPage::System(params) => {
+                let data = self.client.system_notify(params.clone()).await?;
+                let next = params.next_page(&data).map(FeedPage::System);
+                let items = data
+                    .system_notify_list
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(Notification::System)
+                    .collect();
+                (items, next)
+            }
+        })
+    }
+}
+
+impl<'a> MessageClient<'a> {
+    /// 创建通知中心聚合流。
+    pub fn notification_stream(
+        &self,
+        kinds: Vec<NotificationKind>,
+    ) -> BpiResult<NotificationStream<'a>> {
+        NotificationStream::new(*self, kinds)
+    }
+}
+
+enum FeedPage {
+    Reply(MessageReplyFeedParams),
+    At(MessageAtFeedParams),
+    Like(MessageLikeFeedParams),
+    System(MessageSystemNotifyParams),
+}
+
+impl FeedPage {
+    fn first(kind: NotificationKind) -> Self {
+        match kind {
+            NotificationKind::Reply => Self::Reply(MessageReplyFeedParams::new()),
+            NotificationKind::At => Self::At(MessageAtFeedParams::new()),
+            NotificationKind::Like => Self::Like(MessageLikeFeedParams::new()),
+            NotificationKind::System => Self::System(MessageSystemNotifyParams::new()),
+        }
+    }
+}
+
+/// 有未读时就拉取通知流。
+///
+/// 不比较未读数的变化：在别处读掉一条、同时来了一条新通知时未读数不变，但仍有新通知，
+/// 重复拉到的通知由进度去重。
+fn needs_pull(unread: u32) -> bool {
+    unread > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BpiClient;
+
+    fn like(id: u64, like_time: u64) -> BpiResult<Notification> {
+        let item: LikeItem = serde_json::from_value(serde_json::json!({
+            "id": id,
+            "users": [],
+            "item": {
+                "item_id": 9,
+                "type": "reply",
+                "native_uri": "bilibili://comment/detail/1/100/9/?subType=0"
+            },
+            "counts": 3,
+            "like_time": like_time
+        }))?;
+        Ok(Notification::Like(item))
+    }
+
+    #[test]
+    fn feed_cursor_treats_updated_merged_likes_as_new() -> BpiResult<()> {
+        let mut state = NotificationFeedCursor::default();
+        state.advance(&[like(5, 100)?, like(4, 90)?]);
+
+        assert_eq!(state.latest_time, Some(100));
+        assert!(!state.is_new(&like(5, 100)?));
+        assert!(state.is_new(&like(4, 120)?));
+        Ok(())
+    }
+
+    #[test]
+    fn stream_pulls_feeds_with_unread_even_when_count_is_unchanged() -> BpiResult<()> {
+        // 上次未读 1 条，读掉 1 条又来了 1 条新通知，未读数仍为 1
+        let mut state = NotificationFeedCursor::default();
+        state.advance(&[like(5, 100)?]);
+        state.last_unread = 1;
+
+        assert!(needs_pull(1));
+        assert!(!needs_pull(0));
+        assert!(state.is_new(&like(6, 110)?));
+        assert!(!state.is_new(&like(5, 100)?));
+
+        let client = BpiClient::new()?;
+        assert!(NotificationStream::new(client.message(), Vec::new()).is_err());
+        let stream = client
+            .message()
+            .notification_stream(vec![NotificationKind::Like, NotificationKind::Like])?;
+        assert_eq!(stream.kinds, vec![NotificationKind::Like]);
+        Ok(())
+    }
+
+    #[test]
+    fn like_notification_points_back_to_comment() -> BpiResult<()> {
+        let notification = like(5, 100)?;
+
+        assert_eq!(
+            notification.source(),
+            Some(NotificationSource::Comment {
+                business_id: 1,
+                oid: 100,
+                rpid: 9,
+                root_id: 0,
+            })
+        );
+        #[cfg(feature = "comment")]
+        assert!(
+            notification
+                .source()
+                .and_then(|source| source.comment_target())
+                .is_some()
+        );
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use crate::message::msg::{ReplyCursor, SystemNotifyData};
 use crate::{BpiError, BpiResult};
 
 /// `/x/im/web/msgfeed/unread` 的参数。
@@ -103,6 +104,16 @@ impl MessageReplyFeedParams {
 
         pairs
     }
+
+    /// 根据上一页的游标生成下一页参数，已到末尾时返回 `None`。
+    pub fn next_page(&self, cursor: &ReplyCursor) -> Option<Self> {
+        let (id, time) = cursor_position(cursor)?;
+        Some(Self {
+            start_id: Some(id),
+            start_time: Some(time),
+            ..self.clone()
+        })
+    }
 }
 
 /// `/session_svr/v1/session_svr/single_unread` 接受的未读分类。
@@ -192,7 +203,190 @@ impl MessageSingleUnreadParams {
     }
 }
 
-fn bool_flag(value: bool) -> &'static str {
+/// `/x/msgfeed/at` 的参数。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageAtFeedParams {
+    start_id: Option<u64>,
+    start_time: Option<u64>,
+}
+
+impl MessageAtFeedParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置上一页返回的游标 ID。
+    pub fn with_start_id(mut self, start_id: u64) -> BpiResult<Self> {
+        self.start_id = Some(validate_positive_u64("id", start_id)?);
+        Ok(self)
+    }
+
+    /// 设置上一页返回的游标时间戳。
+    pub fn with_start_time(mut self, start_time: u64) -> BpiResult<Self> {
+        self.start_time = Some(validate_positive_u64("at_time", start_time)?);
+        Ok(self)
+    }
+
+    /// 根据上一页的游标生成下一页参数，已到末尾时返回 `None`。
+    pub fn next_page(&self, cursor: &ReplyCursor) -> Option<Self> {
+        let (id, time) = cursor_position(cursor)?;
+        Some(Self {
+            start_id: Some(id),
+            start_time: Some(time),
+        })
+    }
+
+    pub(crate) fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = vec![
+            ("build", "0".to_string()),
+            ("mobi_app", "web".to_string()),
+            ("platform", "web".to_string()),
+        ];
+
+        if let Some(start_id) = self.start_id {
+            pairs.push(("id", start_id.to_string()));
+        }
+        if let Some(start_time) = self.start_time {
+            pairs.push(("at_time", start_time.to_string()));
+        }
+
+        pairs
+    }
+}
+
+/// `/x/msgfeed/like` 的参数。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageLikeFeedParams {
+    start_id: Option<u64>,
+    start_time: Option<u64>,
+}
+
+impl MessageLikeFeedParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置上一页返回的游标 ID。
+    pub fn with_start_id(mut self, start_id: u64) -> BpiResult<Self> {
+        self.start_id = Some(validate_positive_u64("id", start_id)?);
+        Ok(self)
+    }
+
+    /// 设置上一页返回的游标时间戳。
+    pub fn with_start_time(mut self, start_time: u64) -> BpiResult<Self> {
+        self.start_time = Some(validate_positive_u64("like_time", start_time)?);
+        Ok(self)
+    }
+
+    /// 根据上一页的游标生成下一页参数，已到末尾时返回 `None`。
+    pub fn next_page(&self, cursor: &ReplyCursor) -> Option<Self> {
+        let (id, time) = cursor_position(cursor)?;
+        Some(Self {
+            start_id: Some(id),
+            start_time: Some(time),
+        })
+    }
+
+    pub(crate) fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = vec![
+            ("build", "0".to_string()),
+            ("mobi_app", "web".to_string()),
+            ("platform", "web".to_string()),
+        ];
+
+        if let Some(start_id) = self.start_id {
+            pairs.push(("id", start_id.to_string()));
+        }
+        if let Some(start_time) = self.start_time {
+            pairs.push(("like_time", start_time.to_string()));
+        }
+
+        pairs
+    }
+}
+
+/// `/x/sys-msg/query_user_notify` 的参数。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageSystemNotifyParams {
+    page_size: u32,
+    cursor: Option<u64>,
+}
+
+impl Default for MessageSystemNotifyParams {
+    fn default() -> Self {
+        Self {
+            page_size: 20,
+            cursor: None,
+        }
+    }
+}
+
+impl MessageSystemNotifyParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置每页条数，取值 1-50。
+    pub fn with_page_size(mut self, page_size: u32) -> BpiResult<Self> {
+        if page_size == 0 || page_size > 50 {
+            return Err(BpiError::invalid_parameter(
+                "page_size",
+                "value must be between 1 and 50",
+            ));
+        }
+
+        self.page_size = page_size;
+        Ok(self)
+    }
+
+    /// 设置上一页最后一条通知的游标。
+    pub fn with_cursor(mut self, cursor: u64) -> BpiResult<Self> {
+        self.cursor = Some(validate_positive_u64("cursor", cursor)?);
+        Ok(self)
+    }
+
+    /// 根据上一页的结果生成下一页参数，不足一页时返回 `None`。
+    pub fn next_page(&self, data: &SystemNotifyData) -> Option<Self> {
+        let items = data.items();
+        if items.len() < self.page_size as usize {
+            return None;
+        }
+
+        let cursor = items.last()?.cursor;
+        (cursor != 0).then(|| Self {
+            cursor: Some(cursor),
+            ..self.clone()
+        })
+    }
+
+    pub(crate) fn query_pairs(&self, csrf: String) -> Vec<(&'static str, String)> {
+        let mut pairs = vec![
+            ("csrf", csrf),
+            ("page_size", self.page_size.to_string()),
+            ("build", "0".to_string()),
+            ("mobi_app", "web".to_string()),
+        ];
+
+        if let Some(cursor) = self.cursor {
+            pairs.push(("cursor", cursor.to_string()));
+        }
+
+        pairs
+    }
+}
+
+fn cursor_position(cursor: &ReplyCursor) -> Option<(u64, u64)> {
+    if cursor.is_end {
+        return None;
+    }
+
+    Some((
+        cursor.id.filter(|id| *id != 0)?,
+        cursor.time.filter(|time| *time != 0)?,
+    ))
+}
+
+pub(super) fn bool_flag(value: bool) -> &'static str {
     if value { "1" } else { "0" }
 }
 
@@ -367,4 +561,51 @@ mod tests {
             }
         ));
     }
+
+    #[test]
+    fn at_and_like_params_follow_feed_cursor() {
+        let cursor = ReplyCursor {
+            is_end: false,
+            id: Some(7),
+            time: Some(1_700_000_000),
+        };
+        let at = MessageAtFeedParams::new().next_page(&cursor).expect("more");
+        let like = MessageLikeFeedParams::new()
+            .next_page(&cursor)
+            .expect("more");
+
+        assert!(
+            at.query_pairs()
+                .contains(&("at_time", "1700000000".to_string()))
+        );
+        assert!(
+            like.query_pairs()
+                .contains(&("like_time", "1700000000".to_string()))
+        );
+        assert!(
+            MessageAtFeedParams::new()
+                .next_page(&ReplyCursor {
+                    is_end: true,
+                    ..cursor
+                })
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn system_notify_params_page_by_last_cursor() -> BpiResult<()> {
+        let params = MessageSystemNotifyParams::new().with_page_size(2)?;
+        let data: SystemNotifyData = serde_json::from_str(
+            r#"{"system_notify_list":[{"id":3,"cursor":30},{"id":2,"cursor":20}]}"#,
+        )?;
+        let next = params.next_page(&data).expect("full page");
+
+        assert!(
+            next.query_pairs("csrf".to_string())
+                .contains(&("cursor", "20".to_string()))
+        );
+        assert!(MessageSystemNotifyParams::new().next_page(&data).is_none());
+        assert!(MessageSystemNotifyParams::new().with_page_size(51).is_err());
+        Ok(())
+    }
 }
@@ -4,12 +4,26 @@ use crate::BilibiliRequest;
 use crate::BpiError;
 use crate::BpiResult;
 use crate::message::MessageClient;
+use crate::message::params::bool_flag;
+use crate::message::private_msg_content::PrivateMessageContent;
 use chrono::Utc;
+use reqwest::multipart::{Form, Part};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use serde_json::json;
+use std::path::PathBuf;
 use uuid::Uuid;
 
+const SESSIONS_ENDPOINT: &str =
+    "https://api.vc.bilibili.com/session_svr/v1/session_svr/get_sessions";
+const SESSION_MSGS_ENDPOINT: &str =
+    "https://api.vc.bilibili.com/svr_sync/v1/svr_sync/fetch_session_msgs";
+const UPDATE_ACK_ENDPOINT: &str =
+    "https://api.vc.bilibili.com/session_svr/v1/session_svr/update_ack";
+const UPLOAD_IMAGE_ENDPOINT: &str = "https://api.bilibili.com/x/dynamic/feed/draw/upload_bfs";
+
+/// 单次拉取会话消息的最大条数。
+pub const SESSION_MSGS_MAX_SIZE: u32 = 200;
+
 /// 未读私信数数据
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -62,7 +76,173 @@ pub struct Image {
     pub size: f64,
 }
 
-/// 私信消息类型
+/// 私信消息
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PrivateMessage {
+    /// 发送者 mid
+    pub sender_uid: u64,
+    /// 接收者类型 1: 用户 2: 粉丝团
+    pub receiver_type: u32,
+    /// 接收者 ID
+    pub receiver_id: u64,
+    /// 消息类型，见 [`PrivateMessageContent`]
+    pub msg_type: u32,
+    /// 原始消息内容，通常为 JSON 字符串
+    pub content: String,
+    /// 会话内的消息序号
+    pub msg_seqno: u64,
+    /// 发送时间 UNIX秒级时间戳
+    pub timestamp: i64,
+    /// 被 @ 的用户
+    #[serde(default)]
+    pub at_uids: Option<Vec<u64>>,
+    /// 消息唯一键，撤回时引用
+    pub msg_key: u64,
+    /// 消息状态 0: 正常 1: 已撤回
+    #[serde(default)]
+    pub msg_status: u32,
+    /// 通知类消息的通知码
+    #[serde(default)]
+    pub notify_code: Option<String>,
+    /// 表情版本
+    #[serde(default)]
+    pub new_face_version: Option<u32>,
+    /// 消息来源
+    #[serde(default)]
+    pub msg_source: Option<u32>,
+}
+
+impl PrivateMessage {
+    /// 按 `msg_type` 解析消息内容。
+    pub fn parsed_content(&self) -> BpiResult<PrivateMessageContent> {
+        PrivateMessageContent::parse(self.msg_type, &self.content)
+    }
+}
+
+/// 私信会话
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MessageSession {
+    /// 对方 mid 或粉丝团 ID
+    pub talker_id: u64,
+    /// 会话类型 1: 用户 2: 粉丝团
+    pub session_type: u32,
+    /// 最近一条 @ 我的消息序号
+    #[serde(default)]
+    pub at_seqno: u64,
+    /// 置顶时间 微秒级时间戳，0 为未置顶
+    #[serde(default)]
+    pub top_ts: i64,
+    /// 粉丝团名称
+    #[serde(default)]
+    pub group_name: Option<String>,
+    /// 粉丝团头像
+    #[serde(default)]
+    pub group_cover: Option<String>,
+    /// 是否已关注对方
+    #[serde(default)]
+    pub is_follow: u8,
+    /// 是否开启免打扰
+    #[serde(default)]
+    pub is_dnd: u8,
+    /// 已读到的消息序号
+    #[serde(default)]
+    pub ack_seqno: u64,
+    /// 已读时间 微秒级时间戳
+    #[serde(default)]
+    pub ack_ts: i64,
+    /// 会话时间 微秒级时间戳，用于翻页
+    pub session_ts: i64,
+    /// 未读消息数
+    #[serde(default)]
+    pub unread_count: u32,
+    /// 最近一条消息
+    #[serde(default)]
+    pub last_msg: Option<PrivateMessage>,
+    /// 最新的消息序号
+    #[serde(default)]
+    pub max_seqno: u64,
+    /// 是否被拦截
+    #[serde(default)]
+    pub is_intercept: u8,
+}
+
+/// 会话列表
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SessionListData {
+    /// 会话列表，无会话时为 `null`
+    #[serde(default)]
+    pub session_list: Option<Vec<MessageSession>>,
+    /// 是否还有更多 0: 否 1: 是
+    #[serde(default)]
+    pub has_more: u8,
+}
+
+impl SessionListData {
+    pub fn sessions(&self) -> &[MessageSession] {
+        self.session_list.as_deref().unwrap_or_default()
+    }
+}
+
+/// 会话消息记录
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SessionMsgsData {
+    /// 消息列表，按序号从新到旧排列；无消息时为 `null`
+    #[serde(default)]
+    pub messages: Option<Vec<PrivateMessage>>,
+    /// 是否还有更早的消息 0: 否 1: 是
+    #[serde(default)]
+    pub has_more: u8,
+    /// 本页最小序号
+    #[serde(default)]
+    pub min_seqno: u64,
+    /// 本页最大序号
+    #[serde(default)]
+    pub max_seqno: u64,
+    /// 消息中用到的表情
+    #[serde(default)]
+    pub e_infos: Option<Vec<EmojiInfo>>,
+}
+
+impl SessionMsgsData {
+    pub fn messages(&self) -> &[PrivateMessage] {
+        self.messages.as_deref().unwrap_or_default()
+    }
+}
+
+/// 私信图片上传的响应数据
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MessageImageUploadData {
+    /// 已上传图片 URL
+    pub image_url: String,
+    /// 已上传图片宽度
+    pub image_width: u64,
+    /// 已上传图片高度
+    pub image_height: u64,
+    /// 已上传图片大小 单位为 KiB
+    pub img_size: f64,
+}
+
+impl MessageImageUploadData {
+    /// 转为发送图片私信所需的图片信息。
+    pub fn into_image(self) -> Image {
+        let image_type = self
+            .image_url
+            .rsplit_once('.')
+            .map(|(_, ext)| ext.to_ascii_lowercase())
+            .filter(|ext| !ext.contains('/'));
+
+        Image {
+            url: self.image_url,
+            height: self.image_height,
+            width: self.image_width,
+            image_type,
+            original: Some(1),
+            size: self.img_size,
+        }
+    }
+}
+
+/// 发送私信时常用的消息类型，编码见 [`PrivateMessageContent`]。
 pub enum MessageType {
     /// 文本消息，内容为纯文本
     Text(String),
@@ -74,33 +254,424 @@ pub enum MessageType {
 pub struct MessageSendParams {
     receiver_id: u64,
     receiver_type: u32,
-    message_type: MessageType,
+    content: PrivateMessageContent,
 }
 
 impl MessageSendParams {
-    pub fn new(receiver_id: u64, receiver_type: u32, message_type: MessageType) -> BpiResult<Self> {
-        if receiver_id == 0 {
+    /// `receiver_type` 为 1 时发给用户，为 2 时发到群聊；内容可传入 [`MessageType`] 或
+    /// [`PrivateMessageContent`]。
+    pub fn new(
+        receiver_id: u64,
+        receiver_type: u32,
+        content: impl Into<PrivateMessageContent>,
+    ) -> BpiResult<Self> {
+        validate_receiver(receiver_id, receiver_type)?;
+
+        Ok(Self {
+            receiver_id,
+            receiver_type,
+            content: content.into(),
+        })
+    }
+}
+
+/// 会话列表的筛选类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageSessionListType {
+    /// 已关注的用户和系统消息
+    Followed,
+    /// 未关注的用户
+    Unfollowed,
+    /// 粉丝团
+    FanGroup,
+    /// 全部
+    All,
+    /// 被拦截的消息
+    Blocked,
+}
+
+impl MessageSessionListType {
+    fn code(self) -> u32 {
+        match self {
+            Self::Followed => 1,
+            Self::Unfollowed => 2,
+            Self::FanGroup => 3,
+            Self::All => 4,
+            Self::Blocked => 5,
+        }
+    }
+}
+
+/// 会话对象的类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageTalkerType {
+    /// 用户
+    User,
+    /// 粉丝团
+    FanGroup,
+}
+
+impl MessageTalkerType {
+    fn code(self) -> u32 {
+        match self {
+            Self::User => 1,
+            Self::FanGroup => 2,
+        }
+    }
+
+    fn from_code(code: u32) -> BpiResult<Self> {
+        match code {
+            1 => Ok(Self::User),
+            2 => Ok(Self::FanGroup),
+            _ => Err(BpiError::invalid_parameter(
+                "session_type",
+                "value must be 1 or 2",
+            )),
+        }
+    }
+}
+
+/// `/session_svr/v1/session_svr/get_sessions` 的参数。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageSessionsParams {
+    session_type: MessageSessionListType,
+    group_fold: bool,
+    unfollow_fold: bool,
+    sort_rule: u32,
+    begin_ts: Option<i64>,
+    end_ts: Option<i64>,
+}
+
+impl MessageSessionsParams {
+    pub fn new(session_type: MessageSessionListType) -> Self {
+        Self {
+            session_type,
+            group_fold: true,
+            unfollow_fold: false,
+            sort_rule: 2,
+            begin_ts: None,
+            end_ts: None,
+        }
+    }
+
+    /// 是否折叠粉丝团消息。
+    pub fn group_fold(mut self, fold: bool) -> Self {
+        self.group_fold = fold;
+        self
+    }
+
+    /// 是否折叠未关注人消息。
+    pub fn unfollow_fold(mut self, fold: bool) -> Self {
+        self.unfollow_fold = fold;
+        self
+    }
+
+    /// 只返回 `session_ts` 晚于该值的会话，微秒级时间戳。
+    pub fn with_begin_ts(mut self, begin_ts: i64) -> BpiResult<Self> {
+        self.begin_ts = Some(validate_positive_ts("begin_ts", begin_ts)?);
+        Ok(self)
+    }
+
+    /// 只返回 `session_ts` 早于该值的会话，微秒级时间戳，用于翻页。
+    pub fn with_end_ts(mut self, end_ts: i64) -> BpiResult<Self> {
+        self.end_ts = Some(validate_positive_ts("end_ts", end_ts)?);
+        Ok(self)
+    }
+
+    /// 根据本页结果生成下一页的参数，没有更多会话时返回 `None`。
+    pub fn next_page(&self, data: &SessionListData) -> Option<Self> {
+        if data.has_more == 0 {
+            return None;
+        }
+
+        let end_ts = data
+            .sessions()
+            .iter()
+            .map(|session| session.session_ts)
+            .filter(|ts| *ts > 0)
+            .min()?;
+        let mut next = self.clone();
+        next.end_ts = Some(end_ts);
+        Some(next)
+    }
+
+    pub(crate) fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = vec![
+            ("session_type", self.session_type.code().to_string()),
+            ("group_fold", bool_flag(self.group_fold).to_string()),
+            ("unfollow_fold", bool_flag(self.unfollow_fold).to_string()),
+            ("sort_rule", self.sort_rule.to_string()),
+            ("build", "0".to_string()),
+            ("mobi_app", "web".to_string()),
+        ];
+
+        if let Some(begin_ts) = self.begin_ts {
+            pairs.push(("begin_ts", begin_ts.to_string()));
+        }
+        if let Some(end_ts) = self.end_ts {
+            pairs.push(("end_ts", end_ts.to_string()));
+        }
+
+        pairs
+    }
+}
+
+/// `/svr_sync/v1/svr_sync/fetch_session_msgs` 的参数。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageSessionMsgsParams {
+    talker_id: u64,
+    session_type: MessageTalkerType,
+    size: u32,
+    begin_seqno: Option<u64>,
+    end_seqno: Option<u64>,
+}
+
+impl MessageSessionMsgsParams {
+    pub fn new(talker_id: u64, session_type: MessageTalkerType) -> BpiResult<Self> {
+        if talker_id == 0 {
             return Err(BpiError::invalid_parameter(
-                "receiver_id",
+                "talker_id",
                 "id must be non-zero",
             ));
         }
-        if !matches!(receiver_type, 1 | 2) {
+
+        Ok(Self {
+            talker_id,
+            session_type,
+            size: 20,
+            begin_seqno: None,
+            end_seqno: None,
+        })
+    }
+
+    /// 为会话列表中的会话创建参数。
+    pub fn for_session(session: &MessageSession) -> BpiResult<Self> {
+        Self::new(
+            session.talker_id,
+            MessageTalkerType::from_code(session.session_type)?,
+        )
+    }
+
+    /// 设置返回条数，取值 1-200。
+    pub fn with_size(mut self, size: u32) -> BpiResult<Self> {
+        if size == 0 || size > SESSION_MSGS_MAX_SIZE {
             return Err(BpiError::invalid_parameter(
-                "receiver_type",
-                "value must be 1 or 2",
+                "size",
+                "value must be between 1 and 200",
+            ));
+        }
+
+        self.size = size;
+        Ok(self)
+    }
+
+    /// 只返回序号大于该值的消息，用于增量同步。
+    pub fn with_begin_seqno(mut self, begin_seqno: u64) -> Self {
+        self.begin_seqno = Some(begin_seqno);
+        self
+    }
+
+    /// 只返回序号小于该值的消息，用于向前翻页。
+    pub fn with_end_seqno(mut self, end_seqno: u64) -> BpiResult<Self> {
+        if end_seqno == 0 {
+            return Err(BpiError::invalid_parameter(
+                "end_seqno",
+                "value must be non-zero",
+            ));
+        }
+
+        self.end_seqno = Some(end_seqno);
+        Ok(self)
+    }
+
+    /// 根据本页结果生成更早一页的参数，没有更多消息时返回 `None`。
+    pub fn previous_page(&self, data: &SessionMsgsData) -> Option<Self> {
+        if data.has_more == 0 || data.min_seqno == 0 {
+            return None;
+        }
+
+        let mut previous = self.clone();
+        previous.end_seqno = Some(data.min_seqno);
+        Some(previous)
+    }
+
+    pub(crate) fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = vec![
+            ("talker_id", self.talker_id.to_string()),
+            ("session_type", self.session_type.code().to_string()),
+            ("size", self.size.to_string()),
+            ("sender_device_id", "1".to_string()),
+            ("build", "0".to_string()),
+            ("mobi_app", "web".to_string()),
+        ];
+
+        if let Some(begin_seqno) = self.begin_seqno {
+            pairs.push(("begin_seqno", begin_seqno.to_string()));
+        }
+        if let Some(end_seqno) = self.end_seqno {
+            pairs.push(("end_seqno", end_seqno.to_string()));
+        }
+
+        pairs
+    }
+}
+
+/// `/session_svr/v1/session_svr/update_ack` 的参数。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageUpdateAckParams {
+    talker_id: u64,
+    session_type: MessageTalkerType,
+    ack_seqno: u64,
+}
+
+impl MessageUpdateAckParams {
+    pub fn new(talker_id: u64, session_type: MessageTalkerType, ack_seqno: u64) -> BpiResult<Self> {
+        if talker_id == 0 {
+            return Err(BpiError::invalid_parameter(
+                "talker_id",
+                "id must be non-zero",
             ));
         }
 
         Ok(Self {
-            receiver_id,
-            receiver_type,
-            message_type,
+            talker_id,
+            session_type,
+            ack_seqno,
         })
     }
+
+    /// 将会话标记为已读到最新一条消息。
+    pub fn for_session(session: &MessageSession) -> BpiResult<Self> {
+        Self::new(
+            session.talker_id,
+            MessageTalkerType::from_code(session.session_type)?,
+            session.max_seqno,
+        )
+    }
+
+    fn form_pairs(&self, csrf: String) -> Vec<(&'static str, String)> {
+        vec![
+            ("talker_id", self.talker_id.to_string()),
+            ("session_type", self.session_type.code().to_string()),
+            ("ack_seqno", self.ack_seqno.to_string()),
+            ("build", "0".to_string()),
+            ("mobi_app", "web".to_string()),
+            ("csrf_token", csrf.clone()),
+            ("csrf", csrf),
+        ]
+    }
+}
+
+/// 上传私信图片的参数。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageImageUploadParams {
+    file_path: PathBuf,
+}
+
+impl MessageImageUploadParams {
+    pub fn new(file_path: impl Into<PathBuf>) -> Self {
+        Self {
+            file_path: file_path.into(),
+        }
+    }
+
+    fn mime_type(&self) -> &'static str {
+        let ext = self
+            .file_path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_ascii_lowercase());
+        match ext.as_deref() {
+            Some("png") => "image/png",
+            Some("gif") => "image/gif",
+            Some("webp") => "image/webp",
+            _ => "image/jpeg",
+        }
+    }
 }
 
 impl<'a> MessageClient<'a> {
+    /// 分页获取私信会话列表。
+    pub async fn sessions(&self, params: MessageSessionsParams) -> BpiResult<SessionListData> {
+        self.client
+            .get(SESSIONS_ENDPOINT)
+            .query(&params.query_pairs())
+            .send_bpi_payload("message.private.sessions")
+            .await
+    }
+
+    /// 获取会话的消息记录。
+    pub async fn fetch_session_msgs(
+        &self,
+        params: MessageSessionMsgsParams,
+    ) -> BpiResult<SessionMsgsData> {
+        self.client
+            .get(SESSION_MSGS_ENDPOINT)
+            .query(&params.query_pairs())
+            .send_bpi_payload("message.private.session_msgs")
+            .await
+    }
+
+    /// 将会话标记为已读。
+    pub async fn update_ack(&self, params: MessageUpdateAckParams) -> BpiResult<Option<Value>> {
+        let csrf = self.client.csrf()?;
+
+        self.client
+            .post(UPDATE_ACK_ENDPOINT)
+            .form(&params.form_pairs(csrf))
+            .send_bpi_optional_payload("message.private.update_ack")
+            .await
+    }
+
+    /// 上传私信图片。
+    pub async fn upload_image(
+        &self,
+        params: MessageImageUploadParams,
+    ) -> BpiResult<MessageImageUploadData> {
+        let csrf = self.client.csrf()?;
+        let bytes = tokio::fs::read(&params.file_path)
+            .await
+            .map_err(|e| BpiError::parse(format!("读取文件失败: {}", e)))?;
+        let file_name = params
+            .file_path
+            .file_name()
+            .ok_or_else(|| BpiError::parse("Invalid file path, cannot get file name"))?
+            .to_string_lossy()
+            .into_owned();
+
+        let file_part = Part::bytes(bytes)
+            .file_name(file_name)
+            .mime_str(params.mime_type())?;
+        let form = Form::new()
+            .part("file_up", file_part)
+            .text("csrf", csrf)
+            .text("category", "daily")
+            .text("biz", "im");
+
+        self.client
+            .post(UPLOAD_IMAGE_ENDPOINT)
+            .multipart(form)
+            .send_bpi_payload("message.private.upload_image")
+            .await
+    }
+
+    /// 上传图片并作为私信发送，`receiver_type` 与 [`MessageSendParams::new`] 相同。
+    pub async fn send_image(
+        &self,
+        receiver_id: u64,
+        receiver_type: u32,
+        params: MessageImageUploadParams,
+    ) -> BpiResult<SendMsgData> {
+        // 先校验接收方，避免参数错误时白白上传图片
+        validate_receiver(receiver_id, receiver_type)?;
+        let image = self.upload_image(params).await?.into_image();
+        self.send(MessageSendParams::new(
+            receiver_id,
+            receiver_type,
+            MessageType::Image(image),
+        )?)
+        .await
+    }
+
     /// 发送私信并返回标准 payload 结果。
     pub async fn send(&self, params: MessageSendParams) -> BpiResult<SendMsgData> {
         let csrf = self.client.csrf()?;
@@ -112,16 +683,11 @@ impl<'a> MessageClient<'a> {
         let dev_id = Uuid::new_v4().to_string();
         let timestamp = Utc::now().timestamp();
 
-        let msg_type = match &params.message_type {
-            MessageType::Text(_) => 1,
-            MessageType::Image(_) => 2,
-        };
-
         let mut form = vec![
             ("msg[sender_uid]", sender_uid.to_string()),
             ("msg[receiver_id]", params.receiver_id.to_string()),
             ("msg[receiver_type]", params.receiver_type.to_string()),
-            ("msg[msg_type]", msg_type.to_string()),
+            ("msg[msg_type]", params.content.msg_type().to_string()),
             ("msg[msg_status]", "0".to_string()),
             ("msg[dev_id]", dev_id.clone()),
             ("msg[timestamp]", timestamp.to_string()),
@@ -132,12 +698,7 @@ impl<'a> MessageClient<'a> {
             ("mobi_app", "web".to_string()),
         ];
 
-        let content = match params.message_type {
-            MessageType::Text(text) => json!({ "content": text }).to_string(),
-            MessageType::Image(image) => serde_json::to_string(&image)?,
-        };
-
-        form.push(("msg[content]", content));
+        form.push(("msg[content]", params.content.to_content()?));
 
         let params = vec![
             ("w_sender_uid", sender_uid.to_string()),
@@ -157,6 +718,31 @@ impl<'a> MessageClient<'a> {
     }
 }
 
+fn validate_receiver(receiver_id: u64, receiver_type: u32) -> BpiResult<()> {
+    if receiver_id == 0 {
+        return Err(BpiError::invalid_parameter(
+            "receiver_id",
+            "id must be non-zero",
+        ));
+    }
+    if !matches!(receiver_type, 1 | 2) {
+        return Err(BpiError::invalid_parameter(
+            "receiver_type",
+            "value must be 1 or 2",
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_positive_ts(field: &'static str, value: i64) -> BpiResult<i64> {
+    if value <= 0 {
+        return Err(BpiError::invalid_parameter(field, "value must be positive"));
+    }
+
+    Ok(value)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -171,6 +757,17 @@ mod tests {
         ))
     }
 
+    #[test]
+    fn send_params_accept_group_receivers_and_typed_content() -> BpiResult<()> {
+        let params = MessageSendParams::new(1_000_002, 2, MessageType::Text("hi".to_string()))?;
+
+        assert_eq!(params.receiver_type, 2);
+        assert_eq!(params.content.msg_type(), 1);
+        assert!(MessageSendParams::new(0, 1, MessageType::Text("hi".to_string())).is_err());
+        assert!(validate_receiver(1_000_002, 3).is_err());
+        Ok(())
+    }
+
     #[test]
     fn message_single_unread_contract_matches_endpoint_request() -> BpiResult<()> {
         let contract = contract()?;
@@ -245,6 +842,100 @@ mod tests {
         Ok(())
     }
 
+    fn session_list() -> BpiResult<SessionListData> {
+        Ok(serde_json::from_str(
+            r#"{"session_list":[
+                {"talker_id":2,"session_type":1,"session_ts":1700000000000002,"unread_count":3,"max_seqno":9,
+                 "last_msg":{"sender_uid":2,"receiver_type":1,"receiver_id":1,"msg_type":1,"content":"{\"content\":\"hi\"}","msg_seqno":9,"timestamp":1700000000,"msg_key":42}},
+                {"talker_id":3,"session_type":2,"session_ts":1700000000000001,"last_msg":null}
+            ],"has_more":1}"#,
+        )?)
+    }
+
+    #[test]
+    fn sessions_params_page_by_oldest_session_ts() -> BpiResult<()> {
+        let params = MessageSessionsParams::new(MessageSessionListType::All);
+        let data = session_list()?;
+        let next = params.next_page(&data).expect("has more");
+
+        assert!(
+            next.query_pairs()
+                .contains(&("end_ts", "1700000000000001".to_string()))
+        );
+        assert!(
+            params
+                .query_pairs()
+                .contains(&("session_type", "4".to_string()))
+        );
+
+        let done = SessionListData {
+            has_more: 0,
+            ..data
+        };
+        assert!(params.next_page(&done).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn session_last_message_parses_typed_content() -> BpiResult<()> {
+        let data = session_list()?;
+        let session = &data.sessions()[0];
+        let content = session
+            .last_msg
+            .as_ref()
+            .expect("last msg")
+            .parsed_content()?;
+
+        assert_eq!(content.summary(), "hi");
+        let ack = MessageUpdateAckParams::for_session(session)?.form_pairs("csrf".to_string());
+        assert!(ack.contains(&("ack_seqno", "9".to_string())));
+        assert!(ack.contains(&("csrf_token", "csrf".to_string())));
+        Ok(())
+    }
+
+    #[test]
+    fn session_msgs_params_validate_and_page_backwards() -> BpiResult<()> {
+        let params = MessageSessionMsgsParams::new(2, MessageTalkerType::User)?
+            .with_size(50)?
+            .with_begin_seqno(3);
+        let data: SessionMsgsData =
+            serde_json::from_str(r#"{"messages":null,"has_more":1,"min_seqno":4,"max_seqno":8}"#)?;
+        let previous = params.previous_page(&data).expect("has more");
+
+        assert!(data.messages().is_empty());
+        assert!(
+            previous
+                .query_pairs()
+                .contains(&("end_seqno", "4".to_string()))
+        );
+        assert!(
+            params
+                .query_pairs()
+                .contains(&("begin_seqno", "3".to_string()))
+        );
+        assert!(MessageSessionMsgsParams::new(0, MessageTalkerType::User).is_err());
+        assert!(params.with_size(201).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn uploaded_image_converts_to_send_payload() {
+        let image = MessageImageUploadData {
+            image_url: "https://i0.hdslb.com/bfs/new_dyn/a.PNG".to_string(),
+            image_width: 20,
+            image_height: 10,
+            img_size: 1.5,
+        }
+        .into_image();
+
+        assert_eq!(image.image_type.as_deref(), Some("png"));
+        assert_eq!(image.width, 20);
+        assert_eq!(
+            MessageImageUploadParams::new("shot.webp").mime_type(),
+            "image/webp"
+        );
+    }
+
     fn local_probe_body(profile: &str) -> Option<serde_json::Value> {
         let path =
             format!("target/bpi-probe-runs/message/read/single-unread/{profile}.response.json");
@@ -1 +1,213 @@
 // https://github.com/Yuelioi/bilibili-API-collect/tree/cfc5fddcc8a94b74d91970bb5b4eaeb349addc47/docs/message/private_msg_content.md
+
+use serde::{Deserialize, Serialize};
+
+use crate::BpiResult;
+use crate::message::private_msg::{Image, MessageType};
+
+const MSG_TYPE_TEXT: u32 = 1;
+const MSG_TYPE_IMAGE: u32 = 2;
+const MSG_TYPE_WITHDRAW: u32 = 5;
+const MSG_TYPE_SHARE: u32 = 7;
+const MSG_TYPE_NOTIFICATION: u32 = 10;
+
+/// 私信消息内容，按 `msg_type` 解析 `content` 字段得到，发送私信时也按它编码。
+#[derive(Debug, Clone)]
+pub enum PrivateMessageContent {
+    /// 文本消息，`msg_type=1`
+    Text(TextContent),
+    /// 图片消息，`msg_type=2`
+    Image(Image),
+    /// 撤回消息，`msg_type=5`，内容为被撤回消息的 `msg_key`
+    Withdraw(String),
+    /// 分享卡片，`msg_type=7`
+    Share(ShareContent),
+    /// 通知卡片，`msg_type=10`
+    Notification(NotificationContent),
+    /// 尚未建模的消息类型，保留原始内容
+    Unknown { msg_type: u32, content: String },
+}
+
+impl PrivateMessageContent {
+    /// 按消息类型解析原始 `content` 字符串。
+    pub fn parse(msg_type: u32, content: &str) -> BpiResult<Self> {
+        Ok(match msg_type {
+            MSG_TYPE_TEXT => Self::Text(serde_json::from_str(content)?),
+            MSG_TYPE_IMAGE => Self::Image(serde_json::from_str(content)?),
+            MSG_TYPE_WITHDRAW => Self::Withdraw(content.trim_matches('"').to_string()),
+            MSG_TYPE_SHARE => Self::Share(serde_json::from_str(content)?),
+            MSG_TYPE_NOTIFICATION => Self::Notification(serde_json::from_str(content)?),
+            _ => Self::Unknown {
+                msg_type,
+                content: content.to_string(),
+            },
+        })
+    }
+
+    /// 消息类型，即 `msg_type`。
+    pub fn msg_type(&self) -> u32 {
+        match self {
+            Self::Text(_) => MSG_TYPE_TEXT,
+            Self::Image(_) => MSG_TYPE_IMAGE,
+            Self::Withdraw(_) => MSG_TYPE_WITHDRAW,
+            Self::Share(_) => MSG_TYPE_SHARE,
+            Self::Notification(_) => MSG_TYPE_NOTIFICATION,
+            Self::Unknown { msg_type, .. } => *msg_type,
+        }
+    }
+
+    /// 编码为 `content` 字符串，与 [`Self::parse`] 互逆。
+    pub fn to_content(&self) -> BpiResult<String> {
+        Ok(match self {
+            Self::Text(text) => serde_json::to_string(text)?,
+            Self::Image(image) => serde_json::to_string(image)?,
+            Self::Withdraw(key) => key.clone(),
+            Self::Share(share) => serde_json::to_string(share)?,
+            Self::Notification(notification) => serde_json::to_string(notification)?,
+            Self::Unknown { content, .. } => content.clone(),
+        })
+    }
+
+    /// 消息的纯文本摘要，用于日志或客服工单。
+    pub fn summary(&self) -> String {
+        match self {
+            Self::Text(text) => text.content.clone(),
+            Self::Image(image) => format!("[图片] {}", image.url),
+            Self::Withdraw(_) => "[撤回了一条消息]".to_string(),
+            Self::Share(share) => format!("[分享] {}", share.title),
+            Self::Notification(notification) => {
+                format!("[通知] {}", notification.title)
+            }
+            Self::Unknown { msg_type, .. } => format!("[消息类型 {msg_type}]"),
+        }
+    }
+}
+
+impl From<MessageType> for PrivateMessageContent {
+    fn from(message: MessageType) -> Self {
+        match message {
+            MessageType::Text(content) => Self::Text(TextContent { content }),
+            MessageType::Image(image) => Self::Image(image),
+        }
+    }
+}
+
+/// 文本消息内容
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TextContent {
+    pub content: String,
+}
+
+/// 分享卡片内容
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ShareContent {
+    /// 作者昵称
+    pub author: String,
+    /// 卡片副标题
+    pub headline: String,
+    /// 分享对象 ID，视频为 aid
+    pub id: u64,
+    /// 分享来源类型，5 为视频
+    pub source: i32,
+    /// 封面
+    pub thumb: String,
+    /// 标题
+    pub title: String,
+    /// 跳转地址
+    pub url: String,
+    /// 视频 bvid
+    pub bvid: String,
+}
+
+/// 通知卡片内容
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NotificationContent {
+    /// 标题
+    pub title: String,
+    /// 正文
+    pub text: String,
+    /// 跳转按钮文字
+    pub jump_text: String,
+    /// 跳转地址
+    pub jump_uri: String,
+    /// 附加信息列表
+    pub modules: Vec<NotificationModule>,
+}
+
+/// 通知卡片的附加信息
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NotificationModule {
+    pub title: String,
+    pub detail: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_dispatches_on_message_type() -> BpiResult<()> {
+        let text = PrivateMessageContent::parse(1, r#"{"content":"你好"}"#)?;
+        let image = PrivateMessageContent::parse(
+            2,
+            r#"{"url":"https://i0.hdslb.com/bfs/im/a.png","height":10,"width":20,"imageType":"png","original":1,"size":1.5}"#,
+        )?;
+        let withdraw = PrivateMessageContent::parse(5, "7123456789")?;
+        let share = PrivateMessageContent::parse(
+            7,
+            r#"{"author":"up","id":2,"source":5,"thumb":"","title":"视频","bvid":"BV1xx411c7mD"}"#,
+        )?;
+        let notification = PrivateMessageContent::parse(
+            10,
+            r#"{"title":"通知","text":"内容","modules":[{"title":"时间","detail":"今天"}]}"#,
+        )?;
+        let unknown = PrivateMessageContent::parse(18, r#"{"content":"系统提示"}"#)?;
+
+        assert!(matches!(text, PrivateMessageContent::Text(ref text) if text.content == "你好"));
+        assert!(matches!(image, PrivateMessageContent::Image(ref image) if image.width == 20));
+        assert!(
+            matches!(withdraw, PrivateMessageContent::Withdraw(ref key) if key == "7123456789")
+        );
+        assert!(matches!(share, PrivateMessageContent::Share(ref share) if share.id == 2));
+        assert!(matches!(
+            notification,
+            PrivateMessageContent::Notification(ref notification)
+                if notification.modules[0].detail == "今天"
+        ));
+        assert!(matches!(
+            unknown,
+            PrivateMessageContent::Unknown { msg_type: 18, .. }
+        ));
+        assert_eq!(share.summary(), "[分享] 视频");
+        Ok(())
+    }
+
+    #[test]
+    fn content_round_trips_through_msg_type_and_encoding() -> BpiResult<()> {
+        let text = PrivateMessageContent::from(MessageType::Text("你好".to_string()));
+        assert_eq!(text.msg_type(), 1);
+        assert_eq!(text.to_content()?, r#"{"content":"你好"}"#);
+
+        for content in [
+            text,
+            PrivateMessageContent::Withdraw("7123456789".to_string()),
+            PrivateMessageContent::Unknown {
+                msg_type: 18,
+                content: "{}".to_string(),
+            },
+        ] {
+            let parsed = PrivateMessageContent::parse(content.msg_type(), &content.to_content()?)?;
+            assert_eq!(parsed.summary(), content.summary());
+            assert_eq!(parsed.msg_type(), content.msg_type());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn content_rejects_malformed_text() {
+        assert!(PrivateMessageContent::parse(1, "not json").is_err());
+    }
+}
@@ -1 +1,162 @@
+//! 私信设置
 
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::message::MessageClient;
+use crate::message::params::bool_flag;
+use crate::{BilibiliRequest, BpiError, BpiResult};
+
+const SETTINGS_GET_ENDPOINT: &str = "https://api.vc.bilibili.com/link_setting/v1/link_setting/get";
+const SETTINGS_SET_ENDPOINT: &str = "https://api.vc.bilibili.com/link_setting/v1/link_setting/set";
+
+/// 私信设置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MessageSettingsData {
+    /// 是否接收未关注人的消息 0: 否 1: 是
+    #[serde(default)]
+    pub show_unfollowed_msg: Option<u8>,
+    /// 消息提醒方式 1: 全部 2: 仅已关注 3: 关闭
+    #[serde(default)]
+    pub msg_notify: Option<u8>,
+    /// 其余尚未建模的设置项
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, Value>,
+}
+
+impl MessageSettingsData {
+    pub fn notify_mode(&self) -> Option<MessageNotifyMode> {
+        self.msg_notify.and_then(MessageNotifyMode::from_code)
+    }
+}
+
+/// 私信消息提醒方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageNotifyMode {
+    /// 接收全部提醒
+    All,
+    /// 仅提醒已关注人的消息
+    Followed,
+    /// 关闭提醒
+    Off,
+}
+
+impl MessageNotifyMode {
+    fn code(self) -> u8 {
+        match self {
+            Self::All => 1,
+            Self::Followed => 2,
+            Self::Off => 3,
+        }
+    }
+
+    fn from_code(code: u8) -> Option<Self> {
+        match code {
+            1 => Some(Self::All),
+            2 => Some(Self::Followed),
+            3 => Some(Self::Off),
+            _ => None,
+        }
+    }
+}
+
+/// `/link_setting/v1/link_setting/set` 的参数。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageSettingsParams {
+    show_unfollowed_msg: Option<bool>,
+    msg_notify: Option<MessageNotifyMode>,
+}
+
+impl MessageSettingsParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 是否接收未关注人的消息。
+    pub fn with_show_unfollowed_msg(mut self, show: bool) -> Self {
+        self.show_unfollowed_msg = Some(show);
+        self
+    }
+
+    /// 设置消息提醒方式。
+    pub fn with_msg_notify(mut self, mode: MessageNotifyMode) -> Self {
+        self.msg_notify = Some(mode);
+        self
+    }
+
+    pub(crate) fn form_pairs(&self, csrf: String) -> BpiResult<Vec<(&'static str, String)>> {
+        if self.show_unfollowed_msg.is_none() && self.msg_notify.is_none() {
+            return Err(BpiError::invalid_parameter(
+                "settings",
+                "at least one setting must be provided",
+            ));
+        }
+
+        let mut pairs = Vec::new();
+        if let Some(show) = self.show_unfollowed_msg {
+            pairs.push(("show_unfollowed_msg", bool_flag(show).to_string()));
+        }
+        if let Some(mode) = self.msg_notify {
+            pairs.push(("msg_notify", mode.code().to_string()));
+        }
+        pairs.push(("build", "0".to_string()));
+        pairs.push(("mobi_app", "web".to_string()));
+        pairs.push(("csrf_token", csrf.clone()));
+        pairs.push(("csrf", csrf));
+        Ok(pairs)
+    }
+}
+
+impl<'a> MessageClient<'a> {
+    /// 获取私信设置。
+    pub async fn settings(&self) -> BpiResult<MessageSettingsData> {
+        self.client
+            .get(SETTINGS_GET_ENDPOINT)
+            .query(&[("build", "0"), ("mobi_app", "web")])
+            .send_bpi_payload("message.settings")
+            .await
+    }
+
+    /// 修改私信设置，只提交设置过的项。
+    pub async fn update_settings(&self, params: MessageSettingsParams) -> BpiResult<Option<Value>> {
+        let csrf = self.client.csrf()?;
+        let form = params.form_pairs(csrf)?;
+
+        self.client
+            .post(SETTINGS_SET_ENDPOINT)
+            .form(&form)
+            .send_bpi_optional_payload("message.settings.update")
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_params_only_submit_selected_toggles() -> BpiResult<()> {
+        let pairs = MessageSettingsParams::new()
+            .with_msg_notify(MessageNotifyMode::Followed)
+            .form_pairs("csrf".to_string())?;
+
+        assert!(pairs.contains(&("msg_notify", "2".to_string())));
+        assert!(!pairs.iter().any(|(key, _)| *key == "show_unfollowed_msg"));
+        assert!(
+            MessageSettingsParams::new()
+                .form_pairs("csrf".to_string())
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn settings_data_keeps_unknown_fields() -> BpiResult<()> {
+        let data: MessageSettingsData =
+            serde_json::from_str(r#"{"show_unfollowed_msg":1,"msg_notify":3,"fans_msg":0}"#)?;
+
+        assert_eq!(data.notify_mode(), Some(MessageNotifyMode::Off));
+        assert!(data.other.contains_key("fans_msg"));
+        Ok(())
+    }
+}
@@ -5,8 +5,8 @@ use crate::BpiError;
 use crate::BpiResult;
 use crate::ids::Aid;
 use crate::note::NoteClient;
+use crate::note::document::NoteDocument;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
 
 /// 保存视频笔记的响应数据
 
@@ -22,7 +22,7 @@ pub struct NoteAddParams {
     oid: Aid,
     title: String,
     summary: String,
-    content: String,
+    content: NoteDocument,
     note_id: Option<String>,
     tags: Option<String>,
     publish: Option<bool>,
@@ -69,7 +69,7 @@ impl NoteAddParams {
             oid,
             title: title.into(),
             summary: summary.into(),
-            content: content.into(),
+            content: NoteDocument::new().text(content),
             note_id: None,
             tags: None,
             publish: None,
@@ -79,6 +79,33 @@ impl NoteAddParams {
         Ok(params)
     }
 
+    /// 由结构化正文创建笔记保存参数，摘要和跳转 tag 从正文生成。
+    ///
+    /// 正文没有文字时以标题作为摘要。
+    pub fn from_document(
+        oid: Aid,
+        title: impl Into<String>,
+        document: NoteDocument,
+    ) -> BpiResult<Self> {
+        let title = title.into();
+        let summary = Some(document.summary())
+            .filter(|summary| !summary.is_empty())
+            .unwrap_or_else(|| title.clone());
+        let tags = document.tags_param();
+        let params = Self {
+            oid,
+            title,
+            summary,
+            content: document,
+            note_id: None,
+            tags,
+            publish: None,
+            auto_comment: None,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+
     /// 更新现有笔记时设置笔记 ID。
     pub fn note_id(mut self, note_id: impl Into<String>) -> BpiResult<Self> {
         self.note_id = Some(note_id.into());
@@ -108,7 +135,12 @@ impl NoteAddParams {
     fn validate(&self) -> BpiResult<()> {
         normalize_non_blank("title", self.title.clone())?;
         normalize_non_blank("summary", self.summary.clone())?;
-        normalize_non_blank("content", self.content.clone())?;
+        if self.content.is_blank() {
+            return Err(BpiError::invalid_parameter(
+                "content",
+                "value cannot be blank",
+            ));
+        }
         if let Some(note_id) = self.note_id.as_deref() {
             normalize_non_blank("note_id", note_id.to_string())?;
         }
@@ -118,14 +150,13 @@ impl NoteAddParams {
         Ok(())
     }
 
-    fn form_pairs(&self, csrf: impl Into<String>) -> Vec<(&'static str, String)> {
-        let content = json!([{"insert": self.content}]);
+    fn form_pairs(&self, csrf: impl Into<String>) -> BpiResult<Vec<(&'static str, String)>> {
         let mut form = vec![
             ("oid", self.oid.to_string()),
             ("oid_type", "0".to_string()),
             ("title", self.title.clone()),
             ("summary", self.summary.clone()),
-            ("content", content.to_string()),
+            ("content", self.content.to_content()?),
             ("cls", "1".to_string()),
             ("from", "save".to_string()),
             ("platform", "web".to_string()),
@@ -145,7 +176,7 @@ impl NoteAddParams {
             form.push(("auto_comment", bool_flag(auto_comment)));
         }
 
-        form
+        Ok(form)
     }
 }
 
@@ -172,7 +203,7 @@ impl<'a> NoteClient<'a> {
     /// 保存视频笔记并返回标准 payload 结果。
     pub async fn add(&self, params: NoteAddParams) -> BpiResult<NoteAddResponseData> {
         let csrf = self.client.csrf()?;
-        let form = params.form_pairs(csrf);
+        let form = params.form_pairs(csrf)?;
 
         self.client
             .post("https://api.bilibili.com/x/note/add")
@@ -216,4 +247,36 @@ mod tests {
             }
         ));
     }
+
+    #[test]
+    fn note_add_params_keeps_plain_content_format() -> BpiResult<()> {
+        let params = NoteAddParams::new(Aid::new(170001)?, "title", "summary", "正文")?;
+        let form = params.form_pairs("csrf")?;
+
+        assert!(form.contains(&(
+            "content",
+            r#"[{"insert":"正文"},{"insert":"\n"}]"#.to_string()
+        )));
+        Ok(())
+    }
+
+    #[test]
+    fn note_add_params_from_document_fills_summary_and_tags() -> BpiResult<()> {
+        use crate::ids::Cid;
+        use crate::note::document::NoteTimeTag;
+
+        let document = NoteDocument::new()
+            .text("开头")
+            .timestamp(NoteTimeTag::new(Cid::new(10)?, 5))
+            .text("结尾\n");
+        let params = NoteAddParams::from_document(Aid::new(170001)?, "标题", document)?;
+        let form = params.form_pairs("csrf")?;
+
+        assert!(form.contains(&("summary", "开头结尾".to_string())));
+        assert!(form.contains(&("tags", "10-0-0-5-2".to_string())));
+        assert!(
+            NoteAddParams::from_document(Aid::new(170001)?, "标题", NoteDocument::new()).is_err()
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,442 @@
+//! 笔记正文
+//!
+//! 笔记正文是 Quill delta 格式的 JSON 数组：文本插入可以带加粗、颜色、标题等属性，
+//! 嵌入内容包括跳转到视频指定时间的 `tag` 和截图 `imageUpload`。
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::BpiResult;
+use crate::ids::Cid;
+use crate::note::info::PrivateNoteTag;
+
+/// 自动生成摘要时保留的最大字符数。
+pub const NOTE_SUMMARY_MAX_CHARS: usize = 100;
+
+/// 笔记正文。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct NoteDocument {
+    ops: Vec<NoteOp>,
+}
+
+/// 一次插入操作。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoteOp {
+    /// 格式属性；加在换行符上时作用于整行
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attributes: Option<NoteAttributes>,
+    /// 插入内容
+    pub insert: NoteInsert,
+}
+
+/// 插入内容。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum NoteInsert {
+    /// 文本
+    Text(String),
+    /// 时间跳转标签
+    Tag { tag: NoteTimeTag },
+    /// 截图或上传的图片
+    Image {
+        #[serde(rename = "imageUpload")]
+        image: NoteImage,
+    },
+    /// 其他嵌入内容，原样保留
+    Embed(serde_json::Value),
+}
+
+/// 文本格式属性。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoteAttributes {
+    /// 加粗
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bold: Option<bool>,
+    /// 斜体
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub italic: Option<bool>,
+    /// 下划线
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub underline: Option<bool>,
+    /// 删除线
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strike: Option<bool>,
+    /// 文字颜色，如 `#ee230d`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// 背景颜色
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    /// 标题级别，仅作用于换行符
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header: Option<u8>,
+    /// 列表类型 `ordered` 或 `bullet`，仅作用于换行符
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list: Option<String>,
+    /// 其他未建模的属性
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+impl NoteAttributes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bold(mut self) -> Self {
+        self.bold = Some(true);
+        self
+    }
+
+    pub fn italic(mut self) -> Self {
+        self.italic = Some(true);
+        self
+    }
+
+    pub fn underline(mut self) -> Self {
+        self.underline = Some(true);
+        self
+    }
+
+    pub fn strike(mut self) -> Self {
+        self.strike = Some(true);
+        self
+    }
+
+    pub fn color(mut self, color: impl Into<String>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    pub fn background(mut self, background: impl Into<String>) -> Self {
+        self.background = Some(background.into());
+        self
+    }
+}
+
+/// 跳转到视频指定分 P 和时间的标签。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoteTimeTag {
+    /// 分 P 的 cid
+    pub cid: Cid,
+    /// 稿件类型，0 为普通视频
+    #[serde(default)]
+    pub oid_type: u8,
+    #[serde(default)]
+    pub status: u8,
+    /// 分 P 序号，从 0 开始
+    #[serde(default)]
+    pub index: u32,
+    /// 时间偏移，单位为秒
+    pub seconds: u32,
+    /// 稿件分 P 总数
+    #[serde(rename = "cidCount", default = "default_cid_count")]
+    pub cid_count: u32,
+    /// 前端生成的唯一键
+    #[serde(default)]
+    pub key: String,
+    /// 展示文本，如 `P1 - 01:05`
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub epid: u64,
+}
+
+impl NoteTimeTag {
+    /// 创建指向第 1 P 指定秒数的标签。
+    pub fn new(cid: Cid, seconds: u32) -> Self {
+        Self {
+            cid,
+            oid_type: 0,
+            status: 0,
+            index: 0,
+            seconds,
+            cid_count: default_cid_count(),
+            key: chrono::Utc::now().timestamp_millis().to_string(),
+            title: tag_title(0, seconds),
+            epid: 0,
+        }
+    }
+
+    /// 设置分 P 序号（从 0 开始）和稿件分 P 总数，并更新展示文本。
+    pub fn with_part(mut self, index: u32, cid_count: u32) -> Self {
+        self.index = index;
+        self.cid_count = cid_count.max(index + 1);
+        self.title = tag_title(index, self.seconds);
+        self
+    }
+}
+
+/// 笔记中的图片。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoteImage {
+    /// 图片地址
+    pub url: String,
+    /// 前端生成的图片 ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// 上传状态，已上传为 `done`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    /// 图片来源，视频截图为 `video`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl NoteImage {
+    /// 已上传图片，`url` 通常来自笔记图片上传接口。
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            id: Some(format!("IMAGE_{}", chrono::Utc::now().timestamp_millis())),
+            status: Some("done".to_string()),
+            width: None,
+            source: None,
+        }
+    }
+
+    pub fn with_width(mut self, width: u32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// 标记为视频截图。
+    pub fn screenshot(mut self) -> Self {
+        self.source = Some("video".to_string());
+        self
+    }
+}
+
+impl NoteDocument {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 解析接口返回或提交的 `content` 字符串。
+    pub fn parse(content: &str) -> BpiResult<Self> {
+        Ok(serde_json::from_str(content)?)
+    }
+
+    /// 序列化为 `content` 字段；正文末尾缺少换行时补上，与网页端保持一致。
+    pub fn to_content(&self) -> BpiResult<String> {
+        let ends_with_newline = matches!(
+            self.ops.last(),
+            Some(NoteOp {
+                insert: NoteInsert::Text(text),
+                ..
+            }) if text.ends_with('\n')
+        );
+        if self.ops.is_empty() || ends_with_newline {
+            return Ok(serde_json::to_string(&self.ops)?);
+        }
+
+        Ok(serde_json::to_string(&self.clone().line())?)
+    }
+
+    pub fn ops(&self) -> &[NoteOp] {
+        &self.ops
+    }
+
+    /// 追加纯文本。
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.push(NoteInsert::Text(text.into()), None)
+    }
+
+    /// 追加带格式的文本。
+    pub fn styled_text(self, text: impl Into<String>, attributes: NoteAttributes) -> Self {
+        self.push(NoteInsert::Text(text.into()), Some(attributes))
+    }
+
+    /// 结束当前行。
+    pub fn line(self) -> Self {
+        self.text("\n")
+    }
+
+    /// 追加一行标题，`level` 取值 1-6。
+    pub fn heading(self, text: impl Into<String>, level: u8) -> Self {
+        let attributes = NoteAttributes {
+            header: Some(level.clamp(1, 6)),
+            ..NoteAttributes::default()
+        };
+        self.ensure_line_start()
+            .text(text)
+            .push(NoteInsert::Text("\n".to_string()), Some(attributes))
+    }
+
+    /// 追加时间跳转标签。
+    pub fn timestamp(self, tag: NoteTimeTag) -> Self {
+        self.push(NoteInsert::Tag { tag }, None)
+    }
+
+    /// 追加图片。
+    pub fn image(self, image: NoteImage) -> Self {
+        self.push(NoteInsert::Image { image }, None)
+    }
+
+    /// 不包含任何文字和嵌入内容。
+    pub fn is_blank(&self) -> bool {
+        self.ops.iter().all(|op| match &op.insert {
+            NoteInsert::Text(text) => text.trim().is_empty(),
+            _ => false,
+        })
+    }
+
+    /// 所有文本插入拼接后的纯文本，嵌入内容被忽略。
+    pub fn plain_text(&self) -> String {
+        self.ops
+            .iter()
+            .filter_map(|op| match &op.insert {
+                NoteInsert::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// 由正文前 [`NOTE_SUMMARY_MAX_CHARS`] 个字符生成的摘要，空白会被折叠。
+    pub fn summary(&self) -> String {
+        self.plain_text()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(NOTE_SUMMARY_MAX_CHARS)
+            .collect()
+    }
+
+    /// 时间跳转标签及其在正文中的位置。
+    ///
+    /// 位置按 Quill 的规则计算：文本按 UTF-16 长度计数，每个嵌入内容计为 1。
+    pub fn tags(&self) -> Vec<PrivateNoteTag> {
+        let mut pos = 0;
+        let mut tags = Vec::new();
+        for op in &self.ops {
+            match &op.insert {
+                NoteInsert::Text(text) => pos += text.encode_utf16().count(),
+                NoteInsert::Tag { tag } => {
+                    tags.push(PrivateNoteTag {
+                        cid: tag.cid.get(),
+                        status: tag.status,
+                        index: tag.index,
+                        seconds: tag.seconds,
+                        pos: u32::try_from(pos).unwrap_or(u32::MAX),
+                    });
+                    pos += 1;
+                }
+                _ => pos += 1,
+            }
+        }
+
+        tags
+    }
+
+    /// 保存笔记时 `tags` 字段的值，格式为 `cid-status-index-seconds-pos`，多个标签以逗号分隔。
+    pub fn tags_param(&self) -> Option<String> {
+        let tags = self
+            .tags()
+            .iter()
+            .map(|tag| {
+                format!(
+                    "{}-{}-{}-{}-{}",
+                    tag.cid, tag.status, tag.index, tag.seconds, tag.pos
+                )
+            })
+            .collect::<Vec<_>>();
+
+        (!tags.is_empty()).then(|| tags.join(","))
+    }
+
+    fn push(mut self, insert: NoteInsert, attributes: Option<NoteAttributes>) -> Self {
+        self.ops.push(NoteOp { attributes, insert });
+        self
+    }
+
+    fn ensure_line_start(self) -> Self {
+        let at_line_start = match self.ops.last() {
+            None => true,
+            Some(NoteOp {
+                insert: NoteInsert::Text(text),
+                ..
+            }) => text.ends_with('\n'),
+            Some(_) => false,
+        };
+
+        if at_line_start { self } else { self.line() }
+    }
+}
+
+fn default_cid_count() -> u32 {
+    1
+}
+
+/// 网页端的标签展示文本，如 `P1 - 01:05`、`P2 - 1:02:05`。
+fn tag_title(index: u32, seconds: u32) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("P{} - {hours}:{minutes:02}:{seconds:02}", index + 1)
+    } else {
+        format!("P{} - {minutes:02}:{seconds:02}", index + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = r##"[{"insert":"标题"},{"attributes":{"header":1},"insert":"\n"},{"insert":{"tag":{"cid":279786,"oid_type":0,"status":0,"index":1,"seconds":65,"cidCount":2,"key":"1700000000000","title":"P2 - 01:05","epid":0}}},{"attributes":{"bold":true,"color":"#ee230d"},"insert":"重点"},{"insert":"\n"},{"insert":{"imageUpload":{"url":"//api.bilibili.com/x/note/image?image_id=1","id":"IMAGE_1","status":"done","width":315,"source":"video"}}},{"insert":{"unknown":{"id":1}}},{"insert":"\n"}]"##;
+
+    #[test]
+    fn document_round_trips_web_content() -> BpiResult<()> {
+        let document = NoteDocument::parse(CONTENT)?;
+
+        assert!(matches!(
+            document.ops()[2].insert,
+            NoteInsert::Tag { ref tag } if tag.seconds == 65 && tag.index == 1
+        ));
+        assert!(matches!(
+            document.ops()[5].insert,
+            NoteInsert::Image { ref image } if image.width == Some(315)
+        ));
+        assert!(matches!(document.ops()[6].insert, NoteInsert::Embed(_)));
+        assert_eq!(document.to_content()?, CONTENT);
+        Ok(())
+    }
+
+    #[test]
+    fn document_reports_tag_positions() -> BpiResult<()> {
+        let document = NoteDocument::parse(CONTENT)?;
+
+        let tags = document.tags();
+
+        assert_eq!(tags.len(), 1);
+        assert_eq!((tags[0].cid, tags[0].seconds, tags[0].pos), (279786, 65, 3));
+        assert_eq!(document.tags_param().as_deref(), Some("279786-0-1-65-3"));
+        Ok(())
+    }
+
+    #[test]
+    fn builder_generates_content_and_summary() -> BpiResult<()> {
+        let document = NoteDocument::new()
+            .heading("第一章", 2)
+            .timestamp(NoteTimeTag::new(Cid::new(10)?, 3725).with_part(1, 3))
+            .styled_text("要点", NoteAttributes::new().bold().color("#ee230d"))
+            .text(" 说明")
+            .image(NoteImage::new("https://example.invalid/a.png").screenshot());
+
+        let ops: serde_json::Value = serde_json::from_str(&document.to_content()?)?;
+
+        assert_eq!(ops[1]["attributes"]["header"], 2);
+        assert_eq!(ops[2]["insert"]["tag"]["title"], "P2 - 1:02:05");
+        assert_eq!(ops[2]["insert"]["tag"]["cidCount"], 3);
+        assert_eq!(ops[3]["attributes"]["color"], "#ee230d");
+        assert_eq!(ops[5]["insert"]["imageUpload"]["source"], "video");
+        assert_eq!(ops[6]["insert"], "\n");
+        assert_eq!(document.summary(), "第一章 要点 说明");
+        assert!(!document.is_blank());
+        assert!(NoteDocument::new().text(" \n").is_blank());
+        Ok(())
+    }
+}
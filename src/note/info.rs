@@ -1,3 +1,5 @@
+use crate::BpiResult;
+use crate::note::document::NoteDocument;
 use serde::{Deserialize, Serialize};
 
 // --- 查询该稿件是否禁止笔记 ---
@@ -48,6 +50,13 @@ pub struct PrivateNoteInfoData {
     pub title: String,
 }
 
+impl PrivateNoteInfoData {
+    /// 解析笔记正文。
+    pub fn document(&self) -> BpiResult<NoteDocument> {
+        NoteDocument::parse(&self.content)
+    }
+}
+
 // --- 查询公开笔记内容 ---
 
 /// 公开笔记的稿件信息
@@ -88,6 +97,13 @@ pub struct PublicNoteInfoData {
     pub forbid_note_entrance: bool,
 }
 
+impl PublicNoteInfoData {
+    /// 解析笔记正文。
+    pub fn document(&self) -> BpiResult<NoteDocument> {
+        NoteDocument::parse(&self.content)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -300,6 +316,10 @@ mod tests {
         ))?
         .into_payload()?;
         assert_eq!(private_info.title, "sanitized private note title");
+        assert_eq!(
+            private_info.document()?.plain_text(),
+            "sanitized private note content\n"
+        );
 
         let public_info = ApiEnvelope::<PublicNoteInfoData>::from_slice(include_bytes!(
             "../../tests/contracts/note/read/public-info/responses/success.json"
@@ -307,6 +327,7 @@ mod tests {
         .into_payload()?;
         assert_eq!(public_info.cvid, TEST_CVID);
         assert_eq!(public_info.author.name, "sanitized author");
+        assert_eq!(public_info.document()?.to_content()?, public_info.content);
         Ok(())
     }
 
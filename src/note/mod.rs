@@ -2,12 +2,14 @@
 
 pub mod action;
 mod client;
+pub mod document;
 pub mod info;
 pub mod list;
 pub mod params;
 
 pub use action::{NoteAddParams, NoteDeleteParams};
 pub use client::NoteClient;
+pub use document::{NoteAttributes, NoteDocument, NoteImage, NoteInsert, NoteOp, NoteTimeTag};
 pub use params::{
     NoteArchiveListParams, NoteIsForbidParams, NotePrivateInfoParams, NotePublicArchiveListParams,
     NotePublicInfoParams, NoteUserPrivateListParams, NoteUserPublicListParams,
@@ -1,4 +1,4 @@
-#[cfg(feature = "manga")]
+#[cfg(any(feature = "live", feature = "manga"))]
 use crate::response::ApiEnvelope;
 use crate::{
     BpiError,
@@ -93,7 +93,7 @@ impl BilibiliRequest for RequestBuilder {
     }
 }
 
-#[cfg(feature = "manga")]
+#[cfg(any(feature = "live", feature = "manga"))]
 pub(crate) async fn send_bpi_envelope<T>(
     request: RequestBuilder,
     operation_name: &str,
@@ -111,7 +111,7 @@ where
     Ok(result)
 }
 
-#[cfg(feature = "manga")]
+#[cfg(any(feature = "live", feature = "manga"))]
 fn decode_bpi_envelope_response<T>(
     operation_name: &str,
     response: &TransportResponse,
@@ -12,6 +12,7 @@ pub mod official_role;
 pub mod params;
 pub mod register;
 pub mod relation;
+pub mod resolve;
 pub mod search;
 pub mod space;
 pub mod status_number;
@@ -36,8 +37,14 @@ pub use params::{
     UserUploadedVideosParams,
 };
 pub use relation::{
-    ModifyRelationResponseData, RelationAction, RelationSource, UserGroupCreateParams,
-    UserGroupDeleteParams, UserGroupMoveUsersParams, UserGroupUpdateParams, UserGroupUsersParams,
-    UserModifyRelationParams,
+    DesiredFollow, ModifyRelationResponseData, RelationAction, RelationApplyOptions,
+    RelationApplyReport, RelationClient, RelationDirection, RelationEdge, RelationExportParams,
+    RelationGraph, RelationOperation, RelationOperationOutcome, RelationPlan, RelationPrune,
+    RelationSource, UserGroupCreateParams, UserGroupDeleteParams, UserGroupMoveUsersParams,
+    UserGroupUpdateParams, UserGroupUsersParams, UserModifyRelationParams,
+};
+pub use resolve::{
+    USER_CARDS_MAX_CHUNK, UserCardCache, UserResolveFailure, UserResolveManyData,
+    UserResolveManyParams,
 };
 pub use space::UserSpaceNoticeSetParams;
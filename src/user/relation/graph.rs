@@ -0,0 +1,808 @@
+//! 关系图导出与关注列表同步
+//!
+//! [`RelationClient::export_graph`] 翻页拉取关注和粉丝列表，并标注分组和互粉状态；
+//! [`RelationGraph::diff`] 根据期望的关注列表生成 [`RelationPlan`]，
+//! 再由 [`RelationClient::apply_plan`] 预览或执行。
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ids::Mid;
+use crate::user::relation::{RelationAction, UserGroupUsersParams, UserModifyRelationParams};
+use crate::user::{
+    UserClient, UserFollowTag, UserFollower, UserFollowersParams, UserFollowing,
+    UserFollowingsParams,
+};
+use crate::utils::csv;
+use crate::{BpiClient, BpiError, BpiResult};
+
+const RELATION_PAGE_SIZE: u32 = 50;
+/// 非本人账号的关注和粉丝列表只开放前 5 页；本人账号默认不限页数。
+const DEFAULT_MAX_PAGES: u32 = 5;
+const DEFAULT_APPLY_INTERVAL: Duration = Duration::from_secs(2);
+/// 关系列表属性中表示互相关注的值。
+const ATTRIBUTE_MUTUAL: u8 = 6;
+
+/// 关系方向。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationDirection {
+    /// 目标用户关注的人。
+    Following,
+    /// 关注目标用户的人。
+    Follower,
+}
+
+impl RelationDirection {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Following => "following",
+            Self::Follower => "follower",
+        }
+    }
+}
+
+/// 关系图中的一条关系。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelationEdge {
+    /// 关系方向。
+    pub direction: RelationDirection,
+    /// 对方 mid。
+    pub mid: Mid,
+    /// 对方昵称。
+    pub name: String,
+    /// 关注时间戳，单位秒。
+    pub mtime: Option<u64>,
+    /// 是否互相关注。
+    pub mutual: bool,
+    /// 是否特别关注。
+    pub special: bool,
+    /// 所在分组 ID，只有导出本人关系图时可见。
+    pub tag_ids: Vec<i64>,
+    /// 所在分组名称，与 `tag_ids` 一一对应。
+    pub tag_names: Vec<String>,
+}
+
+/// [`RelationClient::export_graph`] 导出的关系图。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelationGraph {
+    /// 关系图所属用户。
+    pub mid: Mid,
+    /// 关注分组，只有导出本人关系图时可见。
+    pub tags: Vec<UserFollowTag>,
+    /// 关注列表。
+    pub followings: Vec<RelationEdge>,
+    /// 粉丝列表。
+    pub followers: Vec<RelationEdge>,
+    /// 服务端报告的关注总数。
+    pub following_total: u64,
+    /// 服务端报告的粉丝总数。
+    pub follower_total: u64,
+    /// 关注列表是否因可见范围限制而不完整。
+    pub followings_truncated: bool,
+    /// 粉丝列表是否因可见范围限制而不完整。
+    pub followers_truncated: bool,
+}
+
+impl RelationGraph {
+    /// 序列化为 JSON。
+    pub fn to_json(&self) -> BpiResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// 序列化为 CSV，每条关系一行，分组名称用 `|` 分隔。
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("direction,mid,name,mtime,mutual,special,tag_ids,tag_names\n");
+        for edge in self.followings.iter().chain(&self.followers) {
+            let tag_ids = edge
+                .tag_ids
+                .iter()
+                .map(i64::to_string)
+                .collect::<Vec<_>>()
+                .join("|");
+            let fields = [
+                edge.direction.as_str().to_string(),
+                edge.mid.to_string(),
+                edge.name.clone(),
+                edge.mtime
+                    .map(|mtime| mtime.to_string())
+                    .unwrap_or_default(),
+                edge.mutual.to_string(),
+                edge.special.to_string(),
+                tag_ids,
+                edge.tag_names.join("|"),
+            ];
+            csv::push_row(&mut csv, &fields);
+        }
+
+        csv
+    }
+
+    /// 对比期望的关注列表，生成需要执行的操作。
+    ///
+    /// 关注列表不完整时无法判断未导出的部分，返回错误而不是生成重复关注或遗漏取关的计划。
+    pub fn diff(&self, desired: &[DesiredFollow], prune: RelationPrune) -> BpiResult<RelationPlan> {
+        if self.followings_truncated {
+            return Err(BpiError::invalid_parameter(
+                "graph",
+                "following list is truncated; export the complete graph before diffing",
+            ));
+        }
+
+        let current = self
+            .followings
+            .iter()
+            .map(|edge| (edge.mid, edge))
+            .collect::<HashMap<_, _>>();
+        let wanted = desired
+            .iter()
+            .map(|follow| (follow.mid, follow))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut operations = Vec::new();
+        let mut assignments: BTreeMap<Vec<i64>, Vec<Mid>> = BTreeMap::new();
+        for (mid, follow) in &wanted {
+            let tag_ids = follow
+                .tag_ids
+                .iter()
+                .copied()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect::<Vec<_>>();
+
+            match current.get(mid) {
+                None => operations.push(RelationOperation::Follow(*mid)),
+                Some(edge) => {
+                    let mut current_tags = edge.tag_ids.clone();
+                    current_tags.sort_unstable();
+                    if current_tags == tag_ids {
+                        continue;
+                    }
+                }
+            }
+
+            if !tag_ids.is_empty() {
+                assignments.entry(tag_ids).or_default().push(*mid);
+            }
+        }
+
+        if prune == RelationPrune::Unfollow {
+            operations.extend(
+                self.followings
+                    .iter()
+                    .filter(|edge| !wanted.contains_key(&edge.mid))
+                    .map(|edge| RelationOperation::Unfollow(edge.mid)),
+            );
+        }
+
+        operations.extend(
+            assignments
+                .into_iter()
+                .map(|(tag_ids, mids)| RelationOperation::SetTags { mids, tag_ids }),
+        );
+
+        Ok(RelationPlan { operations })
+    }
+}
+
+/// 期望关注的用户及其分组。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DesiredFollow {
+    /// 期望关注的 mid。
+    pub mid: Mid,
+    /// 期望所在的分组 ID，为空表示不调整分组。
+    #[serde(default)]
+    pub tag_ids: Vec<i64>,
+}
+
+impl DesiredFollow {
+    pub fn new(mid: Mid) -> Self {
+        Self {
+            mid,
+            tag_ids: Vec::new(),
+        }
+    }
+
+    pub fn with_tag_ids(mut self, tag_ids: impl IntoIterator<Item = i64>) -> Self {
+        self.tag_ids = tag_ids.into_iter().collect();
+        self
+    }
+}
+
+/// 期望列表之外的已关注用户如何处理。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RelationPrune {
+    /// 保留，不取关。
+    #[default]
+    Keep,
+    /// 取关。
+    Unfollow,
+}
+
+/// 关系同步中的单个操作。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationOperation {
+    /// 关注用户。
+    Follow(Mid),
+    /// 取关用户。
+    Unfollow(Mid),
+    /// 把用户设置到指定分组。
+    SetTags { mids: Vec<Mid>, tag_ids: Vec<i64> },
+}
+
+impl fmt::Display for RelationOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Follow(mid) => write!(f, "+ follow {mid}"),
+            Self::Unfollow(mid) => write!(f, "- unfollow {mid}"),
+            Self::SetTags { mids, tag_ids } => {
+                let mids = mids.iter().map(Mid::to_string).collect::<Vec<_>>();
+                let tag_ids = tag_ids.iter().map(i64::to_string).collect::<Vec<_>>();
+                write!(f, "~ tags [{}] <- {}", tag_ids.join(","), mids.join(","))
+            }
+        }
+    }
+}
+
+/// 由 [`RelationGraph::diff`] 生成的同步计划。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelationPlan {
+    /// 按执行顺序排列的操作。
+    pub operations: Vec<RelationOperation>,
+}
+
+impl RelationPlan {
+    /// 计划是否为空。
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// 返回可读的预览，每个操作一行。
+    pub fn preview(&self) -> String {
+        self.operations
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// [`RelationClient::apply_plan`] 的执行方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelationApplyOptions {
+    dry_run: bool,
+    interval: Duration,
+}
+
+impl RelationApplyOptions {
+    /// 只预览，不发送任何请求。
+    pub fn dry_run() -> Self {
+        Self {
+            dry_run: true,
+            interval: DEFAULT_APPLY_INTERVAL,
+        }
+    }
+
+    /// 实际执行计划。
+    pub fn execute() -> Self {
+        Self {
+            dry_run: false,
+            interval: DEFAULT_APPLY_INTERVAL,
+        }
+    }
+
+    /// 设置相邻两个操作之间的间隔，降低触发频率限制的概率。
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+/// 单个操作的执行结果。
+#[derive(Debug)]
+pub struct RelationOperationOutcome {
+    /// 执行的操作。
+    pub operation: RelationOperation,
+    /// 失败原因；成功或预览时为 `None`。
+    pub error: Option<BpiError>,
+}
+
+/// [`RelationClient::apply_plan`] 的执行报告。
+#[derive(Debug, Default)]
+pub struct RelationApplyReport {
+    /// 是否为预览。
+    pub dry_run: bool,
+    /// 每个操作的结果。
+    pub outcomes: Vec<RelationOperationOutcome>,
+}
+
+impl RelationApplyReport {
+    /// 失败的操作。
+    pub fn failures(&self) -> impl Iterator<Item = &RelationOperationOutcome> {
+        self.outcomes
+            .iter()
+            .filter(|outcome| outcome.error.is_some())
+    }
+}
+
+/// [`RelationClient::export_graph`] 的参数。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelationExportParams {
+    mid: Mid,
+    max_pages: Option<u32>,
+}
+
+impl RelationExportParams {
+    pub fn new(mid: Mid) -> Self {
+        Self {
+            mid,
+            max_pages: None,
+        }
+    }
+
+    /// 设置每个列表最多翻页数。默认本人账号不限页数，其他账号为 5 页。
+    pub fn with_max_pages(mut self, max_pages: u32) -> BpiResult<Self> {
+        if max_pages == 0 {
+            return Err(BpiError::invalid_parameter(
+                "max_pages",
+                "value must be non-zero",
+            ));
+        }
+
+        self.max_pages = Some(max_pages);
+        Ok(self)
+    }
+
+    fn page_limit(&self, is_self: bool) -> u32 {
+        self.max_pages
+            .unwrap_or(if is_self { u32::MAX } else { DEFAULT_MAX_PAGES })
+    }
+}
+
+/// 用户关系图 API 客户端。
+#[derive(Clone, Copy)]
+pub struct RelationClient<'a> {
+    client: &'a BpiClient,
+}
+
+impl<'a> RelationClient<'a> {
+    pub(crate) fn new(client: &'a BpiClient) -> Self {
+        Self { client }
+    }
+
+    fn user(&self) -> UserClient<'a> {
+        UserClient::new(self.client)
+    }
+
+    /// 导出用户的关注和粉丝关系图。
+    ///
+    /// 列表会翻页直到服务端返回空页、达到 `max_pages` 或拒绝访问更多页。导出当前登录账号
+    /// 自己的关系图时会额外拉取关注分组并标注每条关注所在的分组。
+    pub async fn export_graph(&self, params: RelationExportParams) -> BpiResult<RelationGraph> {
+        let is_self = self
+            .client
+            .get_account()
+            .is_some_and(|account| account.dede_user_id == params.mid.to_string());
+        let tags = if is_self {
+            self.user().follow_tags().await?
+        } else {
+            Vec::new()
+        };
+        let tag_names = tags
+            .iter()
+            .map(|tag| (tag.id, tag.name.clone()))
+            .collect::<HashMap<_, _>>();
+
+        let (mid, max_pages) = (params.mid, params.page_limit(is_self));
+        let (followings, following_total, followings_truncated) =
+            collect_pages(max_pages, |page| async move {
+                let params = UserFollowingsParams::new(mid)
+                    .with_page_size(RELATION_PAGE_SIZE)
+                    .with_page(page);
+                let data = self.user().followings(params).await?;
+                Ok((data.list, data.total))
+            })
+            .await?;
+        let (followers, follower_total, followers_truncated) =
+            collect_pages(max_pages, |page| async move {
+                let params = UserFollowersParams::new(mid)
+                    .with_page_size(RELATION_PAGE_SIZE)
+                    .with_page(page);
+                let data = self.user().followers(params).await?;
+                Ok((data.list, data.total))
+            })
+            .await?;
+
+        Ok(RelationGraph {
+            mid: params.mid,
+            tags,
+            followings: followings
+                .iter()
+                .map(|item| following_edge(item, &tag_names))
+                .collect(),
+            followers: followers
+                .iter()
+                .map(|item| follower_edge(item, &tag_names))
+                .collect(),
+            following_total,
+            follower_total,
+            followings_truncated,
+            followers_truncated,
+        })
+    }
+
+    /// 预览或执行同步计划。预览不会发送任何请求。
+    pub async fn apply_plan(
+        &self,
+        plan: &RelationPlan,
+        options: RelationApplyOptions,
+    ) -> BpiResult<RelationApplyReport> {
+        let mut report = RelationApplyReport {
+            dry_run: options.dry_run,
+            outcomes: Vec::with_capacity(plan.operations.len()),
+        };
+
+        for (index, operation) in plan.operations.iter().enumerate() {
+            let error = if options.dry_run {
+                None
+            } else {
+                if index > 0 && !options.interval.is_zero() {
+                    tokio::time::sleep(options.interval).await;
+                }
+                self.apply_operation(operation).await.err()
+            };
+
+            report.outcomes.push(RelationOperationOutcome {
+                operation: operation.clone(),
+                error,
+            });
+        }
+
+        Ok(report)
+    }
+
+    async fn apply_operation(&self, operation: &RelationOperation) -> BpiResult<()> {
+        match operation {
+            RelationOperation::Follow(mid) => {
+                self.user()
+                    .modify_relation(UserModifyRelationParams::new(
+                        mid.get(),
+                        RelationAction::Follow,
+                    )?)
+                    .await?;
+            }
+            RelationOperation::Unfollow(mid) => {
+                self.user()
+                    .modify_relation(UserModifyRelationParams::new(
+                        mid.get(),
+                        RelationAction::Unfollow,
+                    )?)
+                    .await?;
+            }
+            RelationOperation::SetTags { mids, tag_ids } => {
+                let fids = mids.iter().map(|mid| mid.get()).collect::<Vec<_>>();
+                self.user()
+                    .add_group_users_to_tags(UserGroupUsersParams::new(&fids, tag_ids)?)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> UserClient<'a> {
+    /// 创建关系图客户端。
+    pub fn relation(&self) -> RelationClient<'a> {
+        RelationClient::new(self.client)
+    }
+}
+
+fn following_edge(item: &UserFollowing, tag_names: &HashMap<i64, String>) -> RelationEdge {
+    let tag_ids = tag_ids(item.tag.as_deref());
+    RelationEdge {
+        direction: RelationDirection::Following,
+        mid: item.mid,
+        name: item.name.clone(),
+        mtime: Some(item.mtime),
+        mutual: item.attribute == ATTRIBUTE_MUTUAL,
+        special: item.special == 1,
+        tag_names: resolve_tag_names(&tag_ids, tag_names),
+        tag_ids,
+    }
+}
+
+fn follower_edge(item: &UserFollower, tag_names: &HashMap<i64, String>) -> RelationEdge {
+    let tag_ids = tag_ids(item.tag.as_deref());
+    RelationEdge {
+        direction: RelationDirection::Follower,
+        mid: item.mid,
+        name: item.name.clone(),
+        mtime: item.mtime,
+        mutual: item.attribute == ATTRIBUTE_MUTUAL,
+        special: item.special == 1,
+        tag_names: resolve_tag_names(&tag_ids, tag_names),
+        tag_ids,
+    }
+}
+
+fn tag_ids(tags: Option<&[u64]>) -> Vec<i64> {
+    tags.unwrap_or_default()
+        .iter()
+        .filter_map(|tag| i64::try_from(*tag).ok())
+        .collect()
+}
+
+fn resolve_tag_names(tag_ids: &[i64], tag_names: &HashMap<i64, String>) -> Vec<String> {
+    tag_ids
+        .iter()
+        .map(|id| tag_names.get(id).cloned().unwrap_or_else(|| id.to_string()))
+        .collect()
+}
+
+/// 逐页读取关注或粉丝列表，`fetch` 返回一页的条目和总数。
+///
+/// 返回全部条目、总数以及是否未读全；第二页起遇到可见范围限制时返回已读到的部分。
+async fn collect_pages<T, F, Fut>(max_pages: u32, fetch: F) -> BpiResult<(Vec<T>, u64, bool)>
+where
+    F: Fn(u32) -> Fut,
+    Fut: Future<Output = BpiResult<(Vec<T>, u64)>>,
+{
+    let mut items = Vec::new();
+    let mut total = 0;
+    for page in 1..=max_pages {
+        let (list, page_total) = match fetch(page).await {
+            Ok(data) => data,
+            Err(err) if page > 1 && is_visibility_limit(&err) => {
+                return Ok((items, total, true));
+            }
+            Err(err) => return Err(err),
+        };
+
+        total = page_total;
+        let len = list.len();
+        items.extend(list);
+        if len < RELATION_PAGE_SIZE as usize || items.len() as u64 >= total {
+            return Ok((items, total, false));
+        }
+    }
+
+    let truncated = (items.len() as u64) < total;
+    Ok((items, total, truncated))
+}
+
+/// 访问超出可见范围的页时，服务端返回 `22007` 或 `-400` 等业务错误。
+fn is_visibility_limit(err: &BpiError) -> bool {
+    matches!(err.code(), Some(22007) | Some(22115) | Some(-400))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mid(value: u64) -> BpiResult<Mid> {
+        Mid::new(value)
+    }
+
+    fn edge(value: u64, tag_ids: Vec<i64>) -> BpiResult<RelationEdge> {
+        Ok(RelationEdge {
+            direction: RelationDirection::Following,
+            mid: mid(value)?,
+            name: format!("user-{value}"),
+            mtime: Some(1_700_000_000),
+            mutual: false,
+            special: false,
+            tag_names: tag_ids.iter().map(i64::to_string).collect(),
+            tag_ids,
+        })
+    }
+
+    fn graph(followings: Vec<RelationEdge>) -> BpiResult<RelationGraph> {
+        Ok(RelationGraph {
+            mid: mid(1)?,
+            tags: Vec::new(),
+            followings,
+            followers: Vec::new(),
+            following_total: 0,
+            follower_total: 0,
+            followings_truncated: false,
+            followers_truncated: false,
+        })
+    }
+
+    #[test]
+    fn following_edge_marks_mutual_and_resolves_tag_names() -> BpiResult<()> {
+        let item: UserFollowing = serde_json::from_value(serde_json::json!({
+            "mid": 2,
+            "attribute": 6,
+            "mtime": 1700000000,
+            "tag": [7, 9],
+            "special": 1,
+            "uname": "user"
+        }))?;
+        let names = HashMap::from([(7, "朋友".to_string())]);
+
+        let edge = following_edge(&item, &names);
+
+        assert!(edge.mutual);
+        assert!(edge.special);
+        assert_eq!(edge.tag_ids, vec![7, 9]);
+        assert_eq!(edge.tag_names, vec!["朋友".to_string(), "9".to_string()]);
+        Ok(())
+    }
+
+    #[test]
+    fn diff_follows_missing_users_and_sets_changed_tags() -> BpiResult<()> {
+        let graph = graph(vec![edge(2, vec![7])?, edge(3, Vec::new())?])?;
+        let desired = [
+            DesiredFollow::new(mid(2)?).with_tag_ids([7]),
+            DesiredFollow::new(mid(3)?).with_tag_ids([8]),
+            DesiredFollow::new(mid(4)?).with_tag_ids([8]),
+        ];
+
+        let plan = graph.diff(&desired, RelationPrune::Keep)?;
+
+        assert_eq!(
+            plan.operations,
+            vec![
+                RelationOperation::Follow(mid(4)?),
+                RelationOperation::SetTags {
+                    mids: vec![mid(3)?, mid(4)?],
+                    tag_ids: vec![8],
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn diff_unfollows_extra_users_only_when_pruning() -> BpiResult<()> {
+        let graph = graph(vec![edge(2, Vec::new())?, edge(3, Vec::new())?])?;
+        let desired = [DesiredFollow::new(mid(2)?)];
+
+        assert!(graph.diff(&desired, RelationPrune::Keep)?.is_empty());
+        assert_eq!(
+            graph.diff(&desired, RelationPrune::Unfollow)?.operations,
+            vec![RelationOperation::Unfollow(mid(3)?)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn diff_refuses_truncated_following_list() -> BpiResult<()> {
+        let mut graph = graph(vec![edge(2, Vec::new())?])?;
+        graph.followings_truncated = true;
+
+        assert!(matches!(
+            graph.diff(&[DesiredFollow::new(mid(3)?)], RelationPrune::Keep),
+            Err(BpiError::InvalidParameter { field: "graph", .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn export_params_default_to_unlimited_pages_for_self() -> BpiResult<()> {
+        let params = RelationExportParams::new(mid(1)?);
+        assert_eq!(params.page_limit(true), u32::MAX);
+        assert_eq!(params.page_limit(false), DEFAULT_MAX_PAGES);
+
+        let params = params.with_max_pages(3)?;
+        assert_eq!(params.page_limit(true), 3);
+        assert_eq!(params.page_limit(false), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn plan_preview_lists_one_operation_per_line() -> BpiResult<()> {
+        let plan = RelationPlan {
+            operations: vec![
+                RelationOperation::Follow(mid(4)?),
+                RelationOperation::Unfollow(mid(5)?),
+                RelationOperation::SetTags {
+                    mids: vec![mid(4)?, mid(6)?],
+                    tag_ids: vec![7, 8],
+                },
+            ],
+        };
+
+        assert_eq!(
+            plan.preview(),
+            "+ follow 4\n- unfollow 5\n~ tags [7,8] <- 4,6"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn graph_csv_escapes_names_and_joins_tags() -> BpiResult<()> {
+        let mut first = edge(2, vec![7, 8])?;
+        first.name = "a,\"b\"".to_string();
+        let graph = graph(vec![first])?;
+
+        assert_eq!(
+            graph.to_csv(),
+            "direction,mid,name,mtime,mutual,special,tag_ids,tag_names\n\
+             following,2,\"a,\"\"b\"\"\",1700000000,false,false,7|8,7|8\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn graph_json_round_trips() -> BpiResult<()> {
+        let graph = graph(vec![edge(2, vec![7])?])?;
+
+        let parsed: RelationGraph = serde_json::from_str(&graph.to_json()?)?;
+
+        assert_eq!(parsed.followings, graph.followings);
+        Ok(())
+    }
+
+    #[test]
+    fn export_params_rejects_zero_pages() -> BpiResult<()> {
+        assert!(matches!(
+            RelationExportParams::new(mid(1)?).with_max_pages(0),
+            Err(BpiError::InvalidParameter {
+                field: "max_pages",
+                ..
+            })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn collect_pages_stops_on_short_page_and_visibility_limit()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+        let full = RELATION_PAGE_SIZE as usize;
+
+        let (items, total, truncated) = runtime.block_on(collect_pages(10, |page| async move {
+            let len = if page == 1 { full } else { 3 };
+            Ok((vec![page; len], 200))
+        }))?;
+        assert_eq!((items.len(), total, truncated), (full + 3, 200, false));
+
+        let (items, _, truncated) = runtime.block_on(collect_pages(10, |page| async move {
+            match page {
+                1 => Ok((vec![page; full], 200)),
+                _ => Err(BpiError::from_code(22007)),
+            }
+        }))?;
+        assert_eq!((items.len(), truncated), (full, true));
+
+        let (items, _, truncated) = runtime.block_on(collect_pages(2, |page| async move {
+            Ok((vec![page; full], 200))
+        }))?;
+        assert_eq!((items.len(), truncated), (full * 2, true));
+
+        let first_page_error = runtime.block_on(collect_pages::<u32, _, _>(10, |_| async {
+            Err(BpiError::from_code(22007))
+        }));
+        assert!(first_page_error.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn dry_run_reports_operations_without_requests() -> Result<(), Box<dyn std::error::Error>> {
+        let client = BpiClient::new()?;
+        let plan = RelationPlan {
+            operations: vec![RelationOperation::Follow(mid(4)?)],
+        };
+        let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+
+        let report = runtime.block_on(
+            client
+                .user()
+                .relation()
+                .apply_plan(&plan, RelationApplyOptions::dry_run()),
+        )?;
+
+        assert!(report.dry_run);
+        assert_eq!(report.outcomes.len(), 1);
+        assert_eq!(report.failures().count(), 0);
+        Ok(())
+    }
+}
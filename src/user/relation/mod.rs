@@ -6,11 +6,17 @@ pub mod following;
 pub mod following_group;
 
 mod action;
+mod graph;
 mod group;
 
 pub use action::{
     ModifyRelationResponseData, RelationAction, RelationSource, UserModifyRelationParams,
 };
+pub use graph::{
+    DesiredFollow, RelationApplyOptions, RelationApplyReport, RelationClient, RelationDirection,
+    RelationEdge, RelationExportParams, RelationGraph, RelationOperation, RelationOperationOutcome,
+    RelationPlan, RelationPrune,
+};
 pub use group::{
     UserGroupCreateParams, UserGroupDeleteParams, UserGroupMoveUsersParams, UserGroupUpdateParams,
     UserGroupUsersParams,
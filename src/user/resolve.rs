@@ -0,0 +1,484 @@
+//! 批量解析用户名片
+//!
+//! [`UserClient::resolve_many`] 把任意数量的 mid 拆分为 `/account/v1/user/cards` 允许的批次，
+//! 以有限并发请求，并按 mid 汇总成功结果和失败原因。配合 [`UserCardCache`] 可以在多次调用之间
+//! 复用未过期的名片。
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use futures_util::stream::{self, StreamExt};
+
+use crate::ids::Mid;
+use crate::user::{UserBatchCard, UserCardsParams, UserClient};
+use crate::{BpiError, BpiResult};
+
+/// `/account/v1/user/cards` 单次请求允许的最大 mid 数量。
+pub const USER_CARDS_MAX_CHUNK: usize = 50;
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// [`UserClient::resolve_many`] 的参数。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserResolveManyParams {
+    mids: Vec<Mid>,
+    chunk_size: usize,
+    concurrency: usize,
+}
+
+impl UserResolveManyParams {
+    /// 创建批量解析参数，重复的 mid 只请求一次。
+    pub fn new<I>(mids: I) -> BpiResult<Self>
+    where
+        I: IntoIterator<Item = Mid>,
+    {
+        let mut seen = HashSet::new();
+        let mids = mids
+            .into_iter()
+            .filter(|mid| seen.insert(*mid))
+            .collect::<Vec<_>>();
+
+        if mids.is_empty() {
+            return Err(BpiError::invalid_parameter(
+                "mids",
+                "at least one user id is required",
+            ));
+        }
+
+        Ok(Self {
+            mids,
+            chunk_size: USER_CARDS_MAX_CHUNK,
+            concurrency: DEFAULT_CONCURRENCY,
+        })
+    }
+
+    /// 设置每批请求的 mid 数量，不能超过 [`USER_CARDS_MAX_CHUNK`]。
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> BpiResult<Self> {
+        if chunk_size == 0 || chunk_size > USER_CARDS_MAX_CHUNK {
+            return Err(BpiError::invalid_parameter(
+                "chunk_size",
+                "chunk size must be between 1 and 50",
+            ));
+        }
+
+        self.chunk_size = chunk_size;
+        Ok(self)
+    }
+
+    /// 设置同时进行的请求数量。
+    pub fn with_concurrency(mut self, concurrency: usize) -> BpiResult<Self> {
+        if concurrency == 0 {
+            return Err(BpiError::invalid_parameter(
+                "concurrency",
+                "value must be non-zero",
+            ));
+        }
+
+        self.concurrency = concurrency;
+        Ok(self)
+    }
+
+    fn chunks(&self, mids: &[Mid]) -> Vec<Vec<Mid>> {
+        mids.chunks(self.chunk_size).map(<[Mid]>::to_vec).collect()
+    }
+}
+
+/// 一批 mid 的请求失败。
+#[derive(Debug)]
+pub struct UserResolveFailure {
+    /// 受影响的 mid。
+    pub mids: Vec<Mid>,
+    /// 请求失败原因。
+    pub error: BpiError,
+}
+
+/// [`UserClient::resolve_many`] 的结果。
+#[derive(Debug, Default)]
+pub struct UserResolveManyData {
+    /// 成功解析的用户名片。
+    pub cards: HashMap<Mid, UserBatchCard>,
+    /// 请求成功但响应中没有出现的 mid，通常是已注销或不存在的账号。
+    pub missing: Vec<Mid>,
+    /// 请求失败的 mid 及原因。
+    pub failures: Vec<UserResolveFailure>,
+}
+
+impl UserResolveManyData {
+    /// 返回全部请求失败的 mid。
+    pub fn failed_mids(&self) -> impl Iterator<Item = Mid> + '_ {
+        self.failures
+            .iter()
+            .flat_map(|failure| failure.mids.iter().copied())
+    }
+
+    /// 只保留成功解析的用户名片。
+    pub fn into_cards(self) -> HashMap<Mid, UserBatchCard> {
+        self.cards
+    }
+}
+
+/// 带过期时间的 LRU 用户名片缓存。
+///
+/// 缓存内部加锁，可以在多个任务之间共享引用。
+#[derive(Debug)]
+pub struct UserCardCache {
+    capacity: usize,
+    ttl: Duration,
+    state: Mutex<CacheState>,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    tick: u64,
+    entries: HashMap<Mid, CacheEntry>,
+    order: BTreeMap<u64, Mid>,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    card: UserBatchCard,
+    inserted_at: Instant,
+    tick: u64,
+}
+
+impl UserCardCache {
+    /// 创建缓存，`capacity` 为最多保留的名片数量，`ttl` 为单条名片的有效期。
+    pub fn new(capacity: usize, ttl: Duration) -> BpiResult<Self> {
+        if capacity == 0 {
+            return Err(BpiError::invalid_parameter(
+                "capacity",
+                "value must be non-zero",
+            ));
+        }
+        if ttl.is_zero() {
+            return Err(BpiError::invalid_parameter("ttl", "value must be non-zero"));
+        }
+
+        Ok(Self {
+            capacity,
+            ttl,
+            state: Mutex::new(CacheState::default()),
+        })
+    }
+
+    /// 读取未过期的名片，并刷新其最近使用顺序。
+    pub fn get(&self, mid: Mid) -> Option<UserBatchCard> {
+        self.get_at(mid, Instant::now())
+    }
+
+    /// 写入名片，超过容量时淘汰最久未使用的条目。
+    pub fn insert(&self, card: UserBatchCard) {
+        self.insert_at(card, Instant::now());
+    }
+
+    /// 当前缓存的名片数量，可能包含尚未清理的过期条目。
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// 缓存是否为空。
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 清空缓存。
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.entries.clear();
+        state.order.clear();
+    }
+
+    fn get_at(&self, mid: Mid, now: Instant) -> Option<UserBatchCard> {
+        let mut state = self.lock();
+        let entry = state.entries.get(&mid)?;
+        let old_tick = entry.tick;
+
+        if now.duration_since(entry.inserted_at) >= self.ttl {
+            state.entries.remove(&mid);
+            state.order.remove(&old_tick);
+            return None;
+        }
+
+        state.tick += 1;
+        let tick = state.tick;
+        state.order.remove(&old_tick);
+        state.order.insert(tick, mid);
+        let entry = state.entries.get_mut(&mid)?;
+        entry.tick = tick;
+        Some(entry.card.clone())
+    }
+
+    fn insert_at(&self, card: UserBatchCard, now: Instant) {
+        let mut state = self.lock();
+        state.tick += 1;
+        let tick = state.tick;
+        let mid = card.mid;
+
+        if let Some(previous) = state.entries.insert(
+            mid,
+            CacheEntry {
+                card,
+                inserted_at: now,
+                tick,
+            },
+        ) {
+            state.order.remove(&previous.tick);
+        }
+        state.order.insert(tick, mid);
+
+        while state.entries.len() > self.capacity {
+            let Some((_, oldest)) = state.order.pop_first() else {
+                break;
+            };
+            state.entries.remove(&oldest);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        self.state.lock().expect("user card cache mutex poisoned")
+    }
+}
+
+impl UserClient<'_> {
+    /// 批量获取用户名片，自动分批并以有限并发请求。
+    ///
+    /// 某一批请求因业务错误失败时会对半拆分重试，尽量把失败收敛到具体的 mid；
+    /// 登录、风控和网络错误不会拆分，直接记录为整批失败。
+    pub async fn resolve_many(
+        &self,
+        params: UserResolveManyParams,
+    ) -> BpiResult<UserResolveManyData> {
+        self.resolve_many_inner(params, None).await
+    }
+
+    /// 与 [`UserClient::resolve_many`] 相同，但优先读取缓存并把新结果写回缓存。
+    pub async fn resolve_many_cached(
+        &self,
+        params: UserResolveManyParams,
+        cache: &UserCardCache,
+    ) -> BpiResult<UserResolveManyData> {
+        self.resolve_many_inner(params, Some(cache)).await
+    }
+
+    async fn resolve_many_inner(
+        &self,
+        params: UserResolveManyParams,
+        cache: Option<&UserCardCache>,
+    ) -> BpiResult<UserResolveManyData> {
+        let mut data = UserResolveManyData::default();
+        let mut pending = Vec::new();
+        for mid in &params.mids {
+            match cache.and_then(|cache| cache.get(*mid)) {
+                Some(card) => {
+                    data.cards.insert(*mid, card);
+                }
+                None => pending.push(*mid),
+            }
+        }
+
+        let mut queue = params.chunks(&pending);
+        while !queue.is_empty() {
+            let results = stream::iter(std::mem::take(&mut queue))
+                .map(|chunk| async move {
+                    let result = match UserCardsParams::new(chunk.iter().copied()) {
+                        Ok(cards_params) => self.cards(cards_params).await,
+                        Err(err) => Err(err),
+                    };
+                    (chunk, result)
+                })
+                .buffer_unordered(params.concurrency)
+                .collect::<Vec<_>>()
+                .await;
+
+            for (chunk, result) in results {
+                match result {
+                    Ok(cards) => merge_chunk(&mut data, &chunk, cards, cache),
+                    Err(error) if chunk.len() > 1 && should_split(&error) => {
+                        let (left, right) = chunk.split_at(chunk.len() / 2);
+                        queue.push(left.to_vec());
+                        queue.push(right.to_vec());
+                    }
+                    Err(error) => data
+                        .failures
+                        .push(UserResolveFailure { mids: chunk, error }),
+                }
+            }
+        }
+
+        Ok(data)
+    }
+}
+
+fn merge_chunk(
+    data: &mut UserResolveManyData,
+    chunk: &[Mid],
+    cards: Vec<UserBatchCard>,
+    cache: Option<&UserCardCache>,
+) {
+    let requested = chunk.iter().copied().collect::<HashSet<_>>();
+    for card in cards {
+        if !requested.contains(&card.mid) {
+            continue;
+        }
+        if let Some(cache) = cache {
+            cache.insert(card.clone());
+        }
+        data.cards.insert(card.mid, card);
+    }
+
+    data.missing.extend(
+        chunk
+            .iter()
+            .copied()
+            .filter(|mid| !data.cards.contains_key(mid)),
+    );
+}
+
+fn should_split(error: &BpiError) -> bool {
+    error.code().is_some() && !error.requires_login() && !error.is_risk_control()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BpiClient;
+
+    fn mid(value: u64) -> BpiResult<Mid> {
+        Mid::new(value)
+    }
+
+    fn card(value: u64) -> BpiResult<UserBatchCard> {
+        Ok(serde_json::from_value(serde_json::json!({
+            "mid": value,
+            "name": format!("user-{value}"),
+            "face": "https://i0.hdslb.com/bfs/face/member/noface.jpg"
+        }))?)
+    }
+
+    #[test]
+    fn resolve_params_deduplicates_and_chunks_mids() -> BpiResult<()> {
+        let mids = (1..=7).map(mid).collect::<BpiResult<Vec<_>>>()?;
+        let params = UserResolveManyParams::new(mids.iter().copied().chain(mids.clone()))?
+            .with_chunk_size(3)?;
+
+        let chunks = params.chunks(&params.mids);
+
+        assert_eq!(params.mids.len(), 7);
+        assert_eq!(
+            chunks.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![3, 3, 1]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_params_rejects_invalid_limits() -> BpiResult<()> {
+        assert!(matches!(
+            UserResolveManyParams::new(Vec::new()),
+            Err(BpiError::InvalidParameter { field: "mids", .. })
+        ));
+
+        let params = UserResolveManyParams::new([mid(1)?])?;
+        assert!(matches!(
+            params.clone().with_chunk_size(USER_CARDS_MAX_CHUNK + 1),
+            Err(BpiError::InvalidParameter {
+                field: "chunk_size",
+                ..
+            })
+        ));
+        assert!(matches!(
+            params.with_concurrency(0),
+            Err(BpiError::InvalidParameter {
+                field: "concurrency",
+                ..
+            })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn merge_chunk_records_missing_mids_and_ignores_unrequested_cards() -> BpiResult<()> {
+        let mut data = UserResolveManyData::default();
+        let cache = UserCardCache::new(10, Duration::from_secs(60))?;
+
+        merge_chunk(
+            &mut data,
+            &[mid(1)?, mid(2)?],
+            vec![card(1)?, card(3)?],
+            Some(&cache),
+        );
+
+        assert!(data.cards.contains_key(&mid(1)?));
+        assert!(!data.cards.contains_key(&mid(3)?));
+        assert_eq!(data.missing, vec![mid(2)?]);
+        assert!(cache.get(mid(1)?).is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn split_only_for_business_errors() {
+        assert!(should_split(&BpiError::from_code(-400)));
+        assert!(!should_split(&BpiError::from_code(-101)));
+        assert!(!should_split(&BpiError::from_code(-412)));
+        assert!(!should_split(&BpiError::missing_data()));
+    }
+
+    #[test]
+    fn cache_evicts_least_recently_used_entry() -> BpiResult<()> {
+        let cache = UserCardCache::new(2, Duration::from_secs(60))?;
+        let now = Instant::now();
+
+        cache.insert_at(card(1)?, now);
+        cache.insert_at(card(2)?, now);
+        assert!(cache.get_at(mid(1)?, now).is_some());
+        cache.insert_at(card(3)?, now);
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get_at(mid(1)?, now).is_some());
+        assert!(cache.get_at(mid(2)?, now).is_none());
+        assert!(cache.get_at(mid(3)?, now).is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn cache_expires_entries_after_ttl() -> BpiResult<()> {
+        let cache = UserCardCache::new(2, Duration::from_secs(60))?;
+        let now = Instant::now();
+
+        cache.insert_at(card(1)?, now);
+
+        assert!(
+            cache
+                .get_at(mid(1)?, now + Duration::from_secs(59))
+                .is_some()
+        );
+        assert!(
+            cache
+                .get_at(mid(1)?, now + Duration::from_secs(60))
+                .is_none()
+        );
+        assert!(cache.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_many_cached_skips_requests_for_cached_mids() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let client = BpiClient::new()?;
+        let cache = UserCardCache::new(10, Duration::from_secs(60))?;
+        cache.insert(card(1)?);
+        cache.insert(card(2)?);
+        let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+
+        let data = runtime.block_on(
+            client
+                .user()
+                .resolve_many_cached(UserResolveManyParams::new([mid(1)?, mid(2)?])?, &cache),
+        )?;
+
+        assert_eq!(data.cards.len(), 2);
+        assert!(data.missing.is_empty());
+        assert_eq!(data.failed_mids().count(), 0);
+        Ok(())
+    }
+}
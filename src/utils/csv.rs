@@ -0,0 +1,42 @@
+//! 导出 CSV 时共用的转义与拼行
+
+/// 字段含逗号、引号或换行时加引号，并把引号写成两个。
+pub(crate) fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// 转义各字段后以逗号连接，追加为一行。
+pub(crate) fn push_row(csv: &mut String, fields: &[String]) {
+    let row = fields
+        .iter()
+        .map(|field| escape(field))
+        .collect::<Vec<_>>()
+        .join(",");
+    csv.push_str(&row);
+    csv.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_row_quotes_only_fields_that_need_it() {
+        let mut csv = String::new();
+        push_row(
+            &mut csv,
+            &[
+                "plain".to_string(),
+                "a,b".to_string(),
+                "say \"hi\"".to_string(),
+                "line\nbreak".to_string(),
+            ],
+        );
+
+        assert_eq!(csv, "plain,\"a,b\",\"say \"\"hi\"\"\",\"line\nbreak\"\n");
+    }
+}
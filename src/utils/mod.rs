@@ -1,2 +1,4 @@
 pub mod aid_bvid;
+#[cfg(any(feature = "creativecenter", feature = "live", feature = "user"))]
+pub(crate) mod csv;
 pub mod wbi;
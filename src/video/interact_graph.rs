@@ -0,0 +1,877 @@
+//! 互动视频剧情图
+//!
+//! 从根模块开始按选项跳转的模块 ID 广度优先抓取全部模块，得到完整的 [`InteractiveStoryGraph`]，
+//! 可导出为 Graphviz DOT 或 JSON。[`InteractiveVars`] 按隐藏变量求值选项的出现条件和点击后的
+//! 变量运算语句，用于模拟某条剧情路径。
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::future::Future;
+
+use futures_util::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use crate::video::VideoClient;
+use crate::video::interact_video::{
+    InteractiveVideoChoice, InteractiveVideoHiddenVar, InteractiveVideoInfoResponseData,
+};
+use crate::video::params::InteractiveVideoInfoParams;
+use crate::{BpiError, BpiResult};
+
+const DEFAULT_CONCURRENCY: usize = 4;
+const MAX_CONCURRENCY: usize = 16;
+const DEFAULT_MAX_NODES: usize = 1000;
+
+/// 抓取互动视频剧情图的参数。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InteractiveStoryGraphParams {
+    info: InteractiveVideoInfoParams,
+    concurrency: usize,
+    max_nodes: usize,
+}
+
+impl InteractiveStoryGraphParams {
+    /// 从根模块的查询参数开始抓取，`info` 中的 `edge_id` 会被忽略。
+    pub fn new(info: InteractiveVideoInfoParams) -> Self {
+        Self {
+            info,
+            concurrency: DEFAULT_CONCURRENCY,
+            max_nodes: DEFAULT_MAX_NODES,
+        }
+    }
+
+    /// 设置同时请求的模块数，取值 1-16。
+    pub fn with_concurrency(mut self, concurrency: usize) -> BpiResult<Self> {
+        if concurrency == 0 || concurrency > MAX_CONCURRENCY {
+            return Err(BpiError::invalid_parameter(
+                "concurrency",
+                "value must be between 1 and 16",
+            ));
+        }
+
+        self.concurrency = concurrency;
+        Ok(self)
+    }
+
+    /// 设置最多抓取的模块数，超出后剧情图标记为不完整。
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> BpiResult<Self> {
+        if max_nodes == 0 {
+            return Err(BpiError::invalid_parameter(
+                "max_nodes",
+                "value must be non-zero",
+            ));
+        }
+
+        self.max_nodes = max_nodes;
+        Ok(self)
+    }
+}
+
+/// 剧情图中的模块。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InteractiveStoryNode {
+    /// 模块 ID
+    pub edge_id: u64,
+    /// 模块（分P）标题
+    pub title: String,
+    /// 模块（分P）cid，根模块取自进度回溯信息
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cid: Option<u64>,
+    /// 是否为结束模块
+    pub is_leaf: bool,
+    /// 距根模块的最少选择次数
+    pub depth: u32,
+    /// 进入该模块时接口返回的变量
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hidden_vars: Vec<InteractiveStoryVar>,
+}
+
+/// 剧情图中的变量快照。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InteractiveStoryVar {
+    /// 变量编号，语句中使用此项
+    pub id: String,
+    /// 变量名
+    pub name: String,
+    /// 变量值
+    pub value: i64,
+    /// 是否为随机值变量
+    pub random: bool,
+}
+
+impl From<&InteractiveVideoHiddenVar> for InteractiveStoryVar {
+    fn from(var: &InteractiveVideoHiddenVar) -> Self {
+        Self {
+            id: var.id_v2.clone(),
+            name: var.name.clone(),
+            value: var.value,
+            random: var.var_type == 2,
+        }
+    }
+}
+
+/// 剧情图中的选项，从一个模块跳转到另一个模块。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InteractiveStoryEdge {
+    /// 所在模块 ID
+    pub from: u64,
+    /// 跳转的模块 ID
+    pub to: u64,
+    /// 跳转分P的 cid
+    pub cid: u64,
+    /// 选项文字
+    pub option: String,
+    /// 选项出现条件
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub condition: String,
+    /// 点击后的变量运算语句
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub native_action: String,
+    pub is_default: bool,
+    pub is_hidden: bool,
+    /// 按所在模块返回的变量求值的出现条件，语句无法求值时为 `None`
+    ///
+    /// 接口返回的是当前账号在该模块的变量，不是沿某条剧情路径累积的变量，因此只反映当前账号
+    /// 的状态；模拟具体路径时使用 [`InteractiveStoryGraph::available_choices`] 和
+    /// [`InteractiveVars::choose`] 逐步求值。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition_met: Option<bool>,
+}
+
+/// 完整的互动视频剧情图。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InteractiveStoryGraph {
+    pub graph_version: u64,
+    /// 根模块 ID
+    pub root: u64,
+    pub nodes: BTreeMap<u64, InteractiveStoryNode>,
+    pub edges: Vec<InteractiveStoryEdge>,
+    /// 达到模块数上限或有模块抓取失败而未抓取完整
+    #[serde(default)]
+    pub truncated: bool,
+    /// 抓取失败的模块 ID 及错误信息
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub failed: BTreeMap<u64, String>,
+}
+
+impl InteractiveStoryGraph {
+    /// 返回从指定模块出发的全部选项。
+    pub fn choices(&self, edge_id: u64) -> impl Iterator<Item = &InteractiveStoryEdge> {
+        self.edges.iter().filter(move |edge| edge.from == edge_id)
+    }
+
+    /// 返回在给定变量下可以看到的选项。
+    pub fn available_choices(
+        &self,
+        edge_id: u64,
+        vars: &InteractiveVars,
+    ) -> BpiResult<Vec<&InteractiveStoryEdge>> {
+        let mut available = Vec::new();
+        for edge in self.choices(edge_id) {
+            if vars.evaluate_condition(&edge.condition)? {
+                available.push(edge);
+            }
+        }
+        Ok(available)
+    }
+
+    /// 根模块的初始变量。
+    pub fn initial_vars(&self) -> InteractiveVars {
+        self.nodes
+            .get(&self.root)
+            .map(|node| InteractiveVars::from_story_vars(&node.hidden_vars))
+            .unwrap_or_default()
+    }
+
+    /// 序列化为 JSON。
+    pub fn to_json(&self) -> BpiResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// 从 [`Self::to_json`] 的输出恢复剧情图。
+    pub fn from_json(json: &str) -> BpiResult<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// 导出为 Graphviz DOT，结束模块使用双圈，隐藏选项使用虚线。
+    pub fn to_dot(&self) -> String {
+        let mut out = format!("digraph \"interactive_{}\" {{\n", self.graph_version);
+        out.push_str("    rankdir=LR;\n");
+        for node in self.nodes.values() {
+            let shape = if node.is_leaf { "doublecircle" } else { "box" };
+            let peripheries = if node.edge_id == self.root {
+                ", penwidth=2"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "    \"{}\" [label=\"{}\", shape={shape}{peripheries}];\n",
+                node.edge_id,
+                dot_escape(&node.title)
+            ));
+        }
+        for edge in &self.edges {
+            let mut label = dot_escape(&edge.option);
+            if !edge.condition.is_empty() {
+                label.push_str(&format!("\\n[{}]", dot_escape(&edge.condition)));
+            }
+            if !edge.native_action.is_empty() {
+                label.push_str(&format!("\\n{{{}}}", dot_escape(&edge.native_action)));
+            }
+            let style = if edge.is_hidden { ", style=dashed" } else { "" };
+            out.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{label}\"{style}];\n",
+                edge.from, edge.to
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn record_failure(&mut self, edge_id: u64, err: &BpiError) {
+        self.failed.insert(edge_id, err.to_string());
+        self.truncated = true;
+    }
+
+    fn insert(&mut self, data: &InteractiveVideoInfoResponseData, cid: Option<u64>, depth: u32) {
+        let hidden_vars: Vec<_> = data
+            .hidden_vars
+            .iter()
+            .map(InteractiveStoryVar::from)
+            .collect();
+        let vars = InteractiveVars::from_story_vars(&hidden_vars);
+        let node = InteractiveStoryNode {
+            edge_id: data.edge_id,
+            title: data.title.clone(),
+            cid,
+            is_leaf: data.is_leaf == 1,
+            depth,
+            hidden_vars,
+        };
+        self.nodes.insert(data.edge_id, node);
+
+        let questions = data.edges.iter().flat_map(|edges| edges.questions.iter());
+        for choice in questions.flat_map(|question| question.choices.iter()) {
+            self.edges.push(story_edge(
+                data.edge_id,
+                choice,
+                &vars,
+                !data.hidden_vars.is_empty(),
+            ));
+        }
+    }
+}
+
+impl<'a> VideoClient<'a> {
+    /// 从根模块开始广度优先抓取互动视频的全部模块和选项。
+    ///
+    /// 根模块抓取失败时返回错误；其余模块失败时记录在 [`InteractiveStoryGraph::failed`] 中并继续抓取。
+    pub async fn interactive_story_graph(
+        &self,
+        params: InteractiveStoryGraphParams,
+    ) -> BpiResult<InteractiveStoryGraph> {
+        let root = self.interactive_video_info(params.info.clone()).await?;
+        let root_cid = root
+            .story_list
+            .iter()
+            .find(|story| story.edge_id == root.edge_id || story.is_current == 1)
+            .map(|story| story.cid);
+
+        let info = &params.info;
+        Ok(
+            crawl_story_graph(&params, root, root_cid, |edge_id| async move {
+                self.interactive_video_info(info.clone().edge_id(edge_id)?)
+                    .await
+            })
+            .await,
+        )
+    }
+}
+
+/// 从已抓取的根模块开始按层抓取其余模块，`fetch` 按模块 ID 抓取单个模块。
+///
+/// 某个模块抓取失败时记录在剧情图中并继续，不会丢弃已抓取的部分。
+async fn crawl_story_graph<F, Fut>(
+    params: &InteractiveStoryGraphParams,
+    root: InteractiveVideoInfoResponseData,
+    root_cid: Option<u64>,
+    fetch: F,
+) -> InteractiveStoryGraph
+where
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = BpiResult<InteractiveVideoInfoResponseData>>,
+{
+    let mut graph = InteractiveStoryGraph {
+        graph_version: params.info.graph_version(),
+        root: root.edge_id,
+        nodes: BTreeMap::new(),
+        edges: Vec::new(),
+        truncated: false,
+        failed: BTreeMap::new(),
+    };
+    graph.insert(&root, root_cid, 0);
+
+    let mut seen = BTreeSet::from([root.edge_id]);
+    let mut frontier = next_frontier(&graph, &[root.edge_id], &mut seen);
+    let mut depth = 1;
+    while !frontier.is_empty() {
+        let remaining = params.max_nodes.saturating_sub(graph.nodes.len());
+        if frontier.len() > remaining {
+            frontier.truncate(remaining);
+            graph.truncated = true;
+        }
+        if frontier.is_empty() {
+            break;
+        }
+
+        let results = stream::iter(frontier)
+            .map(|(edge_id, cid)| {
+                let data = fetch(edge_id);
+                async move { (edge_id, cid, data.await) }
+            })
+            .buffer_unordered(params.concurrency)
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut fetched = Vec::with_capacity(results.len());
+        for (edge_id, cid, result) in results {
+            match result {
+                Ok(data) => {
+                    fetched.push(data.edge_id);
+                    graph.insert(&data, Some(cid), depth);
+                }
+                Err(err) => {
+                    tracing::warn!("互动视频模块 {edge_id} 抓取失败: {err}");
+                    graph.record_failure(edge_id, &err);
+                }
+            }
+        }
+        fetched.sort_unstable();
+        frontier = next_frontier(&graph, &fetched, &mut seen);
+        depth += 1;
+    }
+
+    graph
+}
+
+/// 互动视频的隐藏变量，按 `id_v2` 求值条件和运算语句。
+///
+/// 支持整数、`$变量`、`+ - * / %`、比较运算、`&& || !` 和括号；运算语句以 `;` 分隔，
+/// 形如 `$a=$a+1;$b=2`。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InteractiveVars {
+    values: BTreeMap<String, i64>,
+}
+
+impl InteractiveVars {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 从接口返回的变量列表创建。
+    pub fn from_hidden_vars(vars: &[InteractiveVideoHiddenVar]) -> Self {
+        Self {
+            values: vars
+                .iter()
+                .map(|var| (var.id_v2.trim_start_matches('$').to_string(), var.value))
+                .collect(),
+        }
+    }
+
+    fn from_story_vars(vars: &[InteractiveStoryVar]) -> Self {
+        Self {
+            values: vars
+                .iter()
+                .map(|var| (var.id.trim_start_matches('$').to_string(), var.value))
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<i64> {
+        self.values.get(id.trim_start_matches('$')).copied()
+    }
+
+    pub fn set(&mut self, id: impl AsRef<str>, value: i64) {
+        self.values
+            .insert(id.as_ref().trim_start_matches('$').to_string(), value);
+    }
+
+    pub fn values(&self) -> &BTreeMap<String, i64> {
+        &self.values
+    }
+
+    /// 求值选项出现条件，空条件视为成立。
+    pub fn evaluate_condition(&self, condition: &str) -> BpiResult<bool> {
+        if condition.trim().is_empty() {
+            return Ok(true);
+        }
+
+        let tokens = tokenize(condition)?;
+        let mut parser = ExprParser {
+            tokens: &tokens,
+            pos: 0,
+            vars: self,
+        };
+        let value = parser.expr()?;
+        parser.expect_end()?;
+        Ok(value != 0)
+    }
+
+    /// 执行点击选项后的变量运算语句。
+    pub fn apply_action(&mut self, action: &str) -> BpiResult<()> {
+        for statement in action.split(';').map(str::trim) {
+            if statement.is_empty() {
+                continue;
+            }
+
+            let tokens = tokenize(statement)?;
+            let [Token::Var(target), Token::Assign, rest @ ..] = tokens.as_slice() else {
+                return Err(expr_error(statement, "expected assignment"));
+            };
+            let mut parser = ExprParser {
+                tokens: rest,
+                pos: 0,
+                vars: self,
+            };
+            let value = parser.expr()?;
+            parser.expect_end()?;
+            self.values.insert(target.clone(), value);
+        }
+
+        Ok(())
+    }
+
+    /// 选择一个选项：条件成立时执行其运算语句。
+    pub fn choose(&mut self, edge: &InteractiveStoryEdge) -> BpiResult<()> {
+        if !self.evaluate_condition(&edge.condition)? {
+            return Err(BpiError::invalid_parameter(
+                "choice",
+                "choice condition is not satisfied",
+            ));
+        }
+
+        self.apply_action(&edge.native_action)
+    }
+}
+
+fn story_edge(
+    from: u64,
+    choice: &InteractiveVideoChoice,
+    vars: &InteractiveVars,
+    has_vars: bool,
+) -> InteractiveStoryEdge {
+    let condition_met = if choice.condition.trim().is_empty() {
+        Some(true)
+    } else if has_vars {
+        vars.evaluate_condition(&choice.condition).ok()
+    } else {
+        None
+    };
+
+    InteractiveStoryEdge {
+        from,
+        to: choice.id,
+        cid: choice.cid,
+        option: choice.option.clone(),
+        condition: choice.condition.clone(),
+        native_action: choice.native_action.clone(),
+        is_default: choice.is_default == Some(1),
+        is_hidden: choice.is_hidden == Some(1),
+        condition_met,
+    }
+}
+
+/// 收集 `from` 中模块的尚未抓取的跳转目标及其 cid。
+fn next_frontier(
+    graph: &InteractiveStoryGraph,
+    from: &[u64],
+    seen: &mut BTreeSet<u64>,
+) -> Vec<(u64, u64)> {
+    let mut frontier = Vec::new();
+    for edge_id in from {
+        for edge in graph.choices(*edge_id) {
+            if edge.to != 0 && seen.insert(edge.to) {
+                frontier.push((edge.to, edge.cid));
+            }
+        }
+    }
+    frontier
+}
+
+fn dot_escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Num(i64),
+    Var(String),
+    Op(&'static str),
+    Assign,
+    LParen,
+    RParen,
+}
+
+fn expr_error(expr: &str, reason: &str) -> BpiError {
+    BpiError::parse(format!("互动视频表达式 `{expr}` 无法解析: {reason}"))
+}
+
+fn tokenize(expr: &str) -> BpiResult<Vec<Token>> {
+    const OPS: [&str; 13] = [
+        "&&", "||", "==", "!=", ">=", "<=", ">", "<", "+", "-", "*", "/", "%",
+    ];
+
+    let mut tokens = Vec::new();
+    let mut rest = expr.trim_start();
+    while !rest.is_empty() {
+        let ch = rest.chars().next().unwrap_or_default();
+        if ch.is_ascii_digit() {
+            let end = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let value = rest[..end]
+                .parse()
+                .map_err(|_| expr_error(expr, "number out of range"))?;
+            tokens.push(Token::Num(value));
+            rest = &rest[end..];
+        } else if ch == '$' || ch.is_ascii_alphabetic() || ch == '_' {
+            let body = rest.strip_prefix('$').unwrap_or(rest);
+            let end = body
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(body.len());
+            if end == 0 {
+                return Err(expr_error(expr, "empty variable name"));
+            }
+            tokens.push(Token::Var(body[..end].to_string()));
+            rest = &body[end..];
+        } else if ch == '(' {
+            tokens.push(Token::LParen);
+            rest = &rest[1..];
+        } else if ch == ')' {
+            tokens.push(Token::RParen);
+            rest = &rest[1..];
+        } else if let Some(op) = OPS.iter().find(|op| rest.starts_with(**op)) {
+            tokens.push(Token::Op(op));
+            rest = &rest[op.len()..];
+        } else if ch == '=' {
+            tokens.push(Token::Assign);
+            rest = &rest[1..];
+        } else if ch == '!' {
+            tokens.push(Token::Op("!"));
+            rest = &rest[1..];
+        } else {
+            return Err(expr_error(expr, "unexpected character"));
+        }
+        rest = rest.trim_start();
+    }
+
+    Ok(tokens)
+}
+
+/// 按优先级递归下降求值：`||` < `&&` < 比较 < 加减 < 乘除 < 一元运算。
+struct ExprParser<'t, 'v> {
+    tokens: &'t [Token],
+    pos: usize,
+    vars: &'v InteractiveVars,
+}
+
+impl ExprParser<'_, '_> {
+    fn peek_op(&self) -> Option<&'static str> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(op)) => Some(op),
+            _ => None,
+        }
+    }
+
+    fn error(&self, reason: &str) -> BpiError {
+        BpiError::parse(format!(
+            "互动视频表达式无法解析: {reason} (位置 {})",
+            self.pos
+        ))
+    }
+
+    fn expect_end(&self) -> BpiResult<()> {
+        if self.pos == self.tokens.len() {
+            Ok(())
+        } else {
+            Err(self.error("unexpected trailing tokens"))
+        }
+    }
+
+    fn expr(&mut self) -> BpiResult<i64> {
+        let mut value = self.and()?;
+        while self.peek_op() == Some("||") {
+            self.pos += 1;
+            let rhs = self.and()?;
+            value = i64::from(value != 0 || rhs != 0);
+        }
+        Ok(value)
+    }
+
+    fn and(&mut self) -> BpiResult<i64> {
+        let mut value = self.comparison()?;
+        while self.peek_op() == Some("&&") {
+            self.pos += 1;
+            let rhs = self.comparison()?;
+            value = i64::from(value != 0 && rhs != 0);
+        }
+        Ok(value)
+    }
+
+    fn comparison(&mut self) -> BpiResult<i64> {
+        let lhs = self.additive()?;
+        let Some(op) = self
+            .peek_op()
+            .filter(|op| matches!(*op, "==" | "!=" | ">=" | "<=" | ">" | "<"))
+        else {
+            return Ok(lhs);
+        };
+        self.pos += 1;
+        let rhs = self.additive()?;
+        let result = match op {
+            "==" => lhs == rhs,
+            "!=" => lhs != rhs,
+            ">=" => lhs >= rhs,
+            "<=" => lhs <= rhs,
+            ">" => lhs > rhs,
+            _ => lhs < rhs,
+        };
+        Ok(i64::from(result))
+    }
+
+    fn additive(&mut self) -> BpiResult<i64> {
+        let mut value = self.term()?;
+        while let Some(op) = self.peek_op().filter(|op| matches!(*op, "+" | "-")) {
+            self.pos += 1;
+            let rhs = self.term()?;
+            value = if op == "+" {
+                value.wrapping_add(rhs)
+            } else {
+                value.wrapping_sub(rhs)
+            };
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> BpiResult<i64> {
+        let mut value = self.unary()?;
+        while let Some(op) = self.peek_op().filter(|op| matches!(*op, "*" | "/" | "%")) {
+            self.pos += 1;
+            let rhs = self.unary()?;
+            value = match op {
+                "*" => value.wrapping_mul(rhs),
+                _ if rhs == 0 => return Err(self.error("division by zero")),
+                "/" => value.wrapping_div(rhs),
+                _ => value.wrapping_rem(rhs),
+            };
+        }
+        Ok(value)
+    }
+
+    fn unary(&mut self) -> BpiResult<i64> {
+        match self.peek_op() {
+            Some("-") => {
+                self.pos += 1;
+                Ok(self.unary()?.wrapping_neg())
+            }
+            Some("!") => {
+                self.pos += 1;
+                Ok(i64::from(self.unary()? == 0))
+            }
+            _ => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> BpiResult<i64> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .ok_or_else(|| self.error("unexpected end"))?;
+        self.pos += 1;
+        match token {
+            Token::Num(value) => Ok(*value),
+            Token::Var(name) => self
+                .vars
+                .get(name)
+                .ok_or_else(|| self.error(&format!("unknown variable ${name}"))),
+            Token::LParen => {
+                let value = self.expr()?;
+                match self.tokens.get(self.pos) {
+                    Some(Token::RParen) => {
+                        self.pos += 1;
+                        Ok(value)
+                    }
+                    _ => Err(self.error("missing `)`")),
+                }
+            }
+            _ => Err(self.error("unexpected token")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ApiEnvelope;
+    use crate::ids::Aid;
+
+    fn module(edge_id: u64, next: &[u64]) -> BpiResult<InteractiveVideoInfoResponseData> {
+        let choices: Vec<_> = next
+            .iter()
+            .map(|id| {
+                serde_json::json!({
+                    "id": id,
+                    "platform_action": format!("JUMP+{id}+{}", id * 10),
+                    "native_action": "",
+                    "condition": "",
+                    "cid": id * 10,
+                    "option": format!("去 {id}")
+                })
+            })
+            .collect();
+        Ok(serde_json::from_value(serde_json::json!({
+            "title": format!("模块 {edge_id}"),
+            "edge_id": edge_id,
+            "edges": {"questions": [{
+                "id": 0, "type": 1, "start_time_r": 0, "duration": -1,
+                "pause_video": 1, "title": "", "choices": choices
+            }]},
+            "is_leaf": u8::from(next.is_empty())
+        }))?)
+    }
+
+    /// 1 -> 11, 12, 13；11 -> 21；12 抓取失败
+    fn crawl(max_nodes: usize) -> Result<InteractiveStoryGraph, Box<dyn std::error::Error>> {
+        let params = InteractiveStoryGraphParams::new(InteractiveVideoInfoParams::from_aid(
+            Aid::new(170_001)?,
+            1,
+        )?)
+        .with_max_nodes(max_nodes)?
+        .with_concurrency(2)?;
+        let root = module(1, &[11, 12, 13])?;
+        let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+
+        Ok(runtime.block_on(crawl_story_graph(
+            &params,
+            root,
+            Some(10),
+            |edge_id| async move {
+                match edge_id {
+                    11 => module(11, &[21]),
+                    12 => Err(BpiError::network("stub failure")),
+                    _ => module(edge_id, &[]),
+                }
+            },
+        )))
+    }
+
+    #[test]
+    fn crawl_keeps_fetched_modules_when_one_fails() -> Result<(), Box<dyn std::error::Error>> {
+        let graph = crawl(DEFAULT_MAX_NODES)?;
+
+        assert_eq!(
+            graph.nodes.keys().copied().collect::<Vec<_>>(),
+            vec![1, 11, 13, 21]
+        );
+        assert_eq!(graph.nodes[&21].depth, 2);
+        assert_eq!(graph.nodes[&11].cid, Some(110));
+        assert!(graph.failed[&12].contains("stub failure"));
+        assert!(graph.truncated);
+        assert_eq!(graph.choices(1).count(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn crawl_stops_at_node_cap() -> Result<(), Box<dyn std::error::Error>> {
+        let graph = crawl(3)?;
+
+        // 第一层只剩 2 个名额，13 被截掉；12 失败不占名额，第二层的 21 仍能抓到
+        assert_eq!(
+            graph.nodes.keys().copied().collect::<Vec<_>>(),
+            vec![1, 11, 21]
+        );
+        assert!(graph.truncated);
+        assert!(!graph.failed.contains_key(&13));
+        Ok(())
+    }
+
+    fn vars() -> InteractiveVars {
+        let mut vars = InteractiveVars::new();
+        vars.set("$score", 3);
+        vars.set("trust", 0);
+        vars
+    }
+
+    #[test]
+    fn vars_evaluate_conditions_and_actions() -> BpiResult<()> {
+        let mut vars = vars();
+
+        assert!(vars.evaluate_condition("")?);
+        assert!(vars.evaluate_condition("$score>=3&&$trust==0")?);
+        assert!(!vars.evaluate_condition("($score+1)*2 < 8 || !($trust==0)")?);
+        vars.apply_action("$score=$score-1; $trust=$trust+10")?;
+        assert_eq!(vars.get("$score"), Some(2));
+        assert_eq!(vars.get("trust"), Some(10));
+        assert!(vars.evaluate_condition("$missing>1").is_err());
+        assert!(vars.evaluate_condition("$score/0").is_err());
+        assert!(vars.apply_action("$score+1").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn graph_exports_dot_and_round_trips_json() -> BpiResult<()> {
+        let root = ApiEnvelope::<InteractiveVideoInfoResponseData>::from_slice(include_bytes!(
+            "../../tests/contracts/video/player-read/interactive-info/responses/success.json"
+        ))?
+        .into_payload()?;
+        let mut leaf: InteractiveVideoInfoResponseData = serde_json::from_value(
+            serde_json::json!({
+                "title": "结局 \"A\"",
+                "edge_id": 41518745,
+                "hidden_vars": [{"value": 1, "id": "v1", "id_v2": "$score", "type": 1, "is_show": 1, "name": "分数"}],
+                "is_leaf": 1
+            }),
+        )?;
+        let mut graph = InteractiveStoryGraph {
+            graph_version: 1,
+            root: root.edge_id,
+            nodes: BTreeMap::new(),
+            edges: Vec::new(),
+            truncated: false,
+            failed: BTreeMap::new(),
+        };
+        graph.insert(&root, Some(1), 0);
+        let mut seen = BTreeSet::from([root.edge_id]);
+        let frontier = next_frontier(&graph, &[root.edge_id], &mut seen);
+        leaf.edge_id = frontier[0].0;
+        graph.insert(&leaf, Some(frontier[0].1), 1);
+
+        assert_eq!(frontier.len(), 2);
+        assert!(
+            graph
+                .choices(root.edge_id)
+                .all(|edge| edge.condition_met == Some(true))
+        );
+        let dot = graph.to_dot();
+        assert!(dot.contains(&format!("\"{}\" -> \"41518745\"", root.edge_id)));
+        assert!(dot.contains("结局 \\\"A\\\""));
+        assert!(dot.contains("doublecircle"));
+        assert_eq!(InteractiveStoryGraph::from_json(&graph.to_json()?)?, graph);
+        assert!(!graph.to_json()?.contains("failed"));
+
+        graph.record_failure(
+            frontier[1].0,
+            &BpiError::invalid_parameter("edge_id", "boom"),
+        );
+        assert!(graph.truncated);
+        assert!(graph.failed[&frontier[1].0].contains("boom"));
+        assert_eq!(InteractiveStoryGraph::from_json(&graph.to_json()?)?, graph);
+        assert_eq!(
+            graph.nodes[&41518745].hidden_vars[0].id,
+            "$score".to_string()
+        );
+        Ok(())
+    }
+}
@@ -7,6 +7,7 @@ pub mod attribute_data;
 pub mod client;
 pub mod collection;
 pub mod info;
+pub mod interact_graph;
 pub mod interact_video;
 pub mod model;
 pub mod online;
@@ -16,6 +17,7 @@ pub mod player;
 pub mod recommend;
 pub mod report;
 pub mod snapshot;
+pub mod subtitle;
 pub mod summary;
 pub mod tags;
 pub mod video_zone;
@@ -34,6 +36,10 @@ pub use collection::{
     VideoCollectionSeasonsSeriesParams, VideoCollectionSeriesArchivesParams,
     VideoCollectionSeriesInfoParams,
 };
+pub use interact_graph::{
+    InteractiveStoryEdge, InteractiveStoryGraph, InteractiveStoryGraphParams, InteractiveStoryNode,
+    InteractiveStoryVar, InteractiveVars,
+};
 pub use model::{VideoDetail, VideoOwner, VideoPage, VideoRelated, VideoStat, VideoTag, VideoView};
 pub use params::{
     InteractiveVideoInfoParams, VideoAiSummaryParams, VideoDescParams, VideoDetailParams,
@@ -42,3 +48,7 @@ pub use params::{
     VideoViewParams,
 };
 pub use report::VideoWatchProgressParams;
+pub use subtitle::{
+    BccSubtitle, BccSubtitleLine, VideoChapter, VideoChapters, VideoSubtitleTrack, VideoSubtitles,
+};
+pub use video_zone::{Zone, ZoneChange, ZoneRegistry, ZoneVersion};
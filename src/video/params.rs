@@ -503,6 +503,10 @@ impl InteractiveVideoInfoParams {
         Ok(self)
     }
 
+    pub(crate) fn graph_version(&self) -> u64 {
+        self.graph_version
+    }
+
     pub(crate) fn query_pairs(&self) -> Vec<(&'static str, String)> {
         let mut params = vec![("graph_version", self.graph_version.to_string())];
         params.extend(video_id_query_pairs(&self.id));
@@ -0,0 +1,435 @@
+//! 视频字幕和章节
+//!
+//! 从 web 播放器元数据中列出可用字幕（含 AI 生成字幕），下载 BCC JSON 字幕正文并转换为
+//! SRT、WebVTT 或纯文本；把 AI 总结提纲或分段章节转换为 ffmetadata / WebVTT 章节。
+
+use serde::{Deserialize, Serialize};
+
+use crate::ids::{Aid, Cid};
+use crate::video::VideoClient;
+use crate::video::params::VideoPlayerInfoParams;
+use crate::video::player::{SubtitleItem, ViewPoint};
+use crate::video::summary::AiSummaryResponseData;
+use crate::{BilibiliRequest, BpiError, BpiResult};
+
+/// 视频分P的可用字幕列表。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VideoSubtitles {
+    pub aid: u64,
+    pub cid: u64,
+    /// 是否必须登录才能获取字幕
+    pub need_login: bool,
+    pub tracks: Vec<VideoSubtitleTrack>,
+}
+
+impl VideoSubtitles {
+    /// 按语言代码查找字幕，例如 `zh-CN`、`ai-zh`。
+    pub fn track(&self, lan: &str) -> Option<&VideoSubtitleTrack> {
+        self.tracks.iter().find(|track| track.lan == lan)
+    }
+
+    /// 优先返回人工字幕，没有时返回 AI 生成字幕。
+    pub fn preferred(&self) -> Option<&VideoSubtitleTrack> {
+        self.tracks
+            .iter()
+            .find(|track| !track.ai_generated)
+            .or_else(|| self.tracks.first())
+    }
+}
+
+/// 单条字幕轨道。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VideoSubtitleTrack {
+    pub id: u64,
+    /// 语言类型英文字母缩写
+    pub lan: String,
+    /// 语言类型中文名称
+    pub lan_doc: String,
+    /// BCC JSON 正文地址，已补全协议
+    pub url: String,
+    /// 是否为 AI 生成字幕
+    pub ai_generated: bool,
+    pub is_lock: bool,
+}
+
+impl From<&SubtitleItem> for VideoSubtitleTrack {
+    fn from(item: &SubtitleItem) -> Self {
+        Self {
+            id: item.id,
+            lan: item.lan.clone(),
+            lan_doc: item.lan_doc.clone(),
+            url: normalize_subtitle_url(&item.subtitle_url),
+            ai_generated: item.subtitle_type == 1 || item.lan.starts_with("ai-"),
+            is_lock: item.is_lock,
+        }
+    }
+}
+
+/// BCC JSON 字幕正文
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct BccSubtitle {
+    #[serde(default)]
+    pub font_size: Option<f64>,
+    #[serde(default)]
+    pub font_color: Option<String>,
+    #[serde(default)]
+    pub background_alpha: Option<f64>,
+    #[serde(default)]
+    pub background_color: Option<String>,
+    #[serde(default, rename = "Stroke")]
+    pub stroke: Option<String>,
+    #[serde(default)]
+    pub body: Vec<BccSubtitleLine>,
+}
+
+/// BCC 字幕中的一行
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct BccSubtitleLine {
+    /// 开始时间，单位为秒
+    pub from: f64,
+    /// 结束时间，单位为秒
+    pub to: f64,
+    /// 显示位置
+    #[serde(default)]
+    pub location: Option<u8>,
+    pub content: String,
+}
+
+impl BccSubtitle {
+    /// 解析 BCC JSON 正文。
+    pub fn from_slice(bytes: &[u8]) -> BpiResult<Self> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+
+    /// 转换为 SRT。
+    pub fn to_srt(&self) -> String {
+        let mut out = String::new();
+        for (index, line) in self.body.iter().enumerate() {
+            out.push_str(&format!(
+                "{}\n{} --> {}\n{}\n\n",
+                index + 1,
+                format_timestamp(line.from, ','),
+                format_timestamp(line.to, ','),
+                line.content.trim_end()
+            ));
+        }
+        out
+    }
+
+    /// 转换为 WebVTT。
+    pub fn to_vtt(&self) -> String {
+        let mut out = String::from("WEBVTT\n\n");
+        for line in &self.body {
+            out.push_str(&format!(
+                "{} --> {}\n{}\n\n",
+                format_timestamp(line.from, '.'),
+                format_timestamp(line.to, '.'),
+                vtt_escape(line.content.trim_end())
+            ));
+        }
+        out
+    }
+
+    /// 转换为每行一句的纯文本，用于检索。
+    pub fn to_plain_text(&self) -> String {
+        self.body
+            .iter()
+            .map(|line| line.content.trim())
+            .filter(|content| !content.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// 视频章节。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VideoChapter {
+    /// 开始时间，单位为秒
+    pub start: f64,
+    /// 结束时间，单位为秒
+    pub end: f64,
+    pub title: String,
+}
+
+/// 视频章节列表，可导出为 ffmetadata 或 WebVTT 章节。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct VideoChapters(pub Vec<VideoChapter>);
+
+impl VideoChapters {
+    /// 由 AI 总结提纲生成章节；`duration` 为视频时长（秒），用于确定最后一章的结束时间。
+    pub fn from_ai_summary(summary: &AiSummaryResponseData, duration: Option<u64>) -> Self {
+        let outline = summary
+            .model_result
+            .as_ref()
+            .and_then(|result| result.outline.as_deref())
+            .unwrap_or_default();
+        let mut starts: Vec<_> = outline
+            .iter()
+            .map(|item| (item.timestamp, item.title.trim().to_string()))
+            .collect();
+        starts.sort_by_key(|(timestamp, _)| *timestamp);
+
+        let last_point = outline
+            .iter()
+            .flat_map(|item| item.part_outline.iter().map(|part| part.timestamp))
+            .max();
+        Self::from_starts(starts, duration.or(last_point))
+    }
+
+    /// 由播放器返回的分段章节生成章节。
+    pub fn from_view_points(points: &[ViewPoint]) -> Self {
+        Self(
+            points
+                .iter()
+                .map(|point| VideoChapter {
+                    start: point.from as f64,
+                    end: point.to.max(point.from) as f64,
+                    title: point.content.trim().to_string(),
+                })
+                .collect(),
+        )
+    }
+
+    fn from_starts(starts: Vec<(u64, String)>, end: Option<u64>) -> Self {
+        let mut chapters = Vec::with_capacity(starts.len());
+        for (index, (start, title)) in starts.iter().enumerate() {
+            let next = starts.get(index + 1).map(|(next, _)| *next).or(end);
+            chapters.push(VideoChapter {
+                start: *start as f64,
+                end: next.unwrap_or(*start).max(*start) as f64,
+                title: title.clone(),
+            });
+        }
+        Self(chapters)
+    }
+
+    pub fn chapters(&self) -> &[VideoChapter] {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// 导出为 ffmpeg 的 FFMETADATA1 章节，时间基为毫秒。
+    pub fn to_ffmetadata(&self) -> String {
+        let mut out = String::from(";FFMETADATA1\n");
+        for chapter in &self.0 {
+            out.push_str(&format!(
+                "\n[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n",
+                seconds_to_millis(chapter.start),
+                seconds_to_millis(chapter.end),
+                ffmetadata_escape(&chapter.title)
+            ));
+        }
+        out
+    }
+
+    /// 导出为 WebVTT 章节轨道。
+    pub fn to_vtt(&self) -> String {
+        let mut out = String::from("WEBVTT\n\n");
+        for (index, chapter) in self.0.iter().enumerate() {
+            out.push_str(&format!(
+                "Chapter {}\n{} --> {}\n{}\n\n",
+                index + 1,
+                format_timestamp(chapter.start, '.'),
+                format_timestamp(chapter.end, '.'),
+                vtt_escape(&chapter.title)
+            ));
+        }
+        out
+    }
+}
+
+impl<'a> VideoClient<'a> {
+    /// 列出视频分P的可用字幕，包括 AI 生成字幕。
+    pub async fn subtitles(&self, aid: Aid, cid: Cid) -> BpiResult<VideoSubtitles> {
+        let player = self
+            .player_info_v2(VideoPlayerInfoParams::from_aid(aid, cid))
+            .await?;
+        let tracks = player
+            .subtitle
+            .as_ref()
+            .map(|subtitle| {
+                subtitle
+                    .subtitles
+                    .iter()
+                    .filter(|item| !item.subtitle_url.is_empty())
+                    .map(VideoSubtitleTrack::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(VideoSubtitles {
+            aid: player.aid,
+            cid: player.cid,
+            need_login: player.need_login_subtitle,
+            tracks,
+        })
+    }
+
+    /// 下载字幕轨道的 BCC JSON 正文。
+    pub async fn subtitle_body(&self, track: &VideoSubtitleTrack) -> BpiResult<BccSubtitle> {
+        if track.url.is_empty() {
+            return Err(BpiError::invalid_parameter(
+                "subtitle_url",
+                "subtitle url cannot be blank",
+            ));
+        }
+
+        let bytes = self
+            .client
+            .get(&track.url)
+            .send_request("video.subtitle_body")
+            .await?;
+        BccSubtitle::from_slice(&bytes)
+    }
+}
+
+fn normalize_subtitle_url(url: &str) -> String {
+    if url.starts_with("//") {
+        format!("https:{url}")
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        format!("https://{rest}")
+    } else {
+        url.to_string()
+    }
+}
+
+fn seconds_to_millis(seconds: f64) -> u64 {
+    (seconds.max(0.0) * 1000.0).round() as u64
+}
+
+/// 格式化为 `HH:MM:SS{sep}mmm`，SRT 使用 `,`，WebVTT 使用 `.`。
+fn format_timestamp(seconds: f64, separator: char) -> String {
+    let millis = seconds_to_millis(seconds);
+    let hours = millis / 3_600_000;
+    let minutes = millis / 60_000 % 60;
+    let secs = millis / 1000 % 60;
+    format!(
+        "{hours:02}:{minutes:02}:{secs:02}{separator}{:03}",
+        millis % 1000
+    )
+}
+
+fn vtt_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace("-->", "--&gt;")
+}
+
+fn ffmetadata_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if matches!(ch, '=' | ';' | '#' | '\\' | '\n') {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ApiEnvelope;
+    use crate::video::player::PlayerInfoResponseData;
+
+    const BCC: &str = r##"{
+        "font_size": 0.4,
+        "font_color": "#FFFFFF",
+        "background_alpha": 0.5,
+        "background_color": "#9C27B0",
+        "Stroke": "none",
+        "body": [
+            {"from": 0.5, "to": 2.25, "location": 2, "content": "第一句"},
+            {"from": 3661.0, "to": 3662.5, "location": 2, "content": "a < b"}
+        ]
+    }"##;
+
+    #[test]
+    fn bcc_subtitle_exports_srt_vtt_and_text() -> BpiResult<()> {
+        let subtitle = BccSubtitle::from_slice(BCC.as_bytes())?;
+
+        assert_eq!(
+            subtitle.to_srt(),
+            "1\n00:00:00,500 --> 00:00:02,250\n第一句\n\n2\n01:01:01,000 --> 01:01:02,500\na < b\n\n"
+        );
+        assert!(
+            subtitle
+                .to_vtt()
+                .starts_with("WEBVTT\n\n00:00:00.500 --> 00:00:02.250\n第一句\n\n")
+        );
+        assert!(subtitle.to_vtt().contains("a &lt; b"));
+        assert_eq!(subtitle.to_plain_text(), "第一句\na < b");
+        Ok(())
+    }
+
+    #[test]
+    fn subtitle_tracks_mark_ai_generated_and_normalize_urls() -> BpiResult<()> {
+        let item: SubtitleItem = serde_json::from_value(serde_json::json!({
+            "ai_status": 2,
+            "ai_type": 0,
+            "id": 1,
+            "id_str": "1",
+            "is_lock": false,
+            "lan": "ai-zh",
+            "lan_doc": "中文（自动生成）",
+            "subtitle_url": "//aisubtitle.hdslb.com/bfs/ai_subtitle/prod/1",
+            "type": 1
+        }))?;
+        let track = VideoSubtitleTrack::from(&item);
+
+        assert!(track.ai_generated);
+        assert_eq!(
+            track.url,
+            "https://aisubtitle.hdslb.com/bfs/ai_subtitle/prod/1"
+        );
+
+        let player = ApiEnvelope::<PlayerInfoResponseData>::from_slice(include_bytes!(
+            "../../tests/contracts/video/player-read/player-info-v2/responses/anonymous.success.json"
+        ))?
+        .into_payload()?;
+        assert!(VideoChapters::from_view_points(&player.view_points).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn ai_summary_outline_becomes_chapters() -> BpiResult<()> {
+        let summary: AiSummaryResponseData = serde_json::from_value(serde_json::json!({
+            "code": 0,
+            "model_result": {
+                "result_type": 2,
+                "summary": "",
+                "outline": [
+                    {"title": "第二段", "timestamp": 90, "part_outline": [{"timestamp": 120, "content": ""}]},
+                    {"title": "开场; 介绍", "timestamp": 0, "part_outline": []}
+                ]
+            },
+            "like_num": 0,
+            "dislike_num": 0
+        }))?;
+        let chapters = VideoChapters::from_ai_summary(&summary, Some(300));
+
+        assert_eq!(chapters.chapters()[0].title, "开场; 介绍");
+        assert_eq!(chapters.chapters()[0].end, 90.0);
+        assert_eq!(chapters.chapters()[1].end, 300.0);
+        assert!(
+            chapters
+                .to_ffmetadata()
+                .contains("[CHAPTER]\nTIMEBASE=1/1000\nSTART=0\nEND=90000\ntitle=开场\\; 介绍\n")
+        );
+        assert!(
+            chapters
+                .to_vtt()
+                .contains("Chapter 2\n00:01:30.000 --> 00:05:00.000\n第二段")
+        );
+        assert_eq!(
+            VideoChapters::from_ai_summary(&summary, None).chapters()[1].end,
+            120.0
+        );
+        Ok(())
+    }
+}
@@ -1,3 +1,971 @@
-//! B站视频分区一览 (v2)
+//! B站视频分区一览
+//!
+//! [`ZoneRegistry`] 把 v1（`tid`）和 v2（`tid_v2`）分区表作为数据保存，支持按 tid、代号或
+//! 中文名查找、查询主分区与子分区，以及 v1 与 v2 tid 之间的对应关系。内置的 v1 分区表可以用
+//! [`VideoClient::zone_registry`] 从投稿页接口刷新，并用 [`ZoneRegistry::diff`] 对比差异；
+//! 该接口不返回 v2 分区，v2 分区只有内置分区表。
 //!
 //! [查看 API 文档](https://github.com/SocialSisterYi/bilibili-API-collect/tree/master/docs/video)
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::video::VideoClient;
+use crate::video::video_zone_v2::VideoPartitionV2;
+use crate::{BilibiliRequest, BpiError, BpiResult};
+
+const ARCHIVE_PRE_ENDPOINT: &str = "https://member.bilibili.com/x/vupre/web/archive/pre";
+
+static BUILTIN: OnceLock<ZoneRegistry> = OnceLock::new();
+
+/// 分区表版本。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ZoneVersion {
+    /// 旧版分区，用于分区排行、分区动态等接口
+    V1,
+    /// 新版分区，用于投稿
+    V2,
+}
+
+/// 视频分区。
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Zone {
+    pub version: ZoneVersion,
+    /// 分区 ID
+    pub tid: u32,
+    /// 主分区 ID，主分区为 `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<u32>,
+    /// 分区代号
+    pub alias: String,
+    /// 分区名称
+    pub name: String,
+}
+
+impl Zone {
+    pub fn new(
+        version: ZoneVersion,
+        tid: u32,
+        parent: Option<u32>,
+        alias: impl Into<String>,
+        name: impl Into<String>,
+    ) -> Self {
+        Self {
+            version,
+            tid,
+            parent,
+            alias: alias.into(),
+            name: name.into(),
+        }
+    }
+
+    /// 在内置分区表中查找 v1 分区。
+    pub fn v1(tid: u32) -> BpiResult<&'static Zone> {
+        ZoneRegistry::builtin()
+            .get(ZoneVersion::V1, tid)
+            .ok_or_else(|| unknown_zone("tid"))
+    }
+
+    /// 在内置分区表中查找 v2 分区。
+    pub fn v2(tid: u32) -> BpiResult<&'static Zone> {
+        ZoneRegistry::builtin()
+            .get(ZoneVersion::V2, tid)
+            .ok_or_else(|| unknown_zone("tid_v2"))
+    }
+
+    /// 在内置分区表中按 tid、代号或中文名查找，见 [`ZoneRegistry::lookup`]。
+    ///
+    /// v1 与 v2 同名时返回 v2 分区，只接受 v1 分区的接口应使用 [`Zone::lookup_in`]。
+    pub fn lookup(query: &str) -> BpiResult<&'static Zone> {
+        ZoneRegistry::builtin()
+            .lookup(query)
+            .ok_or_else(|| unknown_zone("zone"))
+    }
+
+    /// 在内置分区表的指定版本中按 tid、代号或中文名查找，见 [`ZoneRegistry::lookup_in`]。
+    pub fn lookup_in(version: ZoneVersion, query: &str) -> BpiResult<&'static Zone> {
+        ZoneRegistry::builtin()
+            .lookup_in(version, query)
+            .ok_or_else(|| unknown_zone("zone"))
+    }
+
+    pub fn is_main(&self) -> bool {
+        self.parent.is_none()
+    }
+
+    /// 校验分区版本并返回 tid，用于只接受某一版分区的接口。
+    pub fn expect_version(&self, version: ZoneVersion) -> BpiResult<u32> {
+        if self.version != version {
+            let message = match version {
+                ZoneVersion::V1 => "endpoint expects a v1 zone",
+                ZoneVersion::V2 => "endpoint expects a v2 zone",
+            };
+            return Err(BpiError::invalid_parameter("zone", message));
+        }
+
+        Ok(self.tid)
+    }
+}
+
+impl From<&VideoPartitionV2> for Zone {
+    fn from(partition: &VideoPartitionV2) -> Self {
+        ZoneRegistry::builtin()
+            .get(ZoneVersion::V2, partition.tid())
+            .cloned()
+            .unwrap_or_else(|| {
+                Zone::new(
+                    ZoneVersion::V2,
+                    partition.tid(),
+                    None,
+                    partition.alias(),
+                    partition.name(),
+                )
+            })
+    }
+}
+
+impl From<VideoPartitionV2> for Zone {
+    fn from(partition: VideoPartitionV2) -> Self {
+        Self::from(&partition)
+    }
+}
+
+impl From<&Zone> for Zone {
+    fn from(zone: &Zone) -> Self {
+        zone.clone()
+    }
+}
+
+impl VideoPartitionV2 {
+    /// 返回内置分区表中的对应分区。
+    pub fn zone(&self) -> Zone {
+        Zone::from(self)
+    }
+}
+
+/// 分区表的一项变化。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum ZoneChange {
+    /// 对方表中新增的分区
+    Added { zone: Zone },
+    /// 对方表中已不存在的分区
+    Removed { zone: Zone },
+    /// 代号、名称或主分区发生变化
+    Changed { before: Zone, after: Zone },
+}
+
+/// 视频分区表。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ZoneRegistry {
+    zones: BTreeMap<(ZoneVersion, u32), Zone>,
+    v1_to_v2: HashMap<u32, u32>,
+}
+
+impl ZoneRegistry {
+    /// 内置分区表。
+    pub fn builtin() -> &'static ZoneRegistry {
+        BUILTIN.get_or_init(|| {
+            let zones = V1_ZONES
+                .iter()
+                .map(|row| (ZoneVersion::V1, row))
+                .chain(V2_ZONES.iter().map(|row| (ZoneVersion::V2, row)))
+                .map(|(version, (tid, parent, alias, name))| {
+                    Zone::new(
+                        version,
+                        *tid,
+                        (*parent != 0).then_some(*parent),
+                        *alias,
+                        *name,
+                    )
+                });
+            let mut registry = Self::from_zones(zones);
+            registry.v1_to_v2 = V1_TO_V2.iter().copied().collect();
+            registry
+        })
+    }
+
+    /// 由分区列表创建分区表，同一版本的重复 tid 以后出现的为准。
+    pub fn from_zones(zones: impl IntoIterator<Item = Zone>) -> Self {
+        Self {
+            zones: zones
+                .into_iter()
+                .map(|zone| ((zone.version, zone.tid), zone))
+                .collect(),
+            v1_to_v2: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.zones.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.zones.is_empty()
+    }
+
+    /// 按版本和 tid 查找。
+    pub fn get(&self, version: ZoneVersion, tid: u32) -> Option<&Zone> {
+        self.zones.get(&(version, tid))
+    }
+
+    /// 按 tid 查找，v1 与 v2 的 tid 不重叠。
+    pub fn by_tid(&self, tid: u32) -> Option<&Zone> {
+        self.get(ZoneVersion::V1, tid)
+            .or_else(|| self.get(ZoneVersion::V2, tid))
+    }
+
+    /// 指定版本的全部分区。
+    pub fn zones(&self, version: ZoneVersion) -> impl Iterator<Item = &Zone> {
+        self.zones
+            .range((version, 0)..=(version, u32::MAX))
+            .map(|(_, zone)| zone)
+    }
+
+    /// 指定版本的主分区。
+    pub fn main_zones(&self, version: ZoneVersion) -> impl Iterator<Item = &Zone> {
+        self.zones(version).filter(|zone| zone.is_main())
+    }
+
+    /// 分区的主分区，主分区返回 `None`。
+    pub fn parent(&self, zone: &Zone) -> Option<&Zone> {
+        self.get(zone.version, zone.parent?)
+    }
+
+    /// 主分区下的子分区。
+    pub fn children(&self, zone: &Zone) -> impl Iterator<Item = &Zone> {
+        let (version, tid) = (zone.version, zone.tid);
+        self.zones(version)
+            .filter(move |child| child.parent == Some(tid))
+    }
+
+    /// 按代号查找，`parent/alias` 形式可以区分不同主分区下的同名代号。
+    pub fn by_alias(&self, version: ZoneVersion, alias: &str) -> Option<&Zone> {
+        self.find_path(version, alias, |zone| zone.alias.as_str())
+    }
+
+    /// 按中文名查找，`主分区/子分区` 形式可以区分不同主分区下的同名分区。
+    pub fn by_name(&self, version: ZoneVersion, name: &str) -> Option<&Zone> {
+        self.find_path(version, name, |zone| zone.name.as_str())
+    }
+
+    /// 依次按 tid、v2 代号、v1 代号、v2 中文名、v1 中文名查找。
+    ///
+    /// 两版分区同名时（如“动画”）返回 v2 分区；需要固定版本时使用 [`ZoneRegistry::lookup_in`]。
+    pub fn lookup(&self, query: &str) -> Option<&Zone> {
+        let query = query.trim();
+        if let Ok(tid) = query.parse() {
+            return self.by_tid(tid);
+        }
+
+        [ZoneVersion::V2, ZoneVersion::V1]
+            .into_iter()
+            .find_map(|version| self.by_alias(version, query))
+            .or_else(|| {
+                [ZoneVersion::V2, ZoneVersion::V1]
+                    .into_iter()
+                    .find_map(|version| self.by_name(version, query))
+            })
+    }
+
+    /// 只在指定版本中依次按 tid、代号、中文名查找。
+    pub fn lookup_in(&self, version: ZoneVersion, query: &str) -> Option<&Zone> {
+        let query = query.trim();
+        if let Ok(tid) = query.parse() {
+            return self.get(version, tid);
+        }
+
+        self.by_alias(version, query)
+            .or_else(|| self.by_name(version, query))
+    }
+
+    /// v1 分区对应的 v2 分区；子分区没有直接对应时退回其主分区的对应分区。
+    pub fn to_v2(&self, zone: &Zone) -> Option<&Zone> {
+        match zone.version {
+            ZoneVersion::V2 => self.get(ZoneVersion::V2, zone.tid),
+            ZoneVersion::V1 => self
+                .v1_to_v2
+                .get(&zone.tid)
+                .or_else(|| self.v1_to_v2.get(&zone.parent?))
+                .and_then(|tid| self.get(ZoneVersion::V2, *tid)),
+        }
+    }
+
+    /// v2 分区对应的 v1 分区；子分区没有直接对应时退回其主分区的对应分区。
+    pub fn to_v1(&self, zone: &Zone) -> Option<&Zone> {
+        let find = |tid: u32| {
+            self.v1_to_v2
+                .iter()
+                .filter(|(_, v2)| **v2 == tid)
+                .map(|(v1, _)| *v1)
+                .min()
+        };
+
+        match zone.version {
+            ZoneVersion::V1 => self.get(ZoneVersion::V1, zone.tid),
+            ZoneVersion::V2 => find(zone.tid)
+                .or_else(|| find(zone.parent?))
+                .and_then(|tid| self.get(ZoneVersion::V1, tid)),
+        }
+    }
+
+    /// 对比两个分区表，只比较 `other` 中出现的版本。
+    pub fn diff(&self, other: &ZoneRegistry) -> Vec<ZoneChange> {
+        let versions: Vec<_> = [ZoneVersion::V1, ZoneVersion::V2]
+            .into_iter()
+            .filter(|version| other.zones(*version).next().is_some())
+            .collect();
+
+        let mut changes = Vec::new();
+        for version in versions {
+            for zone in self.zones(version) {
+                match other.get(version, zone.tid) {
+                    None => changes.push(ZoneChange::Removed { zone: zone.clone() }),
+                    Some(after) if after != zone => changes.push(ZoneChange::Changed {
+                        before: zone.clone(),
+                        after: after.clone(),
+                    }),
+                    Some(_) => {}
+                }
+            }
+            for zone in other.zones(version) {
+                if self.get(version, zone.tid).is_none() {
+                    changes.push(ZoneChange::Added { zone: zone.clone() });
+                }
+            }
+        }
+        changes
+    }
+
+    fn find_path(
+        &self,
+        version: ZoneVersion,
+        query: &str,
+        key: impl Fn(&Zone) -> &str,
+    ) -> Option<&Zone> {
+        let query = query.trim();
+        match query.split_once('/') {
+            Some((parent, child)) => {
+                let parent = self
+                    .main_zones(version)
+                    .find(|zone| key(zone) == parent.trim())?;
+                self.children(parent).find(|zone| key(zone) == child.trim())
+            }
+            None => self
+                .main_zones(version)
+                .find(|zone| key(zone) == query)
+                .or_else(|| self.zones(version).find(|zone| key(zone) == query)),
+        }
+    }
+}
+
+/// 投稿页接口返回的分区
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ArchivePreType {
+    pub id: u32,
+    #[serde(default)]
+    pub parent: u32,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub desc: String,
+    #[serde(default)]
+    pub children: Vec<ArchivePreType>,
+}
+
+/// 投稿页预加载数据，仅保留分区表
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ArchivePreData {
+    #[serde(default)]
+    pub typelist: Vec<ArchivePreType>,
+}
+
+impl ArchivePreData {
+    /// 转换为 v1 分区表；接口不返回代号，代号沿用内置分区表。
+    pub fn into_registry(self) -> ZoneRegistry {
+        let builtin = ZoneRegistry::builtin();
+        let mut zones = Vec::new();
+        let mut stack = self.typelist;
+        while let Some(item) = stack.pop() {
+            let alias = builtin
+                .get(ZoneVersion::V1, item.id)
+                .map(|zone| zone.alias.clone())
+                .unwrap_or_default();
+            zones.push(Zone::new(
+                ZoneVersion::V1,
+                item.id,
+                (item.parent != 0).then_some(item.parent),
+                alias,
+                item.name.trim(),
+            ));
+            stack.extend(item.children);
+        }
+
+        let mut registry = ZoneRegistry::from_zones(zones);
+        registry.v1_to_v2 = builtin.v1_to_v2.clone();
+        registry
+    }
+}
+
+impl<'a> VideoClient<'a> {
+    /// 从投稿页接口获取当前的 v1 分区表，需要登录。
+    ///
+    /// 投稿页接口只返回 v1 分区，v2 分区只能使用内置分区表；[`ZoneRegistry::diff`] 只比较
+    /// 返回中出现的版本，因此不会把 v2 分区报告为已删除。
+    pub async fn zone_registry(&self) -> BpiResult<ZoneRegistry> {
+        let data: ArchivePreData = self
+            .client
+            .get(ARCHIVE_PRE_ENDPOINT)
+            .query(&[("lang", "cn")])
+            .send_bpi_payload("video.zone_registry")
+            .await?;
+
+        Ok(data.into_registry())
+    }
+}
+
+fn unknown_zone(field: &'static str) -> BpiError {
+    BpiError::invalid_parameter(field, "zone is not in the built-in zone table")
+}
+
+/// v1 分区：`(tid, 主分区 tid, 代号, 名称)`，主分区的主分区 tid 为 0。
+#[rustfmt::skip]
+const V1_ZONES: &[(u32, u32, &str, &str)] = &[
+    (1, 0, "douga", "动画"),
+    (24, 1, "mad", "MAD·AMV"),
+    (25, 1, "mmd", "MMD·3D"),
+    (47, 1, "handdrawn", "短片·手书"),
+    (257, 1, "voice", "配音"),
+    (210, 1, "garage_kit", "手办·模玩"),
+    (86, 1, "tokusatsu", "特摄"),
+    (253, 1, "acgntalks", "动漫杂谈"),
+    (27, 1, "other", "综合"),
+    (13, 0, "anime", "番剧"),
+    (51, 13, "information", "资讯"),
+    (152, 13, "offical", "官方延伸"),
+    (32, 13, "finish", "完结动画"),
+    (33, 13, "serial", "连载动画"),
+    (167, 0, "guochuang", "国创"),
+    (153, 167, "chinese", "国产动画"),
+    (168, 167, "original", "国产原创相关"),
+    (169, 167, "puppetry", "布袋戏"),
+    (170, 167, "information", "资讯"),
+    (195, 167, "motioncomic", "动态漫·广播剧"),
+    (3, 0, "music", "音乐"),
+    (28, 3, "original", "原创音乐"),
+    (31, 3, "cover", "翻唱"),
+    (59, 3, "perform", "演奏"),
+    (30, 3, "vocaloid", "VOCALOID·UTAU"),
+    (29, 3, "live", "音乐现场"),
+    (193, 3, "mv", "MV"),
+    (243, 3, "commentary", "乐评盘点"),
+    (244, 3, "tutorial", "音乐教学"),
+    (130, 3, "other", "音乐综合"),
+    (265, 3, "ai_music", "AI音乐"),
+    (266, 3, "radio", "电台"),
+    (129, 0, "dance", "舞蹈"),
+    (20, 129, "otaku", "宅舞"),
+    (154, 129, "three_d", "舞蹈综合"),
+    (156, 129, "demo", "舞蹈教程"),
+    (198, 129, "hiphop", "街舞"),
+    (199, 129, "star", "明星舞蹈"),
+    (200, 129, "china", "国风舞蹈"),
+    (255, 129, "gestures", "手势·网红舞"),
+    (4, 0, "game", "游戏"),
+    (17, 4, "stand_alone", "单机游戏"),
+    (171, 4, "esports", "电子竞技"),
+    (172, 4, "mobile", "手机游戏"),
+    (65, 4, "online", "网络游戏"),
+    (173, 4, "board", "桌游棋牌"),
+    (121, 4, "gmv", "GMV"),
+    (136, 4, "music", "音游"),
+    (19, 4, "mugen", "Mugen"),
+    (36, 0, "knowledge", "知识"),
+    (201, 36, "science", "科学科普"),
+    (124, 36, "social_science", "社科·法律·心理"),
+    (228, 36, "humanity_history", "人文历史"),
+    (207, 36, "business", "财经商业"),
+    (208, 36, "campus", "校园学习"),
+    (209, 36, "career", "职业职场"),
+    (229, 36, "design", "设计·创意"),
+    (122, 36, "skill", "野生技术协会"),
+    (188, 0, "tech", "科技"),
+    (95, 188, "digital", "数码"),
+    (230, 188, "application", "软件应用"),
+    (231, 188, "computer_tech", "计算机技术"),
+    (232, 188, "industry", "科工机械"),
+    (233, 188, "diy", "极客DIY"),
+    (234, 0, "sports", "运动"),
+    (235, 234, "basketball", "篮球"),
+    (249, 234, "football", "足球"),
+    (164, 234, "aerobics", "健身"),
+    (236, 234, "athletic", "竞技体育"),
+    (237, 234, "culture", "运动文化"),
+    (238, 234, "comprehensive", "运动综合"),
+    (223, 0, "car", "汽车"),
+    (258, 223, "knowledge", "汽车知识科普"),
+    (245, 223, "racing", "赛车"),
+    (246, 223, "modifiedvehicle", "改装玩车"),
+    (247, 223, "newenergyvehicle", "新能源车"),
+    (248, 223, "touringcar", "房车"),
+    (240, 223, "motorcycle", "摩托车"),
+    (227, 223, "strategy", "购车攻略"),
+    (176, 223, "life", "汽车生活"),
+    (160, 0, "life", "生活"),
+    (138, 160, "funny", "搞笑"),
+    (250, 160, "travel", "出行"),
+    (251, 160, "rurallife", "三农"),
+    (239, 160, "home", "家居房产"),
+    (161, 160, "handmake", "手工"),
+    (162, 160, "painting", "绘画"),
+    (21, 160, "daily", "日常"),
+    (254, 160, "parenting", "亲子"),
+    (211, 0, "food", "美食"),
+    (76, 211, "make", "美食制作"),
+    (212, 211, "detective", "美食侦探"),
+    (213, 211, "measurement", "美食测评"),
+    (214, 211, "rural", "田园美食"),
+    (215, 211, "record", "美食记录"),
+    (217, 0, "animal", "动物圈"),
+    (218, 217, "cat", "喵星人"),
+    (219, 217, "dog", "汪星人"),
+    (222, 217, "reptiles", "小宠异宠"),
+    (221, 217, "wild_animal", "野生动物"),
+    (220, 217, "second_edition", "动物二创"),
+    (75, 217, "animal_composite", "动物综合"),
+    (119, 0, "kichiku", "鬼畜"),
+    (22, 119, "guide", "鬼畜调教"),
+    (26, 119, "mad", "音MAD"),
+    (126, 119, "manual_vocaloid", "人力VOCALOID"),
+    (216, 119, "theatre", "鬼畜剧场"),
+    (127, 119, "course", "教程演示"),
+    (155, 0, "fashion", "时尚"),
+    (157, 155, "makeup", "美妆护肤"),
+    (252, 155, "cos", "仿妆cos"),
+    (158, 155, "clothing", "穿搭"),
+    (159, 155, "catwalk", "时尚潮流"),
+    (202, 0, "information", "资讯"),
+    (203, 202, "hotspot", "热点"),
+    (204, 202, "global", "环球"),
+    (205, 202, "social", "社会"),
+    (206, 202, "multiple", "综合"),
+    (5, 0, "ent", "娱乐"),
+    (71, 5, "variety", "综艺"),
+    (241, 5, "talker", "娱乐杂谈"),
+    (242, 5, "fans", "粉丝创作"),
+    (137, 5, "celebrity", "明星综合"),
+    (181, 0, "cinephile", "影视"),
+    (182, 181, "cinecism", "影视杂谈"),
+    (183, 181, "montage", "影视剪辑"),
+    (85, 181, "shortplay", "小剧场"),
+    (184, 181, "trailer_info", "预告·资讯"),
+    (256, 181, "shortfilm", "短片"),
+    (177, 0, "documentary", "纪录片"),
+    (37, 177, "history", "人文·历史"),
+    (178, 177, "science", "科学·探索·自然"),
+    (179, 177, "military", "军事"),
+    (180, 177, "travel", "社会·美食·旅行"),
+    (23, 0, "movie", "电影"),
+    (147, 23, "chinese", "华语电影"),
+    (145, 23, "west", "欧美电影"),
+    (146, 23, "japan", "日本电影"),
+    (83, 23, "movie", "其他国家"),
+    (11, 0, "tv", "电视剧"),
+    (185, 11, "mainland", "国产剧"),
+    (187, 11, "overseas", "海外剧"),
+];
+
+/// v2 分区：`(tid_v2, 主分区 tid_v2, 代号, 名称)`，[`VideoPartitionV2`] 的代号和名称取自这里。
+#[rustfmt::skip]
+const V2_ZONES: &[(u32, u32, &str, &str)] = &[
+    (1005, 0, "douga", "动画"),
+    (2037, 1005, "fan_anime", "同人动画"),
+    (2038, 1005, "garage_kit", "模玩周边"),
+    (2039, 1005, "cosplay", "cosplay"),
+    (2040, 1005, "offline", "二次元线下"),
+    (2041, 1005, "editing", "动漫剪辑"),
+    (2042, 1005, "commentary", "动漫评论"),
+    (2043, 1005, "quick_view", "动漫速读"),
+    (2044, 1005, "voice", "动漫配音"),
+    (2045, 1005, "information", "动漫资讯"),
+    (2046, 1005, "interpret", "网文解读"),
+    (2047, 1005, "vup", "虚拟up主"),
+    (2048, 1005, "tokusatsu", "特摄"),
+    (2049, 1005, "puppetry", "布袋戏"),
+    (2050, 1005, "comic", "漫画·动态漫"),
+    (2051, 1005, "motion", "广播剧"),
+    (2052, 1005, "reaction", "动漫reaction"),
+    (2053, 1005, "tutorial", "动漫教学"),
+    (2054, 1005, "other", "二次元其他"),
+    (1008, 0, "game", "游戏"),
+    (2064, 1008, "rpg", "单人RPG游戏"),
+    (2065, 1008, "mmorpg", "MMORPG游戏"),
+    (2066, 1008, "stand_alone", "单机主机类游戏"),
+    (2067, 1008, "slg", "SLG游戏"),
+    (2068, 1008, "tbs", "回合制策略游戏"),
+    (2069, 1008, "rts", "即时策略游戏"),
+    (2070, 1008, "moba", "MOBA游戏"),
+    (2071, 1008, "stg", "射击游戏"),
+    (2072, 1008, "spg", "体育竞速游戏"),
+    (2073, 1008, "act", "动作竞技游戏"),
+    (2074, 1008, "msc", "音游舞游"),
+    (2075, 1008, "sim", "模拟经营游戏"),
+    (2076, 1008, "otome", "女性向游戏"),
+    (2077, 1008, "puz", "休闲/小游戏"),
+    (2078, 1008, "sandbox", "沙盒类"),
+    (2079, 1008, "other", "其他游戏"),
+    (1007, 0, "kichiku", "鬼畜"),
+    (2059, 1007, "guide", "鬼畜调教"),
+    (2060, 1007, "theatre", "鬼畜剧场"),
+    (2061, 1007, "manual_vocaloid", "人力VOCALOID"),
+    (2062, 1007, "mad", "音MAD"),
+    (2063, 1007, "other", "鬼畜综合"),
+    (1003, 0, "music", "音乐"),
+    (2016, 1003, "original", "原创音乐"),
+    (2017, 1003, "mv", "MV"),
+    (2018, 1003, "live", "音乐现场"),
+    (2019, 1003, "fan_videos", "乐迷饭拍"),
+    (2020, 1003, "cover", "翻唱"),
+    (2021, 1003, "perform", "演奏"),
+    (2022, 1003, "vocaloid", "VOCALOID"),
+    (2023, 1003, "ai_music", "AI音乐"),
+    (2024, 1003, "radio", "电台·歌单"),
+    (2025, 1003, "tutorial", "音乐教学"),
+    (2026, 1003, "commentary", "乐评盘点"),
+    (2027, 1003, "other", "音乐综合"),
+    (1004, 0, "dance", "舞蹈"),
+    (2028, 1004, "otaku", "宅舞"),
+    (2029, 1004, "hiphop", "街舞"),
+    (2030, 1004, "gestures", "颜值·网红舞"),
+    (2031, 1004, "star", "明星舞蹈"),
+    (2032, 1004, "china", "国风舞蹈"),
+    (2033, 1004, "tutorial", "舞蹈教学"),
+    (2034, 1004, "ballet", "芭蕾舞"),
+    (2035, 1004, "wota", "wota艺"),
+    (2036, 1004, "other", "舞蹈综合"),
+    (1001, 0, "cinephile", "影视"),
+    (2001, 1001, "commentary", "影视解读"),
+    (2002, 1001, "montage", "影视剪辑"),
+    (2003, 1001, "information", "影视资讯"),
+    (2004, 1001, "porterage", "影视正片搬运"),
+    (2005, 1001, "shortfilm", "短剧短片"),
+    (2006, 1001, "ai", "AI影视"),
+    (2007, 1001, "reaction", "影视reaction"),
+    (2008, 1001, "other", "影视综合"),
+    (1002, 0, "ent", "娱乐"),
+    (2009, 1002, "commentary", "娱乐评论"),
+    (2010, 1002, "montage", "明星剪辑"),
+    (2011, 1002, "fans_video", "娱乐饭拍&现场"),
+    (2012, 1002, "information", "娱乐资讯"),
+    (2013, 1002, "reaction", "娱乐reaction"),
+    (2014, 1002, "variety", "娱乐综艺正片"),
+    (2015, 1002, "other", "娱乐综合"),
+    (1010, 0, "knowledge", "知识"),
+    (2084, 1010, "exam", "应试教育"),
+    (2085, 1010, "lang_skill", "非应试语言学习"),
+    (2086, 1010, "campus", "大学专业知识"),
+    (2087, 1010, "business", "商业财经"),
+    (2088, 1010, "social_observation", "社会观察"),
+    (2089, 1010, "politics", "时政解读"),
+    (2090, 1010, "humanity_history", "人文历史"),
+    (2091, 1010, "design", "设计艺术"),
+    (2092, 1010, "psychology", "心理杂谈"),
+    (2093, 1010, "career", "职场发展"),
+    (2094, 1010, "science", "科学科普"),
+    (2095, 1010, "other", "其他知识杂谈"),
+    (1012, 0, "tech", "科技数码"),
+    (2099, 1012, "computer", "电脑"),
+    (2100, 1012, "phone", "手机"),
+    (2101, 1012, "pad", "平板电脑"),
+    (2102, 1012, "photography", "摄影摄像"),
+    (2103, 1012, "machine", "工程机械"),
+    (2104, 1012, "create", "自制发明/设备"),
+    (2105, 1012, "other", "科技数码综合"),
+    (1009, 0, "information", "资讯"),
+    (2080, 1009, "politics", "时政资讯"),
+    (2081, 1009, "overseas", "海外资讯"),
+    (2082, 1009, "social", "社会资讯"),
+    (2083, 1009, "other", "综合资讯"),
+    (1020, 0, "food", "美食"),
+    (2149, 1020, "make", "美食制作"),
+    (2150, 1020, "detective", "美食探店"),
+    (2151, 1020, "commentary", "美食测评"),
+    (2152, 1020, "record", "美食记录"),
+    (2153, 1020, "other", "美食综合"),
+    (1021, 0, "shortplay", "小剧场"),
+    (2154, 1021, "plot", "剧情演绎"),
+    (2155, 1021, "lang", "语言类小剧场"),
+    (2156, 1021, "up_variety", "UP主小综艺"),
+    (2157, 1021, "interview", "街头采访"),
+    (1013, 0, "car", "汽车"),
+    (2106, 1013, "commentary", "汽车测评"),
+    (2107, 1013, "culture", "汽车文化"),
+    (2108, 1013, "life", "汽车生活"),
+    (2109, 1013, "tech", "汽车技术"),
+    (2110, 1013, "other", "汽车综合"),
+    (1014, 0, "fashion", "时尚美妆"),
+    (2111, 1014, "makeup", "美妆"),
+    (2112, 1014, "skincare", "护肤"),
+    (2113, 1014, "cos", "仿装cos"),
+    (2114, 1014, "outfits", "鞋服穿搭"),
+    (2115, 1014, "accessories", "箱包配饰"),
+    (2116, 1014, "jewelry", "珠宝首饰"),
+    (2117, 1014, "trick", "三坑"),
+    (2118, 1014, "commentary", "时尚解读"),
+    (2119, 1014, "other", "时尚综合"),
+    (1018, 0, "sports", "体育运动"),
+    (2133, 1018, "trend", "潮流运动"),
+    (2134, 1018, "football", "足球"),
+    (2135, 1018, "basketball", "篮球"),
+    (2136, 1018, "running", "跑步"),
+    (2137, 1018, "kungfu", "武术"),
+    (2138, 1018, "fighting", "格斗"),
+    (2139, 1018, "badminton", "羽毛球"),
+    (2140, 1018, "information", "体育资讯"),
+    (2141, 1018, "match", "体育赛事"),
+    (2142, 1018, "other", "体育综合"),
+    (1024, 0, "animal", "动物"),
+    (2167, 1024, "cat", "猫"),
+    (2168, 1024, "dog", "狗"),
+    (2169, 1024, "reptiles", "小宠异宠"),
+    (2170, 1024, "science", "野生动物·动物解说科普"),
+    (2171, 1024, "other", "动物综合·二创"),
+    (1029, 0, "vlog", "vlog"),
+    (2194, 1029, "life", "中外生活vlog"),
+    (2195, 1029, "student", "学生vlog"),
+    (2196, 1029, "career", "职业vlog"),
+    (2197, 1029, "other", "其他vlog"),
+    (1006, 0, "painting", "绘画"),
+    (2055, 1006, "acg", "二次元绘画"),
+    (2056, 1006, "none_acg", "非二次元绘画"),
+    (2057, 1006, "tutorial", "绘画学习"),
+    (2058, 1006, "other", "绘画综合"),
+    (1011, 0, "ai", "人工智能"),
+    (2096, 1011, "tutorial", "AI学习"),
+    (2097, 1011, "information", "AI资讯"),
+    (2098, 1011, "other", "AI杂谈"),
+    (1015, 0, "home", "家装房产"),
+    (2120, 1015, "trade", "买房租房"),
+    (2121, 1015, "renovation", "家庭装修"),
+    (2122, 1015, "furniture", "家居展示"),
+    (2123, 1015, "appliances", "家用电器"),
+    (1016, 0, "outdoors", "户外潮流"),
+    (2124, 1016, "camping", "露营"),
+    (2125, 1016, "hiking", "徒步"),
+    (2126, 1016, "explore", "户外探秘"),
+    (2127, 1016, "other", "户外综合"),
+    (1017, 0, "gym", "健身"),
+    (2128, 1017, "science", "健身科普"),
+    (2129, 1017, "tutorial", "健身跟练教学"),
+    (2130, 1017, "record", "健身记录"),
+    (2131, 1017, "figure", "健身身材展示"),
+    (2132, 1017, "other", "健身综合"),
+    (1019, 0, "handmake", "手工"),
+    (2143, 1019, "handbook", "文具手帐"),
+    (2144, 1019, "light", "轻手作"),
+    (2145, 1019, "traditional", "传统手工艺"),
+    (2146, 1019, "relief", "解压手工"),
+    (2147, 1019, "diy", "DIY玩具"),
+    (2148, 1019, "other", "其他手工"),
+    (1022, 0, "travel", "旅游出行"),
+    (2158, 1022, "record", "旅游记录"),
+    (2159, 1022, "strategy", "旅游攻略"),
+    (2160, 1022, "city", "城市出行"),
+    (2161, 1022, "transport", "公共交通"),
+    (1023, 0, "rural", "三农"),
+    (2162, 1023, "planting", "农村种植"),
+    (2163, 1023, "fishing", "赶海捕鱼"),
+    (2164, 1023, "harvest", "打野采摘"),
+    (2165, 1023, "tech", "农业技术"),
+    (2166, 1023, "life", "农村生活"),
+    (1025, 0, "parenting", "亲子"),
+    (2172, 1025, "pregnant_care", "孕产护理"),
+    (2173, 1025, "infant_care", "婴幼护理"),
+    (2174, 1025, "talent", "儿童才艺"),
+    (2175, 1025, "cute", "萌娃"),
+    (2176, 1025, "interaction", "亲子互动"),
+    (2177, 1025, "education", "亲子教育"),
+    (2178, 1025, "other", "亲子综合"),
+    (1026, 0, "health", "健康"),
+    (2179, 1026, "science", "健康科普"),
+    (2180, 1026, "regimen", "养生"),
+    (2181, 1026, "sexes", "两性知识"),
+    (2182, 1026, "psychology", "心理健康"),
+    (2183, 1026, "asmr", "助眠视频·ASMR"),
+    (2184, 1026, "other", "医疗保健综合"),
+    (1027, 0, "emotion", "情感"),
+    (2185, 1027, "family", "家庭关系"),
+    (2186, 1027, "romantic", "恋爱关系"),
+    (2187, 1027, "interpersonal", "人际关系"),
+    (2188, 1027, "growth", "自我成长"),
+    (1030, 0, "life_joy", "生活兴趣"),
+    (2198, 1030, "leisure", "休闲玩乐"),
+    (2199, 1030, "on_site", "线下演出"),
+    (2200, 1030, "artistic_products", "文玩文创"),
+    (2201, 1030, "trendy_toys", "潮玩玩具"),
+    (2202, 1030, "other", "兴趣综合"),
+    (1031, 0, "life_experience", "生活经验"),
+    (2203, 1031, "skills", "生活技能"),
+    (2204, 1031, "procedures", "办事流程"),
+    (2205, 1031, "marriage", "婚嫁"),
+    (1028, 0, "mysticism", "神秘学"),
+    (2189, 1028, "tarot", "塔罗占卜"),
+    (2190, 1028, "horoscope", "星座占星"),
+    (2191, 1028, "metaphysics", "传统玄学"),
+    (2192, 1028, "healing", "疗愈成长"),
+    (2193, 1028, "other", "其他神秘学"),
+];
+
+/// v1 tid 到 v2 tid 的对应关系，番剧、国创、纪录片、电影、电视剧等 PGC 分区在 v2 中没有对应。
+#[rustfmt::skip]
+const V1_TO_V2: &[(u32, u32)] = &[
+    (1, 1005), (210, 2038), (86, 2048), (257, 2044), (27, 2054), (169, 2049),
+    (3, 1003), (130, 2027),
+    (129, 1004), (154, 2036),
+    (4, 1008),
+    (36, 1010),
+    (188, 1012),
+    (234, 1018), (164, 1017),
+    (223, 1013),
+    (250, 1022), (251, 1023), (239, 1015), (161, 1019), (162, 1006), (21, 1029), (254, 1025),
+    (211, 1020),
+    (217, 1024), (75, 2171),
+    (119, 1007),
+    (155, 1014),
+    (202, 1009), (206, 2083),
+    (5, 1002), (137, 2015),
+    (181, 1001), (85, 1021),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::video::video_zone_v2::Douga;
+
+    #[test]
+    fn builtin_registry_matches_partition_enums() -> BpiResult<()> {
+        let registry = ZoneRegistry::builtin();
+        let fan_anime = VideoPartitionV2::Douga(Douga::FanAnime).zone();
+
+        assert_eq!(fan_anime.parent, Some(1005));
+        assert_eq!(
+            registry.parent(&fan_anime).map(|zone| zone.alias.as_str()),
+            Some("douga")
+        );
+        assert_eq!(registry.main_zones(ZoneVersion::V2).count(), 31);
+        assert!(
+            registry
+                .children(Zone::v2(1005)?)
+                .any(|zone| zone.tid == 2037)
+        );
+        for version in [ZoneVersion::V1, ZoneVersion::V2] {
+            for zone in registry.zones(version) {
+                if let Some(parent) = zone.parent {
+                    assert!(registry.get(version, parent).is_some(), "{zone:?}");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn lookup_accepts_tid_alias_name_and_paths() -> BpiResult<()> {
+        assert_eq!(Zone::lookup("21")?.name, "日常");
+        assert_eq!(Zone::lookup("fan_anime")?.tid, 2037);
+        assert_eq!(Zone::lookup("douga/other")?.tid, 2054);
+        assert_eq!(Zone::lookup("music/other")?.tid, 2027);
+        assert_eq!(Zone::lookup("计算机技术")?.tid, 231);
+        assert_eq!(Zone::lookup("动画")?.tid, 1005);
+        assert!(Zone::lookup("不存在的分区").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn lookup_in_pins_the_zone_version() -> BpiResult<()> {
+        assert_eq!(Zone::lookup("动画")?.version, ZoneVersion::V2);
+        assert_eq!(Zone::lookup_in(ZoneVersion::V1, "动画")?.tid, 1);
+        assert_eq!(Zone::lookup_in(ZoneVersion::V1, "douga")?.tid, 1);
+        assert_eq!(Zone::lookup_in(ZoneVersion::V2, "douga")?.tid, 1005);
+        assert_eq!(Zone::lookup_in(ZoneVersion::V1, "21")?.name, "日常");
+        assert!(Zone::lookup_in(ZoneVersion::V2, "21").is_err());
+        assert!(Zone::lookup_in(ZoneVersion::V1, "fan_anime").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn partition_enum_reads_alias_and_name_from_builtin_table() -> BpiResult<()> {
+        use crate::video::video_zone_v2::{Douga, Mysticism};
+
+        let main = VideoPartitionV2::Douga(Douga::Douga);
+        assert_eq!(
+            (main.tid(), main.alias(), main.name()),
+            (1005, "douga", "动画")
+        );
+        let child = VideoPartitionV2::Mysticism(Mysticism::Other);
+        assert_eq!((child.tid(), child.alias()), (2193, "other"));
+        assert_eq!(child.zone().parent, Some(1028));
+        assert_eq!(Zone::from(child), *Zone::v2(2193)?);
+        assert_eq!(ZoneRegistry::builtin().zones(ZoneVersion::V2).count(), 236);
+        Ok(())
+    }
+
+    #[test]
+    fn v1_and_v2_tids_map_both_ways() -> BpiResult<()> {
+        let registry = ZoneRegistry::builtin();
+
+        assert_eq!(
+            registry.to_v2(Zone::v1(21)?).map(|zone| zone.tid),
+            Some(1029)
+        );
+        assert_eq!(
+            registry.to_v2(Zone::v1(24)?).map(|zone| zone.tid),
+            Some(1005)
+        );
+        assert_eq!(
+            registry.to_v1(Zone::v2(2037)?).map(|zone| zone.tid),
+            Some(1)
+        );
+        assert!(registry.to_v2(Zone::v1(13)?).is_none());
+        assert!(Zone::v2(21).is_err());
+        assert!(Zone::v1(21)?.expect_version(ZoneVersion::V2).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn live_typelist_diffs_against_builtin_table() -> BpiResult<()> {
+        let data: ArchivePreData = serde_json::from_value(serde_json::json!({
+            "typelist": [{
+                "id": 1,
+                "parent": 0,
+                "name": "动画",
+                "children": [
+                    {"id": 24, "parent": 1, "name": "MAD·AMV"},
+                    {"id": 999, "parent": 1, "name": "新分区"}
+                ]
+            }]
+        }))?;
+        let live = data.into_registry();
+        let changes = ZoneRegistry::builtin().diff(&live);
+
+        assert!(changes.contains(&ZoneChange::Added {
+            zone: Zone::new(ZoneVersion::V1, 999, Some(1), "", "新分区"),
+        }));
+        assert!(
+            changes
+                .iter()
+                .any(|change| matches!(change, ZoneChange::Removed { zone } if zone.tid == 21))
+        );
+        assert!(!changes.iter().any(|change| match change {
+            ZoneChange::Removed { zone } | ZoneChange::Added { zone } =>
+                zone.version == ZoneVersion::V2,
+            ZoneChange::Changed { .. } => false,
+        }));
+        assert!(!changes.iter().any(
+            |change| matches!(change, ZoneChange::Changed { before, .. } if before.tid == 24)
+        ));
+        Ok(())
+    }
+}
@@ -173,6 +173,7 @@ mod tests {
 
     use crate::probe::contract::HttpMethod;
     use crate::probe::endpoint_contract::EndpointContract;
+    use crate::video::Zone;
     use crate::video_ranking::dynamic::{NewListRankData, RegionArchivesData};
     use crate::video_ranking::params::{
         PopularSeriesOneParams, VideoNewListRankOrder, VideoPopularListParams,
@@ -333,27 +334,27 @@ mod tests {
         assert_ranking_list_future(
             video_ranking.ranking_list(
                 VideoRankingListParams::new()
-                    .with_rid(1)?
+                    .with_zone(Zone::v1(1)?)?
                     .with_type(VideoRankingType::All),
             ),
         );
         assert_region_archives_future(
             video_ranking.region_dynamic(
-                VideoRegionDynamicParams::new(21)?
+                VideoRegionDynamicParams::new(Zone::v1(21)?)?
                     .with_page(1)?
                     .with_page_size(2)?,
             ),
         );
         assert_region_archives_future(
             video_ranking.region_tag_dynamic(
-                VideoRegionTagDynamicParams::new(136, 10026108)?
+                VideoRegionTagDynamicParams::new(Zone::v1(136)?, 10026108)?
                     .with_page(1)?
                     .with_page_size(2)?,
             ),
         );
         assert_region_archives_future(
             video_ranking.region_newlist(
-                VideoRegionNewListParams::new(231)?
+                VideoRegionNewListParams::new(Zone::v1(231)?)?
                     .with_page(1)?
                     .with_page_size(2)?
                     .with_type(1)?,
@@ -361,7 +362,7 @@ mod tests {
         );
         assert_newlist_rank_future(
             video_ranking.region_newlist_rank(
-                VideoRegionNewListRankParams::new(231, 2, "20260701", "20260703")?
+                VideoRegionNewListRankParams::new(Zone::v1(231)?, 2, "20260701", "20260703")?
                     .with_order(VideoNewListRankOrder::Click)
                     .with_page(1)?,
             ),
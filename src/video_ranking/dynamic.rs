@@ -100,6 +100,7 @@ mod tests {
     use super::super::params::VideoNewListRankOrder;
     use super::*;
     use crate::BpiClient;
+    use crate::video::{Zone, ZoneVersion};
     use chrono::{Duration, Local};
     use tracing::info;
 
@@ -107,8 +108,8 @@ mod tests {
     #[tokio::test]
     async fn test_video_region_dynamic() {
         let bpi = BpiClient::new().expect("client should build");
-        let rid = 21; // 日常分区
-        let params = VideoRegionDynamicParams::new(rid)
+        let zone = Zone::lookup_in(ZoneVersion::V1, "日常").expect("zone is built in");
+        let params = VideoRegionDynamicParams::new(zone)
             .expect("rid is valid")
             .with_page(1)
             .expect("page is valid")
@@ -128,9 +129,9 @@ mod tests {
     #[tokio::test]
     async fn test_video_region_tag_dynamic() {
         let bpi = BpiClient::new().expect("client should build");
-        let rid = 136; // 音游分区
+        let zone = Zone::lookup_in(ZoneVersion::V1, "音游").expect("zone is built in");
         let tag_id = 10026108; // Phigros
-        let params = VideoRegionTagDynamicParams::new(rid, tag_id)
+        let params = VideoRegionTagDynamicParams::new(zone, tag_id)
             .expect("required ids are valid")
             .with_page(1)
             .expect("page is valid")
@@ -150,7 +151,7 @@ mod tests {
     #[tokio::test]
     async fn test_video_region_newlist_rank() {
         let bpi = BpiClient::new().expect("client should build");
-        let cate_id = 231; // 计算机技术
+        let zone = Zone::lookup_in(ZoneVersion::V1, "计算机技术").expect("zone is built in");
         let pagesize = 2;
         let today = Local::now().date_naive();
         let seven_days_ago = today - Duration::days(7);
@@ -158,7 +159,7 @@ mod tests {
         let time_from = seven_days_ago.format("%Y%m%d").to_string();
         let time_to = today.format("%Y%m%d").to_string();
 
-        let params = VideoRegionNewListRankParams::new(cate_id, pagesize, time_from, time_to)
+        let params = VideoRegionNewListRankParams::new(zone, pagesize, time_from, time_to)
             .expect("rank params are valid")
             .with_order(VideoNewListRankOrder::Click)
             .with_page(1)
@@ -37,6 +37,7 @@ mod tests {
     use super::*;
     use crate::probe::contract::HttpMethod;
     use crate::probe::endpoint_contract::EndpointContract;
+    use crate::video::Zone;
     use crate::{ApiEnvelope, BpiError, BpiResult};
 
     use super::dynamic::{NewListRankData, RegionArchivesData};
@@ -118,7 +119,7 @@ mod tests {
                 "video_ranking.ranking_list",
                 RANKING_LIST_ENDPOINT,
                 VideoRankingListParams::new()
-                    .with_rid(1)?
+                    .with_zone(Zone::v1(1)?)?
                     .with_type(VideoRankingType::All)
                     .query_pairs(),
                 Some("RankingListData"),
@@ -129,7 +130,7 @@ mod tests {
                 "region-dynamic",
                 "video_ranking.region_dynamic",
                 REGION_DYNAMIC_ENDPOINT,
-                VideoRegionDynamicParams::new(21)?
+                VideoRegionDynamicParams::new(Zone::v1(21)?)?
                     .with_page(1)?
                     .with_page_size(2)?
                     .query_pairs(),
@@ -141,7 +142,7 @@ mod tests {
                 "region-tag-dynamic",
                 "video_ranking.region_tag_dynamic",
                 REGION_TAG_DYNAMIC_ENDPOINT,
-                VideoRegionTagDynamicParams::new(136, 10026108)?
+                VideoRegionTagDynamicParams::new(Zone::v1(136)?, 10026108)?
                     .with_page(1)?
                     .with_page_size(2)?
                     .query_pairs(),
@@ -153,7 +154,7 @@ mod tests {
                 "region-newlist",
                 "video_ranking.region_newlist",
                 REGION_NEWLIST_ENDPOINT,
-                VideoRegionNewListParams::new(231)?
+                VideoRegionNewListParams::new(Zone::v1(231)?)?
                     .with_page(1)?
                     .with_page_size(2)?
                     .with_type(1)?
@@ -166,7 +167,7 @@ mod tests {
                 "region-newlist-rank",
                 "video_ranking.region_newlist_rank",
                 REGION_NEWLIST_RANK_ENDPOINT,
-                VideoRegionNewListRankParams::new(231, 2, "20260701", "20260703")?
+                VideoRegionNewListRankParams::new(Zone::v1(231)?, 2, "20260701", "20260703")?
                     .with_order(VideoNewListRankOrder::Click)
                     .with_page(1)?
                     .query_pairs(),
@@ -1,5 +1,6 @@
 use std::str::FromStr;
 
+use crate::video::{Zone, ZoneVersion};
 use crate::{BpiError, BpiResult};
 
 /// `/x/web-interface/popular` 的参数。
@@ -102,8 +103,9 @@ impl VideoRankingListParams {
         Self::default()
     }
 
-    pub fn with_rid(mut self, rid: u32) -> BpiResult<Self> {
-        self.rid = Some(validate_positive("rid", rid)?);
+    /// 只看某个 v1 分区的排行榜，按名称查找时使用 [`Zone::lookup_in`]。
+    pub fn with_zone(mut self, zone: &Zone) -> BpiResult<Self> {
+        self.rid = Some(zone.expect_version(ZoneVersion::V1)?);
         Ok(self)
     }
 
@@ -135,9 +137,10 @@ pub struct VideoRegionDynamicParams {
 }
 
 impl VideoRegionDynamicParams {
-    pub fn new(rid: u32) -> BpiResult<Self> {
+    /// 分区必须是 v1 分区，按名称查找时使用 [`Zone::lookup_in`]。
+    pub fn new(zone: &Zone) -> BpiResult<Self> {
         Ok(Self {
-            rid: validate_positive("rid", rid)?,
+            rid: zone.expect_version(ZoneVersion::V1)?,
             page: None,
             page_size: None,
         })
@@ -170,9 +173,10 @@ pub struct VideoRegionTagDynamicParams {
 }
 
 impl VideoRegionTagDynamicParams {
-    pub fn new(rid: u32, tag_id: u64) -> BpiResult<Self> {
+    /// 分区必须是 v1 分区，按名称查找时使用 [`Zone::lookup_in`]。
+    pub fn new(zone: &Zone, tag_id: u64) -> BpiResult<Self> {
         Ok(Self {
-            rid: validate_positive("rid", rid)?,
+            rid: zone.expect_version(ZoneVersion::V1)?,
             tag_id: validate_positive_u64("tag_id", tag_id)?,
             page: None,
             page_size: None,
@@ -209,9 +213,10 @@ pub struct VideoRegionNewListParams {
 }
 
 impl VideoRegionNewListParams {
-    pub fn new(rid: u32) -> BpiResult<Self> {
+    /// 分区必须是 v1 分区，按名称查找时使用 [`Zone::lookup_in`]。
+    pub fn new(zone: &Zone) -> BpiResult<Self> {
         Ok(Self {
-            rid: validate_positive("rid", rid)?,
+            rid: zone.expect_version(ZoneVersion::V1)?,
             page: None,
             page_size: None,
             typ: None,
@@ -289,8 +294,9 @@ pub struct VideoRegionNewListRankParams {
 }
 
 impl VideoRegionNewListRankParams {
+    /// 分区必须是 v1 分区，按名称查找时使用 [`Zone::lookup_in`]。
     pub fn new(
-        cate_id: u32,
+        zone: &Zone,
         page_size: u32,
         time_from: impl Into<String>,
         time_to: impl Into<String>,
@@ -301,7 +307,7 @@ impl VideoRegionNewListRankParams {
         validate_non_blank("time_to", &time_to)?;
 
         Ok(Self {
-            cate_id: validate_positive("cate_id", cate_id)?,
+            cate_id: zone.expect_version(ZoneVersion::V1)?,
             order: None,
             page: None,
             page_size: validate_positive("pagesize", page_size)?,
@@ -432,7 +438,7 @@ mod tests {
     #[test]
     fn ranking_list_params_serializes_filters() -> BpiResult<()> {
         let params = VideoRankingListParams::new()
-            .with_rid(21)?
+            .with_zone(Zone::v1(21)?)?
             .with_type(VideoRankingType::Rookie);
 
         assert_eq!(
@@ -444,7 +450,7 @@ mod tests {
 
     #[test]
     fn region_dynamic_params_serializes_pagination() -> BpiResult<()> {
-        let params = VideoRegionDynamicParams::new(21)?
+        let params = VideoRegionDynamicParams::new(Zone::v1(21)?)?
             .with_page(1)?
             .with_page_size(2)?;
 
@@ -461,7 +467,7 @@ mod tests {
 
     #[test]
     fn region_tag_dynamic_params_serializes_required_values() -> BpiResult<()> {
-        let params = VideoRegionTagDynamicParams::new(136, 10026108)?;
+        let params = VideoRegionTagDynamicParams::new(Zone::v1(136)?, 10026108)?;
 
         assert_eq!(
             params.query_pairs(),
@@ -475,7 +481,7 @@ mod tests {
 
     #[test]
     fn region_newlist_params_serializes_type_filter() -> BpiResult<()> {
-        let params = VideoRegionNewListParams::new(231)?.with_type(1)?;
+        let params = VideoRegionNewListParams::new(Zone::v1(231)?)?.with_type(1)?;
 
         assert_eq!(
             params.query_pairs(),
@@ -486,7 +492,7 @@ mod tests {
 
     #[test]
     fn newlist_rank_params_serializes_required_and_optional_values() -> BpiResult<()> {
-        let params = VideoRegionNewListRankParams::new(231, 2, "20260701", "20260703")?
+        let params = VideoRegionNewListRankParams::new(Zone::v1(231)?, 2, "20260701", "20260703")?
             .with_order(VideoNewListRankOrder::Click)
             .with_page(1)?;
 
@@ -508,7 +514,8 @@ mod tests {
 
     #[test]
     fn newlist_rank_params_rejects_blank_time() {
-        let err = VideoRegionNewListRankParams::new(231, 2, " ", "20260703").unwrap_err();
+        let err = VideoRegionNewListRankParams::new(Zone::v1(231).unwrap(), 2, " ", "20260703")
+            .unwrap_err();
 
         assert!(matches!(
             err,
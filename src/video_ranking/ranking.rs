@@ -28,6 +28,7 @@ mod tests {
     use super::super::params::VideoRankingType;
     use super::*;
     use crate::BpiClient;
+    use crate::video::Zone;
     use tracing::info;
 
     #[ignore = "legacy live API test; requires explicit BPI_LIVE_TEST review"]
@@ -60,8 +61,8 @@ mod tests {
         let bpi = BpiClient::new().expect("client should build");
         // 获取日常分区排行榜 (rid=21)
         let params = VideoRankingListParams::new()
-            .with_rid(21)
-            .expect("rid is valid");
+            .with_zone(Zone::v1(21).expect("zone is built in"))
+            .expect("zone is valid");
         let resp = bpi.video_ranking().ranking_list(params).await;
 
         info!("{:?}", resp);